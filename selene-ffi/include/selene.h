@@ -0,0 +1,37 @@
+/*
+ * C bindings for selene, a Lua linter.
+ *
+ * Every string passed in or returned is UTF-8 and NUL terminated.
+ * Strings returned by selene must be freed with selene_free_string.
+ */
+#ifndef SELENE_H
+#define SELENE_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Lints `source`, using `config_json` as the configuration, which has the same shape as
+ * selene.toml written as JSON. `config_json` can be NULL to use the default configuration.
+ * Only the built-in standard libraries can be used for `std`.
+ *
+ * Returns a JSON object, which is either `{"diagnostics": [...]}` or `{"error": "..."}`.
+ * Each diagnostic has the same shape as the ones from `selene --display-style=json`.
+ * Lines and columns start at 0, and columns are counted in characters, while `start` and `end`
+ * are byte offsets into `source`.
+ * The result must be freed with selene_free_string.
+ */
+char *selene_lint(const char *source, const char *config_json);
+
+/* Frees a string returned by selene. Passing NULL does nothing. */
+void selene_free_string(char *string);
+
+/* The version of selene, such as "0.21.1". This must not be freed. */
+const char *selene_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
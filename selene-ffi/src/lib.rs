@@ -0,0 +1,224 @@
+//! C bindings for selene-lib. See include/selene.h for the interface these provide.
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic,
+};
+
+use selene_lib::{
+    embedding, lines,
+    rules::{Label, Severity},
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LintResult {
+    Diagnostics(Vec<JsonDiagnostic>),
+    Error(String),
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    severity: &'static str,
+    code: &'static str,
+    message: String,
+    primary_label: JsonLabel,
+    notes: Vec<String>,
+    help: Vec<String>,
+    suggestions: Vec<String>,
+    secondary_labels: Vec<JsonLabel>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    span: Span,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Span {
+    start: usize,
+    start_line: usize,
+    start_column: usize,
+    end: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+fn label_to_json(
+    label: &Label,
+    files: &codespan::Files<&str>,
+    file_id: codespan::FileId,
+) -> JsonLabel {
+    let start_location = lines::location(files, file_id, label.range.0);
+    let end_location = lines::location(files, file_id, label.range.1);
+
+    JsonLabel {
+        message: label.message.clone().unwrap_or_default(),
+        span: Span {
+            start: label.range.0 as usize,
+            start_line: start_location.line.into(),
+            start_column: start_location.column.into(),
+            end: label.range.1 as usize,
+            end_line: end_location.line.into(),
+            end_column: end_location.column.into(),
+        },
+    }
+}
+
+fn lint(source: &str, config_json: Option<&str>) -> Result<Vec<JsonDiagnostic>, String> {
+    let diagnostics = embedding::lint(source, embedding::config_from_json(config_json)?)?;
+
+    let mut files = codespan::Files::new();
+    let file_id = files.add("source", source);
+
+    Ok(diagnostics
+        .into_iter()
+        .map(|checker_diagnostic| {
+            let severity = match checker_diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Allow => unreachable!("allowed diagnostics are left out"),
+            };
+
+            let diagnostic = checker_diagnostic.diagnostic;
+
+            JsonDiagnostic {
+                severity,
+                code: diagnostic.code,
+                message: diagnostic.message,
+                primary_label: label_to_json(&diagnostic.primary_label, &files, file_id),
+                notes: diagnostic.notes,
+                help: diagnostic.help,
+                suggestions: diagnostic.suggestions,
+                secondary_labels: diagnostic
+                    .secondary_labels
+                    .iter()
+                    .map(|label| label_to_json(label, &files, file_id))
+                    .collect(),
+            }
+        })
+        .collect())
+}
+
+unsafe fn read_string<'a>(string: *const c_char, name: &str) -> Result<&'a str, String> {
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| format!("{name} was not valid UTF-8"))
+}
+
+fn to_c_string(result: &LintResult) -> *mut c_char {
+    let json = serde_json::to_string(result).expect("couldn't serialize result");
+
+    // JSON escapes NUL characters, so this can't fail
+    CString::new(json).unwrap().into_raw()
+}
+
+/// Lints the given source code, returning a JSON string that must be freed with
+/// `selene_free_string`.
+///
+/// # Safety
+/// `source` must be a valid, NUL terminated string. `config_json` must either be
+/// a valid, NUL terminated string, or null.
+#[no_mangle]
+pub unsafe extern "C" fn selene_lint(
+    source: *const c_char,
+    config_json: *const c_char,
+) -> *mut c_char {
+    if source.is_null() {
+        return to_c_string(&LintResult::Error("source was null".to_owned()));
+    }
+
+    let result = (|| {
+        let source = read_string(source, "source")?;
+        let config_json = if config_json.is_null() {
+            None
+        } else {
+            Some(read_string(config_json, "config_json")?)
+        };
+
+        // Unwinding into C is undefined behavior, so panics become errors instead
+        panic::catch_unwind(|| lint(source, config_json))
+            .unwrap_or_else(|_| Err("selene panicked while linting".to_owned()))
+    })();
+
+    to_c_string(&match result {
+        Ok(diagnostics) => LintResult::Diagnostics(diagnostics),
+        Err(error) => LintResult::Error(error),
+    })
+}
+
+/// Frees a string returned by selene.
+///
+/// # Safety
+/// `string` must either be null, or a string returned by selene that has not already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn selene_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Returns the version of selene. The returned string must not be freed.
+#[no_mangle]
+pub extern "C" fn selene_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_json(source: &str, config_json: Option<&str>) -> serde_json::Value {
+        let source = CString::new(source).unwrap();
+        let config_json = config_json.map(|config_json| CString::new(config_json).unwrap());
+
+        unsafe {
+            let result = selene_lint(
+                source.as_ptr(),
+                config_json
+                    .as_ref()
+                    .map_or(std::ptr::null(), |config_json| config_json.as_ptr()),
+            );
+
+            let json = serde_json::from_str(CStr::from_ptr(result).to_str().unwrap()).unwrap();
+            selene_free_string(result);
+            json
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        let result = lint_json("local x = 1", None);
+        let diagnostics = result["diagnostics"].as_array().unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["code"], "unused_variable");
+        assert_eq!(diagnostics[0]["severity"], "warning");
+        assert_eq!(diagnostics[0]["primary_label"]["span"]["start"], 6);
+    }
+
+    #[test]
+    fn test_config() {
+        let result = lint_json(
+            "local x = 1",
+            Some(r#"{ "rules": { "unused_variable": "allow" } }"#),
+        );
+
+        assert_eq!(result["diagnostics"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(lint_json("local = ", None)["error"].is_string());
+        assert!(lint_json("", Some("not json"))["error"].is_string());
+        assert!(lint_json("", Some(r#"{ "std": "nonexistent" }"#))["error"].is_string());
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(selene_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
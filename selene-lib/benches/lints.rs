@@ -0,0 +1,102 @@
+// Measures how long parsing and each lint take over a corpus of Lua code.
+// By default, the corpus is every test file in this repository. To measure something bigger,
+// such as a real codebase, set SELENE_BENCH_CORPUS to a directory of Lua files:
+//
+//     SELENE_BENCH_CORPUS=path/to/code cargo bench -p selene-lib
+//
+// Contributors adding or changing lints can compare against a baseline with criterion's
+// `--save-baseline` and `--baseline` options to find performance regressions.
+use std::{fs, path::PathBuf};
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use selene_lib::{rules::AstContext, standard_library::StandardLibrary, *};
+
+fn corpus() -> Vec<String> {
+    let directory = match std::env::var_os("SELENE_BENCH_CORPUS") {
+        Some(directory) => PathBuf::from(directory),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests"),
+    };
+
+    let mut paths = Vec::new();
+    for extension in ["lua", "luau"] {
+        let pattern = directory.join("**").join(format!("*.{extension}"));
+        paths.extend(
+            glob::glob(&pattern.to_string_lossy())
+                .expect("invalid corpus path")
+                .filter_map(Result::ok),
+        );
+    }
+
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter(|code| full_moon::parse(code).is_ok())
+        .collect()
+}
+
+fn bench_lints(criterion: &mut Criterion) {
+    let corpus = corpus();
+    let bytes = corpus.iter().map(String::len).sum::<usize>() as u64;
+
+    let checker: Checker<serde_json::Value> = Checker::new(
+        CheckerConfig::default(),
+        StandardLibrary::from_name("lua51").unwrap(),
+    )
+    .unwrap();
+
+    let asts = corpus
+        .iter()
+        .map(|code| full_moon::parse(code).unwrap())
+        .collect::<Vec<_>>();
+
+    let ast_contexts = asts.iter().map(AstContext::from_ast).collect::<Vec<_>>();
+
+    let mut group = criterion.benchmark_group("corpus");
+    group.throughput(Throughput::Bytes(bytes));
+
+    group.bench_function("parse", |bencher| {
+        bencher.iter(|| {
+            for code in &corpus {
+                full_moon::parse(code).unwrap();
+            }
+        })
+    });
+
+    group.bench_function("scopes", |bencher| {
+        bencher.iter(|| {
+            for ast in &asts {
+                AstContext::from_ast(ast);
+            }
+        })
+    });
+
+    group.bench_function("all_lints", |bencher| {
+        bencher.iter(|| {
+            for ast in &asts {
+                checker.test_on(ast);
+            }
+        })
+    });
+
+    group.finish();
+
+    let mut group = criterion.benchmark_group("lint");
+    group.throughput(Throughput::Bytes(bytes));
+
+    for lint in all_rules() {
+        group.bench_function(*lint, |bencher| {
+            bencher.iter(|| {
+                for (ast, ast_context) in asts.iter().zip(&ast_contexts) {
+                    checker.test_on_lint(lint, ast, ast_context).unwrap();
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lints);
+criterion_main!(benches);
@@ -0,0 +1,203 @@
+use crate::standard_library::ArgumentType;
+
+use full_moon::{
+    ast,
+    tokenizer::{Symbol, TokenType},
+};
+
+// Returns the argument type of the expression if it can be constantly resolved
+// Otherwise, returns None
+// Only attempts to resolve constants
+pub fn get_argument_type(expression: &ast::Expression) -> Option<PassedArgumentType> {
+    #[cfg_attr(
+        feature = "force_exhaustive_checks",
+        deny(non_exhaustive_omitted_patterns)
+    )]
+    match expression {
+        ast::Expression::Parentheses { expression, .. } => get_argument_type(expression),
+
+        ast::Expression::UnaryOperator { unop, expression } => {
+            match unop {
+                // CAVEAT: If you're overriding __len on a userdata and then making it not return a number
+                // ...sorry, but I don't care about your code :)
+                ast::UnOp::Hash(_) => Some(ArgumentType::Number.into()),
+                ast::UnOp::Minus(_) => get_argument_type(expression),
+                ast::UnOp::Not(_) => Some(ArgumentType::Bool.into()),
+                _ => None,
+            }
+        }
+
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::Function(_) => Some(ArgumentType::Function.into()),
+            ast::Value::FunctionCall(_) => None,
+            ast::Value::Number(_) => Some(ArgumentType::Number.into()),
+            ast::Value::ParenthesesExpression(expression) => get_argument_type(expression),
+            ast::Value::String(token) => {
+                Some(PassedArgumentType::from_string(token.token().to_string()))
+            }
+            #[cfg_attr(
+                feature = "force_exhaustive_checks",
+                allow(non_exhaustive_omitted_patterns)
+            )]
+            ast::Value::Symbol(symbol) => match *symbol.token_type() {
+                TokenType::Symbol { symbol } => match symbol {
+                    Symbol::False => Some(ArgumentType::Bool.into()),
+                    Symbol::True => Some(ArgumentType::Bool.into()),
+                    Symbol::Nil => Some(ArgumentType::Nil.into()),
+                    Symbol::Ellipse => Some(ArgumentType::Vararg.into()),
+                    ref other => {
+                        unreachable!("TokenType::Symbol was not expected ({:?})", other)
+                    }
+                },
+
+                ref other => unreachable!(
+                    "ast::Value::Symbol token_type != TokenType::Symbol ({:?})",
+                    other
+                ),
+            },
+            ast::Value::TableConstructor(_) => Some(ArgumentType::Table.into()),
+            ast::Value::Var(_) => None,
+
+            #[cfg(feature = "roblox")]
+            ast::Value::IfExpression(if_expression) => {
+                // This could be a union type
+                let expected_type = get_argument_type(if_expression.if_expression())?;
+
+                if let Some(else_if_expressions) = if_expression.else_if_expressions() {
+                    for else_if_expression in else_if_expressions {
+                        if !get_argument_type(else_if_expression.expression())?
+                            .same_type(&expected_type)
+                        {
+                            return None;
+                        }
+                    }
+                }
+
+                if get_argument_type(if_expression.else_expression())?.same_type(&expected_type) {
+                    Some(expected_type)
+                } else {
+                    None
+                }
+            }
+
+            _ => None,
+        },
+
+        ast::Expression::BinaryOperator {
+            lhs, binop, rhs, ..
+        } => {
+            // Nearly all of these will return wrong results if you have a non-idiomatic metatable
+            // I intentionally omitted common metamethod re-typings, like __mul
+            match binop {
+                ast::BinOp::Caret(_) => Some(ArgumentType::Number.into()),
+
+                #[cfg_attr(
+                    feature = "force_exhaustive_checks",
+                    allow(non_exhaustive_omitted_patterns)
+                )]
+                ast::BinOp::GreaterThan(_)
+                | ast::BinOp::GreaterThanEqual(_)
+                | ast::BinOp::LessThan(_)
+                | ast::BinOp::LessThanEqual(_)
+                | ast::BinOp::TwoEqual(_)
+                | ast::BinOp::TildeEqual(_) => {
+                    if_chain::if_chain! {
+                        if let ast::Expression::BinaryOperator { binop, .. } = &**rhs;
+                        if let ast::BinOp::And(_) | ast::BinOp::Or(_) = binop;
+                        then {
+                            None
+                        } else {
+                            Some(ArgumentType::Bool.into())
+                        }
+                    }
+                }
+
+                // Basic types will often re-implement these (e.g. Roblox's Vector3)
+                ast::BinOp::Plus(_)
+                | ast::BinOp::Minus(_)
+                | ast::BinOp::Star(_)
+                | ast::BinOp::Slash(_) => {
+                    let lhs_type = get_argument_type(lhs);
+                    let rhs_type = get_argument_type(rhs);
+
+                    if lhs_type == rhs_type {
+                        lhs_type
+                    } else {
+                        None
+                    }
+                }
+
+                ast::BinOp::Percent(_) => Some(ArgumentType::Number.into()),
+
+                ast::BinOp::TwoDots(_) => Some(ArgumentType::String.into()),
+
+                ast::BinOp::And(_) | ast::BinOp::Or(_) => {
+                    // We could potentially support union types here
+                    // Or even just produce one type if both the left and right sides can be evaluated
+                    // But for now, the evaluation just isn't smart enough to where this would be practical
+                    None
+                }
+
+                _ => None,
+            }
+        }
+
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PassedArgumentType {
+    Primitive(ArgumentType),
+    String(String),
+}
+
+impl PassedArgumentType {
+    pub fn from_string(mut string: String) -> PassedArgumentType {
+        string.pop();
+        PassedArgumentType::String(string.chars().skip(1).collect())
+    }
+
+    pub fn matches(&self, argument_type: &ArgumentType) -> bool {
+        if argument_type == &ArgumentType::Any {
+            return true;
+        }
+
+        match self {
+            PassedArgumentType::Primitive(us) => {
+                us == &ArgumentType::Vararg
+                    || us == argument_type
+                    || (us == &ArgumentType::String
+                        && matches!(argument_type, ArgumentType::Constant(_)))
+            }
+            PassedArgumentType::String(text) => match argument_type {
+                ArgumentType::Constant(constants) => constants.contains(text),
+                ArgumentType::String => true,
+                _ => false,
+            },
+        }
+    }
+
+    // Roblox feature flag uses this, and I don't want to lock it
+    #[allow(dead_code)]
+    pub fn same_type(&self, other: &PassedArgumentType) -> bool {
+        match (self, other) {
+            (PassedArgumentType::Primitive(a), PassedArgumentType::Primitive(b)) => a == b,
+            (PassedArgumentType::String(_), PassedArgumentType::String(_)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn type_name(&self) -> String {
+        match self {
+            PassedArgumentType::Primitive(argument_type) => argument_type.to_string(),
+            PassedArgumentType::String(_) => ArgumentType::String.to_string(),
+        }
+    }
+}
+
+impl From<ArgumentType> for PassedArgumentType {
+    fn from(argument_type: ArgumentType) -> Self {
+        PassedArgumentType::Primitive(argument_type)
+    }
+}
@@ -0,0 +1,278 @@
+// Evaluates expressions that are made up entirely of literals, such as `255 + 1` or `not true`.
+// Anything that can't be known for certain without running the code evaluates to None.
+
+use full_moon::{
+    ast,
+    tokenizer::{Symbol, TokenType},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constant {
+    Bool(bool),
+    Nil,
+    Number(f64),
+    String(String),
+}
+
+impl Constant {
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Constant::Nil | Constant::Bool(false))
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Constant::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    // Only integers are converted, since the formatting of floats differs between Lua versions.
+    fn to_concat_string(&self) -> Option<String> {
+        match self {
+            Constant::String(string) => Some(string.to_owned()),
+            Constant::Number(number)
+                if number.fract() == 0.0 && number.abs() < (1u64 << 53) as f64 =>
+            {
+                Some(format!("{}", *number as i64))
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.replace('_', "");
+
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        return u64::from_str_radix(hex, 16)
+            .ok()
+            .map(|number| number as f64);
+    }
+
+    if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        return u64::from_str_radix(binary, 2)
+            .ok()
+            .map(|number| number as f64);
+    }
+
+    text.parse().ok()
+}
+
+fn string_literal(token_type: &TokenType) -> Option<String> {
+    match token_type {
+        TokenType::StringLiteral {
+            literal,
+            multi_line: Some(_),
+            ..
+        } => {
+            let literal = literal.as_str();
+            Some(
+                literal
+                    .strip_prefix("\r\n")
+                    .or_else(|| literal.strip_prefix('\n'))
+                    .unwrap_or(literal)
+                    .to_owned(),
+            )
+        }
+
+        // Escapes are not worth reimplementing here
+        TokenType::StringLiteral { literal, .. } if !literal.contains('\\') => {
+            Some(literal.to_string())
+        }
+
+        _ => None,
+    }
+}
+
+fn evaluate_value(value: &ast::Value) -> Option<Constant> {
+    #[cfg_attr(
+        feature = "force_exhaustive_checks",
+        deny(non_exhaustive_omitted_patterns)
+    )]
+    match value {
+        ast::Value::Number(token) => parse_number(&token.token().to_string()).map(Constant::Number),
+        ast::Value::ParenthesesExpression(expression) => evaluate_constant(expression),
+        ast::Value::String(token) => string_literal(token.token_type()).map(Constant::String),
+        ast::Value::Symbol(token) => match token.token_type() {
+            TokenType::Symbol {
+                symbol: Symbol::True,
+            } => Some(Constant::Bool(true)),
+            TokenType::Symbol {
+                symbol: Symbol::False,
+            } => Some(Constant::Bool(false)),
+            TokenType::Symbol {
+                symbol: Symbol::Nil,
+            } => Some(Constant::Nil),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn evaluate_arithmetic(binop: &ast::BinOp, lhs: f64, rhs: f64) -> Option<f64> {
+    #[cfg_attr(
+        feature = "force_exhaustive_checks",
+        deny(non_exhaustive_omitted_patterns)
+    )]
+    match binop {
+        ast::BinOp::Caret(_) => Some(lhs.powf(rhs)),
+        ast::BinOp::Minus(_) => Some(lhs - rhs),
+        ast::BinOp::Percent(_) => Some(lhs - (lhs / rhs).floor() * rhs),
+        ast::BinOp::Plus(_) => Some(lhs + rhs),
+        ast::BinOp::Slash(_) => Some(lhs / rhs),
+        ast::BinOp::Star(_) => Some(lhs * rhs),
+        _ => None,
+    }
+}
+
+fn evaluate_binary(
+    lhs: &ast::Expression,
+    binop: &ast::BinOp,
+    rhs: &ast::Expression,
+) -> Option<Constant> {
+    // `and` and `or` short circuit, so the other side doesn't always need to be known
+    match binop {
+        ast::BinOp::And(_) => {
+            let lhs = evaluate_constant(lhs)?;
+            return if lhs.is_truthy() {
+                evaluate_constant(rhs)
+            } else {
+                Some(lhs)
+            };
+        }
+
+        ast::BinOp::Or(_) => {
+            let lhs = evaluate_constant(lhs)?;
+            return if lhs.is_truthy() {
+                Some(lhs)
+            } else {
+                evaluate_constant(rhs)
+            };
+        }
+
+        _ => {}
+    }
+
+    let lhs = evaluate_constant(lhs)?;
+    let rhs = evaluate_constant(rhs)?;
+
+    match binop {
+        ast::BinOp::TwoEqual(_) => Some(Constant::Bool(lhs == rhs)),
+        ast::BinOp::TildeEqual(_) => Some(Constant::Bool(lhs != rhs)),
+
+        ast::BinOp::TwoDots(_) => Some(Constant::String(
+            lhs.to_concat_string()? + &rhs.to_concat_string()?,
+        )),
+
+        ast::BinOp::GreaterThan(_)
+        | ast::BinOp::GreaterThanEqual(_)
+        | ast::BinOp::LessThan(_)
+        | ast::BinOp::LessThanEqual(_) => {
+            let ordering = match (&lhs, &rhs) {
+                (Constant::Number(lhs), Constant::Number(rhs)) => lhs.partial_cmp(rhs),
+                (Constant::String(lhs), Constant::String(rhs)) => Some(lhs.cmp(rhs)),
+                _ => return None,
+            };
+
+            // Comparisons with NaN are always false
+            let ordering = match ordering {
+                Some(ordering) => ordering,
+                None => return Some(Constant::Bool(false)),
+            };
+
+            Some(Constant::Bool(match binop {
+                ast::BinOp::GreaterThan(_) => ordering.is_gt(),
+                ast::BinOp::GreaterThanEqual(_) => ordering.is_ge(),
+                ast::BinOp::LessThan(_) => ordering.is_lt(),
+                _ => ordering.is_le(),
+            }))
+        }
+
+        _ => evaluate_arithmetic(binop, lhs.as_number()?, rhs.as_number()?).map(Constant::Number),
+    }
+}
+
+pub fn evaluate_constant(expression: &ast::Expression) -> Option<Constant> {
+    #[cfg_attr(
+        feature = "force_exhaustive_checks",
+        deny(non_exhaustive_omitted_patterns)
+    )]
+    match expression {
+        ast::Expression::BinaryOperator { lhs, binop, rhs } => evaluate_binary(lhs, binop, rhs),
+
+        ast::Expression::Parentheses { expression, .. } => evaluate_constant(expression),
+
+        ast::Expression::UnaryOperator { unop, expression } => {
+            let value = evaluate_constant(expression)?;
+
+            #[cfg_attr(
+                feature = "force_exhaustive_checks",
+                deny(non_exhaustive_omitted_patterns)
+            )]
+            match unop {
+                ast::UnOp::Hash(_) => match value {
+                    Constant::String(string) => Some(Constant::Number(string.len() as f64)),
+                    _ => None,
+                },
+                ast::UnOp::Minus(_) => Some(Constant::Number(-value.as_number()?)),
+                ast::UnOp::Not(_) => Some(Constant::Bool(!value.is_truthy())),
+                _ => None,
+            }
+        }
+
+        ast::Expression::Value { value, .. } => evaluate_value(value),
+
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(code: &str) -> Option<Constant> {
+        let ast = full_moon::parse(&format!("return {code}")).unwrap();
+
+        match ast.nodes().last_stmt() {
+            Some(ast::LastStmt::Return(return_stmt)) => {
+                evaluate_constant(return_stmt.returns().iter().next().unwrap())
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(evaluate("255 + 1"), Some(Constant::Number(256.0)));
+        assert_eq!(evaluate("2 * 128"), Some(Constant::Number(256.0)));
+        assert_eq!(evaluate("(1 + 2) * 3"), Some(Constant::Number(9.0)));
+        assert_eq!(evaluate("-5 % 3"), Some(Constant::Number(1.0)));
+        assert_eq!(evaluate("2 ^ 10"), Some(Constant::Number(1024.0)));
+        assert_eq!(evaluate("0xFF"), Some(Constant::Number(255.0)));
+        assert_eq!(evaluate("1 + x"), None);
+    }
+
+    #[test]
+    fn test_strings() {
+        assert_eq!(
+            evaluate("\"a\" .. 'b' .. 1"),
+            Some(Constant::String("ab1".to_owned()))
+        );
+        assert_eq!(evaluate("#\"abc\""), Some(Constant::Number(3.0)));
+        assert_eq!(evaluate("\"a\\n\""), None);
+        assert_eq!(evaluate("\"a\" .. 1.5"), None);
+    }
+
+    #[test]
+    fn test_logic() {
+        assert_eq!(evaluate("not nil"), Some(Constant::Bool(true)));
+        assert_eq!(evaluate("1 == 1"), Some(Constant::Bool(true)));
+        assert_eq!(evaluate("\"a\" ~= \"a\""), Some(Constant::Bool(false)));
+        assert_eq!(evaluate("1 < 2"), Some(Constant::Bool(true)));
+        assert_eq!(evaluate("false and x"), Some(Constant::Bool(false)));
+        assert_eq!(evaluate("nil or 5"), Some(Constant::Number(5.0)));
+        assert_eq!(evaluate("true and x"), None);
+        assert_eq!(evaluate("1 < \"2\""), None);
+    }
+}
@@ -6,14 +6,29 @@ use full_moon::{
     tokenizer::{self, Position, TokenReference},
 };
 
+mod argument_type;
+mod constant_eval;
 pub mod name_paths;
 mod purge_trivia;
+mod purity;
+#[cfg(feature = "lua54")]
+mod reassignments;
+mod requires;
 pub mod scopes;
 mod side_effects;
 mod strip_parentheses;
 pub mod visit_nodes;
 
+pub use argument_type::{get_argument_type, PassedArgumentType};
+pub use constant_eval::{evaluate_constant, parse_number, Constant};
 pub use purge_trivia::purge_trivia;
+pub use purity::{HasPurity, Purity};
+#[cfg(feature = "lua54")]
+pub use reassignments::local_reassignments;
+pub use requires::{
+    local_require, require_argument, require_call_argument, require_path, single_string_argument,
+    string_literal, RequireArgument,
+};
 pub use side_effects::HasSideEffects;
 pub use strip_parentheses::strip_parentheses;
 
@@ -0,0 +1,208 @@
+// What evaluating an expression can do besides giving a value, shared by the lints that remove,
+// compare, or skip over code, so that they all agree on what code can do.
+// Unimplemented nodes are impure for safety reasons.
+
+use full_moon::ast;
+
+use super::evaluate_constant;
+
+/// Ordered from least to most that can happen when evaluating the expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Purity {
+    /// Can't run any other code, such as literals, functions, and reading variables.
+    Pure,
+    /// Can only run other code through metamethods, such as indexing, arithmetic, and
+    /// comparisons on values whose metatables aren't known.
+    Metamethods,
+    /// Can run anything, such as function and method calls.
+    Impure,
+}
+
+pub trait HasPurity {
+    fn purity(&self) -> Purity;
+}
+
+impl HasPurity for ast::Expression {
+    fn purity(&self) -> Purity {
+        // `1 + 2` and `"a" .. "b"` don't have values with metatables to look at
+        if evaluate_constant(self).is_some() {
+            return Purity::Pure;
+        }
+
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match self {
+            ast::Expression::BinaryOperator { lhs, binop, rhs } => {
+                let operator = match binop {
+                    ast::BinOp::And(_) | ast::BinOp::Or(_) => Purity::Pure,
+                    _ => Purity::Metamethods,
+                };
+
+                operator.max(lhs.purity()).max(rhs.purity())
+            }
+
+            ast::Expression::UnaryOperator { unop, expression } => match unop {
+                ast::UnOp::Not(_) => expression.purity(),
+                _ => Purity::Metamethods.max(expression.purity()),
+            },
+
+            ast::Expression::Parentheses { expression, .. } => expression.purity(),
+            ast::Expression::Value { value, .. } => value.purity(),
+            _ => Purity::Impure,
+        }
+    }
+}
+
+impl HasPurity for ast::Prefix {
+    fn purity(&self) -> Purity {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match self {
+            ast::Prefix::Expression(expression) => expression.purity(),
+            ast::Prefix::Name(_) => Purity::Pure,
+            _ => Purity::Impure,
+        }
+    }
+}
+
+impl HasPurity for ast::Suffix {
+    fn purity(&self) -> Purity {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match self {
+            ast::Suffix::Call(_) => Purity::Impure,
+            ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                Purity::Metamethods.max(expression.purity())
+            }
+            ast::Suffix::Index(_) => Purity::Metamethods,
+            _ => Purity::Impure,
+        }
+    }
+}
+
+impl HasPurity for ast::Value {
+    fn purity(&self) -> Purity {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match self {
+            ast::Value::Function(_)
+            | ast::Value::Number(_)
+            | ast::Value::String(_)
+            | ast::Value::Symbol(_) => Purity::Pure,
+            ast::Value::FunctionCall(_) => Purity::Impure,
+            ast::Value::ParenthesesExpression(expression) => expression.purity(),
+            ast::Value::TableConstructor(table_constructor) => table_constructor
+                .fields()
+                .into_iter()
+                .map(|field| match field {
+                    ast::Field::ExpressionKey { key, value, .. } => {
+                        key.purity().max(value.purity())
+                    }
+                    ast::Field::NameKey { value, .. } => value.purity(),
+                    ast::Field::NoKey(expression) => expression.purity(),
+                    _ => Purity::Impure,
+                })
+                .max()
+                .unwrap_or(Purity::Pure),
+            ast::Value::Var(var) => var.purity(),
+
+            #[cfg(feature = "roblox")]
+            ast::Value::IfExpression(if_expression) => {
+                let mut purity = if_expression
+                    .if_expression()
+                    .purity()
+                    .max(if_expression.condition().purity())
+                    .max(if_expression.else_expression().purity());
+
+                if let Some(else_if_expressions) = if_expression.else_if_expressions() {
+                    for else_if_expression in else_if_expressions {
+                        purity = purity
+                            .max(else_if_expression.condition().purity())
+                            .max(else_if_expression.expression().purity());
+                    }
+                }
+
+                purity
+            }
+
+            _ => Purity::Impure,
+        }
+    }
+}
+
+impl HasPurity for ast::Var {
+    fn purity(&self) -> Purity {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match self {
+            ast::Var::Expression(var_expr) => var_expr.purity(),
+            ast::Var::Name(_) => Purity::Pure,
+            _ => Purity::Impure,
+        }
+    }
+}
+
+impl HasPurity for ast::VarExpression {
+    fn purity(&self) -> Purity {
+        self.suffixes()
+            .map(HasPurity::purity)
+            .fold(self.prefix().purity(), Purity::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purity(code: &str) -> Purity {
+        let ast = full_moon::parse(&format!("return {code}")).unwrap();
+
+        match ast.nodes().last_stmt() {
+            Some(ast::LastStmt::Return(return_stmt)) => {
+                return_stmt.returns().iter().next().unwrap().purity()
+            }
+
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_pure() {
+        assert_eq!(purity("1"), Purity::Pure);
+        assert_eq!(purity("x"), Purity::Pure);
+        assert_eq!(purity("1 + 2"), Purity::Pure);
+        assert_eq!(purity("not x"), Purity::Pure);
+        assert_eq!(purity("x and y or nil"), Purity::Pure);
+        assert_eq!(purity("function() call() end"), Purity::Pure);
+        assert_eq!(purity("{ 1, x, key = 'value' }"), Purity::Pure);
+    }
+
+    #[test]
+    fn test_metamethods() {
+        assert_eq!(purity("x.y"), Purity::Metamethods);
+        assert_eq!(purity("x[1]"), Purity::Metamethods);
+        assert_eq!(purity("x + 1"), Purity::Metamethods);
+        assert_eq!(purity("#x"), Purity::Metamethods);
+        assert_eq!(purity("x == y"), Purity::Metamethods);
+        assert_eq!(purity("{ x.y }"), Purity::Metamethods);
+    }
+
+    #[test]
+    fn test_impure() {
+        assert_eq!(purity("call()"), Purity::Impure);
+        assert_eq!(purity("x:method()"), Purity::Impure);
+        assert_eq!(purity("x[call()]"), Purity::Impure);
+        assert_eq!(purity("1 + call()"), Purity::Impure);
+        assert_eq!(purity("{ call() }"), Purity::Impure);
+    }
+}
@@ -0,0 +1,86 @@
+// The scope manager counts `x.y = 1` as a write to `x`, so this finds the assignments
+// that replace a local variable itself, such as `x = 1` or `function x() end`.
+use std::collections::HashMap;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+use super::{
+    range,
+    scopes::{ScopeManager, Variable},
+};
+
+/// Every place a local variable is reassigned after being defined,
+/// by the range of the name it was defined with.
+pub fn local_reassignments(
+    ast: &Ast,
+    scope_manager: &ScopeManager,
+) -> HashMap<(usize, usize), Vec<(usize, usize)>> {
+    let mut visitor = ReassignmentVisitor {
+        reassignments: HashMap::new(),
+        scope_manager,
+    };
+
+    visitor.visit_ast(ast);
+
+    visitor
+        .reassignments
+        .into_iter()
+        .filter_map(|(variable, reassignments)| {
+            Some((
+                *scope_manager.variables[variable].identifiers.first()?,
+                reassignments,
+            ))
+        })
+        .collect()
+}
+
+struct ReassignmentVisitor<'a> {
+    reassignments: HashMap<Id<Variable>, Vec<(usize, usize)>>,
+    scope_manager: &'a ScopeManager,
+}
+
+impl ReassignmentVisitor<'_> {
+    fn reassign(&mut self, name: &TokenReference) {
+        if let Some(variable) = self
+            .scope_manager
+            .reference_at_byte(name.start_position().unwrap().bytes())
+            .and_then(|reference| reference.resolved)
+        {
+            self.reassignments
+                .entry(variable)
+                .or_default()
+                .push(range(name));
+        }
+    }
+}
+
+impl Visitor for ReassignmentVisitor<'_> {
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for var in assignment.variables() {
+            if let ast::Var::Name(name) = var {
+                self.reassign(name);
+            }
+        }
+    }
+
+    #[cfg(feature = "roblox")]
+    fn visit_compound_assignment(&mut self, compound_assignment: &ast::types::CompoundAssignment) {
+        if let ast::Var::Name(name) = compound_assignment.lhs() {
+            self.reassign(name);
+        }
+    }
+
+    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
+        let name = function_declaration.name();
+
+        if name.names().len() == 1 && name.method_name().is_none() {
+            self.reassign(name.names().iter().next().unwrap());
+        }
+    }
+}
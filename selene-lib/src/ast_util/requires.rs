@@ -0,0 +1,135 @@
+use std::fmt;
+
+use full_moon::{
+    ast,
+    tokenizer::{TokenReference, TokenType},
+};
+
+/// What is passed to `require`. Strings can be passed without parentheses, such as
+/// `require "module"`, so those aren't an expression.
+#[derive(Clone, Copy, Debug)]
+pub enum RequireArgument<'a> {
+    Expression(&'a ast::Expression),
+    String(&'a TokenReference),
+}
+
+impl<'a> RequireArgument<'a> {
+    /// The expression being required, unless it's a string passed without parentheses.
+    pub fn expression(self) -> Option<&'a ast::Expression> {
+        match self {
+            RequireArgument::Expression(expression) => Some(expression),
+            RequireArgument::String(_) => None,
+        }
+    }
+
+    /// The contents of the string being required, without its quotes, if it is one.
+    pub fn string_literal(self) -> Option<String> {
+        match self {
+            RequireArgument::Expression(expression) => string_literal(expression),
+            RequireArgument::String(token) => token_string_literal(token),
+        }
+    }
+}
+
+impl fmt::Display for RequireArgument<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RequireArgument::Expression(expression) => expression.fmt(formatter),
+            RequireArgument::String(token) => token.fmt(formatter),
+        }
+    }
+}
+
+fn token_string_literal(token: &TokenReference) -> Option<String> {
+    match token.token_type() {
+        TokenType::StringLiteral { literal, .. } => Some(literal.to_string()),
+        _ => None,
+    }
+}
+
+/// The contents of a string literal such as `"module"`, without its quotes.
+pub fn string_literal(expression: &ast::Expression) -> Option<String> {
+    match expression {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::String(token) => token_string_literal(token),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the argument passed to `require`, if the expression is a call to it.
+/// Whether `require` is the global still has to be checked by the caller.
+pub fn require_argument(expression: &ast::Expression) -> Option<RequireArgument<'_>> {
+    match expression {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::FunctionCall(call) => require_call_argument(call),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the argument passed to `require`, if this is a call to it.
+pub fn require_call_argument(call: &ast::FunctionCall) -> Option<RequireArgument<'_>> {
+    match call.prefix() {
+        ast::Prefix::Name(name) if name.token().to_string() == "require" => {}
+        _ => return None,
+    }
+
+    let mut suffixes = call.suffixes();
+
+    match (suffixes.next(), suffixes.next()) {
+        (Some(ast::Suffix::Call(ast::Call::AnonymousCall(args))), None) => single_argument(args),
+        _ => None,
+    }
+}
+
+/// The argument of a call that is given exactly one, such as `(x)` or `"x"`.
+fn single_argument(args: &ast::FunctionArgs) -> Option<RequireArgument<'_>> {
+    match args {
+        ast::FunctionArgs::Parentheses { arguments, .. } if arguments.len() == 1 => {
+            arguments.iter().next().map(RequireArgument::Expression)
+        }
+
+        ast::FunctionArgs::String(token) => Some(RequireArgument::String(token)),
+        _ => None,
+    }
+}
+
+/// The string a call is given as its only argument, such as `game:GetService("Players")`.
+pub fn single_string_argument(args: &ast::FunctionArgs) -> Option<String> {
+    single_argument(args)?.string_literal()
+}
+
+/// Matches `local Name = require(argument)`, returning the name and the argument.
+pub fn local_require(
+    local_assignment: &ast::LocalAssignment,
+) -> Option<(&TokenReference, RequireArgument<'_>)> {
+    let mut names = local_assignment.names().iter();
+    let mut expressions = local_assignment.expressions().iter();
+
+    match (
+        names.next(),
+        names.next(),
+        expressions.next(),
+        expressions.next(),
+    ) {
+        (Some(name), None, Some(expression), None) => Some((name, require_argument(expression)?)),
+        _ => None,
+    }
+}
+
+/// A readable form of what is being required, such as `ReplicatedStorage.Packages.Roact`.
+/// String literals are returned without their quotes.
+pub fn require_path(argument: RequireArgument) -> String {
+    if let Some(literal) = argument.string_literal() {
+        return literal;
+    }
+
+    argument
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -8,7 +8,9 @@ use full_moon::{
 };
 use id_arena::{Arena, Id};
 
-use super::expression_to_ident;
+use crate::standard_library::ArgumentType;
+
+use super::{expression_to_ident, get_argument_type, require_argument, PassedArgumentType};
 
 type Range = (usize, usize);
 
@@ -53,6 +55,15 @@ impl ScopeManager {
         None
     }
 
+    /// Returns true if the code uses `setfenv` or `getfenv`, which frameworks use to give each file
+    /// its own environment of globals. Globals in that code can come from the environment, and
+    /// assigning to them only changes the environment.
+    pub fn uses_environments(&self) -> bool {
+        self.references.iter().any(|(_, reference)| {
+            reference.resolved.is_none() && matches!(reference.name.as_str(), "setfenv" | "getfenv")
+        })
+    }
+
     fn variable_in_scope(&self, scope: Id<Scope>, variable_name: &str) -> VariableInScope {
         if let Some(scope) = self.scopes.get(scope) {
             for variable_id in scope.variables.iter().rev() {
@@ -105,11 +116,19 @@ pub struct Variable {
     pub references: Vec<Id<Reference>>,
     pub shadowed: Option<Id<Variable>>,
     pub is_self: bool,
+    /// True for globals defined in the file, such as by `x = 1` when there's no local `x`.
+    pub is_global: bool,
     pub value: Option<AssignedValue>,
+    /// The type of the value this variable was defined with, if it could be determined.
+    /// This does not account for any later assignments.
+    // TODO: Use the return types of standard library functions once they can be described
+    pub inferred_type: Option<PassedArgumentType>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AssignedValue {
+    /// The result of `require(...)`, which may have been loaded for its side effects
+    Require,
     StaticTable,
 }
 
@@ -229,6 +248,10 @@ fn get_assigned_value(expression: &ast::Expression) -> Option<AssignedValue> {
         }
     }
 
+    if require_argument(expression).is_some() {
+        return Some(AssignedValue::Require);
+    }
+
     None
 }
 
@@ -539,7 +562,15 @@ impl ScopeVisitor {
         };
 
         if self.find_variable(&name).is_none() {
-            let id = self.define_name_full(&name, identifier, write_expr);
+            let id = self.define_name_full_with_variable(
+                &name,
+                identifier,
+                write_expr,
+                Variable {
+                    is_global: true,
+                    ..Variable::default()
+                },
+            );
 
             for (_, reference) in &mut self.scope_manager.references {
                 if reference.read && reference.name == name && reference.resolved.is_none() {
@@ -710,6 +741,7 @@ impl Visitor for ScopeVisitor {
                 range(local_assignment),
                 Variable {
                     value: expression.and_then(get_assigned_value),
+                    inferred_type: expression.and_then(get_argument_type),
                     ..Default::default()
                 },
             );
@@ -913,7 +945,16 @@ impl Visitor for ScopeVisitor {
     }
 
     fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
-        self.define_name(local_function.name(), range(local_function.name()));
+        self.define_name_full_with_variable(
+            &local_function.name().token().to_string(),
+            range(local_function.name()),
+            range(local_function.name()),
+            Variable {
+                inferred_type: Some(ArgumentType::Function.into()),
+                ..Variable::default()
+            },
+        );
+
         self.open_scope(local_function.body());
     }
 
@@ -993,8 +1034,13 @@ impl Visitor for ScopeVisitor {
 
     #[cfg(feature = "roblox")]
     fn visit_type_info(&mut self, type_info: &ast::types::TypeInfo) {
-        if let ast::types::TypeInfo::Module { module, .. } = type_info {
-            self.read_name(module);
+        match type_info {
+            ast::types::TypeInfo::Module { module, .. } => self.read_name(module),
+
+            // `typeof(value)` reads `value`, even though it is only used for its type
+            ast::types::TypeInfo::Typeof { inner, .. } => self.read_expression(inner),
+
+            _ => {}
         }
     }
 }
@@ -0,0 +1,220 @@
+// Finds the modules in the output of Lua bundlers, which put every module in one file as
+// `package.preload["name"] = function(...) ... end`, so that each can be checked on its own.
+use crate::ast_util::string_literal;
+use full_moon::{ast, node::Node};
+
+// A file setting up a single preloaded module is more likely to be written by hand than bundled,
+// and should be checked (and fixed) as it is
+const MIN_BUNDLED_MODULES: usize = 2;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BundledModule {
+    /// The name the module is preloaded as, such as `src.util`.
+    pub name: String,
+    /// The body of the module's function, starting on the line after `function(...)` if nothing
+    /// else is on it. Positions in it are counted from the start of the module, not the bundle.
+    pub source: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Bundle {
+    pub modules: Vec<BundledModule>,
+    /// The bundle with the modules blanked out, leaving the code that loads them. Everything left
+    /// is at the same position as it is in the bundle.
+    pub rest: String,
+}
+
+/// Splits the source into its modules if it looks like the output of a bundler, returning None
+/// otherwise. Only modules preloaded at the top of the file, or in `do` blocks, are found.
+pub fn split_bundle(source: &str) -> Option<Bundle> {
+    if !source.contains("package.preload") {
+        return None;
+    }
+
+    let ast = full_moon::parse(source).ok()?;
+
+    let mut preloads = Vec::new();
+    preloaded_modules(ast.nodes(), &mut preloads);
+
+    if preloads.len() < MIN_BUNDLED_MODULES {
+        return None;
+    }
+
+    let mut rest = source.as_bytes().to_vec();
+
+    let modules = preloads
+        .into_iter()
+        .map(
+            |Preload {
+                 name,
+                 statement,
+                 body: (start, end),
+             }| {
+                let body = &source[start..end];
+
+                // The rest of the `function(...)` line is left to the bundle, so that the module's
+                // lines start at its first line of code
+                let start = match body.find('\n') {
+                    Some(newline) if body[..newline].trim().is_empty() => start + newline + 1,
+                    _ => start,
+                };
+
+                for byte in &mut rest[statement.0..statement.1] {
+                    if *byte != b'\n' && *byte != b'\r' {
+                        *byte = b' ';
+                    }
+                }
+
+                BundledModule {
+                    name,
+                    source: source[start..end].to_owned(),
+                }
+            },
+        )
+        .collect();
+
+    Some(Bundle {
+        modules,
+        rest: String::from_utf8(rest).expect("blanking out bytes should keep the source UTF-8"),
+    })
+}
+
+struct Preload {
+    name: String,
+    statement: (usize, usize),
+    body: (usize, usize),
+}
+
+fn preloaded_modules(block: &ast::Block, preloads: &mut Vec<Preload>) {
+    for stmt in block.stmts() {
+        match stmt {
+            ast::Stmt::Assignment(assignment) => {
+                if let Some(preload) = preloaded_module(assignment) {
+                    preloads.push(preload);
+                }
+            }
+
+            ast::Stmt::Do(do_block) => preloaded_modules(do_block.block(), preloads),
+
+            _ => {}
+        }
+    }
+}
+
+fn preloaded_module(assignment: &ast::Assignment) -> Option<Preload> {
+    if assignment.variables().len() != 1 || assignment.expressions().len() != 1 {
+        return None;
+    }
+
+    let var_expression = match assignment.variables().iter().next()? {
+        ast::Var::Expression(var_expression) => var_expression,
+        _ => return None,
+    };
+
+    match var_expression.prefix() {
+        ast::Prefix::Name(name) if name.token().to_string() == "package" => {}
+        _ => return None,
+    }
+
+    let suffixes = var_expression.suffixes().collect::<Vec<_>>();
+
+    let name = match suffixes.as_slice() {
+        [ast::Suffix::Index(ast::Index::Dot { name: preload, .. }), index]
+            if preload.token().to_string() == "preload" =>
+        {
+            match index {
+                ast::Suffix::Index(ast::Index::Dot { name, .. }) => name.token().to_string(),
+                ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                    string_literal(expression)?
+                }
+                _ => return None,
+            }
+        }
+
+        _ => return None,
+    };
+
+    let function_body = match assignment.expressions().iter().next()? {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::Function((_, function_body)) => function_body,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let (_, right_parenthesis) = function_body.parameters_parentheses().tokens();
+
+    Some(Preload {
+        name,
+        statement: (
+            assignment.start_position()?.bytes(),
+            assignment.end_position()?.bytes(),
+        ),
+        body: (
+            right_parenthesis.end_position()?.bytes(),
+            function_body.end_token().start_position()?.bytes(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &str = r#"package.preload["src.a"] = function(...)
+    local unused = 1
+    return {}
+end
+
+do
+    package.preload.b = function(...) return require("src.a") end
+end
+
+return require("b")
+"#;
+
+    #[test]
+    fn test_split_bundle() {
+        let bundle = split_bundle(BUNDLE).unwrap();
+
+        assert_eq!(
+            bundle.modules,
+            vec![
+                BundledModule {
+                    name: "src.a".to_owned(),
+                    source: "    local unused = 1\n    return {}\n".to_owned(),
+                },
+                BundledModule {
+                    name: "b".to_owned(),
+                    source: " return require(\"src.a\") ".to_owned(),
+                },
+            ]
+        );
+
+        assert_eq!(bundle.rest.len(), BUNDLE.len());
+        assert_eq!(
+            bundle.rest,
+            format!(
+                "{}\n{}\n{}\n   \n\ndo\n{}\nend\n\nreturn require(\"b\")\n",
+                " ".repeat(40),
+                " ".repeat(20),
+                " ".repeat(13),
+                " ".repeat(65),
+            )
+        );
+        assert!(full_moon::parse(&bundle.rest).is_ok());
+    }
+
+    #[test]
+    fn test_not_a_bundle() {
+        assert_eq!(split_bundle("local x = 1"), None);
+        assert_eq!(
+            split_bundle("package.preload.a = function() end\nreturn 1"),
+            None
+        );
+        assert_eq!(
+            split_bundle("package.preload.a = 1\npackage.preload.b = function() end"),
+            None
+        );
+    }
+}
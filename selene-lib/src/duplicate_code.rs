@@ -0,0 +1,352 @@
+// Finds code that has been copied between the files being checked, or within one of them.
+// Each statement is hashed without its whitespace and comments, and runs of statements that
+// hash the same in two places are reported once they're long enough.
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap},
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenType,
+    visitors::Visitor,
+};
+
+use crate::{
+    ast_util::range,
+    messages::Message,
+    rules::{Diagnostic, Label},
+    text::plural,
+};
+
+/// A statement, hashed so that it can be compared with statements in other files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HashedStatement {
+    pub hash: u64,
+    pub tokens: usize,
+    pub range: (u32, u32),
+}
+
+/// Code in `file` that is a copy of code in `original_file`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Duplicate {
+    pub file: PathBuf,
+    pub range: (u32, u32),
+    pub original_file: PathBuf,
+    pub original_range: (u32, u32),
+    pub statements: usize,
+    pub tokens: usize,
+}
+
+fn hash_statement<N: Node>(statement: &N) -> HashedStatement {
+    let mut hasher = DefaultHasher::new();
+    let mut tokens = 0;
+
+    for token in statement.tokens() {
+        if matches!(token.token_type(), TokenType::Eof) {
+            continue;
+        }
+
+        token.token().to_string().hash(&mut hasher);
+        tokens += 1;
+    }
+
+    HashedStatement {
+        hash: hasher.finish(),
+        tokens,
+        range: range(statement),
+    }
+}
+
+struct BlockVisitor {
+    blocks: Vec<Vec<HashedStatement>>,
+}
+
+impl Visitor for BlockVisitor {
+    fn visit_block(&mut self, block: &ast::Block) {
+        let mut statements = block
+            .stmts()
+            .map(hash_statement)
+            .collect::<Vec<HashedStatement>>();
+
+        if let Some(last_stmt) = block.last_stmt() {
+            statements.push(hash_statement(last_stmt));
+        }
+
+        if !statements.is_empty() {
+            self.blocks.push(statements);
+        }
+    }
+}
+
+/// The statements of every block in the file, including ones inside of functions and loops.
+pub fn statement_blocks(ast: &Ast) -> Vec<Vec<HashedStatement>> {
+    let mut visitor = BlockVisitor { blocks: Vec::new() };
+    visitor.visit_ast(ast);
+    visitor.blocks
+}
+
+// Where a run of statements starts, as indexes into the files given to find_duplicates
+#[derive(Clone, Copy)]
+struct Position {
+    file: usize,
+    block: usize,
+    start: usize,
+}
+
+/// Finds every run of statements with at least `min_tokens` tokens that also shows up earlier,
+/// either in an earlier file or earlier in the same file. Copies inside of a longer copy that is
+/// already reported are left out, and the longest copies come first.
+pub fn find_duplicates(
+    files: &BTreeMap<PathBuf, Vec<Vec<HashedStatement>>>,
+    min_tokens: usize,
+) -> Vec<Duplicate> {
+    let files = files.iter().collect::<Vec<_>>();
+    let block = |position: &Position| &files[position.file].1[position.block];
+
+    // The shortest runs of statements from each starting point that are long enough,
+    // grouped by their hashes. Files are sorted, so the first of each group comes first.
+    let mut windows: HashMap<u64, Vec<(Position, usize)>> = HashMap::new();
+
+    for (file_index, (_, blocks)) in files.iter().enumerate() {
+        for (block_index, statements) in blocks.iter().enumerate() {
+            for start in 0..statements.len() {
+                let mut hasher = DefaultHasher::new();
+                let mut tokens = 0;
+
+                for (length, statement) in statements[start..].iter().enumerate() {
+                    statement.hash.hash(&mut hasher);
+                    tokens += statement.tokens;
+
+                    if tokens >= min_tokens {
+                        windows.entry(hasher.finish()).or_default().push((
+                            Position {
+                                file: file_index,
+                                block: block_index,
+                                start,
+                            },
+                            length + 1,
+                        ));
+
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut candidates = Vec::new();
+
+    for positions in windows.values() {
+        let (original, length) = positions[0];
+        let original_statements = block(&original);
+
+        for (copy, copy_length) in &positions[1..] {
+            let copy_statements = block(copy);
+            let same_block = copy.file == original.file && copy.block == original.block;
+
+            let same = |offset: usize| {
+                original_statements[original.start + offset].hash
+                    == copy_statements[copy.start + offset].hash
+            };
+
+            if *copy_length != length || !(0..length).all(same) {
+                continue;
+            }
+
+            // A copy that overlaps what it was copied from is just the same code repeating
+            if same_block && copy.start < original.start + length {
+                continue;
+            }
+
+            // Runs that could start earlier are part of a longer copy, found from its own start
+            if original.start > 0
+                && copy.start > 0
+                && original_statements[original.start - 1].hash
+                    == copy_statements[copy.start - 1].hash
+            {
+                continue;
+            }
+
+            let mut statements = length;
+            while original.start + statements < original_statements.len()
+                && copy.start + statements < copy_statements.len()
+                && !(same_block && original.start + statements >= copy.start)
+                && same(statements)
+            {
+                statements += 1;
+            }
+
+            let original_run = &original_statements[original.start..original.start + statements];
+            let copy_run = &copy_statements[copy.start..copy.start + statements];
+
+            candidates.push(Duplicate {
+                file: files[copy.file].0.clone(),
+                range: (copy_run[0].range.0, copy_run[statements - 1].range.1),
+                original_file: files[original.file].0.clone(),
+                original_range: (
+                    original_run[0].range.0,
+                    original_run[statements - 1].range.1,
+                ),
+                statements,
+                tokens: copy_run.iter().map(|statement| statement.tokens).sum(),
+            });
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.tokens
+            .cmp(&a.tokens)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.range.cmp(&b.range))
+    });
+
+    let mut duplicates: Vec<Duplicate> = Vec::new();
+
+    for candidate in candidates {
+        // Copying a function also copies every block inside of it
+        let inside_reported = duplicates.iter().any(|duplicate| {
+            duplicate.file == candidate.file
+                && duplicate.range.0 <= candidate.range.0
+                && candidate.range.1 <= duplicate.range.1
+        });
+
+        if !inside_reported {
+            duplicates.push(candidate);
+        }
+    }
+
+    duplicates
+}
+
+pub fn duplicate_diagnostic(duplicate: &Duplicate) -> Diagnostic {
+    Diagnostic::new_complete(
+        "duplicate_code",
+        Message::new(plural(
+            duplicate.statements,
+            "duplicate_code.one",
+            "duplicate_code",
+        ))
+        .with("statements", duplicate.statements)
+        .with("file", duplicate.original_file.display()),
+        Label::new(duplicate.range),
+        vec![
+            format!("the copy is {} tokens long", duplicate.tokens),
+            "try moving the shared code into a function that both places can use".to_owned(),
+        ],
+        Vec::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(sources: &[(&str, &str)]) -> BTreeMap<PathBuf, Vec<Vec<HashedStatement>>> {
+        sources
+            .iter()
+            .map(|(path, code)| {
+                (
+                    PathBuf::from(path),
+                    statement_blocks(&full_moon::parse(code).unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    fn found(duplicates: &[Duplicate]) -> Vec<(&str, &str, usize)> {
+        duplicates
+            .iter()
+            .map(|duplicate| {
+                (
+                    duplicate.file.to_str().unwrap(),
+                    duplicate.original_file.to_str().unwrap(),
+                    duplicate.statements,
+                )
+            })
+            .collect()
+    }
+
+    const COPIED: &str = "local total = 0\nfor _, item in ipairs(items) do\n\ttotal = total + item.price * item.count\nend\nprint(total)\n";
+
+    #[test]
+    fn test_across_files() {
+        let files = files(&[
+            ("a.lua", &format!("local items = {{}}\n{COPIED}")),
+            (
+                "b.lua",
+                &format!("-- a comment\nlocal x = 1\n{COPIED}return x\n"),
+            ),
+        ]);
+
+        assert_eq!(
+            found(&find_duplicates(&files, 20)),
+            vec![("b.lua", "a.lua", 3)]
+        );
+    }
+
+    #[test]
+    fn test_ignores_whitespace_and_comments() {
+        let files = files(&[
+            ("a.lua", COPIED),
+            (
+                "b.lua",
+                "local total = 0 -- start from nothing\nfor _, item in ipairs(items) do total = total + item.price * item.count end\n\n\nprint(total)",
+            ),
+        ]);
+
+        assert_eq!(
+            found(&find_duplicates(&files, 20)),
+            vec![("b.lua", "a.lua", 3)]
+        );
+    }
+
+    #[test]
+    fn test_below_threshold() {
+        let files = files(&[("a.lua", COPIED), ("b.lua", COPIED)]);
+
+        assert!(find_duplicates(&files, 1000).is_empty());
+    }
+
+    #[test]
+    fn test_different_code() {
+        let files = files(&[
+            ("a.lua", COPIED),
+            ("b.lua", &COPIED.replace("item.price", "item.cost")),
+        ]);
+
+        assert!(find_duplicates(&files, 20).is_empty());
+    }
+
+    #[test]
+    fn test_same_file() {
+        let files = files(&[("a.lua", &format!("do\n{COPIED}end\ndo\n{COPIED}end\n"))]);
+
+        assert_eq!(
+            found(&find_duplicates(&files, 20)),
+            vec![("a.lua", "a.lua", 1)]
+        );
+    }
+
+    #[test]
+    fn test_every_copy() {
+        let files = files(&[("a.lua", COPIED), ("b.lua", COPIED), ("c.lua", COPIED)]);
+
+        assert_eq!(
+            found(&find_duplicates(&files, 20)),
+            vec![("b.lua", "a.lua", 3), ("c.lua", "a.lua", 3)]
+        );
+    }
+
+    #[test]
+    fn test_repeating_code() {
+        let files = files(&[("a.lua", "print(1, 2, 3)\nprint(1, 2, 3)\nprint(1, 2, 3)\n")]);
+
+        assert_eq!(
+            found(&find_duplicates(&files, 8)),
+            vec![("a.lua", "a.lua", 1), ("a.lua", "a.lua", 1)]
+        );
+    }
+}
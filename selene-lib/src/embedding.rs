@@ -0,0 +1,77 @@
+// What the bindings to other languages (selene-ffi and selene-py) share. They lint one piece of
+// source code at a time with a configuration given to them as JSON, and only have the built-in
+// standard libraries, since there's no project directory to read any others from.
+use crate::{
+    rules::Severity, standard_library::StandardLibrary, Checker, CheckerConfig, CheckerDiagnostic,
+};
+
+/// Reads a configuration with the same shape as selene.toml, or the default one if there's none.
+pub fn config_from_json(json: Option<&str>) -> Result<CheckerConfig<serde_json::Value>, String> {
+    match json {
+        Some(json) => serde_json::from_str(json)
+            .map_err(|error| format!("config was not in the correct format: {error}")),
+        None => Ok(CheckerConfig::default()),
+    }
+}
+
+/// The built-in standard library with the name, where names can be combined with `+`, such as
+/// `lua51+love2d`.
+pub fn builtin_standard_library(name: &str) -> Result<StandardLibrary, String> {
+    let mut standard_library: Option<StandardLibrary> = None;
+
+    for segment in name.split('+') {
+        let segment_library = StandardLibrary::from_name(segment)
+            .ok_or_else(|| format!("`{segment}` is not a built-in standard library"))?;
+
+        match standard_library.as_mut() {
+            Some(standard_library) => standard_library.extend(segment_library),
+            None => standard_library = Some(segment_library),
+        }
+    }
+
+    standard_library.ok_or_else(|| "standard library was empty".to_owned())
+}
+
+/// Lints the source code, returning every diagnostic that isn't allowed, in the order they appear.
+pub fn lint(
+    source: &str,
+    config: CheckerConfig<serde_json::Value>,
+) -> Result<Vec<CheckerDiagnostic>, String> {
+    let standard_library = builtin_standard_library(config.std())?;
+    let checker = Checker::new(config, standard_library).map_err(|error| error.to_string())?;
+
+    let ast = full_moon::parse(source).map_err(|error| format!("couldn't parse: {error}"))?;
+
+    let mut diagnostics = checker.test_on(&ast);
+    diagnostics.retain(|diagnostic| diagnostic.severity != Severity::Allow);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.diagnostic.start_position());
+
+    Ok(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_standard_library() {
+        let standard_library = builtin_standard_library("lua51+luau").unwrap();
+        assert!(standard_library.find_global(&["bit32"]).is_some());
+
+        assert!(builtin_standard_library("lua51+nonexistent").is_err());
+        assert!(builtin_standard_library("").is_err());
+    }
+
+    #[test]
+    fn test_lint() {
+        let diagnostics = lint("local x = 1", config_from_json(None).unwrap()).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].diagnostic.code, "unused_variable");
+
+        let config = config_from_json(Some(r#"{ "rules": { "unused_variable": "allow" } }"#));
+        assert!(lint("local x = 1", config.unwrap()).unwrap().is_empty());
+
+        assert!(config_from_json(Some("not json")).is_err());
+        assert!(lint("local = ", CheckerConfig::default()).is_err());
+    }
+}
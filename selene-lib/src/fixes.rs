@@ -0,0 +1,302 @@
+// Applies the fixes diagnostics come with to the code they were found in, and maps positions in
+// the code to where they are after, so that tools chaining several rewrites, such as a formatter
+// and selene's fixes, don't have to check the code again in between.
+use crate::rules::{Diagnostic, Fix, Label};
+
+/// Where each edit applied to a source was, for finding where positions in the source are after
+/// the edits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EditMap {
+    // The range each edit replaced and how long its replacement is, in order and not overlapping
+    edits: Vec<((u32, u32), u32)>,
+}
+
+impl EditMap {
+    /// Returns where the offset is after the edits, or `None` if it was inside of code that was
+    /// replaced. Offsets where code was inserted stay before the inserted code.
+    pub fn map_offset(&self, offset: u32) -> Option<u32> {
+        self.map(offset, false)
+    }
+
+    /// Returns where the range is after the edits, growing or shrinking it with any edits inside
+    /// of it. Returns `None` if either end was inside of code that was replaced.
+    pub fn map_range(&self, range: (u32, u32)) -> Option<(u32, u32)> {
+        if range.0 == range.1 {
+            let offset = self.map_offset(range.0)?;
+            return Some((offset, offset));
+        }
+
+        // Code inserted right at the start or end of the range is left out of it
+        Some((self.map(range.0, true)?, self.map(range.1, false)?))
+    }
+
+    fn map(&self, offset: u32, after_insertions: bool) -> Option<u32> {
+        let mut mapped = offset as i64;
+
+        for &((start, end), replacement_len) in &self.edits {
+            if offset < start || (offset == start && !(after_insertions && start == end)) {
+                break;
+            }
+
+            if offset < end {
+                return None;
+            }
+
+            mapped += replacement_len as i64 - (end - start) as i64;
+        }
+
+        Some(mapped as u32)
+    }
+
+    /// Returns the diagnostic at where it is after the edits, or `None` if its primary label
+    /// can't be mapped. Secondary labels and fixes that touch replaced code are dropped.
+    pub fn map_diagnostic(&self, diagnostic: Diagnostic) -> Option<Diagnostic> {
+        let map_label = |label: Label| {
+            Some(Label {
+                range: self.map_range(label.range)?,
+                message: label.message,
+            })
+        };
+
+        Some(Diagnostic {
+            primary_label: map_label(diagnostic.primary_label)?,
+            secondary_labels: diagnostic
+                .secondary_labels
+                .into_iter()
+                .filter_map(map_label)
+                .collect(),
+            fixes: diagnostic
+                .fixes
+                .into_iter()
+                .filter_map(|fix| {
+                    Some(Fix {
+                        range: self.map_range(fix.range)?,
+                        ..fix
+                    })
+                })
+                .collect(),
+            ..diagnostic
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Edited {
+    pub source: String,
+    /// Which of the edits were applied, by their index.
+    pub applied: Vec<usize>,
+    pub map: EditMap,
+}
+
+/// Applies as many of the edits as possible to the source, the same way as `apply_fixes`, along
+/// with where everything in the source is after them.
+pub fn apply_edits(source: &str, edits: &[&Fix]) -> Edited {
+    let mut order = (0..edits.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&index| edits[index].range);
+
+    let mut output = String::with_capacity(source.len());
+    let mut applied = Vec::new();
+    let mut map = EditMap::default();
+    let mut position = 0;
+
+    for index in order {
+        let edit = edits[index];
+        let (start, end) = (edit.range.0 as usize, edit.range.1 as usize);
+
+        if start < position || end > source.len() {
+            continue;
+        }
+
+        output.push_str(&source[position..start]);
+        output.push_str(&edit.replacement);
+        position = end;
+        applied.push(index);
+        map.edits.push((edit.range, edit.replacement.len() as u32));
+    }
+
+    output.push_str(&source[position..]);
+    applied.sort_unstable();
+
+    Edited {
+        source: output,
+        applied,
+        map,
+    }
+}
+
+/// Applies as many of the fixes as possible to the source, returning the new source
+/// and which of the fixes were applied, by their index.
+/// Fixes are applied in order of their range, ties going to whichever was given first,
+/// and fixes that overlap one that comes before it are skipped.
+pub fn apply_fixes(source: &str, fixes: &[&Fix]) -> (String, Vec<usize>) {
+    let edited = apply_edits(source, fixes);
+    (edited.source, edited.applied)
+}
+
+/// Applies fixes until there are none left, or `max_passes` passes were made. Skipped fixes, and
+/// problems that only show up once others are fixed, are found by checking the fixed source again
+/// with `check`, which returns `None` if the source shouldn't be kept, such as when it no longer
+/// parses. Returns the fixed source and how many fixes were applied.
+pub fn apply_fixes_until_settled(
+    source: &str,
+    max_passes: usize,
+    mut check: impl FnMut(&str) -> Option<Vec<Fix>>,
+) -> (String, usize) {
+    let mut source = source.to_owned();
+    let mut applied_count = 0;
+
+    let mut fixes = match check(&source) {
+        Some(fixes) => fixes,
+        None => return (source, 0),
+    };
+
+    for _ in 0..max_passes {
+        if fixes.is_empty() {
+            break;
+        }
+
+        let (fixed_source, applied) = apply_fixes(&source, &fixes.iter().collect::<Vec<_>>());
+
+        match check(&fixed_source) {
+            Some(next_fixes) => fixes = next_fixes,
+            None => break,
+        }
+
+        source = fixed_source;
+        applied_count += applied.len();
+    }
+
+    (source, applied_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_fixes() {
+        let remove_semicolon = Fix::removal((11, 12));
+        let rename = Fix::new((6, 7), "y".to_owned());
+
+        assert_eq!(
+            apply_fixes("local x = 1;", &[&remove_semicolon, &rename]),
+            ("local y = 1".to_owned(), vec![0, 1])
+        );
+    }
+
+    #[test]
+    fn test_overlapping_fixes() {
+        let first = Fix::new((0, 5), "a".to_owned());
+        let overlapping = Fix::new((3, 7), "b".to_owned());
+
+        assert_eq!(
+            apply_fixes("0123456789", &[&overlapping, &first]),
+            ("a56789".to_owned(), vec![1])
+        );
+    }
+
+    #[test]
+    fn test_edit_map() {
+        let source = "local x = 1;\nprint(x)";
+        let rename = Fix::new((6, 7), "value".to_owned());
+        let remove_semicolon = Fix::removal((11, 12));
+        let insert = Fix::new((13, 13), "-- x\n".to_owned());
+
+        let edited = apply_edits(source, &[&rename, &remove_semicolon, &insert]);
+        assert_eq!(edited.source, "local value = 1\n-- x\nprint(x)");
+
+        let map = edited.map;
+        assert_eq!(map.map_offset(0), Some(0));
+        assert_eq!(map.map_offset(6), Some(6));
+        assert_eq!(map.map_offset(8), Some(12));
+        assert_eq!(map.map_offset(11), Some(15));
+        assert_eq!(map.map_offset(13), Some(16));
+        assert_eq!(map.map_range((6, 7)), Some((6, 11)));
+        assert_eq!(map.map_range((6, 8)), Some((6, 12)));
+
+        // The inserted comment isn't part of the `print(x)` after it
+        assert_eq!(map.map_range((13, 21)), Some((21, 29)));
+        assert_eq!(&edited.source[21..29], "print(x)");
+    }
+
+    #[test]
+    fn test_edit_map_replaced() {
+        let replace = Fix::new((2, 6), "ab".to_owned());
+        let map = apply_edits("0123456789", &[&replace]).map;
+
+        assert_eq!(map.map_offset(4), None);
+        assert_eq!(map.map_range((0, 3)), None);
+        assert_eq!(map.map_range((1, 7)), Some((1, 5)));
+    }
+
+    #[test]
+    fn test_map_diagnostic() {
+        let source = "local x = 1\nlocal x = 2";
+        let map = apply_edits(source, &[&Fix::new((0, 0), "--!strict\n".to_owned())]).map;
+
+        let diagnostic = Diagnostic::new_complete(
+            "shadowing",
+            "shadowing variable `x`".to_owned(),
+            Label::new((18, 19)),
+            Vec::new(),
+            vec![Label::new_with_message(
+                (6, 7),
+                "previously defined here".to_owned(),
+            )],
+        )
+        .with_fix(Fix::new((18, 19), "y".to_owned()));
+
+        let mapped = map.map_diagnostic(diagnostic).unwrap();
+        assert_eq!(mapped.primary_label.range, (28, 29));
+        assert_eq!(mapped.secondary_labels[0].range, (16, 17));
+        assert_eq!(mapped.fixes[0].range, (28, 29));
+
+        let rewritten = apply_edits(source, &[&Fix::new((12, 23), String::new())]).map;
+        let diagnostic = Diagnostic::new("shadowing", String::new(), Label::new((18, 19)));
+        assert!(rewritten.map_diagnostic(diagnostic).is_none());
+    }
+
+    // Replaces every pair of neighboring "x"s with one, and the pairs overlap in longer runs
+    fn halve_runs(source: &str) -> Option<Vec<Fix>> {
+        Some(
+            (0..source.len().saturating_sub(1))
+                .filter(|&start| &source[start..start + 2] == "xx")
+                .map(|start| Fix::new((start as u32, start as u32 + 2), "x".to_owned()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_fixes_until_settled() {
+        assert_eq!(
+            apply_fixes_until_settled("xxxxxxxx", 10, halve_runs),
+            ("x".to_owned(), 7)
+        );
+    }
+
+    #[test]
+    fn test_fixes_until_settled_max_passes() {
+        assert_eq!(
+            apply_fixes_until_settled("xxxxxxxx", 1, halve_runs),
+            ("xxxx".to_owned(), 4)
+        );
+    }
+
+    #[test]
+    fn test_fixes_until_settled_rejected() {
+        let mut checks = 0;
+
+        let fixed = apply_fixes_until_settled("xxxx", 10, |source| {
+            checks += 1;
+
+            if source == "x" {
+                None
+            } else {
+                halve_runs(source)
+            }
+        });
+
+        assert_eq!(fixed, ("xx".to_owned(), 2));
+        assert_eq!(checks, 3);
+    }
+}
@@ -3,7 +3,7 @@
     feature = "force_exhaustive_checks",
     feature(non_exhaustive_omitted_patterns_lint)
 )]
-use std::{collections::HashMap, error::Error, fmt};
+use std::{collections::HashMap, error::Error, fmt, path::Path, time::Instant};
 
 use full_moon::ast::Ast;
 use serde::{
@@ -12,10 +12,23 @@ use serde::{
 };
 
 mod ast_util;
+pub mod bundles;
+pub mod duplicate_code;
+pub mod embedding;
+pub mod fixes;
+pub mod lines;
 mod lint_filtering;
+mod lua_version;
+pub mod messages;
 mod possible_std;
+pub mod renamed_lints;
+pub mod require_graph;
+#[cfg(feature = "roblox")]
+mod rojo;
 pub mod rules;
+pub mod sources;
 pub mod standard_library;
+pub mod symbols;
 mod text;
 
 #[cfg(test)]
@@ -37,6 +50,10 @@ pub struct CheckerError {
 pub enum CheckerErrorProblem {
     ConfigDeserializeError(Box<dyn Error>),
     RuleNewError(Box<dyn Error>),
+    UnknownLint {
+        lint: String,
+        suggestion: Option<&'static str>,
+    },
 }
 
 impl fmt::Display for CheckerError {
@@ -52,13 +69,25 @@ impl fmt::Display for CheckerError {
                 error
             ),
             RuleNewError(error) => write!(formatter, "{}", error),
+            UnknownLint { lint, suggestion } => {
+                write!(formatter, "Unknown lint `{lint}`")?;
+
+                if let Some(suggestion) = suggestion {
+                    write!(formatter, ", did you mean `{suggestion}`?")?;
+                }
+
+                write!(
+                    formatter,
+                    " If this lint is from another version of selene, you can set `unknown-lints = \"allow\"` to ignore it."
+                )
+            }
         }
     }
 }
 
 impl Error for CheckerError {}
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 #[serde(default)]
 #[serde(rename_all = "kebab-case")]
 pub struct CheckerConfig<V> {
@@ -66,15 +95,237 @@ pub struct CheckerConfig<V> {
     pub rules: HashMap<String, RuleVariation>,
     pub std: Option<String>,
 
+    /// A path or URL of another selene.toml that this one is put on top of.
+    /// This is not used by selene-lib itself, which is given the config after it's merged.
+    pub extends: Option<String>,
+
+    /// The SHA-256 that what `extends` points to has to have, so that it can't change unnoticed.
+    /// This is not used by selene-lib itself.
+    #[serde(alias = "extends_sha256")]
+    pub extends_sha256: Option<String>,
+
+    /// A semver requirement that the version of selene being used has to meet, such as ">=0.20, <0.30".
+    /// This is not checked by selene-lib itself.
+    #[serde(alias = "selene_version")]
+    pub selene_version: Option<String>,
+
+    /// The file extensions to check when selene is given a directory.
+    /// This is not used by selene-lib itself.
+    pub extensions: Option<Vec<String>>,
+
+    /// Where to find the source map of a file, where `{file}` is replaced with the path of the file.
+    /// This is not used by selene-lib itself.
+    #[serde(alias = "source_map")]
+    pub source_map: Option<String>,
+
+    /// How many seconds selene can spend checking one file before it gives up on it.
+    /// This is not used by selene-lib itself, see `Checker::test_on_file_before`.
+    #[serde(alias = "time_budget")]
+    pub time_budget: Option<f64>,
+
+    /// How many problems a run can find and still pass, for adopting lints a little at a time.
+    /// This is not used by selene-lib itself.
+    #[serde(alias = "quality_gate")]
+    pub quality_gate: Option<QualityGate>,
+
+    /// Whether files saved as UTF-16 are converted to UTF-8 to be checked, instead of being reported.
+    /// This is not used by selene-lib itself.
+    #[serde(alias = "transcode_utf16")]
+    pub transcode_utf16: bool,
+
+    /// Globs for the test files in the project, which are checked with `for_tests`.
+    /// Defaults to `DEFAULT_TEST_FILES`. This is not used by selene-lib itself.
+    #[serde(alias = "test_files")]
+    pub test_files: Option<Vec<String>>,
+
     // Not locked behind Roblox feature so that selene.toml for Roblox will
     // run even without it.
     pub roblox_std_source: RobloxStdSource,
+
+    #[serde(alias = "unknown_lints")]
+    pub unknown_lints: UnknownLints,
+
+    /// Severities that replace the ones in `rules` in some environments, such as `[severity.ci]`.
+    pub severity: SeverityOverrides,
+
+    /// Named sets of overrides, such as `[profile.ci]`, that can be applied with `apply_profile`.
+    pub profile: HashMap<String, ConfigProfile<V>>,
 }
 
+/// The names that busted and TestEZ give to tests.
+pub const DEFAULT_TEST_FILES: &[&str] = &[
+    "**/*_spec.lua",
+    "**/*.spec.lua",
+    "**/*.spec.luau",
+    "**/*_test.lua",
+    "**/*.test.lua",
+    "**/*.test.luau",
+];
+
 impl<V> CheckerConfig<V> {
     pub fn std(&self) -> &str {
         self.std.as_deref().unwrap_or("lua51")
     }
+
+    pub fn test_files(&self) -> Vec<&str> {
+        match &self.test_files {
+            Some(test_files) => test_files.iter().map(String::as_str).collect(),
+            None => DEFAULT_TEST_FILES.to_vec(),
+        }
+    }
+
+    /// The config to check test files with, which adds the globals of test frameworks to the
+    /// standard library, then applies the `test` profile if there is one.
+    pub fn for_tests(&self) -> Self
+    where
+        V: Clone,
+    {
+        let mut config = self.clone();
+        config.std = Some(format!("{}+testing", self.std()));
+
+        // Without a test profile, tests are only given the globals
+        let _ = config.apply_profile("test");
+
+        config
+    }
+
+    /// Moves lints in `rules` and `config`, including in profiles, from names they used to have to
+    /// their current ones, returning a warning for each. `Checker::new` does this itself, so this
+    /// only has to be called first to show the warnings.
+    pub fn rename_lints(&mut self) -> Vec<renamed_lints::RenamedLintWarning> {
+        renamed_lints::rename_lints(self, renamed_lints::RENAMED_LINTS)
+    }
+
+    /// Applies the profile with the given name on top of the rest of the config.
+    /// Returns false if there is no profile with that name.
+    #[must_use]
+    pub fn apply_profile(&mut self, name: &str) -> bool {
+        let profile = match self.profile.remove(name) {
+            Some(profile) => profile,
+            None => return false,
+        };
+
+        self.config.extend(profile.config);
+        self.rules.extend(profile.rules);
+
+        if profile.std.is_some() {
+            self.std = profile.std;
+        }
+
+        if profile.selene_version.is_some() {
+            self.selene_version = profile.selene_version;
+        }
+
+        if profile.extensions.is_some() {
+            self.extensions = profile.extensions;
+        }
+
+        if profile.source_map.is_some() {
+            self.source_map = profile.source_map;
+        }
+
+        if profile.time_budget.is_some() {
+            self.time_budget = profile.time_budget;
+        }
+
+        if profile.quality_gate.is_some() {
+            self.quality_gate = profile.quality_gate;
+        }
+
+        if let Some(transcode_utf16) = profile.transcode_utf16 {
+            self.transcode_utf16 = transcode_utf16;
+        }
+
+        if profile.test_files.is_some() {
+            self.test_files = profile.test_files;
+        }
+
+        if let Some(roblox_std_source) = profile.roblox_std_source {
+            self.roblox_std_source = roblox_std_source;
+        }
+
+        if let Some(unknown_lints) = profile.unknown_lints {
+            self.unknown_lints = unknown_lints;
+        }
+
+        self.severity.ci.extend(profile.severity.ci);
+
+        true
+    }
+
+    /// Replaces the severities in `rules` with the ones from `[severity.ci]`, for runs in CI.
+    /// Profiles should be applied first, so that their `[severity.ci]` is included.
+    pub fn apply_ci_severity(&mut self) {
+        self.rules.extend(std::mem::take(&mut self.severity.ci));
+    }
+}
+
+/// Severities of lints that are only used in some environments, so that a lint can be a warning
+/// locally but an error in CI without keeping two configs.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SeverityOverrides {
+    /// Applied by `CheckerConfig::apply_ci_severity`.
+    pub ci: HashMap<String, RuleVariation>,
+}
+
+/// Overrides for a `CheckerConfig`, where anything left out keeps the value it already had.
+/// Lint configurations replace the existing configuration for that lint entirely.
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigProfile<V> {
+    pub config: HashMap<String, V>,
+    pub rules: HashMap<String, RuleVariation>,
+    pub std: Option<String>,
+
+    #[serde(alias = "selene_version")]
+    pub selene_version: Option<String>,
+
+    pub extensions: Option<Vec<String>>,
+
+    #[serde(alias = "source_map")]
+    pub source_map: Option<String>,
+
+    #[serde(alias = "time_budget")]
+    pub time_budget: Option<f64>,
+
+    #[serde(alias = "quality_gate")]
+    pub quality_gate: Option<QualityGate>,
+
+    #[serde(alias = "transcode_utf16")]
+    pub transcode_utf16: Option<bool>,
+
+    #[serde(alias = "test_files")]
+    pub test_files: Option<Vec<String>>,
+
+    pub roblox_std_source: Option<RobloxStdSource>,
+
+    #[serde(alias = "unknown_lints")]
+    pub unknown_lints: Option<UnknownLints>,
+
+    /// Added to the severities of the config, replacing the ones for the same lints.
+    pub severity: SeverityOverrides,
+}
+
+impl<V> Default for ConfigProfile<V> {
+    fn default() -> Self {
+        ConfigProfile {
+            config: HashMap::new(),
+            rules: HashMap::new(),
+            std: None,
+            selene_version: None,
+            extensions: None,
+            source_map: None,
+            time_budget: None,
+            quality_gate: None,
+            transcode_utf16: None,
+            test_files: None,
+            roblox_std_source: None,
+            unknown_lints: None,
+            severity: SeverityOverrides::default(),
+        }
+    }
 }
 
 impl<V> Default for CheckerConfig<V> {
@@ -83,11 +334,43 @@ impl<V> Default for CheckerConfig<V> {
             config: HashMap::new(),
             rules: HashMap::new(),
             std: None,
+            extends: None,
+            extends_sha256: None,
+            selene_version: None,
+            extensions: None,
+            source_map: None,
+            time_budget: None,
+            quality_gate: None,
+            transcode_utf16: false,
+            test_files: None,
             roblox_std_source: RobloxStdSource::default(),
+            unknown_lints: UnknownLints::default(),
+            severity: SeverityOverrides::default(),
+            profile: HashMap::new(),
         }
     }
 }
 
+/// The most problems a run can find and still pass, such as `max-warnings = 50`. Lowering these as
+/// problems are fixed keeps new ones from being added while the old ones are cleaned up.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+pub struct QualityGate {
+    /// Errors from lints without their own budget. Defaults to 0, so any error fails the run.
+    #[serde(alias = "max_errors")]
+    pub max_errors: Option<usize>,
+
+    /// Warnings from lints without their own budget. Defaults to no limit.
+    #[serde(alias = "max_warnings")]
+    pub max_warnings: Option<usize>,
+
+    /// Errors and warnings from a single lint, such as `max-per-lint.unused_variable = 10`.
+    /// These lints don't count towards `max_errors` and `max_warnings`.
+    #[serde(alias = "max_per_lint")]
+    pub max_per_lint: HashMap<String, usize>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum RuleVariation {
@@ -119,6 +402,69 @@ impl Default for RobloxStdSource {
     }
 }
 
+/// What to do when selene.toml mentions a lint that selene doesn't know about.
+/// Allowing them is useful when the same selene.toml is used by different versions of selene.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnknownLints {
+    Allow,
+    #[default]
+    Deny,
+}
+
+fn validate_lint_names<V>(
+    config: &CheckerConfig<V>,
+    known_rules: &[&'static str],
+) -> Result<(), CheckerError> {
+    if config.unknown_lints == UnknownLints::Allow {
+        log::debug!("not checking for unknown lints, since unknown-lints is set to allow");
+        return Ok(());
+    }
+
+    let mut sections = vec![
+        ("rules", config.rules.keys().collect::<Vec<_>>()),
+        ("config", config.config.keys().collect()),
+    ];
+
+    if let Some(quality_gate) = &config.quality_gate {
+        sections.push(("quality-gate", quality_gate.max_per_lint.keys().collect()));
+    }
+
+    sections.push(("severity.ci", config.severity.ci.keys().collect()));
+
+    // Profiles that aren't being used are still checked, so typos in them are caught early
+    for profile in config.profile.values() {
+        sections.push(("profile", profile.rules.keys().collect()));
+        sections.push(("profile", profile.config.keys().collect()));
+        sections.push(("profile", profile.severity.ci.keys().collect()));
+
+        if let Some(quality_gate) = &profile.quality_gate {
+            sections.push(("profile", quality_gate.max_per_lint.keys().collect()));
+        }
+    }
+
+    for (section, names) in sections {
+        let mut unknown_names = names
+            .into_iter()
+            .filter(|name| !known_rules.contains(&name.as_str()))
+            .collect::<Vec<_>>();
+
+        unknown_names.sort();
+
+        if let Some(name) = unknown_names.first() {
+            return Err(CheckerError {
+                name: section,
+                problem: CheckerErrorProblem::UnknownLint {
+                    lint: name.to_string(),
+                    suggestion: text::closest_match(name, known_rules),
+                },
+            });
+        }
+    }
+
+    Ok(())
+}
+
 macro_rules! use_rules {
     {
         $(
@@ -126,7 +472,7 @@ macro_rules! use_rules {
         )+
 
         $(
-            #[$meta:meta]
+            #[cfg($cfg:meta)]
             {
                 $($meta_rule_name:ident: $meta_rule_path:ty,)+
             },
@@ -140,13 +486,100 @@ macro_rules! use_rules {
 
                 $(
                     $(
-                        #[$meta]
+                        #[cfg($cfg)]
                         stringify!($meta_rule_name),
                     )+
                 )+
             ];
         }
 
+        // Unlike ALL_RULES, this includes rules from disabled features, since selene.toml
+        // can still configure them.
+        const KNOWN_RULES: &[&str] = &[
+            $(
+                stringify!($rule_name),
+            )+
+
+            $(
+                $(
+                    stringify!($meta_rule_name),
+                )+
+            )+
+        ];
+
+        /// The lints that this build of selene leaves out, along with the feature they need, such
+        /// as `roblox`. selene.toml can still configure them, but they never run.
+        pub fn unavailable_lints() -> Vec<(&'static str, &'static str)> {
+            vec![
+                $(
+                    $(
+                        #[cfg(not($cfg))]
+                        (
+                            stringify!($meta_rule_name),
+                            // `feature = "roblox"` is given as the cfg, which is only the name wanted
+                            stringify!($cfg).rsplit('"').nth(1).unwrap_or_default(),
+                        ),
+                    )+
+                )+
+            ]
+        }
+
+        /// The luacheck warnings that a lint checks for, with the one it's closest to first.
+        /// Lints that luacheck has nothing like have none.
+        pub fn luacheck_codes(name: &str) -> &'static [&'static str] {
+            match name {
+                $(
+                    stringify!($rule_name) => <$rule_path as Rule>::LUACHECK_CODES,
+                )+
+
+                $(
+                    $(
+                        #[cfg($cfg)]
+                        stringify!($meta_rule_name) => <$meta_rule_path as Rule>::LUACHECK_CODES,
+                    )+
+                )+
+
+                _ => &[],
+            }
+        }
+
+        /// Information about every lint, such as for generating documentation.
+        pub fn lint_metadata() -> Vec<LintMetadata> {
+            macro_rules! metadata {
+                ($name:ident, $path:ty) => {
+                    LintMetadata {
+                        name: stringify!($name),
+                        rule_type: <$path as Rule>::RULE_TYPE,
+                        default_severity: <$path as Rule>::SEVERITY,
+                        luacheck_codes: <$path as Rule>::LUACHECK_CODES,
+                        // Lints without a config use (), which can't be represented in TOML
+                        default_config: toml::Value::try_from(
+                            <$path as Rule>::Config::default(),
+                        )
+                        .ok(),
+                        documentation: include_str!(concat!(
+                            "../../docs/src/lints/",
+                            stringify!($name),
+                            ".md"
+                        )),
+                    }
+                };
+            }
+
+            vec![
+                $(
+                    metadata!($rule_name, $rule_path),
+                )+
+
+                $(
+                    $(
+                        #[cfg($cfg)]
+                        metadata!($meta_rule_name, $meta_rule_path),
+                    )+
+                )+
+            ]
+        }
+
         pub struct Checker<V: 'static + DeserializeOwned> {
             config: CheckerConfig<V>,
             context: Context,
@@ -157,18 +590,30 @@ macro_rules! use_rules {
 
             $(
                 $(
-                    #[$meta]
+                    #[cfg($cfg)]
                     $meta_rule_name: $meta_rule_path,
                 )+
             )+
         }
 
         impl<V: 'static + DeserializeOwned> Checker<V> {
-            // TODO: Be more strict about config? Make sure all keys exist
             pub fn new(
                 mut config: CheckerConfig<V>,
-                standard_library: StandardLibrary,
+                mut standard_library: StandardLibrary,
             ) -> Result<Self, CheckerError> where V: for<'de> Deserializer<'de> {
+                // Files that declare globals check against a copy of the library
+                standard_library.share_globals();
+
+                for warning in config.rename_lints() {
+                    log::warn!("{warning}");
+                }
+
+                validate_lint_names(&config, KNOWN_RULES)?;
+
+                for (rule_name, variation) in &config.rules {
+                    log::debug!("`{rule_name}` is set to {variation:?}");
+                }
+
                 macro_rules! rule_field {
                     ($name:ident, $path:ty) => {{
                         let rule_name = stringify!($name);
@@ -176,6 +621,8 @@ macro_rules! use_rules {
                         let rule = <$path>::new({
                             match config.config.remove(rule_name) {
                                 Some(entry_generic) => {
+                                    log::debug!("using configuration from selene.toml for `{rule_name}`");
+
                                     <$path as Rule>::Config::deserialize(entry_generic).map_err(|error| {
                                         CheckerError {
                                             name: rule_name,
@@ -207,7 +654,7 @@ macro_rules! use_rules {
                     )+
                     $(
                         $(
-                            #[$meta]
+                            #[cfg($cfg)]
                             $meta_rule_name: {
                                 rule_field!($meta_rule_name, $meta_rule_path)
                             },
@@ -224,19 +671,144 @@ macro_rules! use_rules {
             }
 
             pub fn test_on(&self, ast: &Ast) -> Vec<CheckerDiagnostic> {
+                self.test_on_ast_context(ast, AstContext::from_ast(ast), None)
+                    .unwrap()
+            }
+
+            /// Like `test_on`, but lets lints know what file they are checking.
+            pub fn test_on_file(&self, ast: &Ast, file_path: &Path) -> Vec<CheckerDiagnostic> {
+                self.test_on_ast_context(ast, AstContext::from_ast_with_path(ast, file_path), None)
+                    .unwrap()
+            }
+
+            /// Like `test_on_file`, but gives up and returns None once the deadline has passed.
+            /// The deadline is only checked between lints, so a single slow lint still runs to
+            /// completion.
+            pub fn test_on_file_before(
+                &self,
+                ast: &Ast,
+                file_path: &Path,
+                deadline: Instant,
+            ) -> Option<Vec<CheckerDiagnostic>> {
+                self.test_on_ast_context(
+                    ast,
+                    AstContext::from_ast_with_path(ast, file_path),
+                    Some(deadline),
+                )
+            }
+
+            /// Reads the file from `sources`, then parses and checks it like `test_on_file`.
+            pub fn test_on_source<S: sources::SourceProvider + ?Sized>(
+                &self,
+                sources: &S,
+                file_path: &Path,
+            ) -> Result<Vec<CheckerDiagnostic>, sources::SourceError> {
+                let source = sources.read(file_path).map_err(sources::SourceError::Read)?;
+                let ast = full_moon::parse(&source).map_err(sources::SourceError::Parse)?;
+
+                Ok(self.test_on_file(&ast, file_path))
+            }
+
+            /// Runs only the lint with the given name, ignoring its severity and lint filters.
+            /// Returns None if there is no lint with that name. This is mostly useful for
+            /// measuring the performance of individual lints.
+            pub fn test_on_lint(
+                &self,
+                name: &str,
+                ast: &Ast,
+                ast_context: &AstContext,
+            ) -> Option<Vec<Diagnostic>> {
+                match name {
+                    $(
+                        stringify!($rule_name) => Some(self.$rule_name.pass(ast, &self.context, ast_context)),
+                    )+
+
+                    $(
+                        $(
+                            #[cfg($cfg)]
+                            stringify!($meta_rule_name) => Some(self.$meta_rule_name.pass(ast, &self.context, ast_context)),
+                        )+
+                    )+
+
+                    _ => None,
+                }
+            }
+
+            /// The severity the lint with the given name runs at, taking selene.toml into account.
+            /// Returns None if there is no lint with that name.
+            pub fn lint_severity(&self, name: &str) -> Option<Severity> {
+                match name {
+                    $(
+                        stringify!($rule_name) => Some(self.get_lint_severity(&self.$rule_name, stringify!($rule_name))),
+                    )+
+
+                    $(
+                        $(
+                            #[cfg($cfg)]
+                            stringify!($meta_rule_name) => Some(self.get_lint_severity(&self.$meta_rule_name, stringify!($meta_rule_name))),
+                        )+
+                    )+
+
+                    _ => None,
+                }
+            }
+
+            /// The severity of `circular_require`, which has to be checked across every file at once
+            /// using `require_graph` instead of through `test_on`.
+            pub fn circular_require_severity(&self) -> Severity {
+                self.get_lint_severity(&self.circular_require, "circular_require")
+            }
+
+            /// The severity of `duplicate_code`, which also has to be checked across every file,
+            /// using `duplicate_code` instead of through `test_on`.
+            pub fn duplicate_code_severity(&self) -> Severity {
+                self.get_lint_severity(&self.duplicate_code, "duplicate_code")
+            }
+
+            /// The fewest tokens a copy has to have for `duplicate_code` to report it.
+            pub fn duplicate_code_min_tokens(&self) -> usize {
+                self.duplicate_code.min_tokens()
+            }
+
+            fn test_on_ast_context(
+                &self,
+                ast: &Ast,
+                ast_context: AstContext,
+                deadline: Option<Instant>,
+            ) -> Option<Vec<CheckerDiagnostic>> {
                 let mut diagnostics = Vec::new();
+                let directives = lint_filtering::get_directives(ast);
+
+                // Globals declared by comments are only defined for this file
+                let declared_globals = directives.globals();
+                let declared_context;
+                let context = if declared_globals.is_empty() {
+                    &self.context
+                } else {
+                    declared_context = Context {
+                        standard_library: self.context.standard_library.with_globals(declared_globals),
+                        standard_library_is_set: self.context.standard_library_is_set,
+                    };
 
-                let ast_context = AstContext::from_ast(ast);
+                    &declared_context
+                };
 
                 macro_rules! check_rule {
                     ($name:ident) => {
+                        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                            log::debug!("ran out of time before `{}`", stringify!($name));
+                            return None;
+                        }
+
                         let rule = &self.$name;
 
                         let rule_pass = {
                             profiling::scope!(&format!("lint: {}", stringify!($name)));
-                            rule.pass(ast, &self.context, &ast_context)
+                            rule.pass(ast, context, &ast_context)
                         };
 
+                        log::trace!("`{}` found {} diagnostics", stringify!($name), rule_pass.len());
+
                         diagnostics.extend(&mut rule_pass.into_iter().map(|diagnostic| {
                             CheckerDiagnostic {
                                 diagnostic,
@@ -252,7 +824,7 @@ macro_rules! use_rules {
 
                 $(
                     $(
-                        #[$meta]
+                        #[cfg($cfg)]
                         {
                             check_rule!($meta_rule_name);
                         }
@@ -261,11 +833,12 @@ macro_rules! use_rules {
 
                 diagnostics = lint_filtering::filter_diagnostics(
                     ast,
+                    directives,
                     diagnostics,
                     self.get_lint_severity(&self.invalid_lint_filter, "invalid_lint_filter"),
                 );
 
-                diagnostics
+                Some(diagnostics)
             }
 
             fn get_lint_severity<R: Rule>(&self, _lint: &R, name: &'static str) -> Severity {
@@ -278,6 +851,19 @@ macro_rules! use_rules {
     };
 }
 
+pub struct LintMetadata {
+    pub name: &'static str,
+    pub rule_type: rules::RuleType,
+    pub default_severity: Severity,
+    /// The luacheck warnings the lint checks for, with the one it's closest to first.
+    pub luacheck_codes: &'static [&'static str],
+    /// The config the lint uses when selene.toml doesn't configure it,
+    /// or None if it doesn't have one.
+    pub default_config: Option<toml::Value>,
+    /// The lint's page from the documentation, in Markdown.
+    pub documentation: &'static str,
+}
+
 #[derive(Debug)]
 pub struct CheckerDiagnostic {
     pub diagnostic: Diagnostic,
@@ -288,36 +874,81 @@ pub fn rule_exists(name: &str) -> bool {
     ALL_RULES.contains(&name)
 }
 
+/// The names of every lint in this build of selene.
+pub fn all_rules() -> &'static [&'static str] {
+    &ALL_RULES
+}
+
 use_rules! {
     almost_swapped: rules::almost_swapped::AlmostSwappedLint,
     bad_string_escape: rules::bad_string_escape::BadStringEscapeLint,
+    banned_api: rules::banned_api::BannedApiLint,
+    circular_require: rules::circular_require::CircularRequireLint,
     compare_nan: rules::compare_nan::CompareNanLint,
+    confusable_identifier: rules::confusable_identifier::ConfusableIdentifierLint,
     constant_table_comparison: rules::constant_table_comparison::ConstantTableComparisonLint,
+    coroutine_misuse: rules::coroutine_misuse::CoroutineMisuseLint,
     deprecated: rules::deprecated::DeprecatedLint,
     divide_by_zero: rules::divide_by_zero::DivideByZeroLint,
+    duplicate_code: rules::duplicate_code::DuplicateCodeLint,
     duplicate_keys: rules::duplicate_keys::DuplicateKeysLint,
+    duplicate_require: rules::duplicate_require::DuplicateRequireLint,
     empty_if: rules::empty_if::EmptyIfLint,
+    error_usage: rules::error_usage::ErrorUsageLint,
     global_usage: rules::global_usage::GlobalLint,
     high_cyclomatic_complexity: rules::high_cyclomatic_complexity::HighCyclomaticComplexityLint,
     if_same_then_else: rules::if_same_then_else::IfSameThenElseLint,
     ifs_same_cond: rules::ifs_same_cond::IfsSameCondLint,
+    imprecise_number: rules::imprecise_number::ImpreciseNumberLint,
     incorrect_standard_library_use: rules::standard_library::StandardLibraryLint,
     invalid_lint_filter: rules::invalid_lint_filter::InvalidLintFilterLint,
+    loop_invariant: rules::loop_invariant::LoopInvariantLint,
+    metatable_misuse: rules::metatable_misuse::MetatableMisuseLint,
+    method_call_mismatch: rules::method_call_mismatch::MethodCallMismatchLint,
     mismatched_arg_count: rules::mismatched_arg_count::MismatchedArgCountLint,
+    mixed_line_endings: rules::mixed_line_endings::MixedLineEndingsLint,
+    module_return: rules::module_return::ModuleReturnLint,
     multiple_statements: rules::multiple_statements::MultipleStatementsLint,
     must_use: rules::must_use::MustUseLint,
+    number_style: rules::number_style::NumberStyleLint,
+    os_date_format: rules::os_date_format::OsDateFormatLint,
     parenthese_conditions: rules::parenthese_conditions::ParentheseConditionsLint,
+    pcall_misuse: rules::pcall_misuse::PcallMisuseLint,
+    require_order: rules::require_order::RequireOrderLint,
+    restricted: rules::restricted::RestrictedLint,
     shadowing: rules::shadowing::ShadowingLint,
+    string_too_long: rules::string_too_long::StringTooLongLint,
     suspicious_reverse_loop: rules::suspicious_reverse_loop::SuspiciousReverseLoopLint,
+    suspicious_semicolon: rules::suspicious_semicolon::SuspiciousSemicolonLint,
+    table_insert_misuse: rules::table_insert_misuse::TableInsertMisuseLint,
     type_check_inside_call: rules::type_check_inside_call::TypeCheckInsideCallLint,
     unbalanced_assignments: rules::unbalanced_assignments::UnbalancedAssignmentsLint,
+    undefined_field: rules::undefined_field::UndefinedFieldLint,
     undefined_variable: rules::undefined_variable::UndefinedVariableLint,
     unscoped_variables: rules::unscoped_variables::UnscopedVariablesLint,
+    unused_require: rules::unused_require::UnusedRequireLint,
     unused_variable: rules::unused_variable::UnusedVariableLint,
+    vararg_misuse: rules::vararg_misuse::VarargMisuseLint,
 
     #[cfg(feature = "roblox")]
     {
+        any_type_cast: rules::any_type_cast::AnyTypeCastLint,
+        prefer_compound_assignment: rules::prefer_compound_assignment::PreferCompoundAssignmentLint,
+        prefer_continue: rules::prefer_continue::PreferContinueLint,
+        prefer_string_interpolation: rules::prefer_string_interpolation::PreferStringInterpolationLint,
+        redundant_type_cast: rules::redundant_type_cast::RedundantTypeCastLint,
         roblox_incorrect_color3_new_bounds: rules::roblox_incorrect_color3_new_bounds::Color3BoundsLint,
         roblox_incorrect_roact_usage: rules::roblox_incorrect_roact_usage::IncorrectRoactUsageLint,
+        roblox_module_script: rules::roblox_module_script::RobloxModuleScriptLint,
+        roblox_parent_before_properties: rules::roblox_parent_before_properties::RobloxParentBeforePropertiesLint,
+        roblox_task_library: rules::roblox_task_library::RobloxTaskLibraryLint,
+        roblox_unchecked_remote_arguments: rules::roblox_unchecked_remote_arguments::RobloxUncheckedRemoteArgumentsLint,
+        unused_type: rules::unused_type::UnusedTypeLint,
+    },
+
+    #[cfg(feature = "lua54")]
+    {
+        const_reassignment: rules::const_reassignment::ConstReassignmentLint,
+        prefer_const: rules::prefer_const::PreferConstLint,
     },
 }
@@ -0,0 +1,81 @@
+// Diagnostics point at byte offsets, but people, editors, and the JSON output count columns in
+// characters, so every place that shows a line and column goes through here.
+use codespan::{FileId, Files, Location};
+
+/// The line and column of a byte offset, both starting at 0, with the column counted in
+/// characters. A `\r` before a `\n` is part of the line ending rather than the line, and a byte
+/// order mark at the start of the file isn't part of the first line. Offsets partway through a
+/// character, or past the end of the file, are moved back to the nearest one.
+pub fn location<S: AsRef<str>>(files: &Files<S>, file_id: FileId, byte_offset: u32) -> Location {
+    let source = files.source(file_id).as_ref();
+
+    let mut byte_offset = (byte_offset as usize).min(source.len());
+    while !source.is_char_boundary(byte_offset) {
+        byte_offset -= 1;
+    }
+
+    let location = files
+        .location(file_id, byte_offset as u32)
+        .expect("offset should be a character boundary inside of the file");
+
+    let mut column = location.column.to_usize();
+
+    if column > 0
+        && source[..byte_offset].ends_with('\r')
+        && source[byte_offset..].starts_with('\n')
+    {
+        column -= 1;
+    }
+
+    if location.line.to_usize() == 0 && column > 0 && source.starts_with('\u{FEFF}') {
+        column -= 1;
+    }
+
+    Location::new(location.line, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_and_column(source: &str, byte_offset: u32) -> (usize, usize) {
+        let mut files = Files::new();
+        let file_id = files.add("test", source);
+        let location = location(&files, file_id, byte_offset);
+        (location.line.to_usize(), location.column.to_usize())
+    }
+
+    #[test]
+    fn test_ascii() {
+        assert_eq!(line_and_column("local x = 1\nprint(x)", 18), (1, 6));
+    }
+
+    #[test]
+    fn test_multibyte_characters() {
+        // "日本" is 6 bytes, but only 2 characters
+        assert_eq!(line_and_column("print(\"日本\", x)", 16), (0, 12));
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert_eq!(line_and_column("local x\r\nprint(x)", 7), (0, 7));
+        assert_eq!(line_and_column("local x\r\nprint(x)", 8), (0, 7));
+        assert_eq!(line_and_column("local x\r\nprint(x)", 9), (1, 0));
+    }
+
+    #[test]
+    fn test_byte_order_mark() {
+        assert_eq!(line_and_column("\u{FEFF}local x = 1\nlocal y", 9), (0, 6));
+        assert_eq!(line_and_column("\u{FEFF}local x = 1\nlocal y", 21), (1, 6));
+    }
+
+    #[test]
+    fn test_inside_character() {
+        assert_eq!(line_and_column("x = \"日\"", 6), (0, 5));
+    }
+
+    #[test]
+    fn test_past_end() {
+        assert_eq!(line_and_column("x = 1", 100), (0, 5));
+    }
+}
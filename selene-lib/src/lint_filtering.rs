@@ -3,12 +3,15 @@ use crate::{
         first_code,
         visit_nodes::{NodeVisitor, VisitorType},
     },
+    messages::Message,
+    renamed_lints::{RenamedLint, RENAMED_LINTS},
     rule_exists,
     rules::{Diagnostic, Label, Severity},
+    standard_library::{Field, FieldKind, PropertyWritability},
     CheckerDiagnostic, RuleVariation,
 };
 use full_moon::{ast::Ast, node::Node, tokenizer::TokenType};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 const GLOBAL_LINT_PREFIX: &str = "#";
 
@@ -34,13 +37,75 @@ struct Filter {
     range: (usize, usize),
 }
 
+// A global declared for the file with `-- selene: globals(name)`, or with
+// `-- selene: read_globals(name)` when what it's a field of can't be assigned to
+#[derive(Clone, Debug)]
+struct DeclaredGlobal {
+    name: String,
+    read_only: bool,
+}
+
+/// Everything the `-- selene:` comments in a file ask for.
 #[derive(Default)]
+pub struct Directives {
+    filters: Vec<Result<Filter, Diagnostic>>,
+    globals: Vec<DeclaredGlobal>,
+    // Filters that use the old name of a renamed lint, which still work
+    deprecations: Vec<Diagnostic>,
+}
+
+impl Directives {
+    /// The globals the file declares, as they would be defined in a standard library.
+    pub fn globals(&self) -> BTreeMap<String, Field> {
+        let mut globals = BTreeMap::new();
+
+        // What's declared could be a table or a function, so it can be used as either
+        for global in &self.globals {
+            globals.insert(global.name.clone(), Field::from_field_kind(FieldKind::Any));
+        }
+
+        // `plugin` in `globals(plugin.hooks)` can be assigned to, but only has the fields declared.
+        // In `read_globals(vim.api)`, `vim` is already read only, since it isn't declared.
+        for global in self.globals.iter().filter(|global| !global.read_only) {
+            let mut parent = global.name.as_str();
+
+            while let Some((name, _)) = parent.rsplit_once('.') {
+                parent = name;
+                globals.entry(parent.to_owned()).or_insert_with(|| {
+                    Field::from_field_kind(FieldKind::Property(PropertyWritability::OverrideFields))
+                });
+            }
+        }
+
+        globals
+    }
+}
+
 struct FilterVisitor {
     comments_checked: HashSet<(usize, usize)>,
-    ranges: Vec<Result<Filter, Diagnostic>>,
+    directives: Directives,
+    renamed_lints: &'static [RenamedLint],
+}
+
+enum Directive {
+    Filters(Vec<FilterConfiguration>),
+    Globals { names: Vec<String>, read_only: bool },
 }
 
-fn parse_comment(comment: &str) -> Option<Vec<FilterConfiguration>> {
+// Global names can have fields, such as `foo.bar`
+fn is_valid_global_name(name: &str) -> bool {
+    name.split('.').all(|segment| {
+        segment
+            .chars()
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+            && segment
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || character == '_')
+    })
+}
+
+fn parse_comment(comment: &str) -> Option<Directive> {
     let global_stripped = comment.strip_prefix(GLOBAL_LINT_PREFIX);
     let global = global_stripped.is_some();
     let config = global_stripped.unwrap_or(comment).strip_prefix("selene:")?;
@@ -72,10 +137,19 @@ fn parse_comment(comment: &str) -> Option<Vec<FilterConfiguration>> {
         "allow" => RuleVariation::Allow,
         "deny" => RuleVariation::Deny,
         "warn" => RuleVariation::Warn,
+
+        // Globals are declared for the whole file, wherever the comment is
+        "globals" | "read_globals" => {
+            return Some(Directive::Globals {
+                names: lint.split(',').map(ToOwned::to_owned).collect(),
+                read_only: variation == "read_globals",
+            })
+        }
+
         _ => return None,
     };
 
-    Some(
+    Some(Directive::Filters(
         lint.split(',')
             .map(|lint| FilterConfiguration {
                 global,
@@ -83,10 +157,12 @@ fn parse_comment(comment: &str) -> Option<Vec<FilterConfiguration>> {
                 variation,
             })
             .collect(),
-    )
+    ))
 }
 
 impl NodeVisitor for FilterVisitor {
+    // The invalid filter diagnostics are returned rather than kept, so their size doesn't matter
+    #[allow(clippy::result_large_err)]
     fn visit_node(&mut self, node: &dyn Node, visitor_type: VisitorType) {
         if NODES_TO_IGNORE.contains(&visitor_type) {
             return;
@@ -113,7 +189,30 @@ impl NodeVisitor for FilterVisitor {
             .map(|line| line.split_whitespace().collect::<String>())
             {
                 let configurations = match parse_comment(&comment) {
-                    Some(configurations) => configurations,
+                    Some(Directive::Filters(configurations)) => configurations,
+
+                    Some(Directive::Globals { names, read_only }) => {
+                        for name in names {
+                            if is_valid_global_name(&name) {
+                                self.directives
+                                    .globals
+                                    .push(DeclaredGlobal { name, read_only });
+                            } else {
+                                self.directives.filters.push(Err(Diagnostic::new(
+                                    "invalid_lint_filter",
+                                    Message::new("invalid_lint_filter.invalid_global")
+                                        .with("name", name),
+                                    Label::new((
+                                        trivia_start_position.bytes(),
+                                        trivia_end_position.bytes(),
+                                    )),
+                                )));
+                            }
+                        }
+
+                        continue;
+                    }
+
                     None => continue,
                 };
 
@@ -127,8 +226,31 @@ impl NodeVisitor for FilterVisitor {
                     )
                 });
 
-                self.ranges
-                    .extend(configurations.into_iter().map(|configuration| {
+                let renamed_lints = self.renamed_lints;
+                let deprecations = &mut self.directives.deprecations;
+
+                self.directives
+                    .filters
+                    .extend(configurations.into_iter().map(|mut configuration| {
+                        if let Some(renamed) = renamed_lints
+                            .iter()
+                            .find(|renamed| renamed.old_name == configuration.lint)
+                        {
+                            deprecations.push(Diagnostic::new(
+                                "invalid_lint_filter",
+                                Message::new("invalid_lint_filter.renamed")
+                                    .with("old_name", renamed.old_name)
+                                    .with("new_name", renamed.new_name)
+                                    .with("removed_in", renamed.removed_in),
+                                Label::new((
+                                    trivia_start_position.bytes(),
+                                    trivia_end_position.bytes(),
+                                )),
+                            ));
+
+                            configuration.lint = renamed.new_name.to_owned();
+                        }
+
                         if rule_exists(&configuration.lint) {
                             Ok(Filter {
                                 configuration,
@@ -141,7 +263,8 @@ impl NodeVisitor for FilterVisitor {
                         } else {
                             Err(Diagnostic::new(
                                 "invalid_lint_filter",
-                                format!("no lint named `{}` exists", configuration.lint),
+                                Message::new("invalid_lint_filter.no_lint")
+                                    .with("name", &configuration.lint),
                                 Label::new((
                                     trivia_start_position.bytes(),
                                     trivia_end_position.bytes(),
@@ -154,10 +277,19 @@ impl NodeVisitor for FilterVisitor {
     }
 }
 
-fn get_filter_ranges(ast: &Ast) -> Vec<Result<Filter, Diagnostic>> {
-    let mut filter_visitor = FilterVisitor::default();
+pub fn get_directives(ast: &Ast) -> Directives {
+    get_directives_with_renames(ast, RENAMED_LINTS)
+}
+
+fn get_directives_with_renames(ast: &Ast, renamed_lints: &'static [RenamedLint]) -> Directives {
+    let mut filter_visitor = FilterVisitor {
+        comments_checked: HashSet::new(),
+        directives: Directives::default(),
+        renamed_lints,
+    };
+
     filter_visitor.visit_nodes(ast);
-    filter_visitor.ranges
+    filter_visitor.directives
 }
 
 #[derive(Debug)]
@@ -183,14 +315,14 @@ impl FilterInstruction {
 
 pub fn filter_diagnostics(
     ast: &Ast,
+    directives: Directives,
     mut diagnostics: Vec<CheckerDiagnostic>,
     invalid_lint_filter_severity: Severity,
 ) -> Vec<CheckerDiagnostic> {
-    let filter_ranges = get_filter_ranges(ast);
     let (mut filters, mut failures) = (Vec::new(), Vec::new());
     let mut new_diagnostics;
 
-    for thing in filter_ranges {
+    for thing in directives.filters {
         match thing {
             Ok(filter) => filters.push(filter),
             Err(failure) => failures.push(failure),
@@ -213,7 +345,7 @@ pub fn filter_diagnostics(
                     if filter.comment_range.0 >= first_code.0.bytes() {
                         failures.push(Diagnostic::new_complete(
                             "invalid_lint_filter",
-                            "global filters must come before any code".to_owned(),
+                            Message::new("invalid_lint_filter.global_after_code"),
                             Label::new(filter.comment_range),
                             Vec::new(),
                             vec![Label::new_with_message(
@@ -234,7 +366,7 @@ pub fn filter_diagnostics(
                         if possibly_conflicting.configuration.lint == filter.configuration.lint {
                             failures.push(Diagnostic::new_complete(
                                 "invalid_lint_filter",
-                                "filter conflicts with a previous one for the same code".to_owned(),
+                                Message::new("invalid_lint_filter.conflicts"),
                                 Label::new(filter.comment_range),
                                 Vec::new(),
                                 vec![Label::new_with_message(
@@ -339,22 +471,68 @@ pub fn filter_diagnostics(
         diagnostic: failure,
     }));
 
+    // The filters still work, so these are only ever warnings
+    if invalid_lint_filter_severity != Severity::Allow {
+        new_diagnostics.extend(directives.deprecations.into_iter().map(|deprecation| {
+            CheckerDiagnostic {
+                severity: Severity::Warning,
+                diagnostic: deprecation,
+            }
+        }));
+    }
+
     new_diagnostics
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{
         test_util::{test_full_run, test_full_run_config},
         CheckerConfig, RuleVariation,
     };
     use std::collections::HashMap;
 
+    #[test]
+    fn test_renamed_lint_filter() {
+        const RENAMED: &[RenamedLint] = &[RenamedLint {
+            old_name: "empty_block",
+            new_name: "empty_if",
+            removed_in: "0.30.0",
+        }];
+
+        let ast = full_moon::parse("-- selene: allow(empty_block)\nif true then end\n").unwrap();
+        let directives = get_directives_with_renames(&ast, RENAMED);
+
+        match directives.filters.as_slice() {
+            [Ok(filter)] => assert_eq!(filter.configuration.lint, "empty_if"),
+            filters => panic!("expected one filter, got {filters:?}"),
+        }
+
+        assert_eq!(
+            directives
+                .deprecations
+                .iter()
+                .map(|deprecation| deprecation.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["`empty_block` has been renamed to `empty_if`, and the old name will stop working in selene 0.30.0"]
+        );
+
+        let diagnostics = filter_diagnostics(&ast, directives, Vec::new(), Severity::Error);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
     #[test]
     fn test_lint_filtering() {
         test_full_run("lint_filtering", "lint_filtering");
     }
 
+    #[test]
+    fn test_declared_globals() {
+        test_full_run("lint_filtering", "declared_globals");
+    }
+
     #[test]
     fn test_just_comments() {
         test_full_run("lint_filtering", "just_comments");
@@ -0,0 +1,33 @@
+// The versions of Lua that lints with a `lua_version` option can be configured for.
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum LuaVersion {
+    #[default]
+    #[serde(rename = "5.1")]
+    Lua51,
+    #[serde(rename = "5.2")]
+    Lua52,
+    #[serde(rename = "5.3")]
+    Lua53,
+    #[serde(rename = "5.4")]
+    Lua54,
+    #[serde(rename = "luau")]
+    Luau,
+}
+
+impl LuaVersion {
+    // Lua 5.3 added 64-bit integers, before that (and in Luau) every number is a double
+    pub fn has_integers(self) -> bool {
+        matches!(self, LuaVersion::Lua53 | LuaVersion::Lua54)
+    }
+
+    // Lua 5.1 creates an `arg` table in vararg functions, which 5.2 removed
+    pub fn has_implicit_arg(self) -> bool {
+        self == LuaVersion::Lua51
+    }
+
+    pub fn has_table_pack(self) -> bool {
+        self != LuaVersion::Lua51
+    }
+}
@@ -0,0 +1,344 @@
+// Lets diagnostics be shown in other languages. Lints give a message ID and its arguments instead
+// of a formatted message, and a catalog has each message written with `{argument}` wherever an
+// argument goes. The English catalog is in locales/en.yml, and is what every other catalog is
+// checked against.
+use std::{collections::HashMap, fmt};
+
+use serde::Deserialize;
+
+use crate::rules::Diagnostic;
+
+lazy_static::lazy_static! {
+    static ref ENGLISH: Catalog = Catalog::from_yaml(include_str!("../locales/en.yml"))
+        .expect("locales/en.yml isn't a valid catalog");
+}
+
+// The catalogs that come with selene besides English, by their locale
+const BUNDLED: &[(&str, &str)] = &[("pt-BR", include_str!("../locales/pt-BR.yml"))];
+
+/// A message that can be shown in another language, such as `shadowing` with a `name`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    /// The ID of the message in catalogs, which is None for messages that can't be translated yet.
+    pub id: Option<&'static str>,
+    pub arguments: Vec<(&'static str, String)>,
+    /// The message in English.
+    pub text: String,
+}
+
+impl Message {
+    /// Panics if `id` isn't in locales/en.yml.
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            id: Some(id),
+            arguments: Vec::new(),
+            text: String::new(),
+        }
+        .formatted()
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl fmt::Display) -> Self {
+        self.arguments.push((name, value.to_string()));
+        self.formatted()
+    }
+
+    fn formatted(mut self) -> Self {
+        if let Some(id) = self.id {
+            self.text = Catalog::english()
+                .format(id, &self.arguments)
+                .unwrap_or_else(|| panic!("message `{id}` isn't in locales/en.yml"));
+        }
+
+        self
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Self {
+            id: None,
+            arguments: Vec::new(),
+            text,
+        }
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        text.to_owned().into()
+    }
+}
+
+/// Messages in one language, by their ID.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// The messages selene is written with, which have every message ID.
+    pub fn english() -> &'static Catalog {
+        &ENGLISH
+    }
+
+    pub fn from_yaml(contents: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(contents)
+    }
+
+    /// The catalog that comes with selene for the locale, such as `pt-BR`, ignoring case.
+    pub fn bundled(locale: &str) -> Option<Catalog> {
+        if locale.eq_ignore_ascii_case("en") {
+            return Some(Catalog::english().clone());
+        }
+
+        BUNDLED
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(locale))
+            .map(|(name, contents)| {
+                Catalog::from_yaml(contents).unwrap_or_else(|error| {
+                    panic!("locales/{name}.yml isn't a valid catalog: {error}")
+                })
+            })
+    }
+
+    /// The locales of the catalogs that come with selene.
+    pub fn bundled_locales() -> impl Iterator<Item = &'static str> {
+        std::iter::once("en").chain(BUNDLED.iter().map(|(name, _)| *name))
+    }
+
+    /// Returns the message with each `{argument}` filled in, or None if the catalog doesn't have it.
+    /// Arguments that aren't given are left as they are written. Values are put in as they are, so
+    /// a value with `{argument}` in it doesn't get filled in too.
+    pub fn format(&self, id: &str, arguments: &[(&str, String)]) -> Option<String> {
+        let mut rest = self.messages.get(id)?.as_str();
+        let mut message = String::with_capacity(rest.len());
+
+        while let Some(start) = rest.find('{') {
+            message.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let value = rest[1..].split_once('}').and_then(|(name, _)| {
+                arguments
+                    .iter()
+                    .find(|(argument, _)| *argument == name)
+                    .map(|(_, value)| (name, value))
+            });
+
+            match value {
+                Some((name, value)) => {
+                    message.push_str(value);
+                    rest = &rest[name.len() + 2..];
+                }
+
+                None => {
+                    message.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        message.push_str(rest);
+        Some(message)
+    }
+
+    /// Replaces the message of the diagnostic with the one in this catalog, if it has one.
+    pub fn localize(&self, diagnostic: &mut Diagnostic) {
+        if let Some(message) = diagnostic
+            .message_id
+            .and_then(|id| self.format(id, &diagnostic.message_arguments))
+        {
+            diagnostic.message = message;
+        }
+    }
+
+    /// Returns the problems with the catalog compared to the English one, such as messages that
+    /// don't exist or arguments they don't have.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let mut ids: Vec<_> = self.messages.keys().collect();
+        ids.sort();
+
+        for id in ids {
+            let english = match Catalog::english().messages.get(id) {
+                Some(english) => english,
+                None => {
+                    problems.push(format!("there is no message `{id}`"));
+                    continue;
+                }
+            };
+
+            for argument in arguments(&self.messages[id]) {
+                if !arguments(english).contains(&argument) {
+                    problems.push(format!("message `{id}` has no argument `{argument}`"));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+// The names of the `{argument}`s in the message
+fn arguments(message: &str) -> Vec<&str> {
+    message
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    // The string literals passed to every `Message::new(...)` outside of tests, other than ones
+    // that are only compared against, such as `what == "function"`
+    fn message_ids(code: &str) -> Vec<String> {
+        let code = code.split("#[cfg(test)]").next().unwrap();
+        let mut ids = Vec::new();
+
+        for call in code.split("Message::new(").skip(1) {
+            let mut depth = 1;
+            let mut literal: Option<String> = None;
+            let mut previous = String::new();
+
+            for character in call.chars() {
+                if let Some(text) = &mut literal {
+                    if character == '"' {
+                        if !previous.trim_end().ends_with("==") {
+                            ids.push(text.clone());
+                        }
+
+                        literal = None;
+                        previous.clear();
+                    } else {
+                        text.push(character);
+                    }
+
+                    continue;
+                }
+
+                match character {
+                    '"' => {
+                        literal = Some(String::new());
+                        continue;
+                    }
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+
+                if depth == 0 {
+                    break;
+                }
+
+                previous.push(character);
+            }
+        }
+
+        ids
+    }
+
+    fn rust_files(directory: &Path, files: &mut Vec<PathBuf>) {
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                rust_files(&path, files);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bundled_catalogs() {
+        for locale in Catalog::bundled_locales() {
+            let catalog = Catalog::bundled(locale).unwrap();
+            assert_eq!(catalog.problems(), Vec::<String>::new(), "{locale}");
+
+            for id in Catalog::english().messages.keys() {
+                assert!(
+                    catalog.messages.contains_key(id),
+                    "locales/{locale}.yml has no message `{id}`"
+                );
+            }
+        }
+
+        assert!(Catalog::bundled("PT-br").is_some());
+        assert!(Catalog::bundled("xx").is_none());
+    }
+
+    #[test]
+    fn test_message_ids_exist() {
+        let mut files = Vec::new();
+        rust_files(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut files,
+        );
+
+        let mut count = 0;
+
+        for file in files {
+            for id in message_ids(&fs::read_to_string(&file).unwrap()) {
+                assert!(
+                    Catalog::english().messages.contains_key(&id),
+                    "{} uses the message `{id}`, which isn't in locales/en.yml",
+                    file.display()
+                );
+
+                count += 1;
+            }
+        }
+
+        assert!(count > 100, "only found {count} message IDs");
+    }
+
+    #[test]
+    fn test_message() {
+        let message = Message::new("shadowing").with("name", "x");
+        assert_eq!(message.text, "shadowing variable `x`");
+        assert_eq!(message.arguments, vec![("name", "x".to_owned())]);
+    }
+
+    #[test]
+    fn test_catalog() {
+        let catalog = Catalog::from_yaml(
+            "shadowing: \"variável `{name}` sombreada\"\n\
+             empty_if.if: \"{kind} vazio\"\n\
+             no_such_message: \"?\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.format("shadowing", &[("name", "x".to_owned())]),
+            Some("variável `x` sombreada".to_owned())
+        );
+        assert_eq!(catalog.format("empty_if.else", &[]), None);
+
+        assert_eq!(
+            catalog.problems(),
+            vec![
+                "message `empty_if.if` has no argument `kind`".to_owned(),
+                "there is no message `no_such_message`".to_owned(),
+            ]
+        );
+
+        // Values are only put in once, even if they look like arguments themselves
+        let values = Catalog::from_yaml("message: \"{a} and {b}, {c} {{b}\"\n").unwrap();
+        assert_eq!(
+            values.format(
+                "message",
+                &[("a", "{b}".to_owned()), ("b", "{a}".to_owned())]
+            ),
+            Some("{b} and {a}, {c} {{a}".to_owned())
+        );
+    }
+}
@@ -2,35 +2,36 @@ use std::borrow::Borrow;
 
 use crate::{standard_library::StandardLibrary, text};
 
+/// Notes about the standard libraries that have the global, and help on how to use them.
 pub fn possible_standard_library_notes<S: Borrow<str>>(
     name_path: &[S],
     standard_library_is_set: bool,
-) -> Vec<String> {
+) -> (Vec<String>, Option<String>) {
     let possible_standard_libraries = possible_standard_libraries(name_path);
 
     if possible_standard_libraries.is_empty() {
-        return Vec::new();
+        return (Vec::new(), None);
     }
 
-    let mut notes = vec![format!(
+    let notes = vec![format!(
         "`{}` was found in the {} standard libar{}",
         name_path.join("."),
         text::english_list(&possible_standard_libraries),
         text::plural(possible_standard_libraries.len(), "y", "ies"),
     )];
 
-    if !standard_library_is_set {
-        notes.push(format!(
+    let help = (!standard_library_is_set).then(|| {
+        format!(
             "you can set the standard library by putting the following inside selene.toml:\n{}",
             possible_standard_libraries
                 .iter()
                 .map(|library| format!("std = \"{library}\""))
                 .collect::<Vec<_>>()
                 .join("\n")
-        ));
-    }
+        )
+    });
 
-    notes
+    (notes, help)
 }
 
 fn possible_standard_libraries<S: Borrow<str>>(name_path: &[S]) -> Vec<&'static str> {
@@ -39,6 +40,11 @@ fn possible_standard_libraries<S: Borrow<str>>(name_path: &[S]) -> Vec<&'static
     let mut possible_standard_libraries = Vec::new();
 
     for (name, default_standard_library) in StandardLibrary::all_default_standard_libraries() {
+        // Only meant to be added to the standard library of test files
+        if *name == "testing" {
+            continue;
+        }
+
         if default_standard_library.find_global(name_path).is_some() {
             possible_standard_libraries.push(*name);
         }
@@ -0,0 +1,224 @@
+// Lints that have been renamed keep working under their old names for a while, so that renaming
+// a lint doesn't break every selene.toml and lint filter that uses it.
+use std::{collections::HashMap, fmt};
+
+use crate::CheckerConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenamedLint {
+    pub old_name: &'static str,
+    pub new_name: &'static str,
+    /// The version of selene that stops accepting the old name, such as `0.30.0`.
+    pub removed_in: &'static str,
+}
+
+/// Every lint that has been renamed. Entries are removed once selene reaches their `removed_in`
+/// version, after which the old name is an unknown lint.
+pub const RENAMED_LINTS: &[RenamedLint] = &[];
+
+/// Returns the rename of the lint, if the name is one a lint used to have.
+pub fn renamed_lint(old_name: &str) -> Option<&'static RenamedLint> {
+    RENAMED_LINTS
+        .iter()
+        .find(|renamed| renamed.old_name == old_name)
+}
+
+/// A selene.toml that uses the old name of a lint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenamedLintWarning {
+    /// Where the old name is, such as `rules` or `profile.ci.config`.
+    pub section: String,
+    pub lint: RenamedLint,
+}
+
+impl fmt::Display for RenamedLintWarning {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "[{}] `{}` has been renamed to `{}`, and the old name will stop working in selene {}",
+            self.section, self.lint.old_name, self.lint.new_name, self.lint.removed_in
+        )
+    }
+}
+
+pub(crate) fn rename_lints<V>(
+    config: &mut CheckerConfig<V>,
+    renamed_lints: &[RenamedLint],
+) -> Vec<RenamedLintWarning> {
+    let mut warnings = Vec::new();
+
+    rename_keys(&mut config.rules, "rules", renamed_lints, &mut warnings);
+    rename_keys(&mut config.config, "config", renamed_lints, &mut warnings);
+    rename_keys(
+        &mut config.severity.ci,
+        "severity.ci",
+        renamed_lints,
+        &mut warnings,
+    );
+
+    if let Some(quality_gate) = &mut config.quality_gate {
+        rename_keys(
+            &mut quality_gate.max_per_lint,
+            "quality-gate.max-per-lint",
+            renamed_lints,
+            &mut warnings,
+        );
+    }
+
+    let mut profile_names = config.profile.keys().cloned().collect::<Vec<_>>();
+    profile_names.sort();
+
+    for name in profile_names {
+        let profile = config.profile.get_mut(&name).unwrap();
+
+        rename_keys(
+            &mut profile.rules,
+            &format!("profile.{name}.rules"),
+            renamed_lints,
+            &mut warnings,
+        );
+
+        rename_keys(
+            &mut profile.config,
+            &format!("profile.{name}.config"),
+            renamed_lints,
+            &mut warnings,
+        );
+
+        rename_keys(
+            &mut profile.severity.ci,
+            &format!("profile.{name}.severity.ci"),
+            renamed_lints,
+            &mut warnings,
+        );
+
+        if let Some(quality_gate) = &mut profile.quality_gate {
+            rename_keys(
+                &mut quality_gate.max_per_lint,
+                &format!("profile.{name}.quality-gate.max-per-lint"),
+                renamed_lints,
+                &mut warnings,
+            );
+        }
+    }
+
+    warnings
+}
+
+fn rename_keys<T>(
+    map: &mut HashMap<String, T>,
+    section: &str,
+    renamed_lints: &[RenamedLint],
+    warnings: &mut Vec<RenamedLintWarning>,
+) {
+    for renamed in renamed_lints {
+        if let Some(value) = map.remove(renamed.old_name) {
+            // When both names are used, the new one is kept
+            map.entry(renamed.new_name.to_owned()).or_insert(value);
+
+            warnings.push(RenamedLintWarning {
+                section: section.to_owned(),
+                lint: *renamed,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConfigProfile, RuleVariation};
+
+    const RENAMED: &[RenamedLint] = &[RenamedLint {
+        old_name: "empty_block",
+        new_name: "empty_if",
+        removed_in: "0.30.0",
+    }];
+
+    #[test]
+    fn test_rename_lints() {
+        let mut profile = ConfigProfile::default();
+        profile
+            .config
+            .insert("empty_block".to_owned(), toml::Value::Boolean(true));
+
+        let mut config = CheckerConfig::<toml::Value>::default();
+        config
+            .rules
+            .insert("empty_block".to_owned(), RuleVariation::Allow);
+        config.profile.insert("ci".to_owned(), profile);
+
+        let warnings = rename_lints(&mut config, RENAMED);
+
+        assert_eq!(config.rules.get("empty_if"), Some(&RuleVariation::Allow));
+        assert!(!config.rules.contains_key("empty_block"));
+        assert!(config.profile["ci"].config.contains_key("empty_if"));
+
+        assert_eq!(
+            warnings
+                .iter()
+                .map(|warning| warning.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "[rules] `empty_block` has been renamed to `empty_if`, and the old name will stop working in selene 0.30.0",
+                "[profile.ci.config] `empty_block` has been renamed to `empty_if`, and the old name will stop working in selene 0.30.0",
+            ]
+        );
+
+        assert!(rename_lints(&mut config, RENAMED).is_empty());
+    }
+
+    #[test]
+    fn test_new_name_wins() {
+        let mut config = CheckerConfig::<toml::Value>::default();
+        config
+            .rules
+            .insert("empty_block".to_owned(), RuleVariation::Allow);
+        config
+            .rules
+            .insert("empty_if".to_owned(), RuleVariation::Deny);
+
+        assert_eq!(rename_lints(&mut config, RENAMED).len(), 1);
+        assert_eq!(config.rules.get("empty_if"), Some(&RuleVariation::Deny));
+    }
+
+    // Old names are only kept for a while, so this fails once selene reaches `removed_in`
+    #[test]
+    fn test_renamed_lints_are_removed() {
+        fn version(version: &str) -> Vec<u64> {
+            version
+                .split('.')
+                .map(|part| part.parse().expect("versions should be like 0.30.0"))
+                .collect()
+        }
+
+        let current_version = version(env!("CARGO_PKG_VERSION"));
+
+        for renamed in RENAMED_LINTS {
+            assert!(
+                current_version < version(renamed.removed_in),
+                "`{}` should have stopped working in selene {}, remove it from RENAMED_LINTS",
+                renamed.old_name,
+                renamed.removed_in
+            );
+        }
+    }
+
+    #[test]
+    fn test_renamed_lints_are_valid() {
+        for renamed in RENAMED_LINTS {
+            assert!(
+                crate::KNOWN_RULES.contains(&renamed.new_name),
+                "`{}` was renamed to `{}`, which isn't a lint",
+                renamed.old_name,
+                renamed.new_name
+            );
+
+            assert!(
+                !crate::KNOWN_RULES.contains(&renamed.old_name),
+                "`{}` is still the name of a lint",
+                renamed.old_name
+            );
+        }
+    }
+}
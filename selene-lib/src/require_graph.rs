@@ -0,0 +1,492 @@
+// Finds the modules each file requires while it is loading, so that require cycles can be
+// found across every file being checked. Modules are found the same way Rojo lays them out,
+// or for string requires, the same way `package.path` does by default.
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ffi::OsString,
+    path::{Component, Path, PathBuf},
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+use crate::{
+    ast_util::{
+        range, require_call_argument, single_string_argument, string_literal, RequireArgument,
+    },
+    messages::Message,
+    rules::{Diagnostic, Label},
+};
+
+/// A require that runs when the module is loaded, along with the files it could be requiring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleRequire {
+    pub range: (u32, u32),
+    pub candidates: Vec<PathBuf>,
+}
+
+/// One step of a require cycle, where `file` requires `required` at `range`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequireLink {
+    pub file: PathBuf,
+    pub range: (u32, u32),
+    pub required: PathBuf,
+}
+
+/// Removes `.` and resolves `..` without touching the file system.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+
+    normalized
+}
+
+// The instance Rojo creates for the file, written as a path, such as `src/shared/Module`
+fn instance_of_file(file_path: &Path) -> Option<PathBuf> {
+    let file_name = file_path.file_name()?.to_str()?;
+    let (stem, _) = file_name.rsplit_once('.')?;
+
+    if stem == "init" {
+        Some(file_path.parent()?.to_path_buf())
+    } else {
+        Some(file_path.with_file_name(stem))
+    }
+}
+
+fn module_candidates(base: &Path) -> Vec<PathBuf> {
+    let base = normalize_path(base);
+
+    let with_extension = |extension: &str| {
+        let mut path = OsString::from(base.as_os_str());
+        path.push(extension);
+        PathBuf::from(path)
+    };
+
+    vec![
+        with_extension(".lua"),
+        with_extension(".luau"),
+        base.join("init.lua"),
+        base.join("init.luau"),
+    ]
+}
+
+struct LoadTimeRequireVisitor<'a> {
+    aliases: HashMap<String, PathBuf>,
+    file_path: &'a Path,
+    function_depth: usize,
+    requires: Vec<ModuleRequire>,
+}
+
+impl LoadTimeRequireVisitor<'_> {
+    fn instance_path(&self, expression: &ast::Expression) -> Option<PathBuf> {
+        let (prefix, suffixes) = match expression {
+            ast::Expression::Value { value, .. } => match &**value {
+                ast::Value::Var(ast::Var::Name(name)) => return self.name(name.token()),
+                ast::Value::Var(ast::Var::Expression(var_expression)) => (
+                    var_expression.prefix(),
+                    var_expression.suffixes().collect::<Vec<_>>(),
+                ),
+                ast::Value::FunctionCall(call) => (call.prefix(), call.suffixes().collect()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let mut path = match prefix {
+            ast::Prefix::Name(name) => self.name(name.token())?,
+            _ => return None,
+        };
+
+        for suffix in suffixes {
+            let child = match suffix {
+                ast::Suffix::Index(ast::Index::Dot { name, .. }) => name.token().to_string(),
+                ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                    string_literal(expression)?
+                }
+                ast::Suffix::Call(ast::Call::MethodCall(method_call))
+                    if matches!(
+                        method_call.name().token().to_string().as_str(),
+                        "WaitForChild" | "FindFirstChild"
+                    ) =>
+                {
+                    single_string_argument(method_call.args())?
+                }
+                _ => return None,
+            };
+
+            if child == "Parent" {
+                path = path.parent()?.to_path_buf();
+            } else {
+                path.push(child);
+            }
+        }
+
+        Some(path)
+    }
+
+    fn name(&self, token: &full_moon::tokenizer::Token) -> Option<PathBuf> {
+        let name = token.to_string();
+
+        match self.aliases.get(&name) {
+            Some(path) => Some(path.to_owned()),
+            None if name == "script" => instance_of_file(self.file_path),
+            None => None,
+        }
+    }
+
+    fn candidates(&self, argument: RequireArgument) -> Option<Vec<PathBuf>> {
+        let string = match argument.string_literal() {
+            Some(string) => string,
+            None => {
+                return Some(module_candidates(
+                    &self.instance_path(argument.expression()?)?,
+                ))
+            }
+        };
+
+        // Aliases such as `@pkg/module` depend on configuration we don't have
+        if string.starts_with('@') {
+            return None;
+        }
+
+        if string.starts_with("./") || string.starts_with("../") {
+            return Some(module_candidates(&self.file_path.parent()?.join(string)));
+        }
+
+        if string.ends_with(".lua") || string.ends_with(".luau") {
+            return Some(vec![normalize_path(Path::new(&string))]);
+        }
+
+        if string.contains('/') {
+            Some(module_candidates(Path::new(&string)))
+        } else {
+            Some(module_candidates(Path::new(&string.replace('.', "/"))))
+        }
+    }
+}
+
+impl Visitor for LoadTimeRequireVisitor<'_> {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        if self.function_depth > 0 {
+            return;
+        }
+
+        for (index, name) in local_assignment.names().iter().enumerate() {
+            let name = name.token().to_string();
+
+            match local_assignment
+                .expressions()
+                .iter()
+                .nth(index)
+                .and_then(|expression| self.instance_path(expression))
+            {
+                Some(path) => self.aliases.insert(name, path),
+                None => self.aliases.remove(&name),
+            };
+        }
+    }
+
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for var in assignment.variables() {
+            if let ast::Var::Name(name) = var {
+                self.aliases.remove(&name.token().to_string());
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if self.function_depth > 0 {
+            return;
+        }
+
+        if let Some(candidates) =
+            require_call_argument(call).and_then(|argument| self.candidates(argument))
+        {
+            self.requires.push(ModuleRequire {
+                range: range(call),
+                candidates,
+            });
+        }
+    }
+}
+
+/// Every require of another module that runs as soon as the file is loaded.
+/// Requires inside of functions are left out, as they are commonly used to break cycles.
+pub fn load_time_requires(ast: &Ast, file_path: &Path) -> Vec<ModuleRequire> {
+    let file_path = normalize_path(file_path);
+
+    let mut visitor = LoadTimeRequireVisitor {
+        aliases: HashMap::new(),
+        file_path: &file_path,
+        function_depth: 0,
+        requires: Vec::new(),
+    };
+
+    visitor.visit_ast(ast);
+    visitor.requires
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    InProgress,
+    Done,
+}
+
+struct CycleFinder<'a> {
+    modules: &'a BTreeMap<PathBuf, Vec<ModuleRequire>>,
+    states: HashMap<&'a Path, VisitState>,
+    stack: Vec<RequireLink>,
+    cycles: Vec<Vec<RequireLink>>,
+    seen: HashSet<Vec<PathBuf>>,
+}
+
+impl<'a> CycleFinder<'a> {
+    fn visit(&mut self, file: &'a Path) {
+        self.states.insert(file, VisitState::InProgress);
+
+        for module_require in &self.modules[file] {
+            let required = match module_require
+                .candidates
+                .iter()
+                .find_map(|candidate| self.modules.get_key_value(candidate.as_path()))
+            {
+                Some((required, _)) => required.as_path(),
+                None => continue,
+            };
+
+            let link = RequireLink {
+                file: file.to_path_buf(),
+                range: module_require.range,
+                required: required.to_path_buf(),
+            };
+
+            match self.states.get(required) {
+                Some(VisitState::InProgress) => {
+                    let start = self
+                        .stack
+                        .iter()
+                        .position(|link| link.file == required)
+                        .unwrap_or(self.stack.len());
+
+                    let mut cycle = self.stack[start..].to_vec();
+                    cycle.push(link);
+                    self.record(cycle);
+                }
+
+                Some(VisitState::Done) => {}
+
+                None => {
+                    self.stack.push(link);
+                    self.visit(required);
+                    self.stack.pop();
+                }
+            }
+        }
+
+        self.states.insert(file, VisitState::Done);
+    }
+
+    // The same cycle can be found starting from any of its files, so it always starts at the first one
+    fn record(&mut self, mut cycle: Vec<RequireLink>) {
+        let first = (0..cycle.len())
+            .min_by_key(|index| &cycle[*index].file)
+            .unwrap_or_default();
+        cycle.rotate_left(first);
+
+        if self
+            .seen
+            .insert(cycle.iter().map(|link| link.file.clone()).collect())
+        {
+            self.cycles.push(cycle);
+        }
+    }
+}
+
+/// Finds require cycles between the given modules, which are keyed by their normalized path.
+pub fn find_cycles(modules: &BTreeMap<PathBuf, Vec<ModuleRequire>>) -> Vec<Vec<RequireLink>> {
+    let mut finder = CycleFinder {
+        modules,
+        states: HashMap::new(),
+        stack: Vec::new(),
+        cycles: Vec::new(),
+        seen: HashSet::new(),
+    };
+
+    for file in modules.keys() {
+        if !finder.states.contains_key(file.as_path()) {
+            finder.visit(file);
+        }
+    }
+
+    finder.cycles
+}
+
+/// The diagnostic for a cycle, placed on the first require in it.
+/// The other requires are in other files, so they are left for the caller to label.
+pub fn cycle_diagnostic(cycle: &[RequireLink]) -> Diagnostic {
+    let first = &cycle[0];
+
+    let chain = std::iter::once(first.file.display().to_string())
+        .chain(cycle.iter().map(|link| link.required.display().to_string()))
+        .collect::<Vec<_>>()
+        .join(" -> ");
+
+    Diagnostic::new_complete(
+        "circular_require",
+        Message::new("circular_require")
+            .with("file", first.file.display())
+            .with("required", first.required.display()),
+        Label::new_with_message(first.range, "start of the cycle".to_owned()),
+        vec![
+            format!("the full cycle is {chain}"),
+            "modules in a cycle are required before they finish loading, which either errors or gives back an incomplete module".to_owned(),
+            "try moving one of the requires into the function that uses it, or moving the shared code into its own module".to_owned(),
+        ],
+        Vec::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requires(code: &str, file_path: &str) -> Vec<Vec<PathBuf>> {
+        load_time_requires(&full_moon::parse(code).unwrap(), Path::new(file_path))
+            .into_iter()
+            .map(|module_require| module_require.candidates)
+            .collect()
+    }
+
+    fn module(path: &str, requires: &[&str]) -> (PathBuf, Vec<ModuleRequire>) {
+        (
+            PathBuf::from(path),
+            requires
+                .iter()
+                .enumerate()
+                .map(|(index, required)| ModuleRequire {
+                    range: (index as u32, index as u32 + 1),
+                    candidates: vec![PathBuf::from(required)],
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_instance_requires() {
+        assert_eq!(
+            requires(
+                "local Shared = script.Parent.Shared\nrequire(Shared.Util)\nrequire(script:FindFirstChild(\"Child\"))",
+                "./src/client/Module.lua"
+            ),
+            vec![
+                module_candidates(Path::new("src/client/Shared/Util")),
+                module_candidates(Path::new("src/client/Module/Child")),
+            ]
+        );
+
+        // The instance of init.lua is the directory it is in
+        assert_eq!(
+            requires("require(script.Parent.Other)", "src/shared/init.luau"),
+            vec![module_candidates(Path::new("src/Other"))]
+        );
+    }
+
+    #[test]
+    fn test_string_requires() {
+        assert_eq!(
+            requires(
+                "require('foo.bar')\nrequire('./sibling')\nrequire('@pkg/module')",
+                "src/main.lua"
+            ),
+            vec![
+                module_candidates(Path::new("foo/bar")),
+                module_candidates(Path::new("src/sibling")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_requires_without_parentheses() {
+        assert_eq!(
+            requires("require 'foo.bar'\nrequire [[./sibling]]", "src/main.lua"),
+            vec![
+                module_candidates(Path::new("foo/bar")),
+                module_candidates(Path::new("src/sibling")),
+            ]
+        );
+
+        let modules = BTreeMap::from([
+            (
+                PathBuf::from("a.lua"),
+                load_time_requires(
+                    &full_moon::parse("require \"b\"").unwrap(),
+                    Path::new("a.lua"),
+                ),
+            ),
+            (
+                PathBuf::from("b.lua"),
+                load_time_requires(
+                    &full_moon::parse("require \"a\"").unwrap(),
+                    Path::new("b.lua"),
+                ),
+            ),
+        ]);
+
+        assert_eq!(find_cycles(&modules).len(), 1);
+    }
+
+    #[test]
+    fn test_lazy_requires() {
+        assert!(requires("local function lazy()\n\trequire('other')\nend", "main.lua").is_empty());
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        let modules = BTreeMap::from([
+            module("a.lua", &["b.lua"]),
+            module("b.lua", &["c.lua", "missing.lua"]),
+            module("c.lua", &["a.lua", "b.lua"]),
+            module("d.lua", &["d.lua"]),
+        ]);
+
+        let cycles = find_cycles(&modules)
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|link| link.file.display().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            cycles,
+            vec![
+                vec!["a.lua".to_owned(), "b.lua".to_owned(), "c.lua".to_owned()],
+                vec!["b.lua".to_owned(), "c.lua".to_owned()],
+                vec!["d.lua".to_owned()],
+            ]
+        );
+    }
+}
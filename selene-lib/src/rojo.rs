@@ -0,0 +1,218 @@
+// Classifies files the same way Rojo does when syncing them into Roblox. Only the files that the
+// tree of the closest Rojo project file, such as `default.project.json`, syncs become scripts.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::require_graph::normalize_path;
+
+const DEFAULT_PROJECT_FILE: &str = "default.project.json";
+
+lazy_static::lazy_static! {
+    // The project of every directory looked at so far, so that each is only read once
+    static ref PROJECTS: Mutex<HashMap<PathBuf, Option<Arc<Project>>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptKind {
+    LocalScript,
+    ModuleScript,
+    Script,
+}
+
+/// The files and directories a Rojo project syncs, which are the `$path`s in its tree.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Project {
+    paths: Vec<PathBuf>,
+}
+
+impl Project {
+    /// Reads a project file, whose `$path`s are relative to `directory`, the one it's in.
+    pub fn from_json(contents: &str, directory: &Path) -> Option<Project> {
+        let project: serde_json::Value = serde_json::from_str(contents).ok()?;
+
+        let mut paths = Vec::new();
+        collect_paths(project.get("tree")?, directory, &mut paths);
+
+        Some(Project { paths })
+    }
+
+    /// Whether the file is one of the project's paths, or inside of one.
+    pub fn contains(&self, path: &Path) -> bool {
+        let path = normalize_path(path);
+        self.paths.iter().any(|synced| path.starts_with(synced))
+    }
+}
+
+fn collect_paths(node: &serde_json::Value, directory: &Path, paths: &mut Vec<PathBuf>) {
+    let node = match node.as_object() {
+        Some(node) => node,
+        None => return,
+    };
+
+    // `$path` can also be `{ "optional": "path" }`, for paths that don't have to exist
+    if let Some(path) = node
+        .get("$path")
+        .and_then(|path| path.as_str().or_else(|| path.get("optional")?.as_str()))
+    {
+        paths.push(normalize_path(&directory.join(path)));
+    }
+
+    for (name, child) in node {
+        if !name.starts_with('$') {
+            collect_paths(child, directory, paths);
+        }
+    }
+}
+
+// The project file in the directory, preferring `default.project.json` over any other
+fn project_file_in(directory: &Path) -> Option<PathBuf> {
+    let default_project_file = directory.join(DEFAULT_PROJECT_FILE);
+    if default_project_file.is_file() {
+        return Some(default_project_file);
+    }
+
+    let mut project_files = fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(".project.json"))
+        })
+        .collect::<Vec<_>>();
+
+    project_files.sort();
+    project_files.into_iter().next()
+}
+
+/// Returns the closest Rojo project to the file, looking through every directory above it.
+pub fn project_for(path: &Path) -> Option<Arc<Project>> {
+    let path = normalize_path(&std::env::current_dir().ok()?.join(path));
+    let mut projects = PROJECTS.lock().unwrap();
+
+    let mut unknown_directories = Vec::new();
+    let mut project = None;
+
+    for directory in path.ancestors().skip(1) {
+        if let Some(known) = projects.get(directory) {
+            project = known.clone();
+            break;
+        }
+
+        unknown_directories.push(directory.to_path_buf());
+
+        if let Some(project_file) = project_file_in(directory) {
+            project = fs::read_to_string(&project_file)
+                .ok()
+                .and_then(|contents| Project::from_json(&contents, directory))
+                .map(Arc::new);
+
+            if project.is_none() {
+                log::warn!("couldn't read the Rojo project {}", project_file.display());
+            }
+
+            break;
+        }
+    }
+
+    for directory in unknown_directories {
+        projects.insert(directory, project.clone());
+    }
+
+    project
+}
+
+/// Returns what kind of script Rojo will create for the file at this path, or None if the file
+/// would not become a script at all, including when no Rojo project syncs it.
+pub fn script_kind(path: &Path) -> Option<ScriptKind> {
+    let kind = script_kind_from_name(path)?;
+    let path = normalize_path(&std::env::current_dir().ok()?.join(path));
+
+    if project_for(&path)?.contains(&path) {
+        Some(kind)
+    } else {
+        None
+    }
+}
+
+// What the file would become from its name alone, if a project synced it
+fn script_kind_from_name(path: &Path) -> Option<ScriptKind> {
+    let extension = path.extension()?.to_str()?;
+    if extension != "lua" && extension != "luau" {
+        return None;
+    }
+
+    let stem = Path::new(path.file_stem()?);
+
+    match stem.extension().and_then(|extension| extension.to_str()) {
+        Some("server") => Some(ScriptKind::Script),
+        Some("client") => Some(ScriptKind::LocalScript),
+        _ => Some(ScriptKind::ModuleScript),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_kind_from_name() {
+        assert_eq!(
+            script_kind_from_name(Path::new("src/shared/Module.lua")),
+            Some(ScriptKind::ModuleScript)
+        );
+
+        assert_eq!(
+            script_kind_from_name(Path::new("src/shared/init.luau")),
+            Some(ScriptKind::ModuleScript)
+        );
+
+        assert_eq!(
+            script_kind_from_name(Path::new("src/server/main.server.lua")),
+            Some(ScriptKind::Script)
+        );
+
+        assert_eq!(
+            script_kind_from_name(Path::new("src/client/init.client.luau")),
+            Some(ScriptKind::LocalScript)
+        );
+
+        assert_eq!(
+            script_kind_from_name(Path::new("default.project.json")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_project() {
+        let project = Project::from_json(
+            r#"{
+                "name": "game",
+                "tree": {
+                    "$className": "DataModel",
+                    "ReplicatedStorage": {
+                        "Shared": { "$path": "src/shared" },
+                        "Packages": { "$path": { "optional": "./Packages" } }
+                    },
+                    "ServerScriptService": {
+                        "Main": { "$path": "src/server/main.server.lua" }
+                    }
+                }
+            }"#,
+            Path::new("/game"),
+        )
+        .unwrap();
+
+        assert!(project.contains(Path::new("/game/src/shared/Module.lua")));
+        assert!(project.contains(Path::new("/game/./Packages/Promise/init.lua")));
+        assert!(project.contains(Path::new("/game/src/server/main.server.lua")));
+        assert!(!project.contains(Path::new("/game/src/server/other.server.lua")));
+        assert!(!project.contains(Path::new("/game/scripts/build.lua")));
+
+        assert_eq!(Project::from_json("{}", Path::new("/game")), None);
+    }
+}
@@ -1,37 +1,79 @@
-use crate::{ast_util::scopes::ScopeManager, standard_library::StandardLibrary};
-use std::convert::TryInto;
+use crate::{ast_util::scopes::ScopeManager, messages::Message, standard_library::StandardLibrary};
+use std::{
+    convert::TryInto,
+    path::{Path, PathBuf},
+};
 
 use codespan_reporting::diagnostic::{
     Diagnostic as CodespanDiagnostic, Label as CodespanLabel, Severity as CodespanSeverity,
 };
 use full_moon::{ast::Ast, node::Node};
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 
 pub mod almost_swapped;
 pub mod bad_string_escape;
+pub mod banned_api;
+pub mod circular_require;
 pub mod compare_nan;
+pub mod confusable_identifier;
 pub mod constant_table_comparison;
+pub mod coroutine_misuse;
 pub mod deprecated;
 pub mod divide_by_zero;
+pub mod duplicate_code;
 pub mod duplicate_keys;
+pub mod duplicate_require;
 pub mod empty_if;
+pub mod error_usage;
 pub mod global_usage;
 pub mod high_cyclomatic_complexity;
 pub mod if_same_then_else;
 pub mod ifs_same_cond;
+pub mod imprecise_number;
 pub mod invalid_lint_filter;
+pub mod loop_invariant;
+pub mod metatable_misuse;
+pub mod method_call_mismatch;
 pub mod mismatched_arg_count;
+pub mod mixed_line_endings;
+pub mod module_return;
 pub mod multiple_statements;
 pub mod must_use;
+pub mod number_style;
+pub mod os_date_format;
 pub mod parenthese_conditions;
+pub mod pcall_misuse;
+pub mod require_order;
+pub mod restricted;
 pub mod shadowing;
 pub mod standard_library;
+pub mod string_too_long;
 pub mod suspicious_reverse_loop;
+pub mod suspicious_semicolon;
+pub mod table_insert_misuse;
 pub mod type_check_inside_call;
 pub mod unbalanced_assignments;
+pub mod undefined_field;
 pub mod undefined_variable;
 pub mod unscoped_variables;
+pub mod unused_require;
 pub mod unused_variable;
+pub mod vararg_misuse;
+
+#[cfg(feature = "roblox")]
+pub mod any_type_cast;
+
+#[cfg(feature = "roblox")]
+pub mod prefer_compound_assignment;
+
+#[cfg(feature = "roblox")]
+pub mod prefer_continue;
+
+#[cfg(feature = "roblox")]
+pub mod prefer_string_interpolation;
+
+#[cfg(feature = "roblox")]
+pub mod redundant_type_cast;
 
 #[cfg(feature = "roblox")]
 pub mod roblox_incorrect_color3_new_bounds;
@@ -39,16 +81,41 @@ pub mod roblox_incorrect_color3_new_bounds;
 #[cfg(feature = "roblox")]
 pub mod roblox_incorrect_roact_usage;
 
+#[cfg(feature = "roblox")]
+pub mod roblox_module_script;
+
+#[cfg(feature = "roblox")]
+pub mod roblox_parent_before_properties;
+
+#[cfg(feature = "roblox")]
+pub mod roblox_task_library;
+
+#[cfg(feature = "roblox")]
+pub mod roblox_unchecked_remote_arguments;
+
+#[cfg(feature = "roblox")]
+pub mod unused_type;
+
+#[cfg(feature = "lua54")]
+pub mod const_reassignment;
+
+#[cfg(feature = "lua54")]
+pub mod prefer_const;
+
 #[cfg(test)]
 mod test_util;
 
 pub trait Rule {
-    type Config: DeserializeOwned;
+    type Config: DeserializeOwned + Serialize;
     type Error: std::error::Error;
 
     const SEVERITY: Severity;
     const RULE_TYPE: RuleType;
 
+    /// The luacheck warnings this lint checks for, such as `211` for `unused_variable`,
+    /// with the one it's closest to first.
+    const LUACHECK_CODES: &'static [&'static str] = &[];
+
     fn new(config: Self::Config) -> Result<Self, Self::Error>
     where
         Self: Sized;
@@ -61,6 +128,7 @@ pub trait Rule {
     ) -> Vec<Diagnostic>;
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RuleType {
     /// Code that does something simple but in a complex way
     Complexity,
@@ -87,39 +155,87 @@ pub enum Severity {
 pub struct Diagnostic {
     pub code: &'static str,
     pub message: String,
+    /// The ID of the message in message catalogs, if it can be shown in other languages.
+    pub message_id: Option<&'static str>,
+    pub message_arguments: Vec<(&'static str, String)>,
+    /// More about the problem, such as why it's a problem or where something came from.
     pub notes: Vec<String>,
+    /// How to fix the problem, such as "use a for loop instead".
+    pub help: Vec<String>,
+    /// Code that would fix the problem, such as `select("#", ...)`. Unlike `fixes`, these are
+    /// never applied, since they can't be written as changes to the code.
+    pub suggestions: Vec<String>,
     pub primary_label: Label,
     pub secondary_labels: Vec<Label>,
+    /// Changes to the code that resolve this diagnostic, applied with `--fix`
+    pub fixes: Vec<Fix>,
 }
 
 impl Diagnostic {
-    pub fn new(code: &'static str, message: String, primary_label: Label) -> Self {
+    pub fn new(code: &'static str, message: impl Into<Message>, primary_label: Label) -> Self {
+        let message = message.into();
+
         Self {
             code,
-            message,
+            message: message.text,
+            message_id: message.id,
+            message_arguments: message.arguments,
             primary_label,
 
             notes: Vec::new(),
+            help: Vec::new(),
+            suggestions: Vec::new(),
             secondary_labels: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
+    /// Help and suggestions are added after with `with_help` and `with_suggestion`.
     pub fn new_complete(
         code: &'static str,
-        message: String,
+        message: impl Into<Message>,
         primary_label: Label,
         notes: Vec<String>,
         secondary_labels: Vec<Label>,
     ) -> Self {
         Self {
-            code,
-            message,
             notes,
-            primary_label,
             secondary_labels,
+            ..Self::new(code, message, primary_label)
         }
     }
 
+    /// Adds how to fix the problem, which is shown after `help: `.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help.push(help.into());
+        self
+    }
+
+    /// Adds code that would fix the problem, which is shown after `try: `.
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestions.push(suggestion.into());
+        self
+    }
+
+    /// Every note the way it's shown, with help and suggestions after the rest.
+    pub fn rendered_notes(&self) -> Vec<String> {
+        self.notes
+            .iter()
+            .cloned()
+            .chain(self.help.iter().map(|help| format!("help: {help}")))
+            .chain(
+                self.suggestions
+                    .iter()
+                    .map(|suggestion| format!("try: {suggestion}")),
+            )
+            .collect()
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
     pub fn into_codespan_diagnostic(
         self,
         file_id: codespan::FileId,
@@ -136,7 +252,7 @@ impl Diagnostic {
             code: Some(self.code.to_owned()),
             labels,
             message: self.message.to_owned(),
-            notes: self.notes,
+            notes: self.rendered_notes(),
             severity,
         }
     }
@@ -146,6 +262,52 @@ impl Diagnostic {
     }
 }
 
+/// Replaces the code in `range` with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fix {
+    pub range: (u32, u32),
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+/// How sure a lint is that its fix keeps the code doing the same thing, like clippy's applicability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Applicability {
+    /// The fix can't change what the code does, so it's applied by `--fix`.
+    Safe,
+    /// The fix could change what the code does, such as by running code in a different order,
+    /// so it's only applied by `--fix-suggestions`.
+    Suggestion,
+}
+
+impl Fix {
+    pub fn new<P: TryInto<u32>>(range: (P, P), replacement: String) -> Fix {
+        Fix {
+            range: (
+                range
+                    .0
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("TryInto failed for Fix::new range")),
+                range
+                    .1
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("TryInto failed for Fix::new range")),
+            ),
+            replacement,
+            applicability: Applicability::Safe,
+        }
+    }
+
+    pub fn with_applicability(mut self, applicability: Applicability) -> Self {
+        self.applicability = applicability;
+        self
+    }
+
+    pub fn removal<P: TryInto<u32>>(range: (P, P)) -> Fix {
+        Fix::new(range, String::new())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Label {
     pub message: Option<String>,
@@ -223,17 +385,33 @@ impl Context {
     pub fn is_roblox(&self) -> bool {
         false
     }
+
+    /// Whether the code is Luau, which has syntax such as compound assignments that other
+    /// versions of Lua don't, so lints can only suggest it then
+    pub fn is_luau(&self) -> bool {
+        self.is_roblox() || self.standard_library.name.as_deref() == Some("luau")
+    }
 }
 
 #[derive(Debug)]
 pub struct AstContext {
     pub scope_manager: ScopeManager,
+    /// The path of the file being linted, if there is one (stdin has none)
+    pub file_path: Option<PathBuf>,
 }
 
 impl AstContext {
     pub fn from_ast(ast: &Ast) -> Self {
         Self {
             scope_manager: ScopeManager::new(ast),
+            file_path: None,
+        }
+    }
+
+    pub fn from_ast_with_path(ast: &Ast, file_path: &Path) -> Self {
+        Self {
+            file_path: Some(file_path.to_path_buf()),
+            ..Self::from_ast(ast)
         }
     }
 }
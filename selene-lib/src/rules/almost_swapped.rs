@@ -33,19 +33,18 @@ impl Rule for AlmostSwappedLint {
             .map(|almost_swap| {
                 Diagnostic::new_complete(
                     "almost_swapped",
-                    format!(
-                        "this looks like you are trying to swap `{}` and `{}`",
-                        (almost_swap.names.0),
-                        (almost_swap.names.1),
-                    ),
+                    Message::new("almost_swapped")
+                        .with("first", &almost_swap.names.0)
+                        .with("second", &almost_swap.names.1),
                     Label::new(almost_swap.range),
-                    vec![format!(
-                        "try: `{name1}, {name2} = {name2}, {name1}`",
-                        name1 = almost_swap.names.0,
-                        name2 = almost_swap.names.1,
-                    )],
+                    Vec::new(),
                     Vec::new(),
                 )
+                .with_suggestion(format!(
+                    "`{name1}, {name2} = {name2}, {name1}`",
+                    name1 = almost_swap.names.0,
+                    name2 = almost_swap.names.1,
+                ))
             })
             .collect()
     }
@@ -0,0 +1,77 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, types, Ast},
+    visitors::Visitor,
+};
+
+pub struct AnyTypeCastLint;
+
+impl Rule for AnyTypeCastLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(AnyTypeCastLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = AnyTypeCastVisitor {
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct AnyTypeCastVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+pub(crate) fn is_any(type_info: &types::TypeInfo) -> bool {
+    matches!(type_info, types::TypeInfo::Basic(name) if name.token().to_string() == "any")
+}
+
+impl Visitor for AnyTypeCastVisitor {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let type_assertion = match expression {
+            ast::Expression::Value {
+                type_assertion: Some(type_assertion),
+                ..
+            } => type_assertion,
+            _ => return,
+        };
+
+        if !is_any(type_assertion.cast_to()) {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "any_type_cast",
+            Message::new("any_type_cast"),
+            Label::from_node(type_assertion, None),
+            vec!["note: casting to `any` turns off type checking for this value".to_owned()],
+            Vec::new(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_any_type_cast() {
+        test_lint(
+            AnyTypeCastLint::new(()).unwrap(),
+            "any_type_cast",
+            "any_type_cast",
+        );
+    }
+}
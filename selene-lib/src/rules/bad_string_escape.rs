@@ -48,44 +48,38 @@ impl Rule for BadStringEscapeLint {
             .map(|sequence| match sequence.issue {
                 ReasonWhy::Invalid => Diagnostic::new(
                     "bad_string_escape",
-                    "string escape sequence doesn't exist".to_owned(),
+                    Message::new("bad_string_escape.invalid"),
                     Label::new(sequence.range.to_owned()),
                 ),
                 ReasonWhy::Malformed => Diagnostic::new(
                     "bad_string_escape",
-                    "string escape sequence is malformed".to_owned(),
+                    Message::new("bad_string_escape.malformed"),
                     Label::new(sequence.range.to_owned()),
                 ),
                 ReasonWhy::DecimalTooHigh => Diagnostic::new_complete(
                     "bad_string_escape",
-                    "decimal escape is too high".to_owned(),
+                    Message::new("bad_string_escape.decimal_too_high"),
                     Label::new(sequence.range.to_owned()),
-                    vec![
-                        "help: the maximum codepoint allowed in decimal escapes is `255`"
-                            .to_owned(),
-                    ],
                     Vec::new(),
-                ),
+                    Vec::new(),
+                )
+                .with_help("the maximum codepoint allowed in decimal escapes is `255`"),
                 ReasonWhy::CodepointTooHigh => Diagnostic::new_complete(
                     "bad_string_escape",
-                    "unicode codepoint is too high for this escape sequence".to_owned(),
+                    Message::new("bad_string_escape.codepoint_too_high"),
                     Label::new(sequence.range.to_owned()),
-                    vec![
-                        "help: the maximum codepoint allowed in unicode escapes is `10ffff`"
-                            .to_owned(),
-                    ],
                     Vec::new(),
-                ),
+                    Vec::new(),
+                )
+                .with_help("the maximum codepoint allowed in unicode escapes is `10ffff`"),
                 ReasonWhy::DoubleInSingle => Diagnostic::new(
                     "bad_string_escape",
-                    "double quotes do not have to be escaped when inside single quoted strings"
-                        .to_owned(),
+                    Message::new("bad_string_escape.double_in_single"),
                     Label::new(sequence.range.to_owned()),
                 ),
                 ReasonWhy::SingleInDouble => Diagnostic::new(
                     "bad_string_escape",
-                    "single quotes do not have to be escaped when inside double quoted strings"
-                        .to_owned(),
+                    Message::new("bad_string_escape.single_in_double"),
                     Label::new(sequence.range.to_owned()),
                 ),
             })
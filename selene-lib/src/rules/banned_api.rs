@@ -0,0 +1,156 @@
+use super::*;
+use crate::ast_util::{name_paths::*, scopes::ScopeManager};
+use std::{collections::BTreeMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+use serde::{Deserialize, Serialize};
+
+/// Maps paths such as `os.execute` to the reason they are banned.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct BannedApiConfig {
+    apis: BTreeMap<String, String>,
+}
+
+struct BannedApi {
+    name: String,
+    path: Vec<String>,
+    message: String,
+}
+
+pub struct BannedApiLint {
+    banned: Vec<BannedApi>,
+}
+
+impl Rule for BannedApiLint {
+    type Config = BannedApiConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(BannedApiLint {
+            banned: config
+                .apis
+                .into_iter()
+                .map(|(name, message)| BannedApi {
+                    // Methods are written as `game:GetService`, but are part of the path all the same
+                    path: name.split(['.', ':']).map(ToOwned::to_owned).collect(),
+                    name,
+                    message,
+                })
+                .collect(),
+        })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if self.banned.is_empty() {
+            return Vec::new();
+        }
+
+        let mut visitor = BannedApiVisitor {
+            banned: &self.banned,
+            diagnostics: Vec::new(),
+            scope_manager: &ast_context.scope_manager,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct BannedApiVisitor<'a> {
+    banned: &'a [BannedApi],
+    diagnostics: Vec<Diagnostic>,
+    scope_manager: &'a ScopeManager,
+}
+
+impl BannedApiVisitor<'_> {
+    fn is_global(&self, node: &impl Node) -> bool {
+        match self
+            .scope_manager
+            .reference_at_byte(node.start_position().unwrap().bytes())
+        {
+            Some(reference) => reference.resolved.is_none(),
+            None => true,
+        }
+    }
+
+    fn check_name_path(&mut self, node: &impl Node, name_path: &[String]) {
+        // Banning `os` bans everything inside of it, but the usage is only reported once
+        let banned = match self
+            .banned
+            .iter()
+            .filter(|banned| name_path.starts_with(&banned.path))
+            .min_by_key(|banned| banned.path.len())
+        {
+            Some(banned) => banned,
+            None => return,
+        };
+
+        let notes = if banned.message.is_empty() {
+            Vec::new()
+        } else {
+            vec![banned.message.to_owned()]
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "banned_api",
+            Message::new("banned_api").with("name", &banned.name),
+            Label::from_node(node, None),
+            notes,
+            Vec::new(),
+        ));
+    }
+}
+
+impl Visitor for BannedApiVisitor<'_> {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if !self.is_global(expression) {
+            return;
+        }
+
+        if let Some(name_path) = name_path(expression) {
+            self.check_name_path(expression, &name_path);
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if !self.is_global(call) {
+            return;
+        }
+
+        if let Some(name_path) = name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+            self.check_name_path(call, &name_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_banned_api() {
+        test_lint(
+            BannedApiLint::new(BannedApiConfig {
+                apis: BTreeMap::from([
+                    ("debug".to_owned(), String::new()),
+                    (
+                        "game:GetService".to_owned(),
+                        "use the Services module".to_owned(),
+                    ),
+                    ("os.execute".to_owned(), "use the sandbox runner".to_owned()),
+                ]),
+            })
+            .unwrap(),
+            "banned_api",
+            "banned_api",
+        );
+    }
+}
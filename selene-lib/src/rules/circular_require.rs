@@ -0,0 +1,25 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::ast::Ast;
+
+/// Require cycles can only be found by looking at every file being checked at once,
+/// which is done with `require_graph` after each file has been checked on its own.
+/// This lint exists so that it can be configured like any other.
+pub struct CircularRequireLint;
+
+impl Rule for CircularRequireLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(CircularRequireLint)
+    }
+
+    fn pass(&self, _: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+}
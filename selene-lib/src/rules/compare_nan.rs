@@ -32,15 +32,16 @@ impl Rule for CompareNanLint {
             .map(|comparisons| {
                 Diagnostic::new_complete(
                     "compare_nan",
-                    "comparing things to nan directly is not allowed".to_owned(),
+                    Message::new("compare_nan"),
                     Label::new(comparisons.range),
-                    vec![format!(
-                        "try: `{variable} {operator} {variable}` instead",
-                        variable = comparisons.variable,
-                        operator = comparisons.operator,
-                    )],
+                    Vec::new(),
                     Vec::new(),
                 )
+                .with_suggestion(format!(
+                    "`{variable} {operator} {variable}` instead",
+                    variable = comparisons.variable,
+                    operator = comparisons.operator,
+                ))
             })
             .collect()
     }
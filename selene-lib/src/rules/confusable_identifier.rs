@@ -0,0 +1,267 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::{ast::Ast, tokenizer::Token, visitors::Visitor};
+
+// Characters from other scripts that are commonly mistaken for ASCII letters
+const CONFUSABLES: &[(char, char)] = &[
+    // Cyrillic
+    ('а', 'a'),
+    ('е', 'e'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('с', 'c'),
+    ('у', 'y'),
+    ('х', 'x'),
+    ('ѕ', 's'),
+    ('ԁ', 'd'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('Е', 'E'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('Н', 'H'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('С', 'C'),
+    ('Т', 'T'),
+    ('У', 'Y'),
+    ('Х', 'X'),
+    ('Ѕ', 'S'),
+    // Greek
+    ('α', 'a'),
+    ('ι', 'i'),
+    ('κ', 'k'),
+    ('ν', 'v'),
+    ('ο', 'o'),
+    ('ρ', 'p'),
+    ('υ', 'u'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+    ('Ρ', 'P'),
+    ('Τ', 'T'),
+    ('Υ', 'Y'),
+    ('Χ', 'X'),
+];
+
+// Characters that take up no space, so can't be seen at all
+fn is_invisible(character: char) -> bool {
+    matches!(
+        character,
+        '\u{00AD}'
+            | '\u{034F}'
+            | '\u{061C}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{17B4}'
+            | '\u{17B5}'
+            | '\u{180E}'
+            | '\u{200B}'..='\u{200F}'
+            | '\u{202A}'..='\u{202E}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{2066}'..='\u{206F}'
+            | '\u{3164}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{FEFF}'
+            | '\u{FFA0}'
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Script {
+    Cyrillic,
+    Greek,
+    Latin,
+}
+
+fn script(character: char) -> Option<Script> {
+    match character {
+        'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' => Some(Script::Latin),
+        '\u{0370}'..='\u{03FF}' => Some(Script::Greek),
+        '\u{0400}'..='\u{052F}' => Some(Script::Cyrillic),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Problem {
+    Invisible(char),
+    Confusable(char, char),
+    MixedScripts,
+}
+
+fn check_identifier(name: &str) -> Option<Problem> {
+    if let Some(character) = name.chars().find(|&character| is_invisible(character)) {
+        return Some(Problem::Invisible(character));
+    }
+
+    let mut scripts = name.chars().filter_map(script);
+    if let Some(first_script) = scripts.next() {
+        if scripts.any(|script| script != first_script) {
+            return Some(
+                match name.chars().find_map(|character| {
+                    CONFUSABLES
+                        .iter()
+                        .find(|(confusable, _)| *confusable == character)
+                }) {
+                    Some((confusable, looks_like)) => Problem::Confusable(*confusable, *looks_like),
+                    None => Problem::MixedScripts,
+                },
+            );
+        }
+    }
+
+    // A name entirely in another script that only uses lookalikes, such as Cyrillic `рое`,
+    // can't be told apart from the ASCII name at all
+    let all_confusable = name.chars().all(|character| {
+        character.is_ascii_digit()
+            || character == '_'
+            || CONFUSABLES
+                .iter()
+                .any(|(confusable, _)| *confusable == character)
+    });
+
+    if all_confusable && !name.is_ascii() {
+        let (confusable, looks_like) = name
+            .chars()
+            .find_map(|character| {
+                CONFUSABLES
+                    .iter()
+                    .find(|(confusable, _)| *confusable == character)
+            })
+            .unwrap();
+
+        return Some(Problem::Confusable(*confusable, *looks_like));
+    }
+
+    None
+}
+
+pub struct ConfusableIdentifierLint;
+
+impl Rule for ConfusableIdentifierLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ConfusableIdentifierLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = ConfusableIdentifierVisitor {
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct ConfusableIdentifierVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for ConfusableIdentifierVisitor {
+    fn visit_identifier(&mut self, token: &Token) {
+        let name = token.to_string();
+
+        // Almost every identifier is ASCII, which can't have any of these problems
+        if name.is_ascii() {
+            return;
+        }
+
+        let (message, note) = match check_identifier(&name) {
+            Some(Problem::Invisible(character)) => (
+                Message::new("confusable_identifier.invisible")
+                    .with("name", &name)
+                    .with("codepoint", format!("{:04X}", character as u32)),
+                "note: invisible characters make identifiers that look the same, but aren't"
+                    .to_owned(),
+            ),
+
+            Some(Problem::Confusable(character, looks_like)) => (
+                Message::new("confusable_identifier.confusable")
+                    .with("name", &name)
+                    .with("character", character)
+                    .with("codepoint", format!("{:04X}", character as u32))
+                    .with("looks_like", looks_like),
+                "note: this can't be told apart from a different identifier written in ASCII"
+                    .to_owned(),
+            ),
+
+            Some(Problem::MixedScripts) => (
+                Message::new("confusable_identifier.mixed_scripts").with("name", &name),
+                "note: identifiers that mix scripts are easily confused with others".to_owned(),
+            ),
+
+            None => return,
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "confusable_identifier",
+            message,
+            Label::new((token.start_position().bytes(), token.end_position().bytes())),
+            vec![note],
+            Vec::new(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_check_identifier() {
+        assert_eq!(check_identifier("player"), None);
+        assert_eq!(check_identifier("café"), None);
+        assert_eq!(check_identifier("привет"), None);
+        assert_eq!(check_identifier("все"), None);
+        assert_eq!(check_identifier("λ"), None);
+
+        // Cyrillic р
+        assert_eq!(
+            check_identifier("\u{0440}layer"),
+            Some(Problem::Confusable('р', 'p'))
+        );
+
+        // Entirely Cyrillic, but looks like `рое`
+        assert_eq!(
+            check_identifier("\u{0440}\u{043E}\u{0435}"),
+            Some(Problem::Confusable('р', 'p'))
+        );
+
+        // Greek λ with Latin
+        assert_eq!(check_identifier("λx"), Some(Problem::MixedScripts));
+
+        assert_eq!(
+            check_identifier("a\u{00AD}b"),
+            Some(Problem::Invisible('\u{00AD}'))
+        );
+    }
+
+    #[test]
+    fn test_confusable_identifier() {
+        test_lint(
+            ConfusableIdentifierLint::new(()).unwrap(),
+            "confusable_identifier",
+            "confusable_identifier",
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use super::*;
+use crate::ast_util::{local_reassignments, range};
+use std::{collections::HashMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct ConstReassignmentLint;
+
+impl Rule for ConstReassignmentLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Error;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ConstReassignmentLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = ConstReassignmentVisitor {
+            diagnostics: Vec::new(),
+            reassignments: local_reassignments(ast, &ast_context.scope_manager),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct ConstReassignmentVisitor {
+    diagnostics: Vec<Diagnostic>,
+    reassignments: HashMap<(usize, usize), Vec<(usize, usize)>>,
+}
+
+impl Visitor for ConstReassignmentVisitor {
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        for (name, attribute) in local_assignment
+            .names()
+            .iter()
+            .zip(local_assignment.attributes())
+        {
+            // `<close>` variables can't be assigned to either
+            let attribute = match attribute {
+                Some(attribute) => attribute.name().token().to_string(),
+                None => continue,
+            };
+
+            let reassignments = match self.reassignments.get(&range(name)) {
+                Some(reassignments) => reassignments,
+                None => continue,
+            };
+
+            for reassignment in reassignments {
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "const_reassignment",
+                    Message::new("const_reassignment")
+                        .with("name", name.token())
+                        .with("attribute", &attribute),
+                    Label::new(*reassignment),
+                    Vec::new(),
+                    vec![Label::new_with_message(
+                        range::<_, u32>(name),
+                        "defined here".to_owned(),
+                    )],
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_const_reassignment() {
+        test_lint(
+            ConstReassignmentLint::new(()).unwrap(),
+            "const_reassignment",
+            "const_reassignment",
+        );
+    }
+}
@@ -32,34 +32,34 @@ impl Rule for ConstantTableComparisonLint {
             .comparisons
             .iter()
             .map(|comparison| {
-                Diagnostic::new_complete(
+                let diagnostic = Diagnostic::new(
                     "constant_table_comparison",
-                    "comparing to a constant table will always fail".to_owned(),
+                    Message::new("constant_table_comparison"),
                     Label::new(comparison.range),
-                    if let Some(empty_side) = comparison.empty_side {
-                        vec![format!(
-                            "try: `next({}) {} nil`",
-                            match empty_side {
-                                EmptyComparison::CheckEmpty(side) => match side {
-                                    EmptyComparisonSide::Left => &comparison.rhs,
-                                    EmptyComparisonSide::Right => &comparison.lhs,
-                                },
-
-                                EmptyComparison::CheckNotEmpty(side) => match side {
-                                    EmptyComparisonSide::Left => &comparison.rhs,
-                                    EmptyComparisonSide::Right => &comparison.lhs,
-                                },
+                );
+
+                match comparison.empty_side {
+                    Some(empty_side) => diagnostic.with_suggestion(format!(
+                        "`next({}) {} nil`",
+                        match empty_side {
+                            EmptyComparison::CheckEmpty(side) => match side {
+                                EmptyComparisonSide::Left => &comparison.rhs,
+                                EmptyComparisonSide::Right => &comparison.lhs,
                             },
-                            match empty_side {
-                                EmptyComparison::CheckEmpty(_) => "==",
-                                EmptyComparison::CheckNotEmpty(_) => "~=",
-                            }
-                        )]
-                    } else {
-                        Vec::new()
-                    },
-                    Vec::new(),
-                )
+
+                            EmptyComparison::CheckNotEmpty(side) => match side {
+                                EmptyComparisonSide::Left => &comparison.rhs,
+                                EmptyComparisonSide::Right => &comparison.lhs,
+                            },
+                        },
+                        match empty_side {
+                            EmptyComparison::CheckEmpty(_) => "==",
+                            EmptyComparison::CheckNotEmpty(_) => "~=",
+                        }
+                    )),
+
+                    None => diagnostic,
+                }
             })
             .collect()
     }
@@ -0,0 +1,137 @@
+use super::*;
+use crate::ast_util::{name_paths::name_path_from_prefix_suffix, scopes::ScopeManager};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+
+pub struct CoroutineMisuseLint;
+
+impl Rule for CoroutineMisuseLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(CoroutineMisuseLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = CoroutineMisuseVisitor {
+            scope_manager: &ast_context.scope_manager,
+            function_depth: 0,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct CoroutineMisuseVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    function_depth: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl CoroutineMisuseVisitor<'_> {
+    // Returns `resume` for `coroutine.resume(...)`, as long as `coroutine` is the global
+    fn coroutine_function(&self, call: &ast::FunctionCall) -> Option<String> {
+        let name_path = name_path_from_prefix_suffix(call.prefix(), call.suffixes())?;
+
+        match (name_path.as_slice(), call.suffixes().count()) {
+            ([library, function], 2) if library == "coroutine" => {
+                if let Some(reference) = self
+                    .scope_manager
+                    .reference_at_byte(call.start_position()?.bytes())
+                {
+                    if reference.resolved.is_some() {
+                        return None;
+                    }
+                }
+
+                Some(function.to_owned())
+            }
+
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for CoroutineMisuseVisitor<'_> {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if self.function_depth == 0 && self.coroutine_function(call).as_deref() == Some("yield") {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "coroutine_misuse",
+                Message::new("coroutine_misuse.yield_outside_function"),
+                Label::from_node(call, None),
+                vec![
+                    "note: this only works if the whole file is run inside of a coroutine"
+                        .to_owned(),
+                ],
+                Vec::new(),
+            ));
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        let call = match stmt {
+            ast::Stmt::FunctionCall(call) => call,
+            _ => return,
+        };
+
+        match self.coroutine_function(call).as_deref() {
+            Some("resume") => self.diagnostics.push(
+                Diagnostic::new_complete(
+                    "coroutine_misuse",
+                    Message::new("coroutine_misuse.resume_ignored"),
+                    Label::from_node(call, None),
+                    Vec::new(),
+                    Vec::new(),
+                )
+                .with_suggestion(format!(
+                    "`local ok, err = {}` and handle `err`",
+                    call.to_string().trim()
+                )),
+            ),
+
+            Some("wrap") => self.diagnostics.push(Diagnostic::new_complete(
+                "coroutine_misuse",
+                Message::new("coroutine_misuse.wrap_not_called"),
+                Label::from_node(call, None),
+                vec!["note: add `()` after coroutine.wrap(...) to start the coroutine".to_owned()],
+                Vec::new(),
+            )),
+
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_coroutine_misuse() {
+        test_lint(
+            CoroutineMisuseLint::new(()).unwrap(),
+            "coroutine_misuse",
+            "coroutine_misuse",
+        );
+    }
+}
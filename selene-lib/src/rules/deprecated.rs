@@ -22,6 +22,8 @@ impl Rule for DeprecatedLint {
     fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
         let mut visitor = DeprecatedVisitor {
             diagnostics: Vec::new(),
+            #[cfg(feature = "roblox")]
+            is_roblox: context.is_roblox(),
             scope_manager: &ast_context.scope_manager,
             standard_library: &context.standard_library,
         };
@@ -34,6 +36,8 @@ impl Rule for DeprecatedLint {
 
 struct DeprecatedVisitor<'a> {
     diagnostics: Vec<Diagnostic>,
+    #[cfg(feature = "roblox")]
+    is_roblox: bool,
     scope_manager: &'a ScopeManager,
     standard_library: &'a StandardLibrary,
 }
@@ -59,22 +63,41 @@ impl DeprecatedVisitor<'_> {
                 _ => continue,
             };
 
+            // These calls are reported by roblox_task_library instead, which can also fix them
+            #[cfg(feature = "roblox")]
+            if self.is_roblox
+                && what == "function"
+                && bound == name_path.len()
+                && super::roblox_task_library::replaced_by_task_library(deprecated)
+            {
+                continue;
+            }
+
             let mut notes = vec![deprecated.message.to_owned()];
 
-            if let Some(replace_with) = deprecated.try_instead(parameters) {
-                notes.push(format!("try: {replace_with}"));
-            }
+            notes.extend(
+                self.standard_library
+                    .global_source_notes(&name_path[0..bound]),
+            );
 
-            self.diagnostics.push(Diagnostic::new_complete(
+            let mut diagnostic = Diagnostic::new_complete(
                 "deprecated",
-                format!(
-                    "standard library {what} `{}` is deprecated",
-                    name_path.join(".")
-                ),
+                Message::new(if what == "function" {
+                    "deprecated.function"
+                } else {
+                    "deprecated.expression"
+                })
+                .with("name", name_path.join(".")),
                 Label::from_node(node, None),
                 notes,
                 Vec::new(),
-            ));
+            );
+
+            if let Some(replace_with) = deprecated.try_instead(parameters) {
+                diagnostic = diagnostic.with_suggestion(replace_with);
+            }
+
+            self.diagnostics.push(diagnostic);
         }
     }
 }
@@ -180,6 +203,16 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "roblox")]
+    #[test]
+    fn test_roblox_task_library() {
+        test_lint(
+            DeprecatedLint::new(()).unwrap(),
+            "deprecated",
+            "roblox_task_library",
+        );
+    }
+
     #[test]
     fn test_toml_forwards_compatibility() {
         test_lint(
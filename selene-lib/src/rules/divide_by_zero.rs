@@ -1,4 +1,5 @@
 use super::*;
+use crate::ast_util::{evaluate_constant, Constant};
 use std::convert::Infallible;
 
 use full_moon::{
@@ -33,7 +34,7 @@ impl Rule for DivideByZeroLint {
             .map(|position| {
                 Diagnostic::new(
                     "divide_by_zero",
-                    "dividing by zero is not allowed, use math.huge instead".to_owned(),
+                    Message::new("divide_by_zero"),
                     Label::new(*position),
                 )
             })
@@ -45,24 +46,17 @@ struct DivideByZeroVisitor {
     positions: Vec<(usize, usize)>,
 }
 
-fn value_is_zero(value: &ast::Value) -> bool {
-    if let ast::Value::Number(token) = value {
-        token.token().to_string() == "0"
-    } else {
-        false
-    }
+fn is_zero(expression: &ast::Expression) -> bool {
+    evaluate_constant(expression) == Some(Constant::Number(0.0))
 }
 
 impl Visitor for DivideByZeroVisitor {
     fn visit_expression(&mut self, node: &ast::Expression) {
         if_chain::if_chain! {
             if let ast::Expression::BinaryOperator { lhs, binop, rhs, .. } = node;
-            if let ast::Expression::Value { value, .. } = &**lhs;
             if let ast::BinOp::Slash(_) = binop;
-            if let ast::Expression::Value {
-                value: rhs_value, ..
-            } = &**rhs;
-            if value_is_zero(rhs_value) && !value_is_zero(value);
+            // 0 / 0 is allowed, as it is a common way to get NaN
+            if is_zero(rhs) && !is_zero(lhs);
             then {
                 let range = node.range().unwrap();
                 self.positions.push((range.0.bytes(), range.1.bytes()));
@@ -83,4 +77,13 @@ mod tests {
             "divide_by_zero",
         );
     }
+
+    #[test]
+    fn test_constant_folding() {
+        test_lint(
+            DivideByZeroLint::new(()).unwrap(),
+            "divide_by_zero",
+            "constant_folding",
+        );
+    }
 }
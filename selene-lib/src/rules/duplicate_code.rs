@@ -0,0 +1,49 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::ast::Ast;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DuplicateCodeConfig {
+    min_tokens: usize,
+}
+
+impl Default for DuplicateCodeConfig {
+    fn default() -> Self {
+        Self { min_tokens: 100 }
+    }
+}
+
+/// Copied code can only be found by looking at every file being checked at once,
+/// which is done with `duplicate_code` after each file has been checked on its own.
+/// This lint exists so that it can be configured like any other.
+#[derive(Default)]
+pub struct DuplicateCodeLint {
+    config: DuplicateCodeConfig,
+}
+
+impl DuplicateCodeLint {
+    /// The fewest tokens a copy can have before it's reported.
+    pub fn min_tokens(&self) -> usize {
+        self.config.min_tokens
+    }
+}
+
+impl Rule for DuplicateCodeLint {
+    type Config = DuplicateCodeConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(DuplicateCodeLint { config })
+    }
+
+    fn pass(&self, _: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+}
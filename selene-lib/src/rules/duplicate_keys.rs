@@ -17,6 +17,7 @@ impl Rule for DuplicateKeysLint {
 
     const SEVERITY: Severity = Severity::Error;
     const RULE_TYPE: RuleType = RuleType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["314"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(DuplicateKeysLint)
@@ -35,7 +36,7 @@ impl Rule for DuplicateKeysLint {
             .map(|duplicate| {
                 Diagnostic::new_complete(
                     "duplicate_keys",
-                    format!("key `{}` is already declared", duplicate.name),
+                    Message::new("duplicate_keys").with("name", &duplicate.name),
                     Label::new(duplicate.position),
                     Vec::new(),
                     vec![Label::new_with_message(
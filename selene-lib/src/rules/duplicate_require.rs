@@ -0,0 +1,278 @@
+use super::*;
+use crate::ast_util::{
+    range, require_call_argument,
+    scopes::{ScopeManager, Variable},
+    single_string_argument, string_literal, RequireArgument,
+};
+use std::{collections::HashMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct DuplicateRequireLint;
+
+impl Rule for DuplicateRequireLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(DuplicateRequireLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let scope_manager = &ast_context.scope_manager;
+
+        let mut visitor = DuplicateRequireVisitor {
+            aliases: HashMap::new(),
+            blocks: Vec::new(),
+            diagnostics: Vec::new(),
+            local_requires: Vec::new(),
+            scope_manager,
+            variables: scope_manager
+                .variables
+                .iter()
+                .filter_map(|(id, variable)| Some((*variable.identifiers.first()?, id)))
+                .collect(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct LocalRequire {
+    name: String,
+    module: Vec<String>,
+    call_range: (usize, usize),
+    // The range of code that can see the local
+    visible: (usize, usize),
+}
+
+struct DuplicateRequireVisitor<'a> {
+    aliases: HashMap<Id<Variable>, Vec<String>>,
+    blocks: Vec<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+    local_requires: Vec<LocalRequire>,
+    scope_manager: &'a ScopeManager,
+    variables: HashMap<(usize, usize), Id<Variable>>,
+}
+
+impl DuplicateRequireVisitor<'_> {
+    // Variables that are set more than once could point to anything
+    fn single_assignment(&self, variable_id: Id<Variable>) -> bool {
+        self.scope_manager.variables[variable_id]
+            .references
+            .iter()
+            .filter(|id| self.scope_manager.references[**id].write.is_some())
+            .count()
+            <= 1
+    }
+
+    fn variable_at(&self, name: &TokenReference) -> Option<Id<Variable>> {
+        let (start, _): (usize, usize) = range(name);
+        self.scope_manager.reference_at_byte(start)?.resolved
+    }
+
+    // The instance path of an expression such as `game:GetService("ReplicatedStorage").Packages`,
+    // with every way of indexing a child written the same way.
+    fn instance_path(&self, expression: &ast::Expression) -> Option<Vec<String>> {
+        let (prefix, suffixes) = match expression {
+            ast::Expression::Value { value, .. } => match &**value {
+                ast::Value::Var(ast::Var::Name(name)) => {
+                    return self.name_path(name);
+                }
+                ast::Value::Var(ast::Var::Expression(var_expression)) => (
+                    var_expression.prefix(),
+                    var_expression.suffixes().collect::<Vec<_>>(),
+                ),
+                ast::Value::FunctionCall(call) => (call.prefix(), call.suffixes().collect()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let mut path = match prefix {
+            ast::Prefix::Name(name) => self.name_path(name)?,
+            _ => return None,
+        };
+
+        for suffix in suffixes {
+            match suffix {
+                ast::Suffix::Index(ast::Index::Dot { name, .. }) => {
+                    path.push(name.token().to_string());
+                }
+
+                ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                    path.push(string_literal(expression)?);
+                }
+
+                ast::Suffix::Call(ast::Call::MethodCall(method_call))
+                    if matches!(
+                        method_call.name().token().to_string().as_str(),
+                        "GetService" | "WaitForChild" | "FindFirstChild"
+                    ) =>
+                {
+                    path.push(single_string_argument(method_call.args())?);
+                }
+
+                _ => return None,
+            }
+        }
+
+        Some(path)
+    }
+
+    fn name_path(&self, name: &TokenReference) -> Option<Vec<String>> {
+        match self.variable_at(name) {
+            Some(variable_id) if self.single_assignment(variable_id) => {
+                self.aliases.get(&variable_id).cloned()
+            }
+
+            Some(_) => None,
+
+            None => match name.token().to_string().as_str() {
+                "workspace" => Some(vec!["game".to_owned(), "Workspace".to_owned()]),
+                global => Some(vec![global.to_owned()]),
+            },
+        }
+    }
+
+    fn module(&self, argument: RequireArgument) -> Option<Vec<String>> {
+        // Strings are kept apart from instances, since they are resolved differently
+        match argument.string_literal() {
+            Some(literal) => Some(vec![String::new(), literal]),
+            None => self.instance_path(argument.expression()?),
+        }
+    }
+
+    fn is_global_require(&self, call: &ast::FunctionCall) -> bool {
+        match self
+            .scope_manager
+            .reference_at_byte(call.start_position().unwrap().bytes())
+        {
+            Some(reference) => reference.resolved.is_none(),
+            None => true,
+        }
+    }
+}
+
+impl Visitor for DuplicateRequireVisitor<'_> {
+    fn visit_block(&mut self, block: &ast::Block) {
+        if let Some((start, end)) = block.range() {
+            self.blocks.push((start.bytes(), end.bytes()));
+        }
+    }
+
+    fn visit_block_end(&mut self, block: &ast::Block) {
+        if block.range().is_some() {
+            self.blocks.pop();
+        }
+    }
+
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        let (_, end): (usize, usize) = range(local_assignment);
+        let visible_until = self.blocks.last().map_or(usize::MAX, |block| block.1);
+
+        for (name, expression) in local_assignment
+            .names()
+            .iter()
+            .zip(local_assignment.expressions().iter())
+        {
+            let variable_id = match self.variables.get(&range(name)) {
+                Some(variable_id) if self.single_assignment(*variable_id) => *variable_id,
+                _ => continue,
+            };
+
+            let call = match expression {
+                ast::Expression::Value { value, .. } => match &**value {
+                    ast::Value::FunctionCall(call) => Some(call),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            match call.and_then(|call| Some((call, require_call_argument(call)?))) {
+                Some((call, argument)) => {
+                    if !self.is_global_require(call) {
+                        continue;
+                    }
+
+                    if let Some(module) = self.module(argument) {
+                        self.local_requires.push(LocalRequire {
+                            name: name.token().to_string(),
+                            module,
+                            call_range: range(call),
+                            visible: (end, visible_until),
+                        });
+                    }
+                }
+
+                None => {
+                    if let Some(path) = self.instance_path(expression) {
+                        self.aliases.insert(variable_id, path);
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let argument = match require_call_argument(call) {
+            Some(argument) if self.is_global_require(call) => argument,
+            _ => return,
+        };
+
+        let module = match self.module(argument) {
+            Some(module) => module,
+            None => return,
+        };
+
+        let call_range: (usize, usize) = range(call);
+
+        let earlier = match self.local_requires.iter().find(|local_require| {
+            local_require.module == module
+                && local_require.call_range != call_range
+                && local_require.visible.0 <= call_range.0
+                && call_range.1 <= local_require.visible.1
+        }) {
+            Some(earlier) => earlier,
+            None => return,
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "duplicate_require",
+            Message::new("duplicate_require")
+                .with("module", argument.to_string().trim())
+                .with("name", &earlier.name),
+            Label::new(call_range),
+            vec![format!("try using `{}` instead", earlier.name)],
+            vec![Label::new_with_message(
+                earlier.call_range,
+                "first required here".to_owned(),
+            )],
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_duplicate_require() {
+        test_lint(
+            DuplicateRequireLint::new(()).unwrap(),
+            "duplicate_require",
+            "duplicate_require",
+        );
+    }
+}
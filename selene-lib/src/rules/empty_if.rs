@@ -7,9 +7,9 @@ use full_moon::{
     tokenizer::{Token, TokenKind},
     visitors::Visitor,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Default, Deserialize)]
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct EmptyIfLintConfig {
     comments_count: bool,
@@ -25,6 +25,7 @@ impl Rule for EmptyIfLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const RULE_TYPE: RuleType = RuleType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &["542"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(EmptyIfLint { config })
@@ -56,12 +57,11 @@ impl Rule for EmptyIfLint {
             .map(|position| {
                 Diagnostic::new(
                     "empty_if",
-                    match position.1 {
-                        EmptyIfKind::If => "empty if block",
-                        EmptyIfKind::ElseIf => "empty elseif block",
-                        EmptyIfKind::Else => "empty else block",
-                    }
-                    .to_owned(),
+                    Message::new(match position.1 {
+                        EmptyIfKind::If => "empty_if.if",
+                        EmptyIfKind::ElseIf => "empty_if.elseif",
+                        EmptyIfKind::Else => "empty_if.else",
+                    }),
                     Label::new(position.0),
                 )
             })
@@ -0,0 +1,179 @@
+use super::standard_library::reports_call;
+use super::*;
+use crate::ast_util::{evaluate_constant, Constant, HasSideEffects};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+
+pub struct ErrorUsageLint;
+
+impl Rule for ErrorUsageLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ErrorUsageLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = ErrorUsageVisitor {
+            context,
+            ast_context,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct ErrorUsageVisitor<'a> {
+    context: &'a Context,
+    ast_context: &'a AstContext,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ErrorUsageVisitor<'_> {
+    fn check_error(&mut self, call: &ast::FunctionCall, arguments: &[&ast::Expression]) {
+        match arguments {
+            [] => self.diagnostics.push(Diagnostic::new_complete(
+                "error_usage",
+                Message::new("error_usage.no_message"),
+                Label::from_node(call, None),
+                vec!["note: the error will be `nil`, which hides where it came from".to_owned()],
+                Vec::new(),
+            )),
+
+            [level] => {
+                if let Some(Constant::Number(_)) = evaluate_constant(level) {
+                    self.diagnostics.push(Diagnostic::new_complete(
+                        "error_usage",
+                        Message::new("error_usage.level_as_message")
+                            .with("level", level.to_string().trim()),
+                        Label::from_node(level, None),
+                        vec!["note: the level is the second argument, after the message, such as `error(\"message\", 2)`".to_owned()],
+                        Vec::new(),
+                    ));
+                }
+            }
+
+            [level, message, ..] => {
+                if let (Some(Constant::Number(_)), Some(Constant::String(_))) =
+                    (evaluate_constant(level), evaluate_constant(message))
+                {
+                    self.diagnostics.push(
+                        Diagnostic::new_complete(
+                            "error_usage",
+                            Message::new("error_usage.swapped"),
+                            Label::from_node(call, None),
+                            Vec::new(),
+                            Vec::new(),
+                        )
+                        .with_suggestion(format!(
+                            "`error({}, {})`",
+                            message.to_string().trim(),
+                            level.to_string().trim()
+                        )),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_assert(&mut self, arguments: &[&ast::Expression]) {
+        if let Some(message) = arguments.get(1) {
+            if message.has_side_effects() {
+                self.diagnostics.push(
+                    Diagnostic::new_complete(
+                        "error_usage",
+                        Message::new("error_usage.assert_message"),
+                        Label::from_node(message, None),
+                        Vec::new(),
+                        Vec::new(),
+                    )
+                    .with_suggestion("`if not condition then error(message) end`"),
+                );
+            }
+        }
+    }
+}
+
+impl Visitor for ErrorUsageVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let name = match call.prefix() {
+            ast::Prefix::Name(name) => name.token().to_string(),
+            _ => return,
+        };
+
+        if name != "error" && name != "assert" {
+            return;
+        }
+
+        // Only the global functions, not a local that happens to share the name
+        if let Some(reference) = self
+            .ast_context
+            .scope_manager
+            .reference_at_byte(call.start_position().unwrap().bytes())
+        {
+            if reference.resolved.is_some() {
+                return;
+            }
+        }
+
+        let mut suffixes = call.suffixes();
+        let arguments = match (suffixes.next(), suffixes.next()) {
+            (
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                    arguments,
+                    ..
+                }))),
+                None,
+            ) => arguments.iter().collect::<Vec<_>>(),
+
+            _ => return,
+        };
+
+        // Calls that don't match the standard library, such as `error()` when the message is
+        // required, are already reported by incorrect_standard_library_use
+        if reports_call(call, self.context, self.ast_context) {
+            return;
+        }
+
+        if name == "error" {
+            self.check_error(call, &arguments);
+        } else {
+            self.check_assert(&arguments);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_error_usage() {
+        test_lint(
+            ErrorUsageLint::new(()).unwrap(),
+            "error_usage",
+            "error_usage",
+        );
+    }
+
+    #[test]
+    fn test_untyped_error() {
+        test_lint(
+            ErrorUsageLint::new(()).unwrap(),
+            "error_usage",
+            "untyped_error",
+        );
+    }
+}
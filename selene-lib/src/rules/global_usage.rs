@@ -1,28 +1,50 @@
 use super::*;
 use std::{collections::HashSet, convert::Infallible};
 
-use full_moon::ast::Ast;
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenType,
+    visitors::Visitor,
+};
+use serde::{Deserialize, Serialize};
 
 fn is_global(name: &str, roblox: bool) -> bool {
     (roblox && name == "shared") || name == "_G"
 }
 
-pub struct GlobalLint;
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GlobalUsageConfig {
+    allow_set: Vec<String>,
+}
+
+pub struct GlobalLint {
+    config: GlobalUsageConfig,
+}
 
 impl Rule for GlobalLint {
-    type Config = ();
+    type Config = GlobalUsageConfig;
     type Error = Infallible;
 
     const SEVERITY: Severity = Severity::Warning;
     const RULE_TYPE: RuleType = RuleType::Complexity;
 
-    fn new(_: Self::Config) -> Result<Self, Self::Error> {
-        Ok(GlobalLint)
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(GlobalLint { config })
     }
 
-    fn pass(&self, _: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
         let mut checked = HashSet::new(); // TODO: Fix ScopeManager having duplicate references
 
+        let mut visitor = AllowedFieldsVisitor {
+            allow_set: &self.config.allow_set,
+            allowed: HashSet::new(),
+        };
+
+        if !self.config.allow_set.is_empty() {
+            visitor.visit_ast(ast);
+        }
+
         ast_context
             .scope_manager
             .references
@@ -30,7 +52,9 @@ impl Rule for GlobalLint {
             .filter(|(_, reference)| {
                 if !checked.contains(&reference.identifier) {
                     checked.insert(reference.identifier);
-                    is_global(&reference.name, context.is_roblox()) && reference.resolved.is_none()
+                    is_global(&reference.name, context.is_roblox())
+                        && reference.resolved.is_none()
+                        && !visitor.allowed.contains(&reference.identifier)
                 } else {
                     false
                 }
@@ -38,10 +62,7 @@ impl Rule for GlobalLint {
             .map(|(_, reference)| {
                 Diagnostic::new(
                     "global_usage",
-                    format!(
-                        "use of `{}` is not allowed, structure your code in a more idiomatic way",
-                        reference.name
-                    ),
+                    Message::new("global_usage").with("name", &reference.name),
                     Label::new(reference.identifier),
                 )
             })
@@ -49,12 +70,64 @@ impl Rule for GlobalLint {
     }
 }
 
+// Finds the `_G` in every `_G.name` where `name` is in `allow_set`
+struct AllowedFieldsVisitor<'a> {
+    allow_set: &'a [String],
+    allowed: HashSet<(usize, usize)>,
+}
+
+impl Visitor for AllowedFieldsVisitor<'_> {
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        let name = match var_expression.prefix() {
+            ast::Prefix::Name(name) => name,
+            _ => return,
+        };
+
+        let field = match var_expression.suffixes().next() {
+            Some(ast::Suffix::Index(ast::Index::Dot { name, .. })) => name.token().to_string(),
+            Some(ast::Suffix::Index(ast::Index::Brackets {
+                expression: ast::Expression::Value { value, .. },
+                ..
+            })) => match &**value {
+                ast::Value::String(token) => match token.token_type() {
+                    TokenType::StringLiteral { literal, .. } => literal.to_string(),
+                    _ => return,
+                },
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if self.allow_set.contains(&field) {
+            if let Some((start, end)) = name.range() {
+                self.allowed.insert((start.bytes(), end.bytes()));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{super::test_util::test_lint, *};
+    use super::{super::test_util::*, *};
 
     #[test]
     fn test_global_usage() {
-        test_lint(GlobalLint::new(()).unwrap(), "global_usage", "global_usage");
+        test_lint(
+            GlobalLint::new(GlobalUsageConfig::default()).unwrap(),
+            "global_usage",
+            "global_usage",
+        );
+    }
+
+    #[test]
+    fn test_allow_set() {
+        test_lint(
+            GlobalLint::new(GlobalUsageConfig {
+                allow_set: vec!["ENV".to_owned(), "self".to_owned()],
+            })
+            .unwrap(),
+            "global_usage",
+            "allow_set",
+        );
     }
 }
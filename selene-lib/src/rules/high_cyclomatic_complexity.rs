@@ -7,9 +7,9 @@ use full_moon::{
     visitors::Visitor,
 };
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Deserialize)]
+#[derive(Clone, Copy, Deserialize, Serialize)]
 pub struct HighCyclomaticComplexityConfig {
     maximum_complexity: u16,
 }
@@ -34,6 +34,7 @@ impl Rule for HighCyclomaticComplexityLint {
 
     const SEVERITY: Severity = Severity::Allow;
     const RULE_TYPE: RuleType = RuleType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &["561"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(HighCyclomaticComplexityLint { config })
@@ -53,10 +54,9 @@ impl Rule for HighCyclomaticComplexityLint {
             .map(|(position, complexity)| {
                 Diagnostic::new(
                     "high_cyclomatic_complexity",
-                    format!(
-                        "cyclomatic complexity is too high ({complexity} > {})",
-                        self.config.maximum_complexity
-                    ),
+                    Message::new("high_cyclomatic_complexity")
+                        .with("complexity", complexity)
+                        .with("maximum", self.config.maximum_complexity),
                     Label::new(position),
                 )
             })
@@ -34,7 +34,7 @@ impl Rule for IfSameThenElseLint {
             .map(|position| {
                 Diagnostic::new_complete(
                     "if_same_then_else",
-                    "this has the same block as a previous if".to_owned(),
+                    Message::new("if_same_then_else"),
                     Label::new(position.0),
                     Vec::new(),
                     vec![Label::new_with_message(
@@ -34,7 +34,7 @@ impl Rule for IfsSameCondLint {
             .map(|position| {
                 Diagnostic::new_complete(
                     "ifs_same_cond",
-                    "this `elseif` has the same condition as a previous if".to_owned(),
+                    Message::new("ifs_same_cond"),
                     Label::new(position.0),
                     Vec::new(),
                     vec![Label::new_with_message(
@@ -0,0 +1,239 @@
+use super::*;
+use crate::lua_version::LuaVersion;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ImpreciseNumberConfig {
+    lua_version: LuaVersion,
+}
+
+pub struct ImpreciseNumberLint {
+    config: ImpreciseNumberConfig,
+}
+
+impl Rule for ImpreciseNumberLint {
+    type Config = ImpreciseNumberConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ImpreciseNumberLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = ImpreciseNumberVisitor {
+            lua_version: self.config.lua_version,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Imprecision {
+    // The number will actually be this
+    BecomesFloat(String),
+    TooManyDigits(String),
+    IntegerOverflow,
+}
+
+fn significant_digits(digits: &str) -> &str {
+    digits.trim_start_matches('0').trim_end_matches('0')
+}
+
+fn check_decimal(text: &str, lua_version: LuaVersion) -> Option<Imprecision> {
+    let is_integer = text.chars().all(|character| character.is_ascii_digit());
+
+    if is_integer && lua_version.has_integers() && text.parse::<i64>().is_ok() {
+        return None;
+    }
+
+    let value = text.parse::<f64>().ok()?;
+    if !value.is_finite() {
+        return None;
+    }
+
+    if is_integer {
+        // {:.0} prints the exact value of the double, rather than the shortest one that round trips
+        let exact = format!("{value:.0}");
+
+        if exact != text.trim_start_matches('0') && !(exact == "0" && value == 0.0) {
+            return Some(Imprecision::BecomesFloat(exact));
+        }
+
+        return None;
+    }
+
+    let mantissa_digits = text
+        .split(['e', 'E'])
+        .next()
+        .unwrap_or_default()
+        .replace('.', "");
+
+    // Doubles only have 15 to 17 significant digits, so anything past 17 is lost
+    if significant_digits(&mantissa_digits).len() > 17 {
+        return Some(Imprecision::TooManyDigits(value.to_string()));
+    }
+
+    None
+}
+
+fn check_number(text: &str, lua_version: LuaVersion) -> Option<Imprecision> {
+    let text = text.replace('_', "");
+
+    let (digits, radix) =
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            (hex.to_owned(), 16)
+        } else if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+            (binary.to_owned(), 2)
+        } else {
+            return check_decimal(&text, lua_version);
+        };
+
+    // Hexadecimal floats, such as 0x1p4
+    if !digits.chars().all(|character| character.is_digit(radix)) {
+        return None;
+    }
+
+    let value = match u128::from_str_radix(&digits, radix) {
+        Ok(value) => value,
+        Err(_) => return Some(Imprecision::IntegerOverflow),
+    };
+
+    if lua_version.has_integers() {
+        // Hexadecimal integers wrap around, so anything that fits in 64 bits is fine
+        return if value > u64::MAX as u128 {
+            Some(Imprecision::IntegerOverflow)
+        } else {
+            None
+        };
+    }
+
+    let float = value as f64;
+    if float as u128 != value {
+        return Some(Imprecision::BecomesFloat(format!("{float:.0}")));
+    }
+
+    None
+}
+
+struct ImpreciseNumberVisitor {
+    lua_version: LuaVersion,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for ImpreciseNumberVisitor {
+    fn visit_value(&mut self, value: &ast::Value) {
+        let token = match value {
+            ast::Value::Number(token) => token,
+            _ => return,
+        };
+
+        let text = token.token().to_string();
+
+        let (message, note) = match check_number(&text, self.lua_version) {
+            Some(Imprecision::BecomesFloat(actual)) => (
+                Message::new("imprecise_number.becomes_float")
+                    .with("number", &text)
+                    .with("actual", actual),
+                "note: integers above 2^53 can't always be represented by a double".to_owned(),
+            ),
+
+            Some(Imprecision::TooManyDigits(actual)) => (
+                Message::new("imprecise_number.too_many_digits").with("number", &text),
+                format!("note: this is the same number as `{actual}`"),
+            ),
+
+            Some(Imprecision::IntegerOverflow) => (
+                Message::new("imprecise_number.integer_overflow").with("number", &text),
+                "note: the extra bits are discarded".to_owned(),
+            ),
+
+            None => return,
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "imprecise_number",
+            message,
+            Label::from_node(token, None),
+            vec![note],
+            Vec::new(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_check_number() {
+        assert_eq!(check_number("9007199254740992", LuaVersion::Lua51), None);
+        assert_eq!(
+            check_number("9007199254740993", LuaVersion::Lua51),
+            Some(Imprecision::BecomesFloat("9007199254740992".to_owned()))
+        );
+        assert_eq!(check_number("9007199254740993", LuaVersion::Lua53), None);
+        assert_eq!(
+            check_number("9223372036854775809", LuaVersion::Lua53),
+            Some(Imprecision::BecomesFloat("9223372036854775808".to_owned()))
+        );
+
+        assert_eq!(check_number("0xFFFFFFFFFFFFFFFF", LuaVersion::Lua53), None);
+        assert_eq!(
+            check_number("0x1FFFFFFFFFFFFFFFF", LuaVersion::Lua53),
+            Some(Imprecision::IntegerOverflow)
+        );
+        assert_eq!(
+            check_number("0xFFFFFFFFFFFFFFFF", LuaVersion::Lua51),
+            Some(Imprecision::BecomesFloat("18446744073709551616".to_owned()))
+        );
+
+        assert_eq!(check_number("3.141592653589793", LuaVersion::Lua51), None);
+        assert_eq!(check_number("1.5e300", LuaVersion::Lua51), None);
+        assert_eq!(
+            check_number("0.10000000000000000000", LuaVersion::Lua51),
+            None
+        );
+        assert_eq!(
+            check_number("3.14159265358979323846", LuaVersion::Lua51),
+            Some(Imprecision::TooManyDigits("3.141592653589793".to_owned()))
+        );
+
+        assert_eq!(check_number("1_000_000", LuaVersion::Luau), None);
+        assert_eq!(check_number("0x1p4", LuaVersion::Lua52), None);
+    }
+
+    #[test]
+    fn test_imprecise_number() {
+        test_lint(
+            ImpreciseNumberLint::new(ImpreciseNumberConfig::default()).unwrap(),
+            "imprecise_number",
+            "imprecise_number",
+        );
+    }
+
+    #[test]
+    fn test_lua53() {
+        test_lint(
+            ImpreciseNumberLint::new(ImpreciseNumberConfig {
+                lua_version: LuaVersion::Lua53,
+            })
+            .unwrap(),
+            "imprecise_number",
+            "lua53",
+        );
+    }
+}
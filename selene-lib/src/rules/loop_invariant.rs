@@ -0,0 +1,394 @@
+use super::*;
+use crate::{
+    ast_util::{
+        evaluate_constant, name_paths::name_path_from_prefix_suffix, range, scopes::Reference,
+        HasPurity, Purity,
+    },
+    standard_library::FieldKind,
+};
+use std::{collections::HashSet, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct LoopInvariantLint;
+
+impl Rule for LoopInvariantLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Performance;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(LoopInvariantLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = LoopInvariantVisitor::default();
+        visitor.visit_ast(ast);
+
+        let checker = InvariantChecker {
+            context,
+            scope_manager: &ast_context.scope_manager,
+            safe_reads: &visitor.safe_reads,
+        };
+
+        let mut diagnostics = Vec::new();
+        let mut reported: Vec<Range> = Vec::new();
+
+        // Candidates are in the order they're visited, so `a * b * c` comes before `a * b`
+        for candidate in &visitor.candidates {
+            if reported
+                .iter()
+                .any(|reported| contains(*reported, candidate.range))
+            {
+                continue;
+            }
+
+            let innermost_loop = visitor
+                .loops
+                .iter()
+                .filter(|loop_stmt| contains(loop_stmt.repeated, candidate.range))
+                .min_by_key(|loop_stmt| loop_stmt.range.1 - loop_stmt.range.0);
+
+            let loop_stmt = match innermost_loop {
+                Some(loop_stmt) => loop_stmt,
+                None => continue,
+            };
+
+            // Functions made in the loop don't run the code in them on every iteration
+            if visitor
+                .function_bodies
+                .iter()
+                .any(|body| contains(loop_stmt.range, *body) && contains(*body, candidate.range))
+            {
+                continue;
+            }
+
+            if !checker.is_invariant(candidate, loop_stmt) {
+                continue;
+            }
+
+            reported.push(candidate.range);
+
+            diagnostics.push(
+                Diagnostic::new_complete(
+                    "loop_invariant",
+                    Message::new("loop_invariant").with("expression", &candidate.text),
+                    Label::new(candidate.range),
+                    Vec::new(),
+                    vec![Label::new_with_message(
+                        loop_stmt.keyword,
+                        "computed on every iteration of this loop".to_owned(),
+                    )],
+                )
+                .with_help("store it in a local variable before the loop"),
+            );
+        }
+
+        diagnostics
+    }
+}
+
+type Range = (usize, usize);
+
+fn contains(outer: Range, inner: Range) -> bool {
+    outer.0 <= inner.0 && inner.1 <= outer.1
+}
+
+struct Loop {
+    range: Range,
+    /// The part that runs on every iteration, which is the body of `for` loops and the whole of
+    /// `while` and `repeat` loops.
+    repeated: Range,
+    keyword: Range,
+}
+
+// Arithmetic, concatenation, or `#` that might compute the same value on every iteration
+struct Candidate {
+    range: Range,
+    text: String,
+    table_reads: Vec<TableRead>,
+}
+
+// A value read out of a table, like `list[1]`, `#list`, or `math.pi`, which could change if the
+// table does
+struct TableRead {
+    /// The name the table is read from, which is None when it isn't a name, as in `f().x`.
+    base: Option<Range>,
+    name_path: Option<Vec<String>>,
+}
+
+#[derive(Default)]
+struct LoopInvariantVisitor {
+    loops: Vec<Loop>,
+    function_bodies: Vec<Range>,
+    candidates: Vec<Candidate>,
+    // Names that are only read from, as in `list[1]` and `#list`, unlike `f(list)` or `list.x = 1`
+    safe_reads: HashSet<Range>,
+}
+
+fn is_candidate(expression: &ast::Expression) -> bool {
+    let computes = match expression {
+        ast::Expression::BinaryOperator { binop, .. } => !matches!(
+            binop,
+            ast::BinOp::And(_)
+                | ast::BinOp::Or(_)
+                | ast::BinOp::GreaterThan(_)
+                | ast::BinOp::GreaterThanEqual(_)
+                | ast::BinOp::LessThan(_)
+                | ast::BinOp::LessThanEqual(_)
+                | ast::BinOp::TildeEqual(_)
+                | ast::BinOp::TwoEqual(_)
+        ),
+
+        ast::Expression::UnaryOperator { unop, .. } => matches!(unop, ast::UnOp::Hash(_)),
+
+        _ => false,
+    };
+
+    computes && expression.purity() != Purity::Impure && evaluate_constant(expression).is_none()
+}
+
+fn table_reads(expression: &ast::Expression, reads: &mut Vec<TableRead>) {
+    match expression {
+        ast::Expression::BinaryOperator { lhs, rhs, .. } => {
+            table_reads(lhs, reads);
+            table_reads(rhs, reads);
+        }
+
+        ast::Expression::UnaryOperator { unop, expression } => {
+            if let (ast::UnOp::Hash(_), ast::Expression::Value { value, .. }) =
+                (unop, &**expression)
+            {
+                if let ast::Value::Var(ast::Var::Name(name)) = &**value {
+                    reads.push(TableRead {
+                        base: Some(range(name)),
+                        name_path: None,
+                    });
+                }
+            }
+
+            table_reads(expression, reads);
+        }
+
+        ast::Expression::Parentheses { expression, .. } => table_reads(expression, reads),
+
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::ParenthesesExpression(expression) => table_reads(expression, reads),
+
+            ast::Value::Var(ast::Var::Expression(var_expression)) => {
+                for suffix in var_expression.suffixes() {
+                    if let ast::Suffix::Index(ast::Index::Brackets { expression, .. }) = suffix {
+                        table_reads(expression, reads);
+                    }
+                }
+
+                reads.push(TableRead {
+                    base: match var_expression.prefix() {
+                        ast::Prefix::Name(name) => Some(range(name)),
+                        _ => None,
+                    },
+                    name_path: name_path_from_prefix_suffix(
+                        var_expression.prefix(),
+                        var_expression.suffixes(),
+                    ),
+                });
+            }
+
+            _ => {}
+        },
+
+        _ => {}
+    }
+}
+
+impl LoopInvariantVisitor {
+    fn push_loop(&mut self, range: Range, repeated: Range, keyword: Range) {
+        self.loops.push(Loop {
+            range,
+            repeated,
+            keyword,
+        });
+    }
+}
+
+impl Visitor for LoopInvariantVisitor {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if let ast::Expression::UnaryOperator {
+            unop: ast::UnOp::Hash(_),
+            expression: operand,
+        } = expression
+        {
+            if let ast::Expression::Value { value, .. } = &**operand {
+                if let ast::Value::Var(ast::Var::Name(name)) = &**value {
+                    self.safe_reads.insert(range(name));
+                }
+            }
+        }
+
+        if let ast::Expression::Value { value, .. } = expression {
+            if let ast::Value::Var(ast::Var::Expression(var_expression)) = &**value {
+                if let ast::Prefix::Name(name) = var_expression.prefix() {
+                    self.safe_reads.insert(range(name));
+                }
+            }
+        }
+
+        if is_candidate(expression) {
+            let mut reads = Vec::new();
+            table_reads(expression, &mut reads);
+
+            self.candidates.push(Candidate {
+                range: range(expression),
+                text: expression.to_string().trim().to_owned(),
+                table_reads: reads,
+            });
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        self.function_bodies.push(range(body));
+    }
+
+    fn visit_generic_for(&mut self, generic_for: &ast::GenericFor) {
+        self.push_loop(
+            range(generic_for),
+            (
+                range::<_, usize>(generic_for.do_token()).0,
+                range::<_, usize>(generic_for.end_token()).1,
+            ),
+            range(generic_for.for_token()),
+        );
+    }
+
+    fn visit_numeric_for(&mut self, numeric_for: &ast::NumericFor) {
+        self.push_loop(
+            range(numeric_for),
+            (
+                range::<_, usize>(numeric_for.do_token()).0,
+                range::<_, usize>(numeric_for.end_token()).1,
+            ),
+            range(numeric_for.for_token()),
+        );
+    }
+
+    fn visit_repeat(&mut self, repeat: &ast::Repeat) {
+        self.push_loop(range(repeat), range(repeat), range(repeat.repeat_token()));
+    }
+
+    fn visit_while(&mut self, while_loop: &ast::While) {
+        self.push_loop(
+            range(while_loop),
+            range(while_loop),
+            range(while_loop.while_token()),
+        );
+    }
+}
+
+struct InvariantChecker<'a> {
+    context: &'a Context,
+    scope_manager: &'a ScopeManager,
+    safe_reads: &'a HashSet<Range>,
+}
+
+impl InvariantChecker<'_> {
+    // The references to the same variable, or for globals, to the same global
+    fn same_variable(&self, reference: &Reference) -> Vec<&Reference> {
+        match reference.resolved {
+            Some(variable) => self.scope_manager.variables[variable]
+                .references
+                .iter()
+                .map(|id| &self.scope_manager.references[*id])
+                .collect(),
+
+            None => self
+                .scope_manager
+                .references
+                .iter()
+                .map(|(_, other)| other)
+                .filter(|other| other.resolved.is_none() && other.name == reference.name)
+                .collect(),
+        }
+    }
+
+    fn is_invariant(&self, candidate: &Candidate, loop_stmt: &Loop) -> bool {
+        let references = self
+            .scope_manager
+            .references
+            .iter()
+            .map(|(_, reference)| reference)
+            .filter(|reference| contains(candidate.range, reference.identifier))
+            .collect::<Vec<_>>();
+
+        for reference in &references {
+            // Locals made in the loop, including the loop's own variables, change every iteration
+            if let Some(variable) = reference.resolved {
+                let definition = self.scope_manager.variables[variable].identifiers[0];
+                if contains(loop_stmt.range, definition) {
+                    return false;
+                }
+            }
+
+            if self
+                .same_variable(reference)
+                .iter()
+                .any(|other| other.write.is_some() && contains(loop_stmt.range, other.identifier))
+            {
+                return false;
+            }
+        }
+
+        candidate.table_reads.iter().all(|table_read| {
+            let base = match table_read.base {
+                Some(base) => base,
+                None => return false,
+            };
+
+            let reference = match references
+                .iter()
+                .find(|reference| reference.identifier == base)
+            {
+                Some(reference) => reference,
+                None => return false,
+            };
+
+            let same_variable = self.same_variable(reference);
+
+            // Fields of the standard library, like `math.pi`, don't change unless the code does
+            if reference.resolved.is_none()
+                && !same_variable.iter().any(|other| other.write.is_some())
+            {
+                if let Some(name_path) = &table_read.name_path {
+                    if let Some(field) = self.context.standard_library.find_global(name_path) {
+                        if !matches!(field.field_kind, FieldKind::Function(_)) {
+                            return true;
+                        }
+                    }
+                }
+            }
+
+            same_variable.iter().all(|other| {
+                !contains(loop_stmt.range, other.identifier)
+                    || self.safe_reads.contains(&other.identifier)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_loop_invariant() {
+        test_lint(
+            LoopInvariantLint::new(()).unwrap(),
+            "loop_invariant",
+            "loop_invariant",
+        );
+    }
+}
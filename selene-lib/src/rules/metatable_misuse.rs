@@ -0,0 +1,235 @@
+use super::*;
+use crate::ast_util::{evaluate_constant, scopes::ScopeManager, strip_parentheses, Constant};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+
+// Metamethods that are always called with exactly two operands
+const BINARY_METAMETHODS: &[&str] = &[
+    "__add", "__concat", "__div", "__eq", "__idiv", "__le", "__lt", "__mod", "__mul", "__pow",
+    "__sub",
+];
+
+pub struct MetatableMisuseLint;
+
+impl Rule for MetatableMisuseLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(MetatableMisuseLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = MetatableMisuseVisitor {
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct MetatableMisuseVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// The name a key in a table constructor refers to, for both `__index = x` and `["__index"] = x`
+fn field_name(field: &ast::Field) -> Option<(String, &ast::Expression)> {
+    match field {
+        ast::Field::NameKey { key, value, .. } => Some((key.token().to_string(), value)),
+        ast::Field::ExpressionKey { key, value, .. } => match evaluate_constant(key) {
+            Some(Constant::String(name)) => Some((name, value)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Returns a description of the value if it is a literal that can't be used as `__index`
+fn invalid_index_literal(expression: &ast::Expression) -> Option<&'static str> {
+    match strip_parentheses(expression) {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::Number(_) => Some("a number"),
+            ast::Value::String(_) => Some("a string"),
+            ast::Value::Symbol(symbol) if symbol.token().to_string() != "nil" => Some("a boolean"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parameter_count(body: &ast::FunctionBody) -> Option<usize> {
+    let mut count = 0;
+
+    for parameter in body.parameters() {
+        match parameter {
+            ast::Parameter::Name(_) => count += 1,
+            // Varargs can take any amount
+            _ => return None,
+        }
+    }
+
+    Some(count)
+}
+
+impl MetatableMisuseVisitor<'_> {
+    fn check_metamethod(&mut self, name: &str, value: &ast::Expression) {
+        if name == "__index" {
+            if let Some(kind) = invalid_index_literal(value) {
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "metatable_misuse",
+                    Message::new("metatable_misuse.index").with("kind", kind),
+                    Label::from_node(value, None),
+                    vec!["note: `__index` must be a table or a function".to_owned()],
+                    Vec::new(),
+                ));
+            }
+
+            return;
+        }
+
+        if let ast::Expression::Value { value, .. } = strip_parentheses(value) {
+            if let ast::Value::Function((_, body)) = &**value {
+                self.check_arity(name, body, 0);
+            }
+        }
+    }
+
+    fn check_arity(&mut self, name: &str, body: &ast::FunctionBody, implicit_parameters: usize) {
+        if !BINARY_METAMETHODS.contains(&name) {
+            return;
+        }
+
+        let count = match parameter_count(body) {
+            Some(count) => count + implicit_parameters,
+            None => return,
+        };
+
+        if count != 2 {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "metatable_misuse",
+                Message::new("metatable_misuse.arguments")
+                    .with("name", name)
+                    .with("count", count),
+                Label::from_node(body.parameters_parentheses(), None),
+                vec![format!(
+                    "note: `{name}` is passed both operands, such as `function(a, b)`"
+                )],
+                Vec::new(),
+            ));
+        }
+    }
+
+    fn check_self_metatable(&mut self, call: &ast::FunctionCall) {
+        if_chain::if_chain! {
+            if let ast::Prefix::Name(name) = call.prefix();
+            if name.token().to_string() == "setmetatable";
+
+            let mut suffixes = call.suffixes();
+            if let Some(ast::Suffix::Call(ast::Call::AnonymousCall(
+                ast::FunctionArgs::Parentheses { arguments, .. }
+            ))) = suffixes.next();
+            if suffixes.next().is_none();
+
+            let arguments = arguments.iter().collect::<Vec<_>>();
+            if let [table, metatable] = arguments.as_slice();
+            if let (
+                ast::Expression::Value { value: table_value, .. },
+                ast::Expression::Value { value: metatable_value, .. },
+            ) = (table, metatable);
+            if let (
+                ast::Value::Var(ast::Var::Name(table_name)),
+                ast::Value::Var(ast::Var::Name(metatable_name)),
+            ) = (&**table_value, &**metatable_value);
+            if table_name.token().to_string() == metatable_name.token().to_string();
+
+            then {
+                if let Some(reference) = self
+                    .scope_manager
+                    .reference_at_byte(call.start_position().unwrap().bytes())
+                {
+                    if reference.resolved.is_some() {
+                        return;
+                    }
+                }
+
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "metatable_misuse",
+                    Message::new("metatable_misuse.own_metatable").with("table", table_name.token()),
+                    Label::from_node(call, None),
+                    vec![
+                        "note: indexing a missing key will look it up in the same table again, which is almost never intended".to_owned(),
+                    ],
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+}
+
+impl Visitor for MetatableMisuseVisitor<'_> {
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for (var, expression) in assignment.variables().iter().zip(assignment.expressions()) {
+            if let ast::Var::Expression(var_expression) = var {
+                if let Some(ast::Suffix::Index(ast::Index::Dot { name, .. })) =
+                    var_expression.suffixes().last()
+                {
+                    self.check_metamethod(&name.token().to_string(), expression);
+                }
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.check_self_metatable(call);
+    }
+
+    fn visit_function_declaration(&mut self, declaration: &ast::FunctionDeclaration) {
+        let name = declaration.name();
+
+        // `function mt:__eq(other)` has an implicit `self`
+        let (name, implicit_parameters) = match name.method_name() {
+            Some(method_name) => (method_name.token().to_string(), 1),
+            None if name.names().len() > 1 => match name.names().iter().last() {
+                Some(last_name) => (last_name.token().to_string(), 0),
+                None => return,
+            },
+            None => return,
+        };
+
+        self.check_arity(&name, declaration.body(), implicit_parameters);
+    }
+
+    fn visit_table_constructor(&mut self, table: &ast::TableConstructor) {
+        for field in table.fields() {
+            if let Some((name, value)) = field_name(field) {
+                self.check_metamethod(&name, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_metatable_misuse() {
+        test_lint(
+            MetatableMisuseLint::new(()).unwrap(),
+            "metatable_misuse",
+            "metatable_misuse",
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use super::*;
+use crate::{
+    ast_util::{
+        evaluate_constant, get_argument_type, range, scopes::Variable, strip_parentheses, Constant,
+        PassedArgumentType,
+    },
+    standard_library::{ArgumentType, FieldKind},
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    convert::Infallible,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct MethodCallMismatchLint;
+
+impl Rule for MethodCallMismatchLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(MethodCallMismatchLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let scope_manager = &ast_context.scope_manager;
+
+        let mut visitor = MethodCallMismatchVisitor {
+            context,
+            scope_manager,
+            diagnostics: Vec::new(),
+            tables: HashMap::new(),
+            written: HashMap::new(),
+            prefixes: HashSet::new(),
+            method_calls: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        let mut diagnostics = visitor.diagnostics;
+
+        for method_call in &visitor.method_calls {
+            let table = match visitor.tables.get(&method_call.variable) {
+                Some(table) => table,
+                None => continue,
+            };
+
+            let written = matches!(
+                visitor.written.get(&method_call.variable),
+                Some(written) if written.contains(&method_call.name)
+            );
+
+            if written || table.fields.contains(&method_call.name) {
+                continue;
+            }
+
+            // Passing the table anywhere, such as to setmetatable, could give it methods
+            let variable = &scope_manager.variables[method_call.variable];
+            let only_fields_used = variable.references.iter().all(|id| {
+                let identifier = scope_manager.references[*id].identifier;
+                identifier == table.definition || visitor.prefixes.contains(&identifier)
+            });
+
+            if !only_fields_used || !is_library_function(context, "table", &method_call.name) {
+                continue;
+            }
+
+            diagnostics.push(
+                Diagnostic::new_complete(
+                    "method_call_mismatch",
+                    Message::new("method_call_mismatch")
+                        .with("name", &variable.name)
+                        .with("method", &method_call.name),
+                    Label::new(method_call.range),
+                    Vec::new(),
+                    vec![Label::new_with_message(
+                        table.definition,
+                        "table defined here".to_owned(),
+                    )],
+                )
+                .with_suggestion(format!(
+                    "table.{}({}, ...)",
+                    method_call.name, variable.name
+                )),
+            );
+        }
+
+        diagnostics.sort_by_key(Diagnostic::start_position);
+        diagnostics
+    }
+}
+
+// Whether `library.name` is a function that takes what it's called on as its first argument
+fn is_library_function(context: &Context, library: &str, name: &str) -> bool {
+    match context.standard_library.find_global(&[library, name]) {
+        Some(field) => match &field.field_kind {
+            FieldKind::Function(function) => !function.method,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+// A local defined with a table constructor, such as `local t = { a = 1 }`
+struct LiteralTable {
+    definition: (usize, usize),
+    fields: BTreeSet<String>,
+}
+
+// `t:f()`, which needs `t` to have a method `f`
+struct MethodCall {
+    variable: Id<Variable>,
+    name: String,
+    range: (usize, usize),
+}
+
+struct MethodCallMismatchVisitor<'a> {
+    context: &'a Context,
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+    tables: HashMap<Id<Variable>, LiteralTable>,
+    // Fields set with `t.f = ...` or `function t:f()`, which could be methods
+    written: HashMap<Id<Variable>, BTreeSet<String>>,
+    // Every name that was used to get or set a field, or to call a method
+    prefixes: HashSet<(usize, usize)>,
+    method_calls: Vec<MethodCall>,
+}
+
+impl MethodCallMismatchVisitor<'_> {
+    fn variable(&self, name: &TokenReference) -> Option<Id<Variable>> {
+        self.scope_manager
+            .reference_at_byte(name.start_position()?.bytes())?
+            .resolved
+    }
+
+    // Whether the prefix is always a string, such as a string literal or a local only ever set to one
+    fn is_string(&self, prefix: &ast::Prefix) -> bool {
+        let passed_type = match prefix {
+            ast::Prefix::Expression(expression) => get_argument_type(strip_parentheses(expression)),
+
+            ast::Prefix::Name(name) => {
+                let variable = match self.variable(name) {
+                    Some(variable) => &self.scope_manager.variables[variable],
+                    None => return false,
+                };
+
+                let writes = variable
+                    .references
+                    .iter()
+                    .filter(|id| self.scope_manager.references[**id].write.is_some())
+                    .count();
+
+                if writes > 1 {
+                    return false;
+                }
+
+                variable.inferred_type.clone()
+            }
+
+            _ => None,
+        };
+
+        matches!(
+            passed_type,
+            Some(PassedArgumentType::Primitive(ArgumentType::String))
+                | Some(PassedArgumentType::String(_))
+        )
+    }
+
+    fn write_field(&mut self, prefix: &TokenReference, field: &TokenReference) {
+        if let Some(variable) = self.variable(prefix) {
+            self.written
+                .entry(variable)
+                .or_default()
+                .insert(field.token().to_string());
+        }
+    }
+}
+
+impl Visitor for MethodCallMismatchVisitor<'_> {
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        for (name, expression) in local_assignment
+            .names()
+            .iter()
+            .zip(local_assignment.expressions().iter())
+        {
+            let table = match expression {
+                ast::Expression::Value { value, .. } => match &**value {
+                    ast::Value::TableConstructor(table) => table,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            let mut fields = BTreeSet::new();
+            let mut dynamic = false;
+
+            for field in table.fields() {
+                match field {
+                    ast::Field::NameKey { key, .. } => {
+                        fields.insert(key.token().to_string());
+                    }
+
+                    ast::Field::ExpressionKey { key, .. } => match evaluate_constant(key) {
+                        Some(Constant::String(key)) => {
+                            fields.insert(key);
+                        }
+                        Some(_) => {}
+                        None => dynamic = true,
+                    },
+
+                    ast::Field::NoKey(_) => {}
+
+                    _ => dynamic = true,
+                }
+            }
+
+            // Any key that can't be known could be the name of a method
+            if dynamic {
+                continue;
+            }
+
+            if let Some(variable) = self.variable(name) {
+                self.tables.insert(
+                    variable,
+                    LiteralTable {
+                        definition: range(name),
+                        fields,
+                    },
+                );
+            }
+        }
+    }
+
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for var in assignment.variables() {
+            if let ast::Var::Expression(var_expression) = var {
+                if let (ast::Prefix::Name(prefix), Some(ast::Suffix::Index(index))) =
+                    (var_expression.prefix(), var_expression.suffixes().next())
+                {
+                    match index {
+                        ast::Index::Dot { name, .. } => self.write_field(prefix, name),
+
+                        // `t[key] = f` could set any method
+                        _ => {
+                            if let Some(variable) = self.variable(prefix) {
+                                self.tables.remove(&variable);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        if let ast::Prefix::Name(prefix) = var_expression.prefix() {
+            self.prefixes.insert(range(prefix));
+        }
+    }
+
+    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
+        let name = function_declaration.name();
+        let mut names = name.names().iter();
+        let prefix = names.next().unwrap();
+
+        self.prefixes.insert(range(prefix));
+
+        match (names.next(), name.method_name()) {
+            (Some(field), _) | (None, Some(field)) => self.write_field(prefix, field),
+            (None, None) => {}
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let mut suffixes = call.suffixes();
+
+        match (call.prefix(), suffixes.next(), suffixes.next()) {
+            // `s.upper()`, which should be `s:upper()`
+            (
+                prefix,
+                Some(ast::Suffix::Index(ast::Index::Dot { name, .. })),
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(arguments))),
+            ) => {
+                if let ast::Prefix::Name(prefix) = prefix {
+                    self.prefixes.insert(range(prefix));
+                }
+
+                let name = name.token().to_string();
+                if !self.is_string(prefix) || !is_library_function(self.context, "string", &name) {
+                    return;
+                }
+
+                let receiver = prefix.to_string().trim().to_owned();
+
+                // `s.format(s, ...)` already passes it
+                if let ast::FunctionArgs::Parentheses { arguments, .. } = arguments {
+                    if let Some(first) = arguments.iter().next() {
+                        if first.to_string().trim() == receiver {
+                            return;
+                        }
+                    }
+                }
+
+                self.diagnostics.push(
+                    Diagnostic::new_complete(
+                        "method_call_mismatch",
+                        Message::new("method_call_mismatch.string")
+                            .with("receiver", &receiver)
+                            .with("name", &name),
+                        Label::new((range::<_, usize>(call.prefix()).0, range(arguments).1)),
+                        Vec::new(),
+                        Vec::new(),
+                    )
+                    .with_suggestion(format!("{receiver}:{name}(...)")),
+                );
+            }
+
+            // `t:insert(x)`, which should be `table.insert(t, x)`
+            (
+                ast::Prefix::Name(prefix),
+                Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))),
+                _,
+            ) => {
+                self.prefixes.insert(range(prefix));
+
+                if let Some(variable) = self.variable(prefix) {
+                    self.method_calls.push(MethodCall {
+                        variable,
+                        name: method_call.name().token().to_string(),
+                        range: (range(prefix).0, range(method_call).1),
+                    });
+                }
+            }
+
+            (ast::Prefix::Name(prefix), Some(_), _) => {
+                self.prefixes.insert(range(prefix));
+            }
+
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_method_call_mismatch() {
+        test_lint(
+            MethodCallMismatchLint::new(()).unwrap(),
+            "method_call_mismatch",
+            "method_call_mismatch",
+        );
+    }
+}
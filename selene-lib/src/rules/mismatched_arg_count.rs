@@ -140,23 +140,23 @@ impl ParameterCount {
         }
     }
 
-    fn to_message(self, provided: PassedArgumentCount) -> String {
+    fn to_message(self, provided: PassedArgumentCount) -> Message {
         match self {
-            ParameterCount::Fixed(required) => {
-                format!(
-                    "this function takes {} {} but {} were supplied",
-                    required,
-                    plural(required, "argument", "arguments"),
-                    provided
-                )
-            }
-            ParameterCount::Minimum(required) => format!(
-                "this function takes at least {} {} but {} were supplied",
+            ParameterCount::Fixed(required) => Message::new(plural(
                 required,
-                plural(required, "argument", "arguments"),
-                provided
-            ),
-            ParameterCount::Variable => "a variable amount of arguments".to_owned(),
+                "mismatched_arg_count.one",
+                "mismatched_arg_count.fixed",
+            ))
+            .with("required", required)
+            .with("provided", provided),
+            ParameterCount::Minimum(required) => Message::new(plural(
+                required,
+                "mismatched_arg_count.minimum_one",
+                "mismatched_arg_count.minimum",
+            ))
+            .with("required", required)
+            .with("provided", provided),
+            ParameterCount::Variable => Message::new("mismatched_arg_count.variable"),
         }
     }
 
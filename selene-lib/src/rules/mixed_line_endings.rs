@@ -0,0 +1,105 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::ast::Ast;
+
+pub struct MixedLineEndingsLint;
+
+impl Rule for MixedLineEndingsLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(MixedLineEndingsLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let source = full_moon::print(ast);
+
+        // The byte range of every line ending, and whether it's `\r\n`
+        let line_endings = source
+            .match_indices('\n')
+            .map(|(index, _)| {
+                if source[..index].ends_with('\r') {
+                    ((index - 1, index + 1), true)
+                } else {
+                    ((index, index + 1), false)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let crlf_count = line_endings.iter().filter(|(_, crlf)| *crlf).count();
+        let lf_count = line_endings.len() - crlf_count;
+
+        if crlf_count == 0 || lf_count == 0 {
+            return Vec::new();
+        }
+
+        // Whichever is used the most is what the file is meant to use, or the first for a tie
+        let expected_crlf = match crlf_count.cmp(&lf_count) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => line_endings[0].1,
+        };
+
+        let (expected, found) = if expected_crlf {
+            ("\\r\\n", "\\n")
+        } else {
+            ("\\n", "\\r\\n")
+        };
+
+        line_endings
+            .into_iter()
+            .filter(|(_, crlf)| *crlf != expected_crlf)
+            .map(|(range, _)| {
+                Diagnostic::new(
+                    "mixed_line_endings",
+                    Message::new("mixed_line_endings")
+                        .with("found", found)
+                        .with("expected", expected),
+                    // Stopping before the `\n` keeps the label on the line it ends
+                    Label::new((range.0, range.1 - 1)),
+                )
+                .with_fix(Fix::new(
+                    range,
+                    if expected_crlf { "\r\n" } else { "\n" }.to_owned(),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_mostly_crlf() {
+        test_lint(
+            MixedLineEndingsLint::new(()).unwrap(),
+            "mixed_line_endings",
+            "mostly_crlf",
+        );
+    }
+
+    #[test]
+    fn test_mostly_lf() {
+        test_lint(
+            MixedLineEndingsLint::new(()).unwrap(),
+            "mixed_line_endings",
+            "mostly_lf",
+        );
+    }
+
+    #[test]
+    fn test_consistent() {
+        test_lint(
+            MixedLineEndingsLint::new(()).unwrap(),
+            "mixed_line_endings",
+            "consistent",
+        );
+    }
+}
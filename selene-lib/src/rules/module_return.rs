@@ -0,0 +1,188 @@
+use super::*;
+
+use full_moon::ast::{self, Ast};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ModuleReturnConfig {
+    library_paths: Vec<String>,
+}
+
+pub struct ModuleReturnLint {
+    library_paths: Vec<glob::Pattern>,
+}
+
+impl ModuleReturnLint {
+    fn applies_to(&self, file_path: Option<&Path>) -> bool {
+        // Code that isn't from a file (such as stdin) can't be required as a module
+        let file_path = match file_path {
+            Some(file_path) => file_path.strip_prefix(".").unwrap_or(file_path),
+            None => return false,
+        };
+
+        // With no library paths configured, every file is treated as a module
+        if self.library_paths.is_empty() {
+            return true;
+        }
+
+        self.library_paths
+            .iter()
+            .any(|pattern| pattern.matches_path(file_path))
+    }
+}
+
+impl Rule for ModuleReturnLint {
+    type Config = ModuleReturnConfig;
+    type Error = glob::PatternError;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ModuleReturnLint {
+            library_paths: config
+                .library_paths
+                .iter()
+                .map(|path| glob::Pattern::new(path))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !self.applies_to(ast_context.file_path.as_deref()) {
+            return Vec::new();
+        }
+
+        let block = ast.nodes();
+
+        let return_stmt = match block.last_stmt() {
+            Some(ast::LastStmt::Return(return_stmt)) => return_stmt,
+
+            Some(last_stmt) => {
+                return vec![missing_return_diagnostic(Label::from_node(last_stmt, None))]
+            }
+
+            None => match block.stmts().last() {
+                Some(stmt) => return vec![missing_return_diagnostic(Label::from_node(stmt, None))],
+                None => return Vec::new(),
+            },
+        };
+
+        let returns = return_stmt.returns();
+
+        match returns.len() {
+            0 => vec![Diagnostic::new_complete(
+                "module_return",
+                Message::new("module_return.no_value"),
+                Label::from_node(return_stmt, None),
+                vec!["modules should return a single table or function".to_owned()],
+                Vec::new(),
+            )],
+
+            1 => {
+                let expression = returns.iter().next().unwrap();
+
+                if is_module_value(expression) {
+                    Vec::new()
+                } else {
+                    vec![Diagnostic::new_complete(
+                        "module_return",
+                        Message::new("module_return.not_table_or_function")
+                            .with("value", expression.to_string().trim()),
+                        Label::from_node(expression, None),
+                        vec!["modules should return a single table or function".to_owned()],
+                        Vec::new(),
+                    )]
+                }
+            }
+
+            amount => vec![Diagnostic::new_complete(
+                "module_return",
+                Message::new("module_return.multiple").with("amount", amount),
+                Label::from_node(return_stmt, None),
+                vec!["modules should return a single table or function".to_owned()],
+                returns
+                    .iter()
+                    .skip(1)
+                    .map(|expression| {
+                        Label::from_node(expression, Some("discarded value".to_owned()))
+                    })
+                    .collect(),
+            )],
+        }
+    }
+}
+
+fn missing_return_diagnostic(label: Label) -> Diagnostic {
+    Diagnostic::new_complete(
+        "module_return",
+        Message::new("module_return.no_return"),
+        label,
+        vec!["try adding a `return` of the module's table at the end of the file".to_owned()],
+        Vec::new(),
+    )
+}
+
+// Anything that could plausibly be a table or function is allowed,
+// we only want to catch values that definitely are not.
+fn is_module_value(expression: &ast::Expression) -> bool {
+    #[cfg_attr(
+        feature = "force_exhaustive_checks",
+        deny(non_exhaustive_omitted_patterns)
+    )]
+    match expression {
+        ast::Expression::Parentheses { expression, .. } => is_module_value(expression),
+        ast::Expression::UnaryOperator { .. } => false,
+        ast::Expression::BinaryOperator { .. } => true,
+        ast::Expression::Value { value, .. } => !matches!(
+            &**value,
+            ast::Value::Number(_) | ast::Value::String(_) | ast::Value::Symbol(_)
+        ),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_module_return() {
+        test_lint(
+            ModuleReturnLint::new(ModuleReturnConfig::default()).unwrap(),
+            "module_return",
+            "module_return",
+        );
+    }
+
+    #[test]
+    fn test_missing_return() {
+        test_lint(
+            ModuleReturnLint::new(ModuleReturnConfig::default()).unwrap(),
+            "module_return",
+            "missing_return",
+        );
+    }
+
+    #[test]
+    fn test_multiple_returns() {
+        test_lint(
+            ModuleReturnLint::new(ModuleReturnConfig::default()).unwrap(),
+            "module_return",
+            "multiple_returns",
+        );
+    }
+
+    #[test]
+    fn test_outside_library_paths() {
+        test_lint(
+            ModuleReturnLint::new(ModuleReturnConfig {
+                library_paths: vec!["src/**/*.lua".to_owned()],
+            })
+            .unwrap(),
+            "module_return",
+            "outside_library_paths",
+        );
+    }
+}
@@ -6,9 +6,9 @@ use full_moon::{
     node::Node,
     visitors::Visitor,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Default, Deserialize)]
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
 pub struct MultipleStatementsConfig {
     one_line_if: OneLineIf,
 }
@@ -17,7 +17,7 @@ pub struct MultipleStatementsLint {
     config: MultipleStatementsConfig,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum OneLineIf {
     Allow,
@@ -56,7 +56,7 @@ impl Rule for MultipleStatementsLint {
             .map(|position| {
                 Diagnostic::new(
                     "multiple_statements",
-                    "only one statement per line is allowed".to_owned(),
+                    Message::new("multiple_statements"),
                     Label::new(*position),
                 )
             })
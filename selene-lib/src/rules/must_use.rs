@@ -42,10 +42,10 @@ impl Rule for MustUseLint {
 
             diagnostics.push(Diagnostic::new(
                 "must_use",
-                format!(
-                    "unused return value of `{}` must be used",
+                Message::new("must_use").with(
+                    "name",
                     // TODO: This is wrong for methods
-                    function_call_stmt.call_name_path.join(".")
+                    function_call_stmt.call_name_path.join("."),
                 ),
                 Label::new(function_call_stmt.call_prefix_range),
             ));
@@ -0,0 +1,360 @@
+use super::*;
+use crate::{ast_util::range, lua_version::LuaVersion};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NumberStyleConfig {
+    lua_version: LuaVersion,
+    hex_digits: HexDigits,
+    scientific_notation_above: Option<u32>,
+    separators: Separators,
+}
+
+impl Default for NumberStyleConfig {
+    fn default() -> Self {
+        Self {
+            lua_version: LuaVersion::default(),
+            hex_digits: HexDigits::Upper,
+            scientific_notation_above: None,
+            separators: Separators::Any,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HexDigits {
+    Upper,
+    Lower,
+    Any,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Separators {
+    Always,
+    Never,
+    Any,
+}
+
+pub struct NumberStyleLint {
+    config: NumberStyleConfig,
+}
+
+impl Rule for NumberStyleLint {
+    type Config = NumberStyleConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(NumberStyleLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = NumberStyleVisitor {
+            config: self.config,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+// Integer parts with at least this many digits are grouped with separators, such as `10_000`
+const GROUPED_DIGITS: usize = 5;
+
+fn group_digits(digits: &str) -> String {
+    let digits = digits.replace('_', "");
+    if digits.len() < GROUPED_DIGITS {
+        return digits;
+    }
+
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push('_');
+        }
+
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+// Returns the plain integer that `1.5e3` is, if it has no more than `max_digits` digits
+fn expand_scientific(mantissa: &str, exponent: &str, max_digits: u32) -> Option<String> {
+    let exponent = exponent.strip_prefix('+').unwrap_or(exponent);
+    if exponent.is_empty() || !exponent.chars().all(|character| character.is_ascii_digit()) {
+        return None;
+    }
+
+    let exponent = exponent.parse::<usize>().ok()?;
+    let mantissa = mantissa.replace('_', "");
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((&mantissa, ""));
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.len() > exponent {
+        return None;
+    }
+
+    let digits = format!("{whole}{fraction}{}", "0".repeat(exponent - fraction.len()));
+    let digits = match digits.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+
+    if digits.len() > max_digits as usize {
+        return None;
+    }
+
+    Some(digits.to_owned())
+}
+
+// Returns `1.5e6` for `1500000`, as long as it's shorter and has more than `min_digits` digits
+fn to_scientific(digits: &str, min_digits: u32) -> Option<String> {
+    let digits = digits.replace('_', "");
+    if digits.starts_with('0') || digits.len() <= min_digits as usize {
+        return None;
+    }
+
+    let significant = digits.trim_end_matches('0');
+    let exponent = digits.len() - 1;
+
+    let scientific = match significant.split_at(1) {
+        (first, "") => format!("{first}e{exponent}"),
+        (first, rest) => format!("{first}.{rest}e{exponent}"),
+    };
+
+    if scientific.len() < digits.len() {
+        Some(scientific)
+    } else {
+        None
+    }
+}
+
+fn normalize_hex(digits: &str, config: &NumberStyleConfig) -> String {
+    // Hexadecimal floats, such as `0x1.8P3`, have a binary exponent after `p`
+    let (digits, exponent) = match digits.find(['p', 'P']) {
+        Some(index) => (&digits[..index], Some(&digits[index + 1..])),
+        None => (digits, None),
+    };
+
+    let mut normalized = match config.hex_digits {
+        HexDigits::Upper => digits.to_ascii_uppercase(),
+        HexDigits::Lower => digits.to_ascii_lowercase(),
+        HexDigits::Any => digits.to_owned(),
+    };
+
+    if let Some(exponent) = exponent {
+        normalized.push('p');
+        normalized.push_str(exponent);
+    }
+
+    normalized
+}
+
+fn normalize_decimal(text: &str, config: &NumberStyleConfig) -> String {
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(index) => (&text[..index], Some(&text[index + 1..])),
+        None => (text, None),
+    };
+
+    if let Some(threshold) = config.scientific_notation_above {
+        match exponent {
+            Some(exponent) => {
+                if let Some(digits) = expand_scientific(mantissa, exponent, threshold) {
+                    return normalize_decimal(&digits, config);
+                }
+            }
+
+            None if !mantissa.contains('.') => {
+                if let Some(scientific) = to_scientific(mantissa, threshold) {
+                    return scientific;
+                }
+            }
+
+            None => {}
+        }
+    }
+
+    let (whole, fraction) = match mantissa.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let mut normalized = match config.separators {
+        Separators::Always => group_digits(whole),
+        Separators::Never => whole.replace('_', ""),
+        Separators::Any => whole.to_owned(),
+    };
+
+    if let Some(fraction) = fraction {
+        normalized.push('.');
+        match config.separators {
+            Separators::Never => normalized.push_str(&fraction.replace('_', "")),
+            _ => normalized.push_str(fraction),
+        }
+    }
+
+    if let Some(exponent) = exponent {
+        normalized.push('e');
+        normalized.push_str(exponent);
+    }
+
+    normalized
+}
+
+fn normalize(text: &str, config: &NumberStyleConfig) -> String {
+    let (prefix, digits) = match text.get(..2) {
+        Some("0x" | "0X") => ("0x", &text[2..]),
+        Some("0b" | "0B") => ("0b", &text[2..]),
+        _ => return normalize_decimal(text, config),
+    };
+
+    let digits = match config.separators {
+        Separators::Never => digits.replace('_', ""),
+        _ => digits.to_owned(),
+    };
+
+    if prefix == "0x" {
+        format!("0x{}", normalize_hex(&digits, config))
+    } else {
+        format!("0b{digits}")
+    }
+}
+
+// Whether the number is written as an integer, which Lua 5.3 and later keep as an integer
+fn is_integer_literal(text: &str) -> bool {
+    let is_hex = matches!(text.get(..2), Some("0x" | "0X"));
+    !text.contains('.') && (is_hex || !text.contains(['e', 'E'])) && !text.contains(['p', 'P'])
+}
+
+struct NumberStyleVisitor {
+    config: NumberStyleConfig,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for NumberStyleVisitor {
+    fn visit_value(&mut self, value: &ast::Value) {
+        let token = match value {
+            ast::Value::Number(token) => token,
+            _ => return,
+        };
+
+        let text = token.token().to_string();
+        let normalized = normalize(&text, &self.config);
+
+        if normalized == text {
+            return;
+        }
+
+        let mut notes = Vec::new();
+        let mut fix = Fix::new(range::<_, usize>(token), normalized.to_owned());
+
+        // `1e6` is a float, but `1000000` is an integer, which changes how it's printed and divided
+        if self.config.lua_version.has_integers()
+            && is_integer_literal(&text) != is_integer_literal(&normalized)
+        {
+            let (integer, float) = if is_integer_literal(&text) {
+                (&text, &normalized)
+            } else {
+                (&normalized, &text)
+            };
+
+            notes.push(format!(
+                "note: `{float}` is a float, but `{integer}` is an integer"
+            ));
+            fix = fix.with_applicability(Applicability::Suggestion);
+        }
+
+        self.diagnostics.push(
+            Diagnostic::new_complete(
+                "number_style",
+                Message::new("number_style")
+                    .with("number", &text)
+                    .with("normalized", &normalized),
+                Label::from_node(token, None),
+                notes,
+                Vec::new(),
+            )
+            .with_fix(fix),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "roblox")]
+    use super::super::test_util::test_lint;
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let config = NumberStyleConfig {
+            scientific_notation_above: Some(6),
+            separators: Separators::Always,
+            ..NumberStyleConfig::default()
+        };
+
+        assert_eq!(normalize("0Xff", &config), "0xFF");
+        assert_eq!(normalize("0x1.8P3", &config), "0x1.8p3");
+        assert_eq!(normalize("0B1010", &config), "0b1010");
+        assert_eq!(normalize("1E5", &config), "100_000");
+        assert_eq!(normalize("1.5e3", &config), "1500");
+        assert_eq!(normalize("1e-3", &config), "1e-3");
+        assert_eq!(normalize("1e9", &config), "1e9");
+        assert_eq!(normalize("1000000000", &config), "1e9");
+        assert_eq!(normalize("1500000000", &config), "1.5e9");
+        assert_eq!(normalize("1234567", &config), "1_234_567");
+        assert_eq!(normalize("1_0000", &config), "10_000");
+        assert_eq!(normalize("10_00", &config), "1000");
+        assert_eq!(normalize("12345.678", &config), "12_345.678");
+
+        let config = NumberStyleConfig {
+            hex_digits: HexDigits::Lower,
+            separators: Separators::Never,
+            ..NumberStyleConfig::default()
+        };
+
+        assert_eq!(normalize("0xFF_FF", &config), "0xffff");
+        assert_eq!(normalize("1_000_000", &config), "1000000");
+        assert_eq!(normalize("1000000000", &config), "1000000000");
+    }
+
+    #[test]
+    #[cfg(feature = "roblox")]
+    fn test_number_style() {
+        test_lint(
+            NumberStyleLint::new(NumberStyleConfig::default()).unwrap(),
+            "number_style",
+            "number_style",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "roblox")]
+    fn test_scientific_notation() {
+        test_lint(
+            NumberStyleLint::new(NumberStyleConfig {
+                lua_version: LuaVersion::Lua53,
+                scientific_notation_above: Some(6),
+                separators: Separators::Always,
+                ..NumberStyleConfig::default()
+            })
+            .unwrap(),
+            "number_style",
+            "scientific_notation",
+        );
+    }
+}
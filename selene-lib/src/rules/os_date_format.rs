@@ -0,0 +1,282 @@
+use super::*;
+use crate::ast_util::{
+    evaluate_constant, name_paths::name_path_from_prefix_suffix, scopes::ScopeManager,
+    strip_parentheses, Constant,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+
+// The conversions Lua 5.2+ accepts, which are the ones from C99's strftime
+const C99_CONVERSIONS: &str = "aAbBcCdDeFgGhHIjmMnprRStTuUVwWxXyYzZ%";
+
+// Luau only supports the conversions from C89
+#[cfg(feature = "roblox")]
+const C89_CONVERSIONS: &str = "aAbBcdHIjmMpSUwWxXyYZ%";
+
+const OS_TIME_REQUIRED_FIELDS: &[&str] = &["year", "month", "day"];
+
+pub struct OsDateFormatLint;
+
+impl Rule for OsDateFormatLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(OsDateFormatLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = OsDateFormatVisitor {
+            scope_manager: &ast_context.scope_manager,
+            conversions: conversions(context),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[cfg(feature = "roblox")]
+fn conversions(context: &Context) -> &'static str {
+    if context.is_roblox() {
+        C89_CONVERSIONS
+    } else {
+        C99_CONVERSIONS
+    }
+}
+
+#[cfg(not(feature = "roblox"))]
+fn conversions(_: &Context) -> &'static str {
+    C99_CONVERSIONS
+}
+
+struct OsDateFormatVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    conversions: &'static str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum FormatProblem {
+    InvalidConversion(String),
+    TableWithText,
+}
+
+fn check_format(format: &str, conversions: &str) -> Vec<FormatProblem> {
+    let format = format.strip_prefix('!').unwrap_or(format);
+
+    if format == "*t" {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+
+    if format.starts_with("*t") {
+        problems.push(FormatProblem::TableWithText);
+    }
+
+    let mut chars = format.chars();
+    while let Some(character) = chars.next() {
+        if character != '%' {
+            continue;
+        }
+
+        match chars.next() {
+            // Lua 5.2+ allows the C99 E and O modifiers, such as `%Ec`
+            Some(modifier @ ('E' | 'O')) if conversions == C99_CONVERSIONS => {
+                let allowed = if modifier == 'E' {
+                    "cCxXyY"
+                } else {
+                    "deHImMSuUVwWy"
+                };
+
+                match chars.next() {
+                    Some(conversion) if allowed.contains(conversion) => {}
+                    Some(conversion) => problems.push(FormatProblem::InvalidConversion(format!(
+                        "%{modifier}{conversion}"
+                    ))),
+                    None => problems.push(FormatProblem::InvalidConversion(format!("%{modifier}"))),
+                }
+            }
+
+            Some(conversion) if conversions.contains(conversion) => {}
+            Some(conversion) => {
+                problems.push(FormatProblem::InvalidConversion(format!("%{conversion}")))
+            }
+            None => problems.push(FormatProblem::InvalidConversion("%".to_owned())),
+        }
+    }
+
+    problems
+}
+
+impl OsDateFormatVisitor<'_> {
+    // Returns the arguments for a call to `os.<function>(...)`, as long as `os` is the global
+    fn os_call<'a>(
+        &self,
+        call: &'a ast::FunctionCall,
+        function: &str,
+    ) -> Option<Vec<&'a ast::Expression>> {
+        let name_path = name_path_from_prefix_suffix(call.prefix(), call.suffixes())?;
+        if name_path != ["os", function] {
+            return None;
+        }
+
+        if let Some(reference) = self
+            .scope_manager
+            .reference_at_byte(call.start_position()?.bytes())
+        {
+            if reference.resolved.is_some() {
+                return None;
+            }
+        }
+
+        let mut suffixes = call.suffixes().skip(1);
+        match (suffixes.next(), suffixes.next()) {
+            (
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                    arguments,
+                    ..
+                }))),
+                None,
+            ) => Some(arguments.iter().collect()),
+
+            _ => None,
+        }
+    }
+
+    fn check_os_date(&mut self, format_expression: &ast::Expression) {
+        let format = match evaluate_constant(format_expression) {
+            Some(Constant::String(format)) => format,
+            _ => return,
+        };
+
+        for problem in check_format(&format, self.conversions) {
+            self.diagnostics.push(match problem {
+                FormatProblem::InvalidConversion(conversion) => Diagnostic::new_complete(
+                    "os_date_format",
+                    Message::new("os_date_format.invalid_conversion")
+                        .with("conversion", conversion),
+                    Label::from_node(format_expression, None),
+                    vec![format!(
+                        "note: the valid conversions are {}",
+                        self.conversions
+                            .chars()
+                            .map(|conversion| format!("%{conversion}"))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    )],
+                    Vec::new(),
+                ),
+
+                FormatProblem::TableWithText => Diagnostic::new_complete(
+                    "os_date_format",
+                    Message::new("os_date_format.table"),
+                    Label::from_node(format_expression, None),
+                    vec!["note: with anything after `*t`, a string is returned instead".to_owned()],
+                    Vec::new(),
+                ),
+            });
+        }
+    }
+
+    fn check_os_time(&mut self, table_expression: &ast::Expression) {
+        let table = match strip_parentheses(table_expression) {
+            ast::Expression::Value { value, .. } => match &**value {
+                ast::Value::TableConstructor(table) => table,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let mut keys = Vec::new();
+
+        for field in table.fields() {
+            match field {
+                ast::Field::NameKey { key, .. } => keys.push(key.token().to_string()),
+                ast::Field::ExpressionKey { key, .. } => match evaluate_constant(key) {
+                    Some(Constant::String(key)) => keys.push(key),
+                    _ => return,
+                },
+                // Can't know what keys are in the table
+                _ => return,
+            }
+        }
+
+        let missing = OS_TIME_REQUIRED_FIELDS
+            .iter()
+            .filter(|field| !keys.iter().any(|key| key == *field))
+            .map(|field| format!("`{field}`"))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "os_date_format",
+                Message::new("os_date_format.missing_fields").with("fields", missing.join(", ")),
+                Label::from_node(table, None),
+                vec!["note: `year`, `month`, and `day` are required".to_owned()],
+                Vec::new(),
+            ));
+        }
+    }
+}
+
+impl Visitor for OsDateFormatVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let Some(arguments) = self.os_call(call, "date") {
+            if let Some(format) = arguments.first() {
+                self.check_os_date(format);
+            }
+        } else if let Some(arguments) = self.os_call(call, "time") {
+            if let Some(table) = arguments.first() {
+                self.check_os_time(table);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_check_format() {
+        assert!(check_format("%Y-%m-%d %H:%M:%S", C99_CONVERSIONS).is_empty());
+        assert!(check_format("!*t", C99_CONVERSIONS).is_empty());
+        assert!(check_format("%Ec %Od", C99_CONVERSIONS).is_empty());
+
+        assert_eq!(
+            check_format("%Q", C99_CONVERSIONS),
+            vec![FormatProblem::InvalidConversion("%Q".to_owned())]
+        );
+
+        assert_eq!(
+            check_format("100%", C99_CONVERSIONS),
+            vec![FormatProblem::InvalidConversion("%".to_owned())]
+        );
+
+        assert_eq!(
+            check_format("*t%Y", C99_CONVERSIONS),
+            vec![FormatProblem::TableWithText]
+        );
+    }
+
+    #[test]
+    fn test_os_date_format() {
+        test_lint(
+            OsDateFormatLint::new(()).unwrap(),
+            "os_date_format",
+            "os_date_format",
+        );
+    }
+}
@@ -33,7 +33,7 @@ impl Rule for ParentheseConditionsLint {
             .map(|position| {
                 Diagnostic::new(
                     "parenthese_conditions",
-                    "lua does not require parentheses around conditions".to_owned(),
+                    Message::new("parenthese_conditions"),
                     Label::new(*position),
                 )
             })
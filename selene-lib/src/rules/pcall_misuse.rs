@@ -0,0 +1,234 @@
+use super::*;
+use crate::{
+    ast_util::{scopes::ScopeManager, strip_parentheses},
+    standard_library::{ArgumentType, FieldKind},
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+
+pub struct PcallMisuseLint;
+
+impl Rule for PcallMisuseLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PcallMisuseLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = PcallMisuseVisitor {
+            scope_manager: &ast_context.scope_manager,
+            xpcall_forwards_arguments: xpcall_forwards_arguments(context),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+// Lua 5.1's xpcall only takes the function and the error handler, while later versions
+// and Luau pass any more arguments along to the function.
+fn xpcall_forwards_arguments(context: &Context) -> bool {
+    match context.standard_library.find_global(&["xpcall"]) {
+        Some(field) => match &field.field_kind {
+            FieldKind::Function(function) => function
+                .arguments
+                .iter()
+                .any(|argument| argument.argument_type == ArgumentType::Vararg),
+            _ => true,
+        },
+
+        None => true,
+    }
+}
+
+struct PcallMisuseVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    xpcall_forwards_arguments: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// Returns the name and arguments of a call to the global `pcall` or `xpcall`
+fn pcall_arguments<'a>(
+    call: &'a ast::FunctionCall,
+    scope_manager: &ScopeManager,
+) -> Option<(String, Vec<&'a ast::Expression>)> {
+    let name = match call.prefix() {
+        ast::Prefix::Name(name) => name.token().to_string(),
+        _ => return None,
+    };
+
+    if name != "pcall" && name != "xpcall" {
+        return None;
+    }
+
+    if let Some(reference) = scope_manager.reference_at_byte(call.start_position()?.bytes()) {
+        if reference.resolved.is_some() {
+            return None;
+        }
+    }
+
+    let mut suffixes = call.suffixes();
+    match (suffixes.next(), suffixes.next()) {
+        (
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                arguments,
+                ..
+            }))),
+            None,
+        ) => Some((name, arguments.iter().collect())),
+
+        _ => None,
+    }
+}
+
+// `f(a, b)` -> `f, a, b`, for suggesting `pcall(f, a, b)`
+fn call_as_pcall_arguments(call: &ast::FunctionCall) -> Option<String> {
+    let suffixes = call.suffixes().collect::<Vec<_>>();
+    let (last, rest) = suffixes.split_last()?;
+
+    let arguments = match last {
+        ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+            arguments,
+            ..
+        })) => arguments,
+        _ => return None,
+    };
+
+    let mut function = call.prefix().to_string();
+    for suffix in rest {
+        function.push_str(&suffix.to_string());
+    }
+
+    let mut parts = vec![function.trim().to_owned()];
+    parts.extend(
+        arguments
+            .iter()
+            .map(|argument| argument.to_string().trim().to_owned()),
+    );
+
+    Some(parts.join(", "))
+}
+
+impl PcallMisuseVisitor<'_> {
+    fn check_call(&mut self, call: &ast::FunctionCall) {
+        let (name, arguments) = match pcall_arguments(call, self.scope_manager) {
+            Some(result) => result,
+            None => return,
+        };
+
+        if let Some(function) = arguments.first() {
+            if let ast::Expression::Value { value, .. } = strip_parentheses(function) {
+                if let ast::Value::FunctionCall(inner_call) = &**value {
+                    let mut diagnostic = Diagnostic::new(
+                        "pcall_misuse",
+                        Message::new("pcall_misuse.called_early")
+                            .with("function", function.to_string().trim())
+                            .with("name", &name),
+                        Label::from_node(function, None),
+                    );
+
+                    match call_as_pcall_arguments(inner_call) {
+                        Some(suggestion) if name == "pcall" => {
+                            diagnostic =
+                                diagnostic.with_suggestion(format!("`pcall({suggestion})`"));
+                        }
+                        _ => diagnostic.notes.push(format!(
+                            "note: {name} takes the function to call, not the result of calling it"
+                        )),
+                    }
+
+                    self.diagnostics.push(diagnostic);
+                }
+            }
+        }
+
+        if name == "xpcall" && !self.xpcall_forwards_arguments && arguments.len() > 2 {
+            self.diagnostics.push(
+                Diagnostic::new_complete(
+                    "pcall_misuse",
+                    Message::new("pcall_misuse.xpcall_arguments"),
+                    Label::from_node(call, None),
+                    Vec::new(),
+                    arguments
+                        .iter()
+                        .skip(2)
+                        .map(|argument| Label::from_node(argument, Some("ignored".to_owned())))
+                        .collect(),
+                )
+                .with_suggestion("`xpcall(function() return f(...) end, handler)`"),
+            );
+        }
+    }
+}
+
+impl Visitor for PcallMisuseVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.check_call(call);
+    }
+
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        let call = match stmt {
+            ast::Stmt::FunctionCall(call) => call,
+            _ => return,
+        };
+
+        if let Some((name, _)) = pcall_arguments(call, self.scope_manager) {
+            self.diagnostics.push(
+                Diagnostic::new_complete(
+                    "pcall_misuse",
+                    Message::new("pcall_misuse.result_ignored").with("name", name),
+                    Label::from_node(call, None),
+                    Vec::new(),
+                    Vec::new(),
+                )
+                .with_suggestion(format!(
+                    "`local ok, err = {}` and handle `err`",
+                    call.to_string().trim()
+                )),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+    use crate::StandardLibrary;
+
+    #[test]
+    fn test_pcall_misuse() {
+        test_lint(
+            PcallMisuseLint::new(()).unwrap(),
+            "pcall_misuse",
+            "pcall_misuse",
+        );
+    }
+
+    #[test]
+    fn test_xpcall_lua52() {
+        test_lint_config(
+            PcallMisuseLint::new(()).unwrap(),
+            "pcall_misuse",
+            "xpcall_lua52",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("lua52").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+use crate::ast_util::{range, HasSideEffects};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct PreferCompoundAssignmentLint;
+
+impl Rule for PreferCompoundAssignmentLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PreferCompoundAssignmentLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        // Other versions of Lua don't have compound assignments, so the fix would break the code
+        if !context.is_luau() {
+            return Vec::new();
+        }
+
+        let mut visitor = PreferCompoundAssignmentVisitor {
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct PreferCompoundAssignmentVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn compound_operator(binop: &ast::BinOp) -> Option<&'static str> {
+    match binop {
+        ast::BinOp::Caret(_) => Some("^="),
+        ast::BinOp::Minus(_) => Some("-="),
+        ast::BinOp::Percent(_) => Some("%="),
+        ast::BinOp::Plus(_) => Some("+="),
+        ast::BinOp::Slash(_) => Some("/="),
+        ast::BinOp::Star(_) => Some("*="),
+        ast::BinOp::TwoDots(_) => Some("..="),
+        _ => None,
+    }
+}
+
+// `x += 1` only evaluates `x` once, so `t[f()] = t[f()] + 1` can't be changed without calling `f` less
+fn var_has_side_effects(var: &ast::Var) -> bool {
+    let var_expression = match var {
+        ast::Var::Expression(var_expression) => var_expression,
+        _ => return false,
+    };
+
+    var_expression.prefix().has_side_effects()
+        || var_expression.suffixes().any(|suffix| match suffix {
+            ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                expression.has_side_effects()
+            }
+            ast::Suffix::Index(_) => false,
+            _ => true,
+        })
+}
+
+impl Visitor for PreferCompoundAssignmentVisitor {
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        if assignment.variables().len() != 1 || assignment.expressions().len() != 1 {
+            return;
+        }
+
+        let var = assignment.variables().iter().next().unwrap();
+
+        let (lhs, binop, rhs) = match assignment.expressions().iter().next().unwrap() {
+            ast::Expression::BinaryOperator { lhs, binop, rhs } => (lhs, binop, rhs),
+            _ => return,
+        };
+
+        let operator = match compound_operator(binop) {
+            Some(operator) => operator,
+            None => return,
+        };
+
+        let var_text = var.to_string().trim().to_owned();
+
+        if lhs.to_string().trim() != var_text || var_has_side_effects(var) {
+            return;
+        }
+
+        let replacement = format!("{var_text} {operator} {}", rhs.to_string().trim());
+        let assignment_range: (usize, usize) = range(assignment);
+
+        let mut diagnostic = Diagnostic::new(
+            "prefer_compound_assignment",
+            Message::new("prefer_compound_assignment").with("operator", operator),
+            Label::from_node(assignment, None),
+        );
+
+        if !replacement.contains('\n') {
+            diagnostic = diagnostic.with_suggestion(format!("`{replacement}`"));
+        }
+
+        self.diagnostics
+            .push(diagnostic.with_fix(Fix::new(assignment_range, replacement)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+    use crate::StandardLibrary;
+
+    #[test]
+    fn test_prefer_compound_assignment() {
+        test_lint_config(
+            PreferCompoundAssignmentLint::new(()).unwrap(),
+            "prefer_compound_assignment",
+            "prefer_compound_assignment",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_not_luau() {
+        test_lint(
+            PreferCompoundAssignmentLint::new(()).unwrap(),
+            "prefer_compound_assignment",
+            "not_luau",
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::*;
+use crate::ast_util::{local_reassignments, range};
+use std::{collections::HashMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct PreferConstLint;
+
+impl Rule for PreferConstLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PreferConstLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = PreferConstVisitor {
+            diagnostics: Vec::new(),
+            reassignments: local_reassignments(ast, &ast_context.scope_manager),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct PreferConstVisitor {
+    diagnostics: Vec<Diagnostic>,
+    reassignments: HashMap<(usize, usize), Vec<(usize, usize)>>,
+}
+
+// Whether the name at this index of `local a, b, c = ...` is given a value
+fn has_value(local_assignment: &ast::LocalAssignment, index: usize) -> bool {
+    let expressions = local_assignment.expressions();
+
+    if index < expressions.len() {
+        return true;
+    }
+
+    // `local a, b = f()` and `local a, b = ...` give both a value
+    match expressions.iter().last() {
+        Some(ast::Expression::Value { value, .. }) => match &**value {
+            ast::Value::FunctionCall(_) => true,
+            ast::Value::Symbol(symbol) => symbol.token().to_string() == "...",
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
+
+impl Visitor for PreferConstVisitor {
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        for (index, (name, attribute)) in local_assignment
+            .names()
+            .iter()
+            .zip(local_assignment.attributes())
+            .enumerate()
+        {
+            if attribute.is_some() || !has_value(local_assignment, index) {
+                continue;
+            }
+
+            if self.reassignments.contains_key(&range(name)) {
+                continue;
+            }
+
+            let name_range = range::<_, u32>(name);
+
+            self.diagnostics.push(
+                Diagnostic::new(
+                    "prefer_const",
+                    Message::new("prefer_const").with("name", name.token()),
+                    Label::new(name_range),
+                )
+                .with_fix(Fix::new(
+                    (name_range.1, name_range.1),
+                    " <const>".to_owned(),
+                )),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_prefer_const() {
+        test_lint(
+            PreferConstLint::new(()).unwrap(),
+            "prefer_const",
+            "prefer_const",
+        );
+    }
+}
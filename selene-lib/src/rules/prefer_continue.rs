@@ -0,0 +1,221 @@
+use super::*;
+use crate::ast_util::range;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{TokenReference, TokenType},
+    visitors::Visitor,
+};
+
+pub struct PreferContinueLint;
+
+impl Rule for PreferContinueLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PreferContinueLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        // `continue` is only a keyword in Luau, elsewhere the fix would break the code
+        if !context.is_luau() {
+            return Vec::new();
+        }
+
+        let mut visitor = PreferContinueVisitor {
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct PreferContinueVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_empty(block: &ast::Block) -> bool {
+    block.stmts().next().is_none() && block.last_stmt().is_none()
+}
+
+fn has_comments(token: &TokenReference) -> bool {
+    token
+        .leading_trivia()
+        .chain(token.trailing_trivia())
+        .any(|trivia| {
+            matches!(
+                trivia.token_type(),
+                TokenType::SingleLineComment { .. } | TokenType::MultiLineComment { .. }
+            )
+        })
+}
+
+// The whitespace at the start of the line the token is on
+fn indentation(token: &TokenReference) -> String {
+    let leading_trivia = token
+        .leading_trivia()
+        .map(ToString::to_string)
+        .collect::<String>();
+
+    leading_trivia
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .to_owned()
+}
+
+// Re-indenting would change the contents of multi-line strings and comments
+fn has_multi_line_tokens(block: &ast::Block) -> bool {
+    block.tokens().any(|token| {
+        std::iter::once(token.token())
+            .chain(token.leading_trivia())
+            .chain(token.trailing_trivia())
+            .any(|token| {
+                matches!(
+                    token.token_type(),
+                    TokenType::StringLiteral {
+                        multi_line: Some(_),
+                        ..
+                    } | TokenType::MultiLineComment { .. }
+                )
+            })
+    })
+}
+
+// Turns `if cond then else body end` into `if cond then continue end` followed by the body
+fn continue_fix(if_stmt: &ast::If, body: &ast::Block) -> Option<Fix> {
+    if has_comments(if_stmt.then_token())
+        || has_comments(if_stmt.else_token()?)
+        || has_multi_line_tokens(body)
+    {
+        return None;
+    }
+
+    let first_token = body
+        .tokens()
+        .min_by_key(|token| token.token().start_position().bytes())?;
+
+    let if_indentation = indentation(if_stmt.if_token());
+    let body_indentation = indentation(first_token);
+    let unit = body_indentation.strip_prefix(&if_indentation)?;
+
+    if unit.is_empty() {
+        return None;
+    }
+
+    let body_text = body
+        .to_string()
+        .lines()
+        .map(|line| match line.strip_prefix(&body_indentation) {
+            Some(rest) => format!("{if_indentation}{rest}"),
+            None => line.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let replacement = format!(
+        "if {} then\n{if_indentation}{unit}continue\n{if_indentation}end\n{}",
+        if_stmt.condition().to_string().trim(),
+        body_text.trim_end()
+    );
+
+    let if_range: (usize, usize) = range(if_stmt);
+    Some(Fix::new(if_range, replacement))
+}
+
+impl PreferContinueVisitor {
+    fn check_loop_block(&mut self, block: &ast::Block) {
+        // Anything after the `if` would be skipped by `continue`
+        if block.last_stmt().is_some() {
+            return;
+        }
+
+        let (if_stmt, semicolon) = match block.stmts_with_semicolon().last() {
+            Some((ast::Stmt::If(if_stmt), semicolon)) => (if_stmt, semicolon),
+            _ => return,
+        };
+
+        let body = match if_stmt.else_block() {
+            Some(body) if if_stmt.else_if().is_none() && is_empty(if_stmt.block()) => body,
+            _ => return,
+        };
+
+        if is_empty(body) {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::new_complete(
+            "prefer_continue",
+            Message::new("prefer_continue"),
+            Label::from_node(if_stmt, None),
+            Vec::new(),
+            Vec::new(),
+        )
+        .with_suggestion(format!(
+            "`if {} then continue end` followed by the `else` block",
+            if_stmt.condition().to_string().trim()
+        ));
+
+        if semicolon.is_none() {
+            if let Some(fix) = continue_fix(if_stmt, body) {
+                diagnostic = diagnostic.with_fix(fix);
+            }
+        }
+
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl Visitor for PreferContinueVisitor {
+    fn visit_generic_for(&mut self, generic_for: &ast::GenericFor) {
+        self.check_loop_block(generic_for.block());
+    }
+
+    fn visit_numeric_for(&mut self, numeric_for: &ast::NumericFor) {
+        self.check_loop_block(numeric_for.block());
+    }
+
+    // `repeat` isn't checked, as Luau doesn't allow `continue` in it when `until` uses a local
+    fn visit_while(&mut self, while_loop: &ast::While) {
+        self.check_loop_block(while_loop.block());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+    use crate::StandardLibrary;
+
+    #[test]
+    fn test_prefer_continue() {
+        test_lint_config(
+            PreferContinueLint::new(()).unwrap(),
+            "prefer_continue",
+            "prefer_continue",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_not_luau() {
+        test_lint(
+            PreferContinueLint::new(()).unwrap(),
+            "prefer_continue",
+            "not_luau",
+        );
+    }
+}
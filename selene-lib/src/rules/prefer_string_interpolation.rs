@@ -0,0 +1,202 @@
+use super::*;
+use crate::ast_util::{name_paths::name_path_from_prefix_suffix, scopes::ScopeManager};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{StringLiteralQuoteType, TokenType},
+    visitors::Visitor,
+};
+
+pub struct PreferStringInterpolationLint;
+
+impl Rule for PreferStringInterpolationLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PreferStringInterpolationLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        // Interpolated strings are only Luau, elsewhere the suggestion would break the code
+        if !context.is_luau() {
+            return Vec::new();
+        }
+
+        let mut visitor = PreferStringInterpolationVisitor {
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct PreferStringInterpolationVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// Builds the interpolated string for a format that only uses `%s`, such as `"Hello %s"`.
+// Returns None for anything that would need to be escaped.
+fn interpolate(format: &str, arguments: &[String]) -> Option<String> {
+    if format.contains(['{', '}', '`', '\\']) {
+        return None;
+    }
+
+    let mut pieces = format.split("%s");
+    let mut interpolated = String::from("`");
+    interpolated.push_str(pieces.next()?);
+
+    let mut arguments = arguments.iter();
+
+    for piece in pieces {
+        interpolated.push('{');
+        interpolated.push_str(arguments.next()?);
+        interpolated.push('}');
+        interpolated.push_str(piece);
+    }
+
+    // Every argument has to be used, and nothing else can be formatted
+    if arguments.next().is_some() || interpolated.contains('%') {
+        return None;
+    }
+
+    interpolated.push('`');
+    Some(interpolated)
+}
+
+impl PreferStringInterpolationVisitor<'_> {
+    fn is_string_format(&self, call: &ast::FunctionCall) -> bool {
+        match name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+            Some(name_path) if name_path == ["string", "format"] => {}
+            _ => return false,
+        }
+
+        match call
+            .start_position()
+            .and_then(|position| self.scope_manager.reference_at_byte(position.bytes()))
+        {
+            Some(reference) => reference.resolved.is_none(),
+            None => true,
+        }
+    }
+}
+
+impl Visitor for PreferStringInterpolationVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if !self.is_string_format(call) {
+            return;
+        }
+
+        let arguments = match call.suffixes().nth(1) {
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                arguments,
+                ..
+            }))) => arguments,
+            _ => return,
+        };
+
+        let mut arguments = arguments.iter();
+
+        let format = match arguments.next() {
+            Some(ast::Expression::Value { value, .. }) => match &**value {
+                ast::Value::String(token) => match token.token_type() {
+                    TokenType::StringLiteral {
+                        literal,
+                        quote_type: StringLiteralQuoteType::Double | StringLiteralQuoteType::Single,
+                        ..
+                    } => literal.to_string(),
+                    _ => return,
+                },
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let arguments = arguments
+            .map(|argument| argument.to_string().trim().to_owned())
+            .collect::<Vec<_>>();
+
+        // Interpolated strings can't be empty, and a format without `%s` is just a string
+        if arguments.is_empty() || arguments.iter().any(|argument| argument.contains('\n')) {
+            return;
+        }
+
+        let interpolated = match interpolate(&format, &arguments) {
+            Some(interpolated) => interpolated,
+            None => return,
+        };
+
+        self.diagnostics.push(
+            Diagnostic::new_complete(
+                "prefer_string_interpolation",
+                Message::new("prefer_string_interpolation"),
+                Label::from_node(call, None),
+                Vec::new(),
+                Vec::new(),
+            )
+            .with_suggestion(interpolated),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+    use crate::StandardLibrary;
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(
+            interpolate("%s", &["x".to_owned()]),
+            Some("`{x}`".to_owned())
+        );
+
+        assert_eq!(
+            interpolate(
+                "Hello %s, you have %s coins",
+                &["name".to_owned(), "coins".to_owned()]
+            ),
+            Some("`Hello {name}, you have {coins} coins`".to_owned())
+        );
+
+        assert_eq!(interpolate("%d", &["x".to_owned()]), None);
+        assert_eq!(interpolate("%s%%", &["x".to_owned()]), None);
+        assert_eq!(interpolate("{%s}", &["x".to_owned()]), None);
+        assert_eq!(interpolate("%s %s", &["x".to_owned()]), None);
+        assert_eq!(interpolate("%s", &["x".to_owned(), "y".to_owned()]), None);
+    }
+
+    #[test]
+    fn test_prefer_string_interpolation() {
+        test_lint_config(
+            PreferStringInterpolationLint::new(()).unwrap(),
+            "prefer_string_interpolation",
+            "prefer_string_interpolation",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_not_luau() {
+        test_lint(
+            PreferStringInterpolationLint::new(()).unwrap(),
+            "prefer_string_interpolation",
+            "not_luau",
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use super::*;
+use crate::ast_util::strip_parentheses;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, types, Ast},
+    tokenizer::{Symbol, TokenType},
+    visitors::Visitor,
+};
+
+pub struct RedundantTypeCastLint;
+
+impl Rule for RedundantTypeCastLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RedundantTypeCastLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = RedundantTypeCastVisitor {
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct RedundantTypeCastVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+// The type Luau gives a literal on its own, such as `number` for `5`
+fn literal_type(value: &ast::Value) -> Option<&'static str> {
+    match value {
+        ast::Value::Number(_) => Some("number"),
+        ast::Value::String(_) => Some("string"),
+        ast::Value::Symbol(token) => match token.token_type() {
+            TokenType::Symbol {
+                symbol: Symbol::True | Symbol::False,
+            } => Some("boolean"),
+            TokenType::Symbol {
+                symbol: Symbol::Nil,
+            } => Some("nil"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn type_assertion(expression: &ast::Expression) -> Option<&types::TypeAssertion> {
+    match expression {
+        ast::Expression::Value {
+            type_assertion: Some(type_assertion),
+            ..
+        } => Some(type_assertion),
+        _ => None,
+    }
+}
+
+impl Visitor for RedundantTypeCastVisitor {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let (value, outer) = match expression {
+            ast::Expression::Value {
+                value,
+                type_assertion: Some(type_assertion),
+            } => (value, type_assertion),
+            _ => return,
+        };
+
+        let cast_to = outer.cast_to();
+
+        if let ast::Value::ParenthesesExpression(inner) = &**value {
+            if let Some(inner) = type_assertion(strip_parentheses(inner)) {
+                // `(x :: any) :: T` is how to cast between types Luau doesn't think are related
+                if super::any_type_cast::is_any(inner.cast_to()) {
+                    return;
+                }
+
+                self.diagnostics.push(
+                    Diagnostic::new_complete(
+                        "redundant_type_cast",
+                        Message::new("redundant_type_cast.cast_again")
+                            .with("type", inner.cast_to().to_string().trim()),
+                        Label::from_node(outer, None),
+                        Vec::new(),
+                        vec![Label::from_node(inner, Some("first cast".to_owned()))],
+                    )
+                    .with_help("only the last cast is needed"),
+                );
+
+                return;
+            }
+        }
+
+        let literal_type = match literal_type(value) {
+            Some(literal_type) => literal_type,
+            None => return,
+        };
+
+        if matches!(cast_to, types::TypeInfo::Basic(name) if name.token().to_string() == literal_type)
+        {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "redundant_type_cast",
+                Message::new("redundant_type_cast.literal").with("type", literal_type),
+                Label::from_node(outer, None),
+                Vec::new(),
+                Vec::new(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_redundant_type_cast() {
+        test_lint(
+            RedundantTypeCastLint::new(()).unwrap(),
+            "redundant_type_cast",
+            "redundant_type_cast",
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use super::*;
+use crate::ast_util::{local_require, range, require_path, scopes::ScopeManager};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{TokenReference, TokenType},
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RequireOrderConfig {
+    sort: bool,
+    groups: Vec<String>,
+}
+
+pub struct RequireOrderLint {
+    sort: bool,
+    groups: Vec<Regex>,
+}
+
+struct Require<'a> {
+    stmt: &'a ast::Stmt,
+    semicolon: Option<&'a TokenReference>,
+    name: String,
+    group: usize,
+    after_blank_line: bool,
+}
+
+impl Rule for RequireOrderLint {
+    type Config = RequireOrderConfig;
+    type Error = regex::Error;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RequireOrderLint {
+            sort: config.sort,
+            groups: config
+                .groups
+                .iter()
+                .map(|group| Regex::new(group))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let mut first_code = None;
+        let mut runs = Vec::new();
+        let mut current_run = Vec::new();
+
+        for (stmt, semicolon) in ast.nodes().stmts_with_semicolon() {
+            let require = match self.require(stmt, semicolon.as_ref(), &ast_context.scope_manager) {
+                Some(require) => require,
+                None => {
+                    if first_code.is_none() && !is_setup(stmt) {
+                        first_code = Some(stmt);
+                    }
+
+                    runs.push(std::mem::take(&mut current_run));
+                    continue;
+                }
+            };
+
+            if let Some(first_code) = first_code {
+                diagnostics.push(Diagnostic::new_complete(
+                    "require_order",
+                    Message::new("require_order.not_at_top"),
+                    Label::from_node(stmt, None),
+                    Vec::new(),
+                    vec![Label::from_node(
+                        first_code,
+                        Some("first code that isn't a require".to_owned()),
+                    )],
+                ));
+            }
+
+            current_run.push(require);
+        }
+
+        runs.push(current_run);
+
+        if self.sort || !self.groups.is_empty() {
+            for run in &runs {
+                if self.groups.is_empty() {
+                    // Without groups, requires separated by blank lines are kept apart
+                    let mut paragraph_start = 0;
+
+                    for index in 1..=run.len() {
+                        if index == run.len() || run[index].after_blank_line {
+                            diagnostics.extend(self.check_run(&run[paragraph_start..index]));
+                            paragraph_start = index;
+                        }
+                    }
+                } else {
+                    diagnostics.extend(self.check_run(run));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl RequireOrderLint {
+    fn require<'a>(
+        &self,
+        stmt: &'a ast::Stmt,
+        semicolon: Option<&'a TokenReference>,
+        scope_manager: &ScopeManager,
+    ) -> Option<Require<'a>> {
+        let local_assignment = match stmt {
+            ast::Stmt::LocalAssignment(local_assignment) => local_assignment,
+            _ => return None,
+        };
+
+        let (name, argument) = local_require(local_assignment)?;
+
+        // `require` could be a local function that does something else
+        let require_position = range(local_assignment.expressions().iter().next()?).0;
+
+        if let Some(reference) = scope_manager.reference_at_byte(require_position) {
+            if reference.resolved.is_some() {
+                return None;
+            }
+        }
+
+        let path = require_path(argument);
+
+        let leading_trivia = stmt
+            .tokens()
+            .min_by_key(|token| token.token().start_position().bytes())?
+            .leading_trivia()
+            .collect::<Vec<_>>();
+
+        // The new line ending the previous statement is part of its trailing trivia,
+        // so any other new line that doesn't end a comment is a blank line
+        let after_blank_line = leading_trivia.iter().enumerate().any(|(index, trivia)| {
+            trivia.to_string().contains('\n')
+                && !(index > 0 && is_comment(leading_trivia[index - 1]))
+        });
+
+        Some(Require {
+            stmt,
+            semicolon,
+            name: name.token().to_string(),
+            group: self
+                .groups
+                .iter()
+                .position(|group| group.is_match(&path))
+                .unwrap_or(self.groups.len()),
+            after_blank_line,
+        })
+    }
+
+    fn check_run(&self, run: &[Require]) -> Option<Diagnostic> {
+        let mut sorted = run.iter().collect::<Vec<_>>();
+        sorted.sort_by_cached_key(|require| {
+            (
+                require.group,
+                if self.sort {
+                    Some(require.name.to_lowercase())
+                } else {
+                    None
+                },
+            )
+        });
+
+        let (index, expected) = sorted
+            .iter()
+            .enumerate()
+            .find(|(index, require)| !std::ptr::eq(**require, &run[*index]))?;
+
+        let actual = &run[index];
+
+        let note = if expected.group != actual.group {
+            "requires are grouped in the order of the `groups` config"
+        } else {
+            "requires are sorted alphabetically"
+        };
+
+        let diagnostic = Diagnostic::new_complete(
+            "require_order",
+            Message::new("require_order")
+                .with("expected", &expected.name)
+                .with("actual", &actual.name),
+            Label::from_node(expected.stmt, None),
+            vec![note.to_owned()],
+            Vec::new(),
+        );
+
+        Some(match reorder_fix(run, &sorted, !self.groups.is_empty()) {
+            Some(fix) => diagnostic.with_fix(fix),
+            None => diagnostic,
+        })
+    }
+}
+
+// Locals that requires commonly depend on, such as `local Packages = ReplicatedStorage.Packages`
+fn is_setup(stmt: &ast::Stmt) -> bool {
+    match stmt {
+        ast::Stmt::LocalAssignment(local_assignment) => {
+            local_assignment
+                .expressions()
+                .iter()
+                .all(|expression| match expression {
+                    ast::Expression::Value { value, .. } => {
+                        matches!(&**value, ast::Value::Var(_) | ast::Value::FunctionCall(_))
+                    }
+                    _ => false,
+                })
+        }
+        _ => false,
+    }
+}
+
+fn is_comment(token: &full_moon::tokenizer::Token) -> bool {
+    matches!(
+        token.token_type(),
+        TokenType::SingleLineComment { .. } | TokenType::MultiLineComment { .. }
+    )
+}
+
+fn trivia_text<'a>(
+    trivia: impl Iterator<Item = &'a full_moon::tokenizer::Token>,
+) -> (String, bool) {
+    let mut text = String::new();
+    let mut has_comments = false;
+
+    for token in trivia {
+        has_comments |= is_comment(token);
+        text.push_str(&token.to_string());
+    }
+
+    (text, has_comments)
+}
+
+// The statement's code, followed by any comments on the same line, along with the trivia around it
+fn split_trivia(stmt: &ast::Stmt) -> Option<(String, (String, bool), String)> {
+    let leading = trivia_text(
+        stmt.tokens()
+            .min_by_key(|token| token.token().start_position().bytes())?
+            .leading_trivia(),
+    );
+
+    let trailing_newline = match stmt
+        .tokens()
+        .max_by_key(|token| token.token().end_position().bytes())?
+        .trailing_trivia()
+        .last()
+    {
+        Some(token) if matches!(token.token_type(), TokenType::Whitespace { .. }) => {
+            token.to_string()
+        }
+        _ => String::new(),
+    };
+
+    let text = stmt.to_string();
+    let code = text.get(leading.0.len()..text.len() - trailing_newline.len())?;
+
+    Some((code.to_owned(), leading, trailing_newline))
+}
+
+// Swaps the code of each statement while keeping the blank lines between them,
+// or when grouping, puts a blank line between each group instead.
+// Comments on their own line can't tell which require they belong to, so those aren't fixed.
+fn reorder_fix(run: &[Require], sorted: &[&Require], separate_groups: bool) -> Option<Fix> {
+    if run.iter().any(|require| require.semicolon.is_some()) {
+        return None;
+    }
+
+    let mut codes = Vec::new();
+    let mut separators = Vec::new();
+    let mut previous_newline: Option<String> = None;
+
+    for require in run {
+        let (code, (leading, leading_has_comments), trailing_newline) = split_trivia(require.stmt)?;
+
+        if let Some(previous_newline) = previous_newline {
+            if leading_has_comments {
+                return None;
+            }
+
+            separators.push(previous_newline + &leading);
+        }
+
+        codes.push(code);
+        previous_newline = Some(trailing_newline);
+    }
+
+    let newline = separators
+        .first()?
+        .split_inclusive('\n')
+        .next()
+        .unwrap_or("\n")
+        .to_owned();
+
+    let mut replacement = String::new();
+
+    for (index, require) in sorted.iter().enumerate() {
+        let original_index = run.iter().position(|other| std::ptr::eq(other, *require))?;
+        replacement.push_str(&codes[original_index]);
+
+        match sorted.get(index + 1) {
+            Some(next) if separate_groups && next.group != require.group => {
+                replacement.push_str(&newline);
+                replacement.push_str(&newline);
+            }
+
+            Some(_) if separate_groups => replacement.push_str(&newline),
+
+            Some(_) => replacement.push_str(&separators[index]),
+
+            None => {}
+        }
+    }
+
+    let (start, _): (usize, usize) = range(run.first()?.stmt);
+    let (last_start, _): (usize, usize) = range(run.last()?.stmt);
+
+    // Modules can have side effects when they're loaded, so loading them in another order could
+    // change what the code does
+    Some(
+        Fix::new((start, last_start + codes.last()?.len()), replacement)
+            .with_applicability(Applicability::Suggestion),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_require_order() {
+        test_lint(
+            RequireOrderLint::new(RequireOrderConfig::default()).unwrap(),
+            "require_order",
+            "require_order",
+        );
+    }
+
+    #[test]
+    fn test_sorted() {
+        test_lint(
+            RequireOrderLint::new(RequireOrderConfig {
+                sort: true,
+                groups: Vec::new(),
+            })
+            .unwrap(),
+            "require_order",
+            "sorted",
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        test_lint(
+            RequireOrderLint::new(RequireOrderConfig {
+                sort: true,
+                groups: vec!["^Packages".to_owned(), "^Shared".to_owned()],
+            })
+            .unwrap(),
+            "require_order",
+            "groups",
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::convert::Infallible;
+
+use full_moon::{ast, visitors::Visitor};
+
+use crate::ast_util::{name_paths::*, scopes::ScopeManager};
+
+use super::{super::standard_library::*, *};
+
+pub struct RestrictedLint;
+
+impl Rule for RestrictedLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Error;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RestrictedLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = RestrictedVisitor {
+            diagnostics: Vec::new(),
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct RestrictedVisitor<'a> {
+    diagnostics: Vec<Diagnostic>,
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+}
+
+impl RestrictedVisitor<'_> {
+    fn check_name_path<N: Node>(&mut self, node: &N, name_path: &[String]) {
+        let is_global = match self
+            .scope_manager
+            .reference_at_byte(node.start_position().unwrap().bytes())
+        {
+            Some(reference) => reference.resolved.is_none(),
+            None => true,
+        };
+
+        if !is_global {
+            return;
+        }
+
+        // Only the outermost restricted field is reported, so `os.execute` is reported once when
+        // all of `os` is restricted
+        for bound in 1..=name_path.len() {
+            let message = match self.standard_library.find_global(&name_path[0..bound]) {
+                Some(Field {
+                    restricted: Some(message),
+                    ..
+                }) => message,
+
+                _ => continue,
+            };
+
+            let mut notes = vec![message.to_owned()];
+            notes.extend(
+                self.standard_library
+                    .global_source_notes(&name_path[0..bound]),
+            );
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "restricted",
+                Message::new("restricted").with("name", name_path[0..bound].join(".")),
+                Label::from_node(node, None),
+                notes,
+                Vec::new(),
+            ));
+
+            return;
+        }
+    }
+}
+
+impl Visitor for RestrictedVisitor<'_> {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if let Some(name_path) = name_path(expression) {
+            self.check_name_path(expression, &name_path);
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let Some(name_path) = name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+            self.check_name_path(call, &name_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::*, *};
+
+    #[test]
+    fn test_restricted() {
+        test_lint(RestrictedLint::new(()).unwrap(), "restricted", "restricted");
+    }
+}
@@ -1,12 +1,18 @@
 use super::*;
-use crate::ast_util::range;
+use crate::{
+    ast_util::{evaluate_constant, name_paths::*, range, scopes::ScopeManager},
+    standard_library::{FieldKind, StandardLibrary},
+};
 use std::convert::Infallible;
 
 use full_moon::{
     ast::{self, Ast},
+    node::Node,
     visitors::Visitor,
 };
 
+// The bounds come from the `range` of arguments in the standard library, such as on `Color3.new`,
+// so that custom standard libraries can use this for their own functions.
 pub struct Color3BoundsLint;
 
 impl Rule for Color3BoundsLint {
@@ -20,61 +26,101 @@ impl Rule for Color3BoundsLint {
         Ok(Color3BoundsLint)
     }
 
-    fn pass(&self, ast: &Ast, context: &Context, _: &AstContext) -> Vec<Diagnostic> {
-        if !context.is_roblox() {
-            return Vec::new();
-        }
-
-        let mut visitor = Color3BoundsVisitor::default();
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = Color3BoundsVisitor {
+            diagnostics: Vec::new(),
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+        };
 
         visitor.visit_ast(ast);
 
-        visitor
-            .positions
-            .iter()
-            .map(|position| {
-                Diagnostic::new_complete(
-                    "roblox_incorrect_color3_new_bounds",
-                    "Color3.new only takes numbers from 0 to 1".to_owned(),
-                    Label::new(*position),
-                    vec!["help: did you mean to use Color3.fromRGB instead?".to_owned()],
-                    Vec::new(),
-                )
-            })
-            .collect()
+        visitor.diagnostics
     }
 }
 
-#[derive(Default)]
-struct Color3BoundsVisitor {
-    positions: Vec<(usize, usize)>,
+struct Color3BoundsVisitor<'std> {
+    diagnostics: Vec<Diagnostic>,
+    scope_manager: &'std ScopeManager,
+    standard_library: &'std StandardLibrary,
 }
 
-impl Visitor for Color3BoundsVisitor {
+impl Visitor for Color3BoundsVisitor<'_> {
     fn visit_function_call(&mut self, call: &ast::FunctionCall) {
-        if_chain::if_chain! {
-            if let ast::Prefix::Name(token) = call.prefix();
-            if token.token().to_string() == "Color3";
-            let mut suffixes = call.suffixes().collect::<Vec<_>>();
-
-            if suffixes.len() == 2; // .new and ()
-            let call_suffix = suffixes.pop().unwrap();
-            let index_suffix = suffixes.pop().unwrap();
-
-            if let ast::Suffix::Index(ast::Index::Dot { name, .. }) = index_suffix;
-            if name.token().to_string() == "new";
-
-            if let ast::Suffix::Call(ast::Call::AnonymousCall(
-                ast::FunctionArgs::Parentheses { arguments, .. }
-            )) = call_suffix;
-
-            then {
-                for argument in arguments {
-                    if let Ok(number) = argument.to_string().parse::<f32>() {
-                        if !(0.0..=1.0).contains(&number) {
-                            self.positions.push(range(argument));
-                        }
+        if let Some(reference) = self
+            .scope_manager
+            .reference_at_byte(call.start_position().unwrap().bytes())
+        {
+            if reference.resolved.is_some() {
+                return;
+            }
+        }
+
+        let mut keep_going = true;
+        let suffixes: Vec<&ast::Suffix> = call
+            .suffixes()
+            .take_while(|suffix| take_while_keep_going(suffix, &mut keep_going))
+            .collect();
+
+        let name_path = match name_path_from_prefix_suffix(call.prefix(), suffixes.iter().copied())
+        {
+            Some(name_path) => name_path,
+            None => return,
+        };
+
+        let function = match self.standard_library.find_global(&name_path) {
+            Some(field) => match &field.field_kind {
+                FieldKind::Function(function) => function,
+                _ => return,
+            },
+            None => return,
+        };
+
+        let function_args = match suffixes.last() {
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(function_args))) => function_args,
+            Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))) => method_call.args(),
+            _ => return,
+        };
+
+        let arguments = match function_args {
+            ast::FunctionArgs::Parentheses { arguments, .. } => arguments,
+            _ => return,
+        };
+
+        for (index, (argument, expected)) in
+            arguments.iter().zip(function.arguments.iter()).enumerate()
+        {
+            let number_range = match &expected.range {
+                Some(number_range) => number_range,
+                None => continue,
+            };
+
+            if let Some(number) = evaluate_constant(argument).and_then(|value| value.as_number()) {
+                if !number_range.contains(number) {
+                    let mut diagnostic = Diagnostic::new_complete(
+                        "roblox_incorrect_color3_new_bounds",
+                        match expected.description(index) {
+                            Some(description) => {
+                                Message::new("roblox_incorrect_color3_new_bounds.argument")
+                                    .with("argument", description)
+                                    .with("name", name_path.join("."))
+                                    .with("range", number_range)
+                            }
+
+                            None => Message::new("roblox_incorrect_color3_new_bounds")
+                                .with("name", name_path.join("."))
+                                .with("range", number_range),
+                        },
+                        Label::new(range::<_, usize>(argument)),
+                        Vec::new(),
+                        Vec::new(),
+                    );
+
+                    if let Some(help) = &number_range.help {
+                        diagnostic = diagnostic.with_help(help);
                     }
+
+                    self.diagnostics.push(diagnostic);
                 }
             }
         }
@@ -83,14 +129,45 @@ impl Visitor for Color3BoundsVisitor {
 
 #[cfg(test)]
 mod tests {
-    use super::{super::test_util::test_lint, *};
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+
+    // The bounds come from the Roblox standard library, rather than the lint
+    fn roblox_config() -> TestUtilConfig {
+        TestUtilConfig {
+            standard_library: StandardLibrary::roblox_base(),
+            ..TestUtilConfig::default()
+        }
+    }
 
     #[test]
     fn test_roblox_incorrect_color3_new_bounds() {
-        test_lint(
+        test_lint_config(
             Color3BoundsLint::new(()).unwrap(),
             "roblox_incorrect_color3_new_bounds",
             "roblox_incorrect_color3_new_bounds",
+            roblox_config(),
+        );
+    }
+
+    #[test]
+    fn test_constant_folding() {
+        test_lint_config(
+            Color3BoundsLint::new(()).unwrap(),
+            "roblox_incorrect_color3_new_bounds",
+            "constant_folding",
+            roblox_config(),
+        );
+    }
+
+    #[test]
+    fn test_custom_std() {
+        test_lint(
+            Color3BoundsLint::new(()).unwrap(),
+            "roblox_incorrect_color3_new_bounds",
+            "custom_std",
         );
     }
 }
@@ -29,10 +29,7 @@ impl Rule for IncorrectRoactUsageLint {
     }
 
     fn pass(&self, ast: &Ast, context: &Context, _: &AstContext) -> Vec<Diagnostic> {
-        if !context.is_roblox() {
-            return Vec::new();
-        }
-
+        // The classes come from the standard library, so this doesn't need a Roblox one
         let roblox_classes = &context.standard_library.roblox_classes;
 
         // Old roblox standard library
@@ -44,6 +41,7 @@ impl Rule for IncorrectRoactUsageLint {
             definitions_of_create_element: HashSet::new(),
             invalid_events: Vec::new(),
             invalid_properties: Vec::new(),
+            missing_properties: Vec::new(),
             unknown_class: Vec::new(),
 
             roblox_classes,
@@ -56,10 +54,9 @@ impl Rule for IncorrectRoactUsageLint {
         for invalid_event in visitor.invalid_events {
             diagnostics.push(Diagnostic::new(
                 "incorrect_roact_usage",
-                format!(
-                    "`{}` is not a valid event for `{}`",
-                    invalid_event.event_name, invalid_event.class_name
-                ),
+                Message::new("roblox_incorrect_roact_usage.invalid_event")
+                    .with("event", &invalid_event.event_name)
+                    .with("class", &invalid_event.class_name),
                 Label::new(invalid_event.range),
             ));
         }
@@ -67,18 +64,28 @@ impl Rule for IncorrectRoactUsageLint {
         for invalid_property in visitor.invalid_properties {
             diagnostics.push(Diagnostic::new(
                 "roblox_incorrect_roact_usage",
-                format!(
-                    "`{}` is not a property of `{}`",
-                    invalid_property.property_name, invalid_property.class_name
-                ),
+                Message::new("roblox_incorrect_roact_usage.invalid_property")
+                    .with("property", &invalid_property.property_name)
+                    .with("class", &invalid_property.class_name),
                 Label::new(invalid_property.range),
             ));
         }
 
+        for missing_property in visitor.missing_properties {
+            diagnostics.push(Diagnostic::new(
+                "roblox_incorrect_roact_usage",
+                Message::new("roblox_incorrect_roact_usage.missing_property")
+                    .with("class", &missing_property.class_name)
+                    .with("property", &missing_property.property_name),
+                Label::new(missing_property.range),
+            ));
+        }
+
         for unknown_class in visitor.unknown_class {
             diagnostics.push(Diagnostic::new(
                 "roblox_incorrect_roact_usage",
-                format!("`{}` is not a valid class", unknown_class.name),
+                Message::new("roblox_incorrect_roact_usage.unknown_class")
+                    .with("class", &unknown_class.name),
                 Label::new(unknown_class.range),
             ));
         }
@@ -106,6 +113,7 @@ struct IncorrectRoactUsageVisitor<'a> {
     definitions_of_create_element: HashSet<String>,
     invalid_events: Vec<InvalidEvent>,
     invalid_properties: Vec<InvalidProperty>,
+    missing_properties: Vec<MissingProperty>,
     unknown_class: Vec<UnknownClass>,
 
     roblox_classes: &'a BTreeMap<String, RobloxClass>,
@@ -125,6 +133,13 @@ struct InvalidProperty {
     range: (usize, usize),
 }
 
+#[derive(Debug)]
+struct MissingProperty {
+    class_name: String,
+    property_name: String,
+    range: (usize, usize),
+}
+
 #[derive(Debug)]
 struct UnknownClass {
     name: String,
@@ -207,10 +222,14 @@ impl<'a> Visitor for IncorrectRoactUsageVisitor<'a> {
             }
         };
 
+        let mut given_properties = HashSet::new();
+
         for field in arguments.fields() {
             match field {
                 ast::Field::NameKey { key, .. } => {
                     let property_name = key.token().to_string();
+                    given_properties.insert(property_name.clone());
+
                     if !class.has_property(self.roblox_classes, &property_name) {
                         self.invalid_properties.push(InvalidProperty {
                             class_name: name.clone(),
@@ -251,6 +270,16 @@ impl<'a> Visitor for IncorrectRoactUsageVisitor<'a> {
                 _ => {}
             }
         }
+
+        for property_name in class.required_properties(self.roblox_classes) {
+            if !given_properties.contains(property_name) {
+                self.missing_properties.push(MissingProperty {
+                    class_name: name.clone(),
+                    property_name: property_name.to_owned(),
+                    range: range(arguments),
+                });
+            }
+        }
     }
 
     fn visit_local_assignment(&mut self, node: &ast::LocalAssignment) {
@@ -280,6 +309,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_required_properties() {
+        test_lint(
+            IncorrectRoactUsageLint::new(()).unwrap(),
+            "roblox_incorrect_roact_usage",
+            "required_properties",
+        );
+    }
+
     #[test]
     fn test_roblox_incorrect_roact_usage() {
         test_lint(
@@ -0,0 +1,190 @@
+use super::*;
+use crate::{
+    ast_util::range,
+    rojo::{self, ScriptKind},
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+const YIELDING_METHODS: &[&str] = &["Wait", "WaitForChild"];
+
+pub struct RobloxModuleScriptLint;
+
+impl Rule for RobloxModuleScriptLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RobloxModuleScriptLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        match ast_context.file_path.as_deref().and_then(rojo::script_kind) {
+            Some(ScriptKind::ModuleScript) => {}
+            _ => return Vec::new(),
+        }
+
+        let mut diagnostics = Vec::new();
+
+        let block = ast.nodes();
+
+        match block.last_stmt() {
+            Some(ast::LastStmt::Return(return_stmt)) if return_stmt.returns().len() == 1 => {}
+
+            Some(ast::LastStmt::Return(return_stmt)) => {
+                diagnostics.push(Diagnostic::new_complete(
+                    "roblox_module_script",
+                    Message::new("roblox_module_script.multiple_returns")
+                        .with("amount", return_stmt.returns().len()),
+                    Label::from_node(return_stmt, None),
+                    Vec::new(),
+                    Vec::new(),
+                ));
+            }
+
+            Some(last_stmt) => {
+                diagnostics.push(missing_return_diagnostic(Label::from_node(last_stmt, None)));
+            }
+
+            None => {
+                if let Some(stmt) = block.stmts().last() {
+                    diagnostics.push(missing_return_diagnostic(Label::from_node(stmt, None)));
+                }
+            }
+        }
+
+        let mut visitor = TopLevelYieldVisitor::default();
+        visitor.visit_ast(ast);
+
+        diagnostics.extend(visitor.yields.into_iter().map(|(name, position)| {
+            Diagnostic::new_complete(
+                "roblox_module_script",
+                Message::new("roblox_module_script.yields").with("name", name),
+                Label::new(position),
+                vec!["every script requiring this module will wait until this finishes".to_owned()],
+                Vec::new(),
+            )
+        }));
+
+        diagnostics
+    }
+}
+
+fn missing_return_diagnostic(label: Label) -> Diagnostic {
+    Diagnostic::new_complete(
+        "roblox_module_script",
+        Message::new("roblox_module_script.no_return"),
+        label,
+        Vec::new(),
+        Vec::new(),
+    )
+}
+
+#[derive(Default)]
+struct TopLevelYieldVisitor {
+    function_depth: usize,
+    yields: Vec<(String, (usize, usize))>,
+}
+
+fn yielding_call_name(call: &ast::FunctionCall) -> Option<String> {
+    let suffixes = call.suffixes().collect::<Vec<_>>();
+
+    for suffix in &suffixes {
+        if let ast::Suffix::Call(ast::Call::MethodCall(method_call)) = suffix {
+            let name = method_call.name().token().to_string();
+            if YIELDING_METHODS.contains(&name.as_str()) {
+                return Some(format!(":{name}"));
+            }
+        }
+    }
+
+    let prefix = match call.prefix() {
+        ast::Prefix::Name(name) => name.token().to_string(),
+        _ => return None,
+    };
+
+    match (prefix.as_str(), suffixes.as_slice()) {
+        ("wait", [ast::Suffix::Call(ast::Call::AnonymousCall(_))]) => Some(prefix),
+
+        ("task", [ast::Suffix::Index(ast::Index::Dot { name, .. }), ast::Suffix::Call(call)])
+            if name.token().to_string() == "wait"
+                && matches!(call, ast::Call::AnonymousCall(_)) =>
+        {
+            Some("task.wait".to_owned())
+        }
+
+        _ => None,
+    }
+}
+
+impl Visitor for TopLevelYieldVisitor {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if self.function_depth > 0 {
+            return;
+        }
+
+        if let Some(name) = yielding_call_name(call) {
+            self.yields.push((name, range(call)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_module_script() {
+        test_lint(
+            RobloxModuleScriptLint::new(()).unwrap(),
+            "roblox_module_script",
+            "roblox_module_script",
+        );
+    }
+
+    #[test]
+    fn test_multiple_returns() {
+        test_lint(
+            RobloxModuleScriptLint::new(()).unwrap(),
+            "roblox_module_script",
+            "multiple_returns",
+        );
+    }
+
+    #[test]
+    fn test_no_return() {
+        test_lint(
+            RobloxModuleScriptLint::new(()).unwrap(),
+            "roblox_module_script",
+            "no_return",
+        );
+    }
+
+    #[test]
+    fn test_outside_project() {
+        test_lint(
+            RobloxModuleScriptLint::new(()).unwrap(),
+            "roblox_module_script",
+            "outside_project",
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use super::*;
+use crate::ast_util::{
+    name_paths::name_path_from_prefix_suffix, range, scopes::ScopeManager, HasSideEffects,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+
+pub struct RobloxParentBeforePropertiesLint;
+
+impl Rule for RobloxParentBeforePropertiesLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Performance;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RobloxParentBeforePropertiesLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let mut visitor = ParentBeforePropertiesVisitor {
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct ParentBeforePropertiesVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// `local name = Instance.new(...)`
+fn created_instance(stmt: &ast::Stmt) -> Option<&TokenReference> {
+    let local_assignment = match stmt {
+        ast::Stmt::LocalAssignment(local_assignment) => local_assignment,
+        _ => return None,
+    };
+
+    if local_assignment.names().len() != 1 || local_assignment.expressions().len() != 1 {
+        return None;
+    }
+
+    let call = match local_assignment.expressions().iter().next()? {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::FunctionCall(call) => call,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    match name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+        Some(name_path) if name_path == ["Instance", "new"] => {
+            Some(local_assignment.names().iter().next()?)
+        }
+        _ => None,
+    }
+}
+
+// `name.Property = value`, returning the property and the value
+fn property_assignment<'a>(
+    stmt: &'a ast::Stmt,
+    name: &str,
+) -> Option<(String, &'a ast::Expression)> {
+    let assignment = match stmt {
+        ast::Stmt::Assignment(assignment) => assignment,
+        _ => return None,
+    };
+
+    if assignment.variables().len() != 1 || assignment.expressions().len() != 1 {
+        return None;
+    }
+
+    let var_expression = match assignment.variables().iter().next()? {
+        ast::Var::Expression(var_expression) => var_expression,
+        _ => return None,
+    };
+
+    match var_expression.prefix() {
+        ast::Prefix::Name(prefix) if prefix.token().to_string() == name => {}
+        _ => return None,
+    }
+
+    let mut suffixes = var_expression.suffixes();
+
+    match (suffixes.next(), suffixes.next()) {
+        (Some(ast::Suffix::Index(ast::Index::Dot { name, .. })), None) => Some((
+            name.token().to_string(),
+            assignment.expressions().iter().next()?,
+        )),
+        _ => None,
+    }
+}
+
+// Constructors of Roblox datatypes, such as `Vector3.new` or `Color3.fromRGB`, don't have side effects
+fn is_datatype_constructor(expression: &ast::Expression) -> bool {
+    let call = match expression {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::FunctionCall(call) => call,
+            _ => return false,
+        },
+        _ => return false,
+    };
+
+    let name_path = match name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+        Some(name_path) => name_path,
+        None => return false,
+    };
+
+    let arguments = match call.suffixes().last() {
+        Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+            arguments,
+            ..
+        }))) => arguments,
+        _ => return false,
+    };
+
+    match name_path.as_slice() {
+        [datatype, constructor]
+            if datatype.starts_with(|character: char| character.is_ascii_uppercase())
+                && (constructor == "new" || constructor.starts_with("from")) => {}
+        _ => return false,
+    }
+
+    arguments
+        .iter()
+        .all(|argument| !argument.has_side_effects())
+}
+
+// Whether the value can be evaluated before the parent is set without changing what it does
+fn can_move_past(expression: &ast::Expression, name: &str) -> bool {
+    if expression
+        .tokens()
+        .any(|token| token.token().to_string() == name)
+    {
+        return false;
+    }
+
+    !expression.has_side_effects() || is_datatype_constructor(expression)
+}
+
+// The byte range of the node including its leading and trailing trivia
+fn range_with_trivia(node: &impl Node) -> Option<(usize, usize)> {
+    let (start, end): (usize, usize) = range(node);
+
+    // The tokens aren't always in order, such as for parentheses
+    let leading = node
+        .tokens()
+        .min_by_key(|token| token.token().start_position().bytes())?
+        .leading_trivia();
+
+    let trailing = node
+        .tokens()
+        .max_by_key(|token| token.token().end_position().bytes())?
+        .trailing_trivia();
+
+    Some((
+        start - leading.map(|token| token.to_string().len()).sum::<usize>(),
+        end + trailing.map(|token| token.to_string().len()).sum::<usize>(),
+    ))
+}
+
+impl ParentBeforePropertiesVisitor<'_> {
+    fn is_global_instance(&self, stmt: &ast::Stmt) -> bool {
+        let expression = match stmt {
+            ast::Stmt::LocalAssignment(local_assignment) => {
+                match local_assignment.expressions().iter().next() {
+                    Some(expression) => expression,
+                    None => return false,
+                }
+            }
+            _ => return false,
+        };
+
+        match self.scope_manager.reference_at_byte(range(expression).0) {
+            Some(reference) => reference.resolved.is_none(),
+            None => true,
+        }
+    }
+
+    fn check_block(&mut self, block: &ast::Block) {
+        let stmts = block.stmts_with_semicolon().collect::<Vec<_>>();
+
+        for (index, (stmt, _)) in stmts.iter().enumerate() {
+            let name = match created_instance(stmt) {
+                Some(name) if self.is_global_instance(stmt) => name.token().to_string(),
+                _ => continue,
+            };
+
+            // Every statement right after the instance is created that sets one of its properties
+            let assignments = stmts[index + 1..]
+                .iter()
+                .map_while(|(stmt, semicolon)| {
+                    let (property, value) = property_assignment(stmt, &name)?;
+                    Some((stmt, semicolon, property, value))
+                })
+                .collect::<Vec<_>>();
+
+            let parent_index = match assignments
+                .iter()
+                .position(|(_, _, property, _)| property == "Parent")
+            {
+                Some(parent_index) if parent_index + 1 < assignments.len() => parent_index,
+                _ => continue,
+            };
+
+            let (parent_stmt, _, _, _) = assignments[parent_index];
+            let later = &assignments[parent_index + 1..];
+
+            let mut diagnostic = Diagnostic::new_complete(
+                "roblox_parent_before_properties",
+                Message::new("roblox_parent_before_properties").with("name", &name),
+                Label::from_node(parent_stmt, None),
+                vec![
+                    "note: once an instance is parented, Roblox has to replicate and update it every time a property changes".to_owned(),
+                ],
+                later
+                    .iter()
+                    .map(|(stmt, _, _, _)| {
+                        Label::from_node(stmt, Some("property set after parenting".to_owned()))
+                    })
+                    .collect(),
+            )
+            .with_help("set `Parent` after every other property");
+
+            let is_safe = later.iter().all(|(_, semicolon, property, value)| {
+                semicolon.is_none() && property != "Parent" && can_move_past(value, &name)
+            }) && assignments[parent_index].1.is_none();
+
+            if is_safe {
+                if let Some(fix) = reorder_fix(parent_stmt, later.iter().map(|(stmt, ..)| *stmt)) {
+                    diagnostic = diagnostic.with_fix(fix);
+                }
+            }
+
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+// Moves the statement setting the parent to after every other statement
+fn reorder_fix<'a>(
+    parent_stmt: &ast::Stmt,
+    later: impl Iterator<Item = &'a ast::Stmt> + Clone,
+) -> Option<Fix> {
+    let (start, _) = range_with_trivia(parent_stmt)?;
+    let (_, end) = range_with_trivia(later.clone().last()?)?;
+
+    let mut replacement = later.map(ToString::to_string).collect::<String>();
+    let mut parent_text = parent_stmt.to_string();
+
+    // The last statement might be at the end of the file, without a new line
+    if !replacement.ends_with('\n') {
+        replacement.push('\n');
+        parent_text = parent_text.trim_end_matches(['\r', '\n']).to_owned();
+    }
+
+    replacement.push_str(&parent_text);
+
+    Some(Fix::new((start, end), replacement))
+}
+
+impl Visitor for ParentBeforePropertiesVisitor<'_> {
+    fn visit_block(&mut self, block: &ast::Block) {
+        self.check_block(block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_parent_before_properties() {
+        test_lint(
+            RobloxParentBeforePropertiesLint::new(()).unwrap(),
+            "roblox_parent_before_properties",
+            "roblox_parent_before_properties",
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+use crate::{
+    ast_util::{range, scopes::ScopeManager},
+    standard_library::{Deprecated, Field, StandardLibrary},
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct RobloxTaskLibraryLint;
+
+impl Rule for RobloxTaskLibraryLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RobloxTaskLibraryLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let mut visitor = TaskLibraryVisitor {
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+/// Whether the deprecated function is replaced by one from the `task` library, such as `wait`.
+/// Calls to these are reported by this lint instead of `deprecated`.
+pub(crate) fn replaced_by_task_library(deprecated: &Deprecated) -> bool {
+    !deprecated.replace.is_empty()
+        && deprecated
+            .replace
+            .iter()
+            .all(|replace| replace.starts_with("task."))
+}
+
+struct TaskLibraryVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for TaskLibraryVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let name = match call.prefix() {
+            ast::Prefix::Name(name) => name,
+            _ => return,
+        };
+
+        let mut suffixes = call.suffixes();
+
+        let arguments = match (suffixes.next(), suffixes.next()) {
+            (
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                    arguments,
+                    ..
+                }))),
+                None,
+            ) => arguments,
+            _ => return,
+        };
+
+        if let Some(reference) = self.scope_manager.reference_at_byte(range(name).0) {
+            if reference.resolved.is_some() {
+                return;
+            }
+        }
+
+        let name = name.token().to_string();
+
+        let deprecated = match self.standard_library.find_global(&[name.to_owned()]) {
+            Some(Field {
+                deprecated: Some(deprecated),
+                ..
+            }) if replaced_by_task_library(deprecated) => deprecated,
+            _ => return,
+        };
+
+        let parameters = arguments
+            .iter()
+            .map(|argument| argument.to_string().trim().to_owned())
+            .collect::<Vec<_>>();
+
+        let replacement = deprecated.try_instead(&parameters);
+
+        let mut diagnostic = Diagnostic::new_complete(
+            "roblox_task_library",
+            Message::new("roblox_task_library").with("name", name),
+            Label::from_node(call, None),
+            vec![format!("note: {}", deprecated.message)],
+            Vec::new(),
+        );
+
+        if let Some(replacement) = replacement
+            .as_ref()
+            .filter(|replacement| !replacement.contains('\n'))
+        {
+            diagnostic = diagnostic.with_suggestion(format!("`{replacement}`"));
+        }
+
+        if let Some(replacement) = replacement {
+            let call_range: (usize, usize) = range(call);
+            // The task library versions don't behave exactly the same
+            diagnostic = diagnostic.with_fix(
+                Fix::new(call_range, replacement).with_applicability(Applicability::Suggestion),
+            );
+        }
+
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_task_library() {
+        test_lint(
+            RobloxTaskLibraryLint::new(()).unwrap(),
+            "roblox_task_library",
+            "roblox_task_library",
+        );
+    }
+}
@@ -0,0 +1,340 @@
+use super::*;
+use crate::ast_util::{
+    expression_to_ident, is_type_function, name_paths::name_path_from_prefix_suffix, range,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use serde::{Deserialize, Serialize};
+
+// Functions that check or convert the type of whatever is passed to them
+const TYPE_CONVERSIONS: &[&str] = &["tonumber", "tostring"];
+
+const CONNECT_METHODS: &[&str] = &["Connect", "ConnectParallel", "Once"];
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RobloxUncheckedRemoteArgumentsConfig {
+    type_guards: Vec<String>,
+}
+
+pub struct RobloxUncheckedRemoteArgumentsLint {
+    config: RobloxUncheckedRemoteArgumentsConfig,
+}
+
+impl Rule for RobloxUncheckedRemoteArgumentsLint {
+    type Config = RobloxUncheckedRemoteArgumentsConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(RobloxUncheckedRemoteArgumentsLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        if !context.is_roblox() {
+            return Vec::new();
+        }
+
+        let mut visitor = RemoteArgumentsVisitor {
+            type_guards: &self.config.type_guards,
+            handler_bodies: Vec::new(),
+            handlers: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UseKind {
+    Arithmetic,
+    Index,
+}
+
+struct Argument {
+    name: String,
+    range: (usize, usize),
+    guarded: bool,
+    first_use: Option<(UseKind, (usize, usize))>,
+}
+
+// A function connected to OnServerEvent or assigned to OnServerInvoke
+struct Handler {
+    body_range: (usize, usize),
+    arguments: Vec<Argument>,
+}
+
+struct RemoteArgumentsVisitor<'a> {
+    type_guards: &'a [String],
+    handler_bodies: Vec<(usize, usize)>,
+    handlers: Vec<Handler>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn anonymous_function(expression: &ast::Expression) -> Option<&ast::FunctionBody> {
+    match expression {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::Function((_, body)) => Some(body),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// The handler in `remote.OnServerEvent:Connect(function(player, ...) end)`
+fn connected_handler(call: &ast::FunctionCall) -> Option<&ast::FunctionBody> {
+    let suffixes = call.suffixes().collect::<Vec<_>>();
+
+    let (event, method_call) = match suffixes.as_slice() {
+        [.., ast::Suffix::Index(ast::Index::Dot { name: event, .. }), ast::Suffix::Call(ast::Call::MethodCall(method_call))] => {
+            (event, method_call)
+        }
+        _ => return None,
+    };
+
+    if event.token().to_string() != "OnServerEvent"
+        || !CONNECT_METHODS.contains(&method_call.name().token().to_string().as_str())
+    {
+        return None;
+    }
+
+    match method_call.args() {
+        ast::FunctionArgs::Parentheses { arguments, .. } if arguments.len() == 1 => {
+            anonymous_function(arguments.iter().next()?)
+        }
+        _ => None,
+    }
+}
+
+impl RemoteArgumentsVisitor<'_> {
+    fn is_type_guard(&self, call: &ast::FunctionCall) -> bool {
+        let name = match name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+            Some(name_path) => name_path.join("."),
+            None => return false,
+        };
+
+        is_type_function(&name, true)
+            || TYPE_CONVERSIONS.contains(&name.as_str())
+            || self.type_guards.contains(&name)
+    }
+
+    fn argument_mut(&mut self, name: &TokenReference) -> Option<&mut Argument> {
+        let name = name.token().to_string();
+
+        self.handlers
+            .last_mut()?
+            .arguments
+            .iter_mut()
+            .find(|argument| argument.name == name)
+    }
+
+    fn record_use(&mut self, name: &TokenReference, kind: UseKind) {
+        let use_range = range(name);
+
+        if let Some(argument) = self.argument_mut(name) {
+            if argument.first_use.is_none() {
+                argument.first_use = Some((kind, use_range));
+            }
+        }
+    }
+
+    fn mark_guarded(&mut self, name: &TokenReference) {
+        if let Some(argument) = self.argument_mut(name) {
+            argument.guarded = true;
+        }
+    }
+}
+
+impl Visitor for RemoteArgumentsVisitor<'_> {
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for (var, expression) in assignment.variables().iter().zip(assignment.expressions()) {
+            // Reassigning an argument, such as `amount = tonumber(amount)`, is treated as checking it
+            if let ast::Var::Name(name) = var {
+                self.mark_guarded(name);
+            }
+
+            let is_on_server_invoke = match var {
+                ast::Var::Expression(var_expression) => matches!(
+                    var_expression.suffixes().last(),
+                    Some(ast::Suffix::Index(ast::Index::Dot { name, .. }))
+                        if name.token().to_string() == "OnServerInvoke"
+                ),
+                _ => false,
+            };
+
+            if is_on_server_invoke {
+                if let Some(body) = anonymous_function(expression) {
+                    self.handler_bodies.push(range(body));
+                }
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let Some(body) = connected_handler(call) {
+            self.handler_bodies.push(range(body));
+        }
+
+        if let ast::Prefix::Name(name) = call.prefix() {
+            if !matches!(
+                call.suffixes().next(),
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(_)))
+            ) {
+                self.record_use(name, UseKind::Index);
+            }
+        }
+
+        if !self.is_type_guard(call) {
+            return;
+        }
+
+        if let Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+            arguments,
+            ..
+        }))) = call.suffixes().last()
+        {
+            for argument in arguments {
+                if let Some(name) = expression_to_ident(argument) {
+                    self.mark_guarded(name);
+                }
+            }
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        if !self.handler_bodies.contains(&range(body)) {
+            return;
+        }
+
+        self.handlers.push(Handler {
+            body_range: range(body),
+            arguments: body
+                .parameters()
+                .iter()
+                // The first argument is the player who fired the remote, which Roblox provides
+                .skip(1)
+                .filter_map(|parameter| match parameter {
+                    ast::Parameter::Name(name) => Some(Argument {
+                        name: name.token().to_string(),
+                        range: range(name),
+                        guarded: false,
+                        first_use: None,
+                    }),
+                    _ => None,
+                })
+                .collect(),
+        });
+    }
+
+    fn visit_function_body_end(&mut self, body: &ast::FunctionBody) {
+        match self.handlers.last() {
+            Some(handler) if handler.body_range == range(body) => {}
+            _ => return,
+        }
+
+        let handler = self.handlers.pop().unwrap();
+
+        for argument in handler.arguments {
+            let (kind, use_range) = match argument.first_use {
+                Some(first_use) if !argument.guarded => first_use,
+                _ => continue,
+            };
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "roblox_unchecked_remote_arguments",
+                Message::new(match kind {
+                    UseKind::Arithmetic => "roblox_unchecked_remote_arguments.arithmetic",
+                    UseKind::Index => "roblox_unchecked_remote_arguments.index",
+                })
+                .with("name", &argument.name),
+                Label::new(use_range),
+                vec![
+                    "note: exploiters can send any value to a remote, not just the ones your client code does".to_owned(),
+                ],
+                vec![Label::new_with_message(
+                    argument.range,
+                    "sent by the client".to_owned(),
+                )],
+            )
+            .with_suggestion(format!("checking `typeof({}) == \"...\"` first", argument.name)));
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if self.handlers.is_empty() {
+            return;
+        }
+
+        let (lhs, rhs) = match expression {
+            ast::Expression::BinaryOperator {
+                lhs,
+                binop:
+                    ast::BinOp::Caret(_)
+                    | ast::BinOp::Minus(_)
+                    | ast::BinOp::Percent(_)
+                    | ast::BinOp::Plus(_)
+                    | ast::BinOp::Slash(_)
+                    | ast::BinOp::Star(_),
+                rhs,
+            } => (lhs, Some(rhs)),
+
+            ast::Expression::UnaryOperator {
+                unop: ast::UnOp::Minus(_),
+                expression,
+            } => (expression, None),
+
+            _ => return,
+        };
+
+        for operand in std::iter::once(lhs).chain(rhs) {
+            if let Some(name) = expression_to_ident(operand) {
+                self.record_use(name, UseKind::Arithmetic);
+            }
+        }
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        if let ast::Prefix::Name(name) = var_expression.prefix() {
+            self.record_use(name, UseKind::Index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_roblox_unchecked_remote_arguments() {
+        test_lint(
+            RobloxUncheckedRemoteArgumentsLint::new(
+                RobloxUncheckedRemoteArgumentsConfig::default(),
+            )
+            .unwrap(),
+            "roblox_unchecked_remote_arguments",
+            "roblox_unchecked_remote_arguments",
+        );
+    }
+
+    #[test]
+    fn test_type_guards() {
+        test_lint(
+            RobloxUncheckedRemoteArgumentsLint::new(RobloxUncheckedRemoteArgumentsConfig {
+                type_guards: vec!["t.number".to_owned()],
+            })
+            .unwrap(),
+            "roblox_unchecked_remote_arguments",
+            "type_guards",
+        );
+    }
+}
@@ -2,9 +2,9 @@ use super::*;
 
 use full_moon::ast::Ast;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ShadowingConfig {
     ignore_pattern: String,
@@ -28,6 +28,9 @@ impl Rule for ShadowingLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const RULE_TYPE: RuleType = RuleType::Style;
+    const LUACHECK_CODES: &'static [&'static str] = &[
+        "421", "422", "423", "431", "432", "433", "411", "412", "413",
+    ];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(ShadowingLint {
@@ -62,7 +65,7 @@ impl Rule for ShadowingLint {
             .map(|shadow| {
                 Diagnostic::new_complete(
                     "shadowing",
-                    format!("shadowing variable `{}`", shadow.name),
+                    Message::new("shadowing").with("name", &shadow.name),
                     Label::new(shadow.range),
                     Vec::new(),
                     vec![Label::new_with_message(
@@ -1,6 +1,9 @@
 use super::{super::standard_library::*, *};
 use crate::{
-    ast_util::{name_paths::*, scopes::ScopeManager},
+    ast_util::{
+        expression_to_ident, get_argument_type, name_paths::*, scopes::ScopeManager,
+        PassedArgumentType,
+    },
     possible_std::possible_standard_library_notes,
 };
 use std::convert::Infallible;
@@ -8,7 +11,7 @@ use std::convert::Infallible;
 use full_moon::{
     ast::{self, Ast},
     node::Node,
-    tokenizer::{Position, Symbol, TokenType},
+    tokenizer::{Position, TokenType},
     visitors::Visitor,
 };
 
@@ -39,147 +42,6 @@ impl Rule for StandardLibraryLint {
     }
 }
 
-// Returns the argument type of the expression if it can be constantly resolved
-// Otherwise, returns None
-// Only attempts to resolve constants
-fn get_argument_type(expression: &ast::Expression) -> Option<PassedArgumentType> {
-    #[cfg_attr(
-        feature = "force_exhaustive_checks",
-        deny(non_exhaustive_omitted_patterns)
-    )]
-    match expression {
-        ast::Expression::Parentheses { expression, .. } => get_argument_type(expression),
-
-        ast::Expression::UnaryOperator { unop, expression } => {
-            match unop {
-                // CAVEAT: If you're overriding __len on a userdata and then making it not return a number
-                // ...sorry, but I don't care about your code :)
-                ast::UnOp::Hash(_) => Some(ArgumentType::Number.into()),
-                ast::UnOp::Minus(_) => get_argument_type(expression),
-                ast::UnOp::Not(_) => Some(ArgumentType::Bool.into()),
-                _ => None,
-            }
-        }
-
-        ast::Expression::Value { value, .. } => match &**value {
-            ast::Value::Function(_) => Some(ArgumentType::Function.into()),
-            ast::Value::FunctionCall(_) => None,
-            ast::Value::Number(_) => Some(ArgumentType::Number.into()),
-            ast::Value::ParenthesesExpression(expression) => get_argument_type(expression),
-            ast::Value::String(token) => {
-                Some(PassedArgumentType::from_string(token.token().to_string()))
-            }
-            #[cfg_attr(
-                feature = "force_exhaustive_checks",
-                allow(non_exhaustive_omitted_patterns)
-            )]
-            ast::Value::Symbol(symbol) => match *symbol.token_type() {
-                TokenType::Symbol { symbol } => match symbol {
-                    Symbol::False => Some(ArgumentType::Bool.into()),
-                    Symbol::True => Some(ArgumentType::Bool.into()),
-                    Symbol::Nil => Some(ArgumentType::Nil.into()),
-                    Symbol::Ellipse => Some(ArgumentType::Vararg.into()),
-                    ref other => {
-                        unreachable!("TokenType::Symbol was not expected ({:?})", other)
-                    }
-                },
-
-                ref other => unreachable!(
-                    "ast::Value::Symbol token_type != TokenType::Symbol ({:?})",
-                    other
-                ),
-            },
-            ast::Value::TableConstructor(_) => Some(ArgumentType::Table.into()),
-            ast::Value::Var(_) => None,
-
-            #[cfg(feature = "roblox")]
-            ast::Value::IfExpression(if_expression) => {
-                // This could be a union type
-                let expected_type = get_argument_type(if_expression.if_expression())?;
-
-                if let Some(else_if_expressions) = if_expression.else_if_expressions() {
-                    for else_if_expression in else_if_expressions {
-                        if !get_argument_type(else_if_expression.expression())?
-                            .same_type(&expected_type)
-                        {
-                            return None;
-                        }
-                    }
-                }
-
-                if get_argument_type(if_expression.else_expression())?.same_type(&expected_type) {
-                    Some(expected_type)
-                } else {
-                    None
-                }
-            }
-
-            _ => None,
-        },
-
-        ast::Expression::BinaryOperator {
-            lhs, binop, rhs, ..
-        } => {
-            // Nearly all of these will return wrong results if you have a non-idiomatic metatable
-            // I intentionally omitted common metamethod re-typings, like __mul
-            match binop {
-                ast::BinOp::Caret(_) => Some(ArgumentType::Number.into()),
-
-                #[cfg_attr(
-                    feature = "force_exhaustive_checks",
-                    allow(non_exhaustive_omitted_patterns)
-                )]
-                ast::BinOp::GreaterThan(_)
-                | ast::BinOp::GreaterThanEqual(_)
-                | ast::BinOp::LessThan(_)
-                | ast::BinOp::LessThanEqual(_)
-                | ast::BinOp::TwoEqual(_)
-                | ast::BinOp::TildeEqual(_) => {
-                    if_chain::if_chain! {
-                        if let ast::Expression::BinaryOperator { binop, .. } = &**rhs;
-                        if let ast::BinOp::And(_) | ast::BinOp::Or(_) = binop;
-                        then {
-                            None
-                        } else {
-                            Some(ArgumentType::Bool.into())
-                        }
-                    }
-                }
-
-                // Basic types will often re-implement these (e.g. Roblox's Vector3)
-                ast::BinOp::Plus(_)
-                | ast::BinOp::Minus(_)
-                | ast::BinOp::Star(_)
-                | ast::BinOp::Slash(_) => {
-                    let lhs_type = get_argument_type(lhs);
-                    let rhs_type = get_argument_type(rhs);
-
-                    if lhs_type == rhs_type {
-                        lhs_type
-                    } else {
-                        None
-                    }
-                }
-
-                ast::BinOp::Percent(_) => Some(ArgumentType::Number.into()),
-
-                ast::BinOp::TwoDots(_) => Some(ArgumentType::String.into()),
-
-                ast::BinOp::And(_) | ast::BinOp::Or(_) => {
-                    // We could potentially support union types here
-                    // Or even just produce one type if both the left and right sides can be evaluated
-                    // But for now, the evaluation just isn't smart enough to where this would be practical
-                    None
-                }
-
-                _ => None,
-            }
-        }
-
-        _ => None,
-    }
-}
-
 pub struct StandardLibraryVisitor<'std> {
     diagnostics: Vec<Diagnostic>,
     scope_manager: &'std ScopeManager,
@@ -188,6 +50,32 @@ pub struct StandardLibraryVisitor<'std> {
 }
 
 impl StandardLibraryVisitor<'_> {
+    // Like get_argument_type, but also uses the inferred types of local variables
+    fn get_argument_type(&self, expression: &ast::Expression) -> Option<PassedArgumentType> {
+        if let Some(argument_type) = get_argument_type(expression) {
+            return Some(argument_type);
+        }
+
+        let name = expression_to_ident(expression)?;
+        let reference = self
+            .scope_manager
+            .reference_at_byte(name.start_position()?.bytes())?;
+        let variable = &self.scope_manager.variables[reference.resolved?];
+
+        // The inferred type is only from the definition, so any other write makes it unreliable
+        let writes = variable
+            .references
+            .iter()
+            .filter(|id| self.scope_manager.references[**id].write.is_some())
+            .count();
+
+        if writes > 1 {
+            return None;
+        }
+
+        variable.inferred_type.clone()
+    }
+
     fn lint_invalid_field_access(
         &mut self,
         mut name_path: Vec<String>,
@@ -204,6 +92,15 @@ impl StandardLibraryVisitor<'_> {
             for bound in 1..=name_path.len() {
                 let path = &name_path[0..bound];
                 match self.standard_library.find_global(path) {
+                    Some(Field {
+                        property_type: Some(property_type),
+                        ..
+                    }) if *property_type != PropertyType::Table => {
+                        let member = name_path.get(bound).unwrap_or(&field);
+                        self.lint_member_access(path, *property_type, member, range);
+                        return;
+                    }
+
                     Some(field) => {
                         match field.field_kind {
                             FieldKind::Any => return,
@@ -227,24 +124,85 @@ impl StandardLibraryVisitor<'_> {
             let mut name_path_with_field = name_path.iter().map(String::as_str).collect::<Vec<_>>();
             name_path_with_field.push(&field);
 
-            self.diagnostics.push(Diagnostic::new_complete(
+            let (message, (notes, help)) =
+                match self.standard_library.removed_by(&name_path_with_field) {
+                    Some(library) => (
+                        Message::new("undefined_variable.removed")
+                            .with("name", name_path_with_field.join("."))
+                            .with("library", library),
+                        (
+                            self.standard_library
+                                .global_source_notes(&name_path_with_field),
+                            None,
+                        ),
+                    ),
+
+                    None => (
+                        Message::new("incorrect_standard_library_use.no_field")
+                            .with("name", name_path.join("."))
+                            .with("field", &field),
+                        possible_standard_library_notes(
+                            &name_path_with_field,
+                            self.standard_library_is_set,
+                        ),
+                    ),
+                };
+
+            let mut diagnostic = Diagnostic::new_complete(
                 "incorrect_standard_library_use",
-                format!(
-                    "standard library global `{}` does not contain the field `{}`",
-                    name_path.join("."),
-                    field,
-                ),
+                message,
                 Label::new((range.0.bytes(), range.1.bytes())),
-                possible_standard_library_notes(
-                    &name_path_with_field,
-                    self.standard_library_is_set,
-                ),
+                notes,
                 Vec::new(),
-            ));
+            );
+
+            if let Some(help) = help {
+                diagnostic = diagnostic.with_help(help);
+            }
+
+            self.diagnostics.push(diagnostic);
         }
     }
 }
 
+impl StandardLibraryVisitor<'_> {
+    // Indexing a property whose type is known, such as `math.huge.foo`
+    fn lint_member_access(
+        &mut self,
+        name_path: &[String],
+        property_type: PropertyType,
+        member: &str,
+        range: (Position, Position),
+    ) {
+        let message = if property_type == PropertyType::String {
+            // Strings are indexed through the string library, like `_VERSION:upper()`
+            if self
+                .standard_library
+                .find_global(&["string", member])
+                .is_some()
+            {
+                return;
+            }
+
+            Message::new("invalid_member_access.string")
+                .with("name", name_path.join("."))
+                .with("member", member)
+        } else {
+            Message::new("invalid_member_access")
+                .with("name", name_path.join("."))
+                .with("type", property_type)
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "invalid_member_access",
+            message,
+            Label::new((range.0.bytes(), range.1.bytes())),
+            self.standard_library.global_source_notes(name_path),
+            Vec::new(),
+        ));
+    }
+}
+
 impl Visitor for StandardLibraryVisitor<'_> {
     fn visit_assignment(&mut self, assignment: &ast::Assignment) {
         for var in assignment.variables() {
@@ -291,12 +249,10 @@ impl Visitor for StandardLibraryVisitor<'_> {
 
                                 self.diagnostics.push(Diagnostic::new_complete(
                                     "incorrect_standard_library_use",
-                                    format!(
-                                        "standard library global `{}` is not writable",
-                                        name_path.join("."),
-                                    ),
+                                    Message::new("incorrect_standard_library_use.not_writable")
+                                        .with("name", name_path.join(".")),
                                     Label::new((range.0.bytes(), range.1.bytes())),
-                                    Vec::new(),
+                                    self.standard_library.global_source_notes(&name_path),
                                     Vec::new(),
                                 ));
                             }
@@ -331,9 +287,10 @@ impl Visitor for StandardLibraryVisitor<'_> {
 
                         self.diagnostics.push(Diagnostic::new_complete(
                             "incorrect_standard_library_use",
-                            format!("standard library global `{}` is not overridable", name,),
+                            Message::new("incorrect_standard_library_use.not_overridable")
+                                .with("name", &name),
                             Label::new((range.0.bytes(), range.1.bytes())),
-                            Vec::new(),
+                            self.standard_library.global_source_notes(&[name]),
                             Vec::new(),
                         ));
                     }
@@ -404,17 +361,19 @@ impl Visitor for StandardLibraryVisitor<'_> {
             }
         };
 
+        let source_notes = self.standard_library.global_source_notes(&name_path);
+
         let function = match &field.field_kind {
             FieldKind::Any => return,
             FieldKind::Function(function) => function,
             _ => {
-                self.diagnostics.push(Diagnostic::new(
+                self.diagnostics.push(Diagnostic::new_complete(
                     "incorrect_standard_library_use",
-                    format!(
-                        "standard library field `{}` is not a function",
-                        name_path.join("."),
-                    ),
+                    Message::new("incorrect_standard_library_use.not_a_function")
+                        .with("name", name_path.join(".")),
                     Label::from_node(call, None),
+                    source_notes,
+                    Vec::new(),
                 ));
 
                 return;
@@ -432,35 +391,31 @@ impl Visitor for StandardLibraryVisitor<'_> {
         };
 
         if function.method != call_is_method {
-            let problem = if call_is_method {
-                "is not a method"
-            } else {
-                "is a method"
-            };
-
             let using = if call_is_method { ":" } else { "." };
             let use_instead = if call_is_method { "." } else { ":" };
 
             let name = name_path.pop().unwrap();
 
-            self.diagnostics.push(Diagnostic::new_complete(
-                "incorrect_standard_library_use",
-                format!(
-                    "standard library function `{}{}{}` {}",
-                    name_path.join("."),
-                    using,
-                    name,
-                    problem,
-                ),
-                Label::from_node(call, None),
-                vec![format!(
-                    "try: {}{}{}(...)",
+            self.diagnostics.push(
+                Diagnostic::new_complete(
+                    "incorrect_standard_library_use",
+                    Message::new(if call_is_method {
+                        "incorrect_standard_library_use.not_a_method"
+                    } else {
+                        "incorrect_standard_library_use.method"
+                    })
+                    .with("name", format!("{}{using}{name}", name_path.join("."))),
+                    Label::from_node(call, None),
+                    source_notes,
+                    Vec::new(),
+                )
+                .with_suggestion(format!(
+                    "{}{}{}(...)",
                     name_path.join("."),
                     use_instead,
                     name
-                )],
-                Vec::new(),
-            ));
+                )),
+            );
 
             return;
         }
@@ -474,7 +429,8 @@ impl Visitor for StandardLibraryVisitor<'_> {
         match function_args {
             ast::FunctionArgs::Parentheses { arguments, .. } => {
                 for argument in arguments {
-                    argument_types.push((argument.range().unwrap(), get_argument_type(argument)));
+                    argument_types
+                        .push((argument.range().unwrap(), self.get_argument_type(argument)));
                 }
             }
 
@@ -492,15 +448,6 @@ impl Visitor for StandardLibraryVisitor<'_> {
             _ => {}
         }
 
-        let mut expected_args = function
-            .arguments
-            .iter()
-            .filter(|arg| arg.required != Required::NotRequired)
-            .count();
-
-        let mut vararg = false;
-        let mut max_args = function.arguments.len();
-
         let mut maybe_more_arguments = false;
 
         if let ast::FunctionArgs::Parentheses { arguments, .. } = function_args {
@@ -525,141 +472,233 @@ impl Visitor for StandardLibraryVisitor<'_> {
             }
         };
 
-        if let Some(last) = function.arguments.last() {
-            if last.argument_type == ArgumentType::Vararg {
-                if let Required::Required(message) = &last.required {
-                    // Functions like math.ceil where not using the vararg is wrong
-                    if function.arguments.len() > argument_types.len() && !maybe_more_arguments {
-                        self.diagnostics.push(Diagnostic::new_complete(
-                            "incorrect_standard_library_use",
-                            format!(
-                                // TODO: This message isn't great
-                                "standard library function `{}` requires use of the vararg",
-                                name_path.join("."),
-                            ),
-                            Label::from_node(call, None),
-                            message.iter().cloned().collect(),
-                            Vec::new(),
-                        ));
-                    }
+        let mut diagnostics = check_arguments(
+            call,
+            &name_path,
+            &function.arguments,
+            &argument_types,
+            maybe_more_arguments,
+        );
+
+        // With overloads, the call is fine if any signature matches it, and otherwise only the
+        // problems with the signature that came closest are reported.
+        // The closest has the fewest problems, then takes the closest number of arguments.
+        if !diagnostics.is_empty() && !function.overloads.is_empty() {
+            let closeness = |diagnostics: &[Diagnostic], arguments: &[Argument]| {
+                (
+                    diagnostics.len(),
+                    arguments.len().abs_diff(argument_types.len()),
+                )
+            };
+
+            let mut closest = &function.arguments;
+
+            for overload in &function.overloads {
+                let overload_diagnostics = check_arguments(
+                    call,
+                    &name_path,
+                    &overload.arguments,
+                    &argument_types,
+                    maybe_more_arguments,
+                );
 
-                    expected_args -= 1;
-                    max_args -= 1;
+                if closeness(&overload_diagnostics, &overload.arguments)
+                    < closeness(&diagnostics, closest)
+                {
+                    diagnostics = overload_diagnostics;
+                    closest = &overload.arguments;
                 }
 
-                vararg = true;
+                if diagnostics.is_empty() {
+                    break;
+                }
             }
-        }
 
-        let arguments_length = argument_types.len();
+            let closest_note = format!(
+                "none of the {} signatures of `{}` match, the closest is `{}`",
+                function.overloads.len() + 1,
+                name_path.join("."),
+                signature(&name_path, closest),
+            );
 
-        if (arguments_length < expected_args && !maybe_more_arguments)
-            || (!vararg && arguments_length > max_args)
-        {
-            self.diagnostics.push(Diagnostic::new(
-                "incorrect_standard_library_use",
-                format!(
-                    "standard library function `{}` requires {} parameters, {} passed",
-                    name_path.join("."),
-                    expected_args,
-                    argument_types.len(),
-                ),
-                Label::from_node(call, None),
-            ));
-        }
-
-        for ((range, passed_type), expected) in argument_types.iter().zip(function.arguments.iter())
-        {
-            if expected.argument_type == ArgumentType::Vararg {
-                continue;
+            for diagnostic in &mut diagnostics {
+                diagnostic.notes.push(closest_note.clone());
             }
+        }
 
-            if let Some(passed_type) = passed_type {
-                // Allow nil for unrequired arguments
-                if expected.required == Required::NotRequired
-                    && passed_type == &PassedArgumentType::Primitive(ArgumentType::Nil)
-                {
-                    continue;
-                }
+        for mut diagnostic in diagnostics {
+            diagnostic.notes.extend(source_notes.iter().cloned());
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
 
-                let matches = passed_type.matches(&expected.argument_type);
+/// Whether incorrect_standard_library_use reports the call, such as for passing the wrong number
+/// or types of arguments, so that other lints can leave it be.
+pub(crate) fn reports_call(
+    call: &ast::FunctionCall,
+    context: &Context,
+    ast_context: &AstContext,
+) -> bool {
+    let mut visitor = StandardLibraryVisitor {
+        diagnostics: Vec::new(),
+        scope_manager: &ast_context.scope_manager,
+        standard_library: &context.standard_library,
+        standard_library_is_set: context.standard_library_is_set,
+    };
+
+    // Only the call itself, not the calls inside of it
+    visitor.visit_function_call(call);
+
+    !visitor.diagnostics.is_empty()
+}
 
-                if !matches {
-                    self.diagnostics.push(Diagnostic::new(
+// The problems with a call to a function that takes `arguments`
+fn check_arguments(
+    call: &ast::FunctionCall,
+    name_path: &[String],
+    arguments: &[Argument],
+    argument_types: &[((Position, Position), Option<PassedArgumentType>)],
+    maybe_more_arguments: bool,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let mut expected_args = arguments
+        .iter()
+        .filter(|arg| arg.required != Required::NotRequired)
+        .count();
+
+    let mut vararg = false;
+    let mut max_args = arguments.len();
+
+    if let Some(last) = arguments.last() {
+        if last.argument_type == ArgumentType::Vararg {
+            if let Required::Required(message) = &last.required {
+                // Functions like math.ceil where not using the vararg is wrong
+                if arguments.len() > argument_types.len() && !maybe_more_arguments {
+                    diagnostics.push(Diagnostic::new_complete(
                         "incorrect_standard_library_use",
-                        format!(
-                            "use of standard_library function `{}` is incorrect",
-                            name_path.join("."),
-                        ),
-                        Label::new_with_message(
-                            (range.0.bytes() as u32, range.1.bytes() as u32),
-                            format!(
-                                "expected `{}`, received `{}`",
-                                expected.argument_type,
-                                passed_type.type_name()
-                            ),
-                        ),
+                        // TODO: This message isn't great
+                        Message::new("incorrect_standard_library_use.vararg")
+                            .with("name", name_path.join(".")),
+                        Label::from_node(call, None),
+                        message.iter().cloned().collect(),
+                        Vec::new(),
                     ));
                 }
+
+                expected_args -= 1;
+                max_args -= 1;
             }
+
+            vararg = true;
         }
     }
-}
 
-#[derive(Debug, PartialEq, Eq)]
-enum PassedArgumentType {
-    Primitive(ArgumentType),
-    String(String),
-}
+    let arguments_length = argument_types.len();
+
+    // The first required argument that wasn't passed, if it has a name to point out
+    let missing_argument = arguments
+        .iter()
+        .enumerate()
+        .skip(arguments_length)
+        .find(|(_, argument)| {
+            argument.required != Required::NotRequired
+                && argument.argument_type != ArgumentType::Vararg
+        })
+        .and_then(|(index, argument)| argument.description(index));
+
+    if (arguments_length < expected_args && !maybe_more_arguments)
+        || (!vararg && arguments_length > max_args)
+    {
+        diagnostics.push(Diagnostic::new_complete(
+            "incorrect_standard_library_use",
+            match missing_argument {
+                Some(description) if arguments_length < expected_args => {
+                    Message::new("incorrect_standard_library_use.missing_argument")
+                        .with("argument", description)
+                        .with("name", name_path.join("."))
+                }
 
-impl PassedArgumentType {
-    fn from_string(mut string: String) -> PassedArgumentType {
-        string.pop();
-        PassedArgumentType::String(string.chars().skip(1).collect())
+                _ => Message::new("incorrect_standard_library_use.argument_count")
+                    .with("name", name_path.join("."))
+                    .with("expected", expected_args)
+                    .with("passed", argument_types.len()),
+            },
+            Label::from_node(call, None),
+            Vec::new(),
+            Vec::new(),
+        ));
     }
 
-    fn matches(&self, argument_type: &ArgumentType) -> bool {
-        if argument_type == &ArgumentType::Any {
-            return true;
+    for (index, ((range, passed_type), expected)) in
+        argument_types.iter().zip(arguments.iter()).enumerate()
+    {
+        if expected.argument_type == ArgumentType::Vararg {
+            continue;
         }
 
-        match self {
-            PassedArgumentType::Primitive(us) => {
-                us == &ArgumentType::Vararg
-                    || us == argument_type
-                    || (us == &ArgumentType::String
-                        && matches!(argument_type, ArgumentType::Constant(_)))
+        if let Some(passed_type) = passed_type {
+            // Allow nil for unrequired arguments
+            if expected.required == Required::NotRequired
+                && passed_type == &PassedArgumentType::Primitive(ArgumentType::Nil)
+            {
+                continue;
             }
-            PassedArgumentType::String(text) => match argument_type {
-                ArgumentType::Constant(constants) => constants.contains(text),
-                ArgumentType::String => true,
-                _ => false,
-            },
-        }
-    }
 
-    // Roblox feature flag uses this, and I don't want to lock it
-    #[allow(dead_code)]
-    fn same_type(&self, other: &PassedArgumentType) -> bool {
-        match (self, other) {
-            (PassedArgumentType::Primitive(a), PassedArgumentType::Primitive(b)) => a == b,
-            (PassedArgumentType::String(_), PassedArgumentType::String(_)) => true,
-            _ => false,
-        }
-    }
+            let matches = passed_type.matches(&expected.argument_type);
+
+            if !matches {
+                diagnostics.push(Diagnostic::new_complete(
+                    "incorrect_standard_library_use",
+                    match expected.description(index) {
+                        Some(description) => {
+                            Message::new("incorrect_standard_library_use.incorrect_argument")
+                                .with("argument", description)
+                                .with("name", name_path.join("."))
+                        }
 
-    fn type_name(&self) -> String {
-        match self {
-            PassedArgumentType::Primitive(argument_type) => argument_type.to_string(),
-            PassedArgumentType::String(_) => ArgumentType::String.to_string(),
+                        None => Message::new("incorrect_standard_library_use.incorrect")
+                            .with("name", name_path.join(".")),
+                    },
+                    Label::new_with_message(
+                        (range.0.bytes() as u32, range.1.bytes() as u32),
+                        format!(
+                            "expected `{}`, received `{}`",
+                            expected.argument_type,
+                            passed_type.type_name()
+                        ),
+                    ),
+                    Vec::new(),
+                    Vec::new(),
+                ));
+            }
         }
     }
+
+    diagnostics
 }
 
-impl From<ArgumentType> for PassedArgumentType {
-    fn from(argument_type: ArgumentType) -> Self {
-        PassedArgumentType::Primitive(argument_type)
-    }
+// How a signature is written in notes, such as `table.remove(t: table, number?)`
+fn signature(name_path: &[String], arguments: &[Argument]) -> String {
+    let arguments = arguments
+        .iter()
+        .map(|argument| {
+            let argument_type = match (&argument.argument_type, &argument.required) {
+                (ArgumentType::Vararg, _) | (_, Required::Required(_)) => {
+                    argument.argument_type.to_string()
+                }
+                (argument_type, Required::NotRequired) => format!("{argument_type}?"),
+            };
+
+            match &argument.name {
+                Some(name) => format!("{name}: {argument_type}"),
+                None => argument_type,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    format!("{}({})", name_path.join("."), arguments.join(", "))
 }
 
 #[cfg(test)]
@@ -750,6 +789,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_inferred_locals() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "inferred_locals",
+        );
+    }
+
     #[test]
     fn test_lua52() {
         test_lint_config(
@@ -763,6 +811,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lua53() {
+        test_lint_config(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "lua53",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("lua53").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_luau() {
+        test_lint_config(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "luau",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
     #[test]
     fn test_math_on_types() {
         test_lint(
@@ -781,6 +855,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_named_arguments() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "named_arguments",
+        );
+    }
+
+    #[test]
+    fn test_overloads() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "overloads",
+        );
+    }
+
+    #[test]
+    fn test_property_types() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "property_types",
+        );
+    }
+
     #[test]
     fn test_required() {
         test_lint(
@@ -0,0 +1,151 @@
+use super::*;
+use crate::{
+    ast_util::{evaluate_constant, name_paths::*, range, scopes::ScopeManager, Constant},
+    standard_library::{FieldKind, StandardLibrary},
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    visitors::Visitor,
+};
+
+// The lengths come from the `max_length` of arguments in the standard library, for APIs that error
+// on strings that are too long, such as keys that are sent over the network.
+pub struct StringTooLongLint;
+
+impl Rule for StringTooLongLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Error;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(StringTooLongLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = StringTooLongVisitor {
+            diagnostics: Vec::new(),
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct StringTooLongVisitor<'std> {
+    diagnostics: Vec<Diagnostic>,
+    scope_manager: &'std ScopeManager,
+    standard_library: &'std StandardLibrary,
+}
+
+impl Visitor for StringTooLongVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let Some(reference) = self
+            .scope_manager
+            .reference_at_byte(call.start_position().unwrap().bytes())
+        {
+            if reference.resolved.is_some() {
+                return;
+            }
+        }
+
+        let mut keep_going = true;
+        let suffixes: Vec<&ast::Suffix> = call
+            .suffixes()
+            .take_while(|suffix| take_while_keep_going(suffix, &mut keep_going))
+            .collect();
+
+        let name_path = match name_path_from_prefix_suffix(call.prefix(), suffixes.iter().copied())
+        {
+            Some(name_path) => name_path,
+            None => return,
+        };
+
+        let function = match self.standard_library.find_global(&name_path) {
+            Some(field) => match &field.field_kind {
+                FieldKind::Function(function) => function,
+                _ => return,
+            },
+            None => return,
+        };
+
+        let function_args = match suffixes.last() {
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(function_args))) => function_args,
+            Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))) => method_call.args(),
+            _ => return,
+        };
+
+        let arguments = match function_args {
+            ast::FunctionArgs::Parentheses { arguments, .. } => arguments,
+            _ => return,
+        };
+
+        for (index, (argument, expected)) in
+            arguments.iter().zip(function.arguments.iter()).enumerate()
+        {
+            let max_length = match expected.max_length {
+                Some(max_length) => max_length,
+                None => continue,
+            };
+
+            let string = match evaluate_constant(argument) {
+                Some(Constant::String(string)) => string,
+                _ => continue,
+            };
+
+            if string.len() <= max_length {
+                continue;
+            }
+
+            let message = match expected.description(index) {
+                Some(description) => {
+                    Message::new("string_too_long.argument").with("argument", description)
+                }
+                None => Message::new("string_too_long"),
+            };
+
+            // Limits are in bytes, which isn't obvious when characters take more than one
+            let characters = string.chars().count();
+            let notes = if characters != string.len() {
+                vec![format!(
+                    "note: this is {characters} characters, but {} bytes in UTF-8",
+                    string.len()
+                )]
+            } else {
+                Vec::new()
+            };
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "string_too_long",
+                message
+                    .with("function", name_path.join("."))
+                    .with("max_length", max_length)
+                    .with("length", string.len()),
+                Label::new(range::<_, usize>(argument)),
+                notes,
+                Vec::new(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_string_too_long() {
+        test_lint(
+            StringTooLongLint::new(()).unwrap(),
+            "string_too_long",
+            "string_too_long",
+        );
+    }
+}
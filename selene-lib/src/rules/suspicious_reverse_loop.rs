@@ -1,5 +1,6 @@
 use super::*;
-use std::{convert::Infallible, str};
+use crate::ast_util::evaluate_constant;
+use std::convert::Infallible;
 
 use full_moon::{
     ast::{self, Ast},
@@ -15,6 +16,7 @@ impl Rule for SuspiciousReverseLoopLint {
 
     const SEVERITY: Severity = Severity::Error;
     const RULE_TYPE: RuleType = RuleType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["571"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(SuspiciousReverseLoopLint)
@@ -33,11 +35,12 @@ impl Rule for SuspiciousReverseLoopLint {
             .map(|position| {
                 Diagnostic::new_complete(
                     "suspicious_reverse_loop",
-                    "this loop will only ever run once at most".to_owned(),
+                    Message::new("suspicious_reverse_loop"),
                     Label::new(*position),
-                    vec!["help: try adding `, -1` after `1`".to_owned()],
+                    Vec::new(),
                     Vec::new(),
                 )
+                .with_help("try adding `, -1` after `1`")
             })
             .collect()
     }
@@ -55,9 +58,8 @@ impl Visitor for SuspiciousReverseLoopVisitor {
                 unop: ast::UnOp::Hash(_),
                 ..
             } = node.start();
-            if let ast::Expression::Value { value, .. } = node.end();
-            if let ast::Value::Number(number) = &**value;
-            if str::parse::<f32>(&number.token().to_string()).ok() <= Some(1.0);
+            if let Some(end) = evaluate_constant(node.end()).and_then(|value| value.as_number());
+            if end <= 1.0;
             then {
                 self.positions.push((
                     node.start().start_position().unwrap().bytes(),
@@ -80,4 +82,13 @@ mod tests {
             "suspicious_reverse_loop",
         );
     }
+
+    #[test]
+    fn test_constant_folding() {
+        test_lint(
+            SuspiciousReverseLoopLint::new(()).unwrap(),
+            "suspicious_reverse_loop",
+            "constant_folding",
+        );
+    }
 }
@@ -0,0 +1,122 @@
+use super::*;
+use crate::ast_util::range;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Symbol, TokenType},
+    visitors::Visitor,
+};
+
+pub struct SuspiciousSemicolonLint;
+
+impl Rule for SuspiciousSemicolonLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(SuspiciousSemicolonLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = SuspiciousSemicolonVisitor {
+            semicolons: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor
+            .semicolons
+            .into_iter()
+            .map(|(position, after_block)| {
+                Diagnostic::new_complete(
+                    "suspicious_semicolon",
+                    Message::new(if after_block {
+                        "suspicious_semicolon.after_end"
+                    } else {
+                        "suspicious_semicolon"
+                    }),
+                    Label::new(position),
+                    Vec::new(),
+                    Vec::new(),
+                )
+                .with_fix(Fix::removal(position))
+            })
+            .collect()
+    }
+}
+
+struct SuspiciousSemicolonVisitor {
+    semicolons: Vec<((usize, usize), bool)>,
+}
+
+// A semicolon is needed before a statement starting with `(`, since otherwise
+// `a = b; (f)()` would be read as `a = b(f)()`.
+fn starts_with_parenthesis(stmt: &ast::Stmt) -> bool {
+    match stmt.tokens().next() {
+        Some(token) => matches!(
+            token.token_type(),
+            TokenType::Symbol {
+                symbol: Symbol::LeftParen
+            }
+        ),
+        None => false,
+    }
+}
+
+fn ends_with_block(stmt: &ast::Stmt) -> bool {
+    matches!(
+        stmt,
+        ast::Stmt::Do(_)
+            | ast::Stmt::FunctionDeclaration(_)
+            | ast::Stmt::GenericFor(_)
+            | ast::Stmt::If(_)
+            | ast::Stmt::LocalFunction(_)
+            | ast::Stmt::NumericFor(_)
+            | ast::Stmt::While(_)
+    )
+}
+
+impl Visitor for SuspiciousSemicolonVisitor {
+    fn visit_block(&mut self, block: &ast::Block) {
+        let stmts = block.stmts_with_semicolon().collect::<Vec<_>>();
+
+        for (index, (stmt, semicolon)) in stmts.iter().enumerate() {
+            let semicolon = match semicolon {
+                Some(semicolon) => semicolon,
+                None => continue,
+            };
+
+            if let Some((next_stmt, _)) = stmts.get(index + 1) {
+                if starts_with_parenthesis(next_stmt) {
+                    continue;
+                }
+            }
+
+            self.semicolons
+                .push((range(semicolon), ends_with_block(stmt)));
+        }
+
+        if let Some((_, Some(semicolon))) = block.last_stmt_with_semicolon() {
+            self.semicolons.push((range(semicolon), false));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_suspicious_semicolon() {
+        test_lint(
+            SuspiciousSemicolonLint::new(()).unwrap(),
+            "suspicious_semicolon",
+            "suspicious_semicolon",
+        );
+    }
+}
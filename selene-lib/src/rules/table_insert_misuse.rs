@@ -0,0 +1,247 @@
+use super::*;
+use crate::ast_util::{
+    evaluate_constant, is_function_call, is_vararg, name_paths::name_path_from_prefix_suffix,
+    parse_number, purge_trivia, range, scopes::ScopeManager, Constant, HasPurity, Purity,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct TableInsertMisuseLint;
+
+impl Rule for TableInsertMisuseLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(TableInsertMisuseLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = TableInsertMisuseVisitor {
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct TableInsertMisuseVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn text(expression: &ast::Expression) -> String {
+    expression.to_string().trim().to_owned()
+}
+
+// `(#t + 1)` can be parsed as either kind of parentheses
+fn without_parentheses(expression: &ast::Expression) -> &ast::Expression {
+    match expression {
+        ast::Expression::Parentheses { expression, .. } => without_parentheses(expression),
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::ParenthesesExpression(expression) => without_parentheses(expression),
+            _ => expression,
+        },
+        _ => expression,
+    }
+}
+
+// Returns `n` for `#t + n` and `n + #t`, where `t` is the table being inserted into
+fn length_offset(position: &ast::Expression, table: &str) -> Option<f64> {
+    let (lhs, rhs) = match without_parentheses(position) {
+        ast::Expression::BinaryOperator {
+            lhs,
+            binop: ast::BinOp::Plus(_),
+            rhs,
+        } => (without_parentheses(lhs), without_parentheses(rhs)),
+        _ => return None,
+    };
+
+    let is_length = |expression: &ast::Expression| match expression {
+        ast::Expression::UnaryOperator {
+            unop: ast::UnOp::Hash(_),
+            expression,
+        } => purge_trivia(&**expression).to_string().trim() == table,
+        _ => false,
+    };
+
+    let offset = if is_length(lhs) {
+        rhs
+    } else if is_length(rhs) {
+        lhs
+    } else {
+        return None;
+    };
+
+    evaluate_constant(offset)?.as_number()
+}
+
+// Whether the position can never be inserted at, no matter how long the table is
+fn is_invalid_position(position: &ast::Expression) -> bool {
+    let number = match evaluate_constant(position) {
+        Some(Constant::Number(number)) => number,
+        // Strings are converted to numbers, like in arithmetic
+        Some(Constant::String(string)) => match parse_number(&string) {
+            Some(number) => number,
+            None => return true,
+        },
+        Some(Constant::Bool(_) | Constant::Nil) => return true,
+        None => return false,
+    };
+
+    number < 1.0 || number.fract() != 0.0
+}
+
+impl TableInsertMisuseVisitor<'_> {
+    // Returns the arguments of `table.insert(...)`, as long as `table` is the global
+    fn insert_arguments<'b>(
+        &self,
+        call: &'b ast::FunctionCall,
+    ) -> Option<Vec<&'b ast::Expression>> {
+        match name_path_from_prefix_suffix(call.prefix(), call.suffixes())?.as_slice() {
+            [library, function] if library == "table" && function == "insert" => {}
+            _ => return None,
+        }
+
+        let mut suffixes = call.suffixes();
+        let arguments = match (suffixes.next(), suffixes.next(), suffixes.next()) {
+            (
+                Some(ast::Suffix::Index(_)),
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                    arguments,
+                    ..
+                }))),
+                None,
+            ) => arguments,
+            _ => return None,
+        };
+
+        if let Some(reference) = self.scope_manager.reference_at_byte(range(call.prefix()).0) {
+            if reference.resolved.is_some() {
+                return None;
+            }
+        }
+
+        Some(arguments.iter().collect())
+    }
+
+    fn check_position(
+        &mut self,
+        table: &ast::Expression,
+        position: &ast::Expression,
+        value: &ast::Expression,
+    ) {
+        let table_text = purge_trivia(table).to_string().trim().to_owned();
+
+        let out_of_range = if is_invalid_position(position) {
+            true
+        } else if table.purity() == Purity::Impure {
+            // `#f() + 1` can be a different table than `f()`
+            false
+        } else {
+            match length_offset(position, &table_text) {
+                Some(offset) => {
+                    if offset == 1.0 {
+                        self.check_append(&table_text, position, value);
+                        return;
+                    }
+
+                    offset > 1.0 || offset.fract() != 0.0
+                }
+
+                None => false,
+            }
+        };
+
+        if out_of_range {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "table_insert_misuse",
+                Message::new("table_insert_misuse.out_of_range")
+                    .with("position", text(position))
+                    .with("table", &table_text),
+                Label::from_node(position, None),
+                vec![format!(
+                    "note: the position must be a whole number from 1 to `#{table_text} + 1`"
+                )],
+                Vec::new(),
+            ));
+        }
+    }
+
+    fn check_append(
+        &mut self,
+        table_text: &str,
+        position: &ast::Expression,
+        value: &ast::Expression,
+    ) {
+        let mut diagnostic = Diagnostic::new_complete(
+            "table_insert_misuse",
+            Message::new("table_insert_misuse.append").with("position", text(position)),
+            Label::from_node(position, None),
+            Vec::new(),
+            Vec::new(),
+        )
+        .with_suggestion(format!("`table.insert({table_text}, {})`", text(value)));
+
+        // Without the position, every value a call or `...` gives would be passed
+        if !is_vararg(value) && !is_function_call(value) {
+            let position_range: (usize, usize) = range(position);
+            let value_range: (usize, usize) = range(value);
+            diagnostic = diagnostic.with_fix(Fix::removal((position_range.0, value_range.0)));
+        }
+
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl Visitor for TableInsertMisuseVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let arguments = match self.insert_arguments(call) {
+            Some(arguments) => arguments,
+            None => return,
+        };
+
+        match arguments.as_slice() {
+            // `table.insert(unpack(values))` could be passed anything
+            [table] if !is_vararg(table) && !is_function_call(table) => {
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "table_insert_misuse",
+                    Message::new("table_insert_misuse.no_value"),
+                    Label::from_node(call, None),
+                    vec![
+                        "note: `table.insert(t, value)` adds `value` to the end of `t`".to_owned(),
+                    ],
+                    Vec::new(),
+                ));
+            }
+
+            [table, position, value] => self.check_position(table, position, value),
+
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_table_insert_misuse() {
+        test_lint(
+            TableInsertMisuseLint::new(()).unwrap(),
+            "table_insert_misuse",
+            "table_insert_misuse",
+        );
+    }
+}
@@ -51,8 +51,13 @@ pub fn test_lint_config_with_output<
     let standard_library_is_set =
         config.standard_library != StandardLibrary::from_name("lua51").unwrap();
 
+    // A test's own library goes on top of the one it's given, as if it were based off of it
     if let Some(standard_library) = configured_standard_library {
-        config.standard_library = standard_library;
+        if standard_library_is_set {
+            config.standard_library.extend(standard_library);
+        } else {
+            config.standard_library = standard_library;
+        }
     }
 
     let lua_source =
@@ -65,14 +70,38 @@ pub fn test_lint_config_with_output<
             standard_library: config.standard_library,
             standard_library_is_set,
         },
-        &AstContext::from_ast(&ast),
+        // Relative to selene-lib, which tests are run from, like the paths selene is given
+        &AstContext::from_ast_with_path(
+            &ast,
+            &Path::new("tests")
+                .join("lints")
+                .join(lint_name)
+                .join(test_name)
+                .with_extension("lua"),
+        ),
     );
 
+    diagnostics.sort_by_key(|diagnostic| diagnostic.primary_label.range);
+
+    let fixes = diagnostics
+        .iter()
+        .flat_map(|diagnostic| &diagnostic.fixes)
+        .collect::<Vec<_>>();
+
+    if !fixes.is_empty() {
+        let (fixed_source, _) = crate::fixes::apply_fixes(&lua_source, &fixes);
+        let fixed_path = path_base.with_extension("fixed.lua");
+
+        if let Ok(expected) = fs::read_to_string(&fixed_path) {
+            pretty_assertions::assert_eq!(PrettyString(&expected), PrettyString(&fixed_source));
+        } else {
+            fs::write(fixed_path, fixed_source).expect("couldn't write to fixed file");
+        }
+    }
+
     let mut files = codespan::Files::new();
     let source_id = files.add(format!("{}.lua", test_name), lua_source);
 
-    diagnostics.sort_by_key(|diagnostic| diagnostic.primary_label.range);
-
     let mut output = termcolor::NoColor::new(Vec::new());
 
     for diagnostic in diagnostics
@@ -34,7 +34,7 @@ impl Rule for TypeCheckInsideCallLint {
             .map(|position| {
                 Diagnostic::new_complete(
                     "type_check_inside_call",
-                    "you are checking the type inside the call, not outside".to_owned(),
+                    Message::new("type_check_inside_call"),
                     Label::new(*position),
                     vec!["note: this will always return `boolean`".to_owned()],
                     Vec::new(),
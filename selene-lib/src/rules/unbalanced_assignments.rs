@@ -16,6 +16,7 @@ impl Rule for UnbalancedAssignmentsLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const RULE_TYPE: RuleType = RuleType::Complexity;
+    const LUACHECK_CODES: &'static [&'static str] = &["531", "532"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(UnbalancedAssignmentsLint)
@@ -35,7 +36,7 @@ impl Rule for UnbalancedAssignmentsLint {
                 if assignment.more {
                     Diagnostic::new(
                         "unbalanced_assignments",
-                        "too many values on the right side of the assignment".to_owned(),
+                        Message::new("unbalanced_assignments.too_many_values"),
                         Label::new(assignment.range),
                     )
                 } else {
@@ -51,8 +52,7 @@ impl Rule for UnbalancedAssignmentsLint {
 
                     Diagnostic::new_complete(
                         "unbalanced_assignments",
-                        "values on right side don't match up to the left side of the assignment"
-                            .to_owned(),
+                        Message::new("unbalanced_assignments.mismatched"),
                         Label::new(assignment.range),
                         Vec::new(),
                         secondary_labels,
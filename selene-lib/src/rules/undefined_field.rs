@@ -0,0 +1,341 @@
+use super::*;
+use crate::{
+    ast_util::{evaluate_constant, range, scopes::Variable, Constant},
+    text::closest_match,
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    convert::Infallible,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct UndefinedFieldLint;
+
+impl Rule for UndefinedFieldLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UndefinedFieldLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let scope_manager = &ast_context.scope_manager;
+
+        let mut visitor = UndefinedFieldVisitor {
+            scope_manager,
+            tables: HashMap::new(),
+            uses: HashMap::new(),
+            assignment_targets: HashSet::new(),
+            functions: vec![0],
+            next_function: 1,
+        };
+
+        visitor.visit_ast(ast);
+
+        let mut diagnostics = Vec::new();
+
+        for (variable_id, table) in &visitor.tables {
+            let uses = match visitor.uses.get(variable_id) {
+                Some(uses) => uses,
+                None => continue,
+            };
+
+            // Anything besides reading and setting fields, such as passing the table to a function,
+            // could give it fields this can't see. So can using it in another function.
+            let variable = &scope_manager.variables[*variable_id];
+            let only_fields_used = variable.references.iter().all(|id| {
+                let identifier = scope_manager.references[*id].identifier;
+
+                identifier == table.definition
+                    || uses.names.get(&identifier) == Some(&table.function)
+            });
+
+            if !only_fields_used {
+                continue;
+            }
+
+            let known_fields = table
+                .fields
+                .iter()
+                .chain(uses.written.iter())
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+
+            for (field, field_range) in &uses.read {
+                if known_fields.contains(&field.as_str()) {
+                    continue;
+                }
+
+                let mut notes = Vec::new();
+                if let Some(suggestion) = closest_match(field, &known_fields) {
+                    notes.push(format!("did you mean `{suggestion}`?"));
+                }
+
+                diagnostics.push(Diagnostic::new_complete(
+                    "undefined_field",
+                    Message::new("undefined_field")
+                        .with("name", &variable.name)
+                        .with("field", field),
+                    Label::new(*field_range),
+                    notes,
+                    vec![Label::new_with_message(
+                        table.definition,
+                        "table defined here".to_owned(),
+                    )],
+                ));
+            }
+        }
+
+        diagnostics.sort_by_key(Diagnostic::start_position);
+        diagnostics
+    }
+}
+
+// A local defined with a table constructor, such as `local t = { a = 1 }`
+struct LiteralTable {
+    definition: (usize, usize),
+    fields: BTreeSet<String>,
+    function: usize,
+}
+
+#[derive(Default)]
+struct TableUses {
+    // The range of every name that was used to get or set a field, and which function it was in
+    names: HashMap<(usize, usize), usize>,
+    read: Vec<(String, (usize, usize))>,
+    written: BTreeSet<String>,
+}
+
+struct UndefinedFieldVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    tables: HashMap<Id<Variable>, LiteralTable>,
+    uses: HashMap<Id<Variable>, TableUses>,
+    assignment_targets: HashSet<(usize, usize)>,
+    functions: Vec<usize>,
+    next_function: usize,
+}
+
+// `a` in `t.a` and `t["a"]`
+fn field_name(suffix: &ast::Suffix) -> Option<(String, &TokenReference)> {
+    match suffix {
+        ast::Suffix::Index(ast::Index::Dot { name, .. }) => Some((name.token().to_string(), name)),
+
+        ast::Suffix::Index(ast::Index::Brackets {
+            brackets,
+            expression,
+        }) => match evaluate_constant(expression)? {
+            Constant::String(field) => Some((field, brackets.tokens().1)),
+            _ => None,
+        },
+
+        _ => None,
+    }
+}
+
+impl UndefinedFieldVisitor<'_> {
+    fn variable(&self, name: &TokenReference) -> Option<Id<Variable>> {
+        self.scope_manager
+            .reference_at_byte(name.start_position()?.bytes())?
+            .resolved
+    }
+
+    fn use_field(
+        &mut self,
+        prefix: &TokenReference,
+        field: &ast::Suffix,
+        write: bool,
+    ) -> Option<()> {
+        let variable = self.variable(prefix)?;
+        let (field, end) = field_name(field)?;
+        let function = *self.functions.last().unwrap();
+
+        let uses = self.uses.entry(variable).or_default();
+        uses.names.insert(range(prefix), function);
+
+        if write {
+            uses.written.insert(field);
+        } else {
+            uses.read.push((field, (range(prefix).0, range(end).1)));
+        }
+
+        Some(())
+    }
+
+    fn use_var_expression(&mut self, var_expression: &ast::VarExpression, write: bool) {
+        if let ast::Prefix::Name(prefix) = var_expression.prefix() {
+            let mut suffixes = var_expression.suffixes();
+
+            if let Some(first) = suffixes.next() {
+                // Only `t.a = 1` sets a field of `t`, `t.a.b = 1` reads `a`
+                let write = write && suffixes.next().is_none();
+                self.use_field(prefix, first, write);
+            }
+        }
+    }
+}
+
+impl Visitor for UndefinedFieldVisitor<'_> {
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        #[cfg(feature = "roblox")]
+        let mut type_specifiers = local_assignment.type_specifiers();
+
+        for (name, expression) in local_assignment
+            .names()
+            .iter()
+            .zip(local_assignment.expressions().iter())
+        {
+            // A type could say which fields are allowed to be missing
+            #[cfg(feature = "roblox")]
+            if let Some(Some(_)) = type_specifiers.next() {
+                continue;
+            }
+
+            let table = match expression {
+                ast::Expression::Value { value, .. } => match &**value {
+                    ast::Value::TableConstructor(table) => table,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            let mut fields = BTreeSet::new();
+            let mut dynamic = false;
+
+            for field in table.fields() {
+                match field {
+                    ast::Field::NameKey { key, .. } => {
+                        fields.insert(key.token().to_string());
+                    }
+
+                    ast::Field::ExpressionKey { key, .. } => match evaluate_constant(key) {
+                        Some(Constant::String(key)) => {
+                            fields.insert(key);
+                        }
+                        Some(_) => {}
+                        None => dynamic = true,
+                    },
+
+                    ast::Field::NoKey(_) => {}
+
+                    _ => dynamic = true,
+                }
+            }
+
+            if dynamic {
+                continue;
+            }
+
+            if let Some(variable) = self.variable(name) {
+                self.tables.insert(
+                    variable,
+                    LiteralTable {
+                        definition: range(name),
+                        fields,
+                        function: *self.functions.last().unwrap(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for var in assignment.variables() {
+            if let ast::Var::Expression(var_expression) = var {
+                self.assignment_targets.insert(range(var_expression));
+                self.use_var_expression(var_expression, true);
+            }
+        }
+    }
+
+    #[cfg(feature = "roblox")]
+    fn visit_compound_assignment(&mut self, compound_assignment: &ast::types::CompoundAssignment) {
+        if let ast::Var::Expression(var_expression) = compound_assignment.lhs() {
+            self.assignment_targets.insert(range(var_expression));
+            self.use_var_expression(var_expression, false);
+        }
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        if !self.assignment_targets.contains(&range(var_expression)) {
+            self.use_var_expression(var_expression, false);
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let mut suffixes = call.suffixes();
+
+        // `t.f()` reads `f`, but `t:f()` passes `t` along, so it's not counted
+        if let (ast::Prefix::Name(prefix), Some(first), Some(_)) =
+            (call.prefix(), suffixes.next(), suffixes.next())
+        {
+            self.use_field(prefix, first, false);
+        }
+    }
+
+    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
+        let name = function_declaration.name();
+        let mut names = name.names().iter();
+        let prefix = names.next().unwrap();
+
+        let function = *self.functions.last().unwrap();
+        let variable = match self.variable(prefix) {
+            Some(variable) => variable,
+            None => return,
+        };
+
+        let field = match (names.next(), names.next(), name.method_name()) {
+            // function t.f() end
+            (Some(field), None, None) => (field, true),
+            // function t:f() end
+            (None, None, Some(method)) => (method, true),
+            // function t.a.f() end
+            (Some(field), _, _) => (field, false),
+            (None, _, _) => return,
+        };
+
+        let uses = self.uses.entry(variable).or_default();
+        uses.names.insert(range(prefix), function);
+
+        let field_name = field.0.token().to_string();
+        if field.1 {
+            uses.written.insert(field_name);
+        } else {
+            uses.read
+                .push((field_name, (range(prefix).0, range(field.0).1)));
+        }
+    }
+
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.functions.push(self.next_function);
+        self.next_function += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.functions.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_undefined_field() {
+        test_lint(
+            UndefinedFieldLint::new(()).unwrap(),
+            "undefined_field",
+            "undefined_field",
+        );
+    }
+}
@@ -19,12 +19,18 @@ impl Rule for UndefinedVariableLint {
 
     const SEVERITY: Severity = Severity::Error;
     const RULE_TYPE: RuleType = RuleType::Correctness;
+    const LUACHECK_CODES: &'static [&'static str] = &["113"];
 
     fn new(_: Self::Config) -> Result<Self, Self::Error> {
         Ok(UndefinedVariableLint)
     }
 
     fn pass(&self, _: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        // Globals can be anything the environment has
+        if ast_context.scope_manager.uses_environments() {
+            return Vec::new();
+        }
+
         // ScopeManager repeats references, and I just don't want to fix it right now
         let mut read = HashSet::new();
 
@@ -39,16 +45,44 @@ impl Rule for UndefinedVariableLint {
             {
                 read.insert(reference.identifier);
 
-                diagnostics.push(Diagnostic::new_complete(
+                let (message, (notes, help)) = match context
+                    .standard_library
+                    .removed_by(&[reference.name.as_str()])
+                {
+                    Some(library) => (
+                        Message::new("undefined_variable.removed")
+                            .with("name", &reference.name)
+                            .with("library", library),
+                        (
+                            context
+                                .standard_library
+                                .global_source_notes(&[reference.name.as_str()]),
+                            None,
+                        ),
+                    ),
+
+                    None => (
+                        Message::new("undefined_variable").with("name", &reference.name),
+                        possible_standard_library_notes(
+                            &[reference.name.as_str()],
+                            context.standard_library_is_set,
+                        ),
+                    ),
+                };
+
+                let mut diagnostic = Diagnostic::new_complete(
                     "undefined_variable",
-                    format!("`{}` is not defined", reference.name),
+                    message,
                     Label::new(reference.identifier),
-                    possible_standard_library_notes(
-                        &[reference.name.as_str()],
-                        context.standard_library_is_set,
-                    ),
+                    notes,
                     Vec::new(),
-                ));
+                );
+
+                if let Some(help) = help {
+                    diagnostic = diagnostic.with_help(help);
+                }
+
+                diagnostics.push(diagnostic);
             }
         }
 
@@ -74,6 +108,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_environments() {
+        test_lint(
+            UndefinedVariableLint::new(()).unwrap(),
+            "undefined_variable",
+            "environments",
+        );
+    }
+
     #[test]
     #[cfg(feature = "roblox")]
     fn test_compound_assignments() {
@@ -102,6 +145,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_removed_globals() {
+        let mut standard_library: StandardLibrary =
+            serde_yaml::from_str("globals:\n  print:\n    removed: true").unwrap();
+        standard_library.set_source_name("game");
+        standard_library.extend(StandardLibrary::from_name("lua51").unwrap());
+
+        test_lint_config(
+            UndefinedVariableLint::new(()).unwrap(),
+            "undefined_variable",
+            "removed_globals",
+            TestUtilConfig {
+                standard_library,
+                ..TestUtilConfig::default()
+            },
+        );
+    }
+
     #[test]
     fn test_self() {
         test_lint(
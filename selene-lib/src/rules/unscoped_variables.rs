@@ -5,9 +5,9 @@ use std::collections::HashSet;
 
 use full_moon::ast::Ast;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct UnscopedVariablesConfig {
     ignore_pattern: String,
@@ -31,6 +31,7 @@ impl Rule for UnscopedVariablesLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const RULE_TYPE: RuleType = RuleType::Complexity;
+    const LUACHECK_CODES: &'static [&'static str] = &["111"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(UnscopedVariablesLint {
@@ -39,6 +40,11 @@ impl Rule for UnscopedVariablesLint {
     }
 
     fn pass(&self, _: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        // Globals are assigned to the environment rather than shared with every file
+        if ast_context.scope_manager.uses_environments() {
+            return Vec::new();
+        }
+
         // ScopeManager repeats references, and I just don't want to fix it right now
         let mut read = HashSet::new();
 
@@ -55,10 +61,7 @@ impl Rule for UnscopedVariablesLint {
 
                 diagnostics.push(Diagnostic::new(
                     "unscoped_variables",
-                    format!(
-                        "`{}` is not declared locally, and will be available in every scope",
-                        reference.name
-                    ),
+                    Message::new("unscoped_variables").with("name", &reference.name),
                     Label::new(reference.identifier),
                 ));
             }
@@ -81,6 +84,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_environments() {
+        test_lint(
+            UnscopedVariablesLint::new(UnscopedVariablesConfig::default()).unwrap(),
+            "unscoped_variables",
+            "environments",
+        );
+    }
+
     #[test]
     fn test_unscoped_variables() {
         test_lint(
@@ -0,0 +1,129 @@
+use super::*;
+use crate::ast_util::{local_require, range, scopes::AssignedValue};
+use std::collections::HashMap;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UnusedRequireConfig {
+    ignore_pattern: String,
+}
+
+impl Default for UnusedRequireConfig {
+    fn default() -> Self {
+        Self {
+            ignore_pattern: "^_".to_owned(),
+        }
+    }
+}
+
+pub struct UnusedRequireLint {
+    ignore_pattern: Regex,
+}
+
+impl Rule for UnusedRequireLint {
+    type Config = UnusedRequireConfig;
+    type Error = regex::Error;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UnusedRequireLint {
+            ignore_pattern: Regex::new(&config.ignore_pattern)?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = LocalRequireVisitor::default();
+        visitor.visit_ast(ast);
+
+        let mut diagnostics = Vec::new();
+
+        for (_, variable) in ast_context.scope_manager.variables.iter() {
+            if variable.value != Some(AssignedValue::Require)
+                || self.ignore_pattern.is_match(&variable.name)
+            {
+                continue;
+            }
+
+            let is_read = variable
+                .references
+                .iter()
+                .any(|id| ast_context.scope_manager.references[*id].write.is_none());
+
+            if is_read {
+                continue;
+            }
+
+            let mut diagnostic = Diagnostic::new_complete(
+                "unused_require",
+                Message::new("unused_require").with("name", &variable.name),
+                Label::new(variable.identifiers[0]),
+                vec![
+                    "if the module isn't needed, remove this require".to_owned(),
+                    "if it's only required for its side effects, call `require` without assigning it"
+                        .to_owned(),
+                ],
+                Vec::new(),
+            );
+
+            let definition = variable.definitions[0];
+
+            // Assignments after the require would be left assigning to a global
+            let is_assigned_later = variable.references.iter().any(|id| {
+                let (start, end) = ast_context.scope_manager.references[*id].identifier;
+                start < definition.0 || end > definition.1
+            });
+
+            // Removing the require would stop the module from running, so the fix keeps it
+            if let (Some(argument), false) =
+                (visitor.local_requires.get(&definition), is_assigned_later)
+            {
+                diagnostic =
+                    diagnostic.with_fix(Fix::new(definition, format!("require({argument})")));
+            }
+
+            diagnostics.push(diagnostic);
+        }
+
+        diagnostics
+    }
+}
+
+// The arguments of every `local Name = require(argument)`, keyed by the range of the statement
+#[derive(Default)]
+struct LocalRequireVisitor {
+    local_requires: HashMap<(usize, usize), String>,
+}
+
+impl Visitor for LocalRequireVisitor {
+    fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
+        if let Some((_, argument)) = local_require(local_assignment) {
+            self.local_requires.insert(
+                range(local_assignment),
+                argument.to_string().trim().to_owned(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_unused_require() {
+        test_lint(
+            UnusedRequireLint::new(UnusedRequireConfig::default()).unwrap(),
+            "unused_require",
+            "unused_require",
+        );
+    }
+}
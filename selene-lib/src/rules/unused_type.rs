@@ -0,0 +1,120 @@
+use super::*;
+use crate::ast_util::range;
+use std::collections::HashSet;
+
+use full_moon::{
+    ast::{types, Ast},
+    visitors::Visitor,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UnusedTypeConfig {
+    ignore_pattern: String,
+}
+
+impl Default for UnusedTypeConfig {
+    fn default() -> Self {
+        Self {
+            ignore_pattern: "^_".to_owned(),
+        }
+    }
+}
+
+pub struct UnusedTypeLint {
+    ignore_pattern: Regex,
+}
+
+impl Rule for UnusedTypeLint {
+    type Config = UnusedTypeConfig;
+    type Error = regex::Error;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self {
+            ignore_pattern: Regex::new(&config.ignore_pattern)?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = TypeVisitor::default();
+        visitor.visit_ast(ast);
+
+        visitor
+            .declarations
+            .iter()
+            .filter(|declaration| !visitor.exported.contains(&declaration.name_range))
+            .filter(|declaration| !self.ignore_pattern.is_match(&declaration.name))
+            .filter(|declaration| {
+                // Recursive types, such as `type List = { next: List? }`, don't count as using themselves
+                !visitor.references.iter().any(|(name, position)| {
+                    *name == declaration.name
+                        && (*position < declaration.range.0 || *position > declaration.range.1)
+                })
+            })
+            .map(|declaration| {
+                Diagnostic::new(
+                    "unused_type",
+                    Message::new("unused_type").with("name", &declaration.name),
+                    Label::new(declaration.name_range),
+                )
+            })
+            .collect()
+    }
+}
+
+struct TypeDeclaration {
+    name: String,
+    name_range: (usize, usize),
+    range: (usize, usize),
+}
+
+#[derive(Default)]
+struct TypeVisitor {
+    declarations: Vec<TypeDeclaration>,
+    exported: HashSet<(usize, usize)>,
+    references: Vec<(String, usize)>,
+}
+
+impl Visitor for TypeVisitor {
+    fn visit_exported_type_declaration(&mut self, exported: &types::ExportedTypeDeclaration) {
+        self.exported
+            .insert(range(exported.type_declaration().type_name()));
+    }
+
+    fn visit_type_declaration(&mut self, declaration: &types::TypeDeclaration) {
+        self.declarations.push(TypeDeclaration {
+            name: declaration.type_name().token().to_string(),
+            name_range: range(declaration.type_name()),
+            range: range(declaration),
+        });
+    }
+
+    fn visit_type_info(&mut self, type_info: &types::TypeInfo) {
+        let name = match type_info {
+            types::TypeInfo::Basic(name) | types::TypeInfo::Generic { base: name, .. } => name,
+            _ => return,
+        };
+
+        self.references
+            .push((name.token().to_string(), range::<_, usize>(name).0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_unused_type() {
+        test_lint(
+            UnusedTypeLint::new(UnusedTypeConfig::default()).unwrap(),
+            "unused_type",
+            "unused_type",
+        );
+    }
+}
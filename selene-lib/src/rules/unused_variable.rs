@@ -1,15 +1,25 @@
 use crate::{
-    ast_util::scopes::AssignedValue,
+    ast_util::{
+        range,
+        scopes::{AssignedValue, Reference, ReferenceWrite, ScopeManager, Variable},
+        HasPurity, HasSideEffects, Purity,
+    },
     standard_library::{Field, FieldKind, Observes},
 };
 
 use super::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use full_moon::ast::Ast;
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Symbol, TokenReference, TokenType},
+    visitors::Visitor,
+};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct UnusedVariableConfig {
     allow_unused_self: bool,
@@ -43,6 +53,8 @@ impl Rule for UnusedVariableLint {
 
     const SEVERITY: Severity = Severity::Warning;
     const RULE_TYPE: RuleType = RuleType::Style;
+    const LUACHECK_CODES: &'static [&'static str] =
+        &["211", "212", "213", "231", "232", "233", "311"];
 
     fn new(config: Self::Config) -> Result<Self, Self::Error> {
         Ok(Self {
@@ -51,9 +63,15 @@ impl Rule for UnusedVariableLint {
         })
     }
 
-    fn pass(&self, _: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
+        let mut flow_visitor = FlowVisitor::default();
+        flow_visitor.visit_ast(ast);
+
+        let mut declaration_visitor = DeclarationVisitor::new(ast);
+        declaration_visitor.visit_ast(ast);
+
         for (_, variable) in ast_context
             .scope_manager
             .variables
@@ -150,15 +168,21 @@ impl Rule for UnusedVariableLint {
                         .push("if you don't need it, consider using `.` instead of `:`".to_owned());
                 }
 
+                // These are reported by unused_require instead, since removing them can change behavior
+                if variable.value == Some(AssignedValue::Require) {
+                    continue;
+                }
+
                 let write_only = !analyzed_references.is_empty();
 
-                diagnostics.push(Diagnostic::new_complete(
+                let mut diagnostic = Diagnostic::new_complete(
                     "unused_variable",
-                    if write_only {
-                        format!("{} is assigned a value, but never used", variable.name)
+                    Message::new(if write_only {
+                        "unused_variable.assigned"
                     } else {
-                        format!("{} is defined, but never used", variable.name)
-                    },
+                        "unused_variable.defined"
+                    })
+                    .with("name", &variable.name),
                     Label::new(variable.identifiers[0]),
                     notes,
                     analyzed_references
@@ -171,14 +195,347 @@ impl Rule for UnusedVariableLint {
                             }
                         })
                         .collect(),
-                ));
-            };
+                );
+
+                for fix in self.rename_fixes(variable, &ast_context.scope_manager) {
+                    diagnostic = diagnostic.with_fix(fix);
+                }
+
+                if let Some(fix) =
+                    removal_fix(variable, &ast_context.scope_manager, &declaration_visitor)
+                {
+                    diagnostic = diagnostic.with_fix(fix);
+                }
+
+                diagnostics.push(diagnostic);
+            } else {
+                diagnostics.extend(dead_stores(variable, ast_context, &flow_visitor));
+            }
         }
 
         diagnostics
     }
 }
 
+impl UnusedVariableLint {
+    // Renaming `x` to `_x` everywhere it's written to is always safe, as long as nothing else is
+    // named `_x` and the new name is ignored
+    fn rename_fixes(&self, variable: &Variable, scope_manager: &ScopeManager) -> Vec<Fix> {
+        let new_name = format!("_{}", variable.name);
+
+        if variable.is_self
+            || variable.is_global
+            || !self.ignore_pattern.is_match(&new_name)
+            || scope_manager
+                .variables
+                .iter()
+                .any(|(_, variable)| variable.name == new_name)
+            || scope_manager
+                .references
+                .iter()
+                .any(|(_, reference)| reference.name == new_name)
+        {
+            return Vec::new();
+        }
+
+        let identifiers = variable
+            .identifiers
+            .iter()
+            .copied()
+            .chain(
+                variable
+                    .references
+                    .iter()
+                    .map(|id| scope_manager.references[*id].identifier),
+            )
+            .collect::<BTreeSet<_>>();
+
+        identifiers
+            .into_iter()
+            .map(|(start, _)| Fix::new((start, start), "_".to_owned()))
+            .collect()
+    }
+}
+
+// Removing the declaration doesn't change what the code does when nothing else uses the variable,
+// but it might be about to be used, so it's only a suggestion
+fn removal_fix(
+    variable: &Variable,
+    scope_manager: &ScopeManager,
+    declaration_visitor: &DeclarationVisitor,
+) -> Option<Fix> {
+    let (statement, removal) = declaration_visitor
+        .removable
+        .get(variable.identifiers.first()?)?;
+
+    // Writes anywhere else would be left assigning to a global
+    let only_declared = variable.references.iter().all(|id| {
+        let (start, end) = scope_manager.references[*id].identifier;
+        start >= statement.0 && end <= statement.1
+    });
+
+    if variable.is_global || !only_declared {
+        return None;
+    }
+
+    Some(Fix::removal(*removal).with_applicability(Applicability::Suggestion))
+}
+
+type Range = (usize, usize);
+
+// Finds the statements that declare a single local without side effects, such as
+// `local x = 1` and `local function f() end`, keyed by the range of the name
+struct DeclarationVisitor {
+    /// The range of each statement, and the range to remove along with it, which is the
+    /// rest of the line when nothing else is on it.
+    removable: HashMap<Range, (Range, Range)>,
+    // Where every line after the first starts
+    line_starts: HashSet<usize>,
+}
+
+impl DeclarationVisitor {
+    fn new(ast: &Ast) -> Self {
+        let line_starts = ast
+            .nodes()
+            .tokens()
+            .flat_map(|token| token.leading_trivia().chain(token.trailing_trivia()))
+            .filter(|trivia| trivia.to_string().ends_with('\n'))
+            .map(|trivia| trivia.end_position().bytes())
+            .collect();
+
+        Self {
+            removable: HashMap::new(),
+            line_starts,
+        }
+    }
+
+    fn removal_range(&self, first: &TokenReference, last: &TokenReference) -> Range {
+        let start = first.start_position().unwrap().bytes();
+        let end = last.end_position().unwrap().bytes();
+
+        let indentation_start = match first.leading_trivia().last() {
+            Some(trivia)
+                if trivia
+                    .to_string()
+                    .chars()
+                    .all(|char| char == ' ' || char == '\t') =>
+            {
+                trivia.start_position().bytes()
+            }
+            _ => start,
+        };
+
+        let starts_line = indentation_start == 0 || self.line_starts.contains(&indentation_start);
+
+        let trailing_trivia = last.trailing_trivia().collect::<Vec<_>>();
+        let ends_line = matches!(trailing_trivia.last(), Some(trivia) if trivia.to_string().ends_with('\n'))
+            && trailing_trivia
+                .iter()
+                .all(|trivia| trivia.to_string().trim().is_empty());
+
+        if starts_line && ends_line {
+            (
+                indentation_start,
+                trailing_trivia.last().unwrap().end_position().bytes(),
+            )
+        } else {
+            (start, end)
+        }
+    }
+}
+
+#[cfg(feature = "lua54")]
+fn has_attributes(local_assignment: &ast::LocalAssignment) -> bool {
+    local_assignment
+        .attributes()
+        .any(|attribute| attribute.is_some())
+}
+
+#[cfg(not(feature = "lua54"))]
+fn has_attributes(_: &ast::LocalAssignment) -> bool {
+    false
+}
+
+impl Visitor for DeclarationVisitor {
+    fn visit_block(&mut self, block: &ast::Block) {
+        for (stmt, semicolon) in block.stmts_with_semicolon() {
+            let name = match stmt {
+                // `local x <close> = y` runs code when `x` goes out of scope
+                ast::Stmt::LocalAssignment(local_assignment)
+                    if local_assignment.names().len() == 1
+                        && !has_attributes(local_assignment)
+                        && !local_assignment
+                            .expressions()
+                            .iter()
+                            .any(HasSideEffects::has_side_effects) =>
+                {
+                    local_assignment.names().iter().next().unwrap()
+                }
+
+                ast::Stmt::LocalFunction(local_function) => local_function.name(),
+
+                _ => continue,
+            };
+
+            // Tokens aren't always in order, such as the braces of a table coming before its fields
+            let first = stmt
+                .tokens()
+                .min_by_key(|token| token.start_position())
+                .unwrap();
+            let last = match semicolon {
+                Some(semicolon) => semicolon,
+                None => stmt
+                    .tokens()
+                    .max_by_key(|token| token.end_position())
+                    .unwrap(),
+            };
+
+            self.removable
+                .insert(range(name), (range(stmt), self.removal_range(first, last)));
+        }
+    }
+}
+
+// Collects the parts of the code that can observe or skip over a write,
+// used to make sure a store is really dead before reporting it.
+#[derive(Default)]
+struct FlowVisitor {
+    control_flow_escapes: Vec<Range>,
+    function_bodies: Vec<Range>,
+    // Calls, and anything that could call a metamethod, such as `t.x` or `a + b`
+    runs_code: Vec<Range>,
+    nil_expressions: HashSet<Range>,
+}
+
+impl FlowVisitor {
+    fn any_between(ranges: &[Range], start: usize, end: usize) -> bool {
+        ranges
+            .iter()
+            .any(|(range_start, _)| *range_start > start && *range_start < end)
+    }
+}
+
+impl Visitor for FlowVisitor {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if expression.purity() != Purity::Pure {
+            self.runs_code.push(range(expression));
+        }
+
+        if_chain::if_chain! {
+            if let ast::Expression::Value { value, .. } = expression;
+            if let ast::Value::Symbol(symbol) = &**value;
+            if let TokenType::Symbol { symbol: Symbol::Nil } = symbol.token_type();
+            then {
+                self.nil_expressions.insert(range(expression));
+            }
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        self.function_bodies.push(range(body));
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.runs_code.push(range(call));
+    }
+
+    // Assigning to `t.x` can call `__newindex`
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        if var_expression.purity() != Purity::Pure {
+            self.runs_code.push(range(var_expression));
+        }
+    }
+
+    fn visit_last_stmt(&mut self, last_stmt: &ast::LastStmt) {
+        self.control_flow_escapes.push(range(last_stmt));
+    }
+}
+
+// The point at which a reference actually interacts with the variable.
+// Writes only happen once the expression being assigned has been evaluated.
+fn effective_position(reference: &Reference) -> usize {
+    match (reference.write, reference.write_expr) {
+        (Some(_), Some(write_expr)) => write_expr.1,
+        _ => reference.identifier.0,
+    }
+}
+
+fn dead_stores(
+    variable: &Variable,
+    ast_context: &AstContext,
+    flow_visitor: &FlowVisitor,
+) -> Vec<Diagnostic> {
+    let mut references = variable
+        .references
+        .iter()
+        .map(|id| &ast_context.scope_manager.references[*id])
+        .collect::<Vec<_>>();
+
+    references.sort_by_key(|reference| effective_position(reference));
+
+    // `x.y = 1` is both a read and a write of `x`, but it doesn't store into `x`
+    let read_identifiers = references
+        .iter()
+        .filter(|reference| reference.read)
+        .map(|reference| reference.identifier)
+        .collect::<HashSet<_>>();
+
+    // If a function captures the variable, then any function call could read it
+    let definition = variable.identifiers[0];
+    let captured = references.iter().any(|reference| {
+        flow_visitor.function_bodies.iter().any(|body| {
+            reference.identifier.0 > body.0
+                && reference.identifier.0 < body.1
+                && !(definition.0 > body.0 && definition.0 < body.1)
+        })
+    });
+
+    let mut diagnostics = Vec::new();
+    let mut pending_store: Option<&Reference> = None;
+
+    for reference in references {
+        let is_plain_store = reference.write == Some(ReferenceWrite::Assign)
+            && reference.write_expr.is_some()
+            && !read_identifiers.contains(&reference.identifier);
+
+        if !is_plain_store {
+            pending_store = None;
+            continue;
+        }
+
+        if let Some(store) = pending_store {
+            let (start, end) = (effective_position(store), effective_position(reference));
+
+            if store.scope_id == reference.scope_id
+                && !FlowVisitor::any_between(&flow_visitor.control_flow_escapes, start, end)
+                && !(captured && FlowVisitor::any_between(&flow_visitor.runs_code, start, end))
+            {
+                diagnostics.push(Diagnostic::new_complete(
+                    "unused_variable",
+                    Message::new("unused_variable.overwritten").with("name", &variable.name),
+                    Label::new((store.identifier.0, start)),
+                    Vec::new(),
+                    vec![Label::new_with_message(
+                        reference.identifier,
+                        "overwritten here".to_owned(),
+                    )],
+                ));
+            }
+        }
+
+        pending_store = if flow_visitor
+            .nil_expressions
+            .contains(&reference.write_expr.unwrap())
+        {
+            None
+        } else {
+            Some(reference)
+        };
+    }
+
+    diagnostics
+}
+
 #[cfg(test)]
 mod tests {
     use super::{super::test_util::test_lint, *};
@@ -201,6 +558,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dead_stores() {
+        test_lint(
+            UnusedVariableLint::new(UnusedVariableConfig::default()).unwrap(),
+            "unused_variable",
+            "dead_stores",
+        );
+    }
+
     #[test]
     fn test_edge_cases() {
         test_lint(
@@ -210,6 +576,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fixes() {
+        test_lint(
+            UnusedVariableLint::new(UnusedVariableConfig::default()).unwrap(),
+            "unused_variable",
+            "fixes",
+        );
+    }
+
     #[test]
     fn test_explicit_self() {
         test_lint(
@@ -340,6 +715,16 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "roblox")]
+    #[test]
+    fn test_type_annotations() {
+        test_lint(
+            UnusedVariableLint::new(UnusedVariableConfig::default()).unwrap(),
+            "unused_variable",
+            "type_annotations",
+        );
+    }
+
     #[cfg(feature = "roblox")]
     #[test]
     fn test_types() {
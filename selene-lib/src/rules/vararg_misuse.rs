@@ -0,0 +1,257 @@
+use super::*;
+use crate::{
+    ast_util::{is_vararg, name_paths::name_path_from_prefix_suffix, scopes::ScopeManager},
+    lua_version::LuaVersion,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Symbol, TokenReference, TokenType},
+    visitors::Visitor,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VarargMisuseConfig {
+    lua_version: LuaVersion,
+}
+
+pub struct VarargMisuseLint {
+    config: VarargMisuseConfig,
+}
+
+impl Rule for VarargMisuseLint {
+    type Config = VarargMisuseConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const RULE_TYPE: RuleType = RuleType::Correctness;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(VarargMisuseLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = VarargMisuseVisitor {
+            lua_version: self.config.lua_version,
+            scope_manager: &ast_context.scope_manager,
+            // The main chunk is always a vararg function
+            function_is_vararg: vec![true],
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct VarargMisuseVisitor<'a> {
+    lua_version: LuaVersion,
+    scope_manager: &'a ScopeManager,
+    function_is_vararg: Vec<bool>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// `{...}`, and nothing else
+fn is_vararg_table(expression: &ast::Expression) -> bool {
+    let table = match expression {
+        ast::Expression::Value { value, .. } => match &**value {
+            ast::Value::TableConstructor(table) => table,
+            _ => return false,
+        },
+        ast::Expression::Parentheses { expression, .. } => return is_vararg_table(expression),
+        _ => return false,
+    };
+
+    let mut fields = table.fields().iter();
+
+    match (fields.next(), fields.next()) {
+        (Some(ast::Field::NoKey(expression)), None) => is_vararg(expression),
+        _ => false,
+    }
+}
+
+impl VarargMisuseVisitor<'_> {
+    fn is_global(&self, node: &impl Node) -> bool {
+        let position = match node.start_position() {
+            Some(position) => position,
+            None => return false,
+        };
+
+        match self.scope_manager.reference_at_byte(position.bytes()) {
+            Some(reference) => reference.resolved.is_none(),
+            None => true,
+        }
+    }
+
+    // `table.pack(...)`, using the global `table`
+    fn is_table_pack_vararg(&self, expression: &ast::Expression) -> bool {
+        let call = match expression {
+            ast::Expression::Value { value, .. } => match &**value {
+                ast::Value::FunctionCall(call) => call,
+                _ => return false,
+            },
+            _ => return false,
+        };
+
+        match name_path_from_prefix_suffix(call.prefix(), call.suffixes()) {
+            Some(name_path) if name_path == ["table", "pack"] => {}
+            _ => return false,
+        }
+
+        let arguments = match call.suffixes().nth(1) {
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                arguments,
+                ..
+            }))) => arguments,
+            _ => return false,
+        };
+
+        arguments.len() == 1 && is_vararg(arguments.iter().next().unwrap()) && self.is_global(call)
+    }
+
+    fn check_arg(&mut self, token: &TokenReference) {
+        if token.token().to_string() != "arg"
+            || self.lua_version.has_implicit_arg()
+            // `arg` in the main chunk is the command line arguments
+            || self.function_is_vararg.len() == 1
+            || !self.function_is_vararg.last().copied().unwrap_or_default()
+            || !self.is_global(token)
+        {
+            return;
+        }
+
+        self.diagnostics.push(
+            Diagnostic::new_complete(
+                "vararg_misuse",
+                Message::new("vararg_misuse.arg"),
+                Label::from_node(token, None),
+                Vec::new(),
+                Vec::new(),
+            )
+            .with_suggestion(if self.lua_version.has_table_pack() {
+                "`local arg = table.pack(...)`"
+            } else {
+                "`local arg = {...}`"
+            }),
+        );
+    }
+}
+
+impl Visitor for VarargMisuseVisitor<'_> {
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        self.function_is_vararg.push(
+            body.parameters()
+                .iter()
+                .any(|parameter| matches!(parameter, ast::Parameter::Ellipse(_))),
+        );
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_is_vararg.pop();
+    }
+
+    fn visit_value(&mut self, value: &ast::Value) {
+        let token = match value {
+            ast::Value::Symbol(token) => token,
+            _ => return,
+        };
+
+        if !matches!(
+            token.token_type(),
+            TokenType::Symbol {
+                symbol: Symbol::Ellipse
+            }
+        ) || self.function_is_vararg.last().copied().unwrap_or(true)
+        {
+            return;
+        }
+
+        self.diagnostics.push(
+            Diagnostic::new_complete(
+                "vararg_misuse",
+                Message::new("vararg_misuse.outside_vararg_function"),
+                Label::from_node(token, None),
+                Vec::new(),
+                Vec::new(),
+            )
+            .with_help("add `...` to the end of this function's parameters"),
+        );
+    }
+
+    fn visit_prefix(&mut self, prefix: &ast::Prefix) {
+        if let ast::Prefix::Name(name) = prefix {
+            self.check_arg(name);
+        }
+    }
+
+    fn visit_var(&mut self, var: &ast::Var) {
+        if let ast::Var::Name(name) = var {
+            self.check_arg(name);
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let operand = match expression {
+            ast::Expression::UnaryOperator {
+                unop: ast::UnOp::Hash(_),
+                expression,
+            } => expression,
+            _ => return,
+        };
+
+        if is_vararg_table(operand) {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "vararg_misuse",
+                Message::new("vararg_misuse.length_of_table"),
+                Label::from_node(expression, None),
+                vec![
+                    "note: the length of a table with nils in it can be any border, such as the position of the first nil".to_owned(),
+                ],
+                Vec::new(),
+            )
+            .with_suggestion("`select(\"#\", ...)`"));
+        } else if self.is_table_pack_vararg(operand) {
+            self.diagnostics.push(
+                Diagnostic::new_complete(
+                    "vararg_misuse",
+                    Message::new("vararg_misuse.length_of_pack"),
+                    Label::from_node(expression, None),
+                    Vec::new(),
+                    Vec::new(),
+                )
+                .with_suggestion("`table.pack(...).n` or `select(\"#\", ...)`"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_vararg_misuse() {
+        test_lint(
+            VarargMisuseLint::new(VarargMisuseConfig::default()).unwrap(),
+            "vararg_misuse",
+            "vararg_misuse",
+        );
+    }
+
+    #[test]
+    fn test_lua52() {
+        test_lint(
+            VarargMisuseLint::new(VarargMisuseConfig {
+                lua_version: LuaVersion::Lua52,
+            })
+            .unwrap(),
+            "vararg_misuse",
+            "lua52",
+        );
+    }
+}
@@ -0,0 +1,139 @@
+// Where the code being checked comes from. selene-lib never reads files itself, so anything that
+// can look up the contents of a path can be checked, such as an editor's unsaved buffers.
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// Gives the contents of files by their path. The paths are the same ones given to lints, such
+/// as through `Checker::test_on_source`, and aren't required to exist on the file system.
+pub trait SourceProvider {
+    /// Returns the contents of the file, or an error of the `NotFound` kind if there is no such
+    /// file.
+    fn read(&self, path: &Path) -> io::Result<String>;
+
+    /// Returns the contents of the file as they are stored, for files that might not be UTF-8.
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read(path).map(String::into_bytes)
+    }
+}
+
+impl<P: SourceProvider + ?Sized> SourceProvider for &P {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        (**self).read(path)
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read_bytes(path)
+    }
+}
+
+impl SourceProvider for HashMap<PathBuf, String> {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        self.get(path).cloned().ok_or_else(|| not_found(path))
+    }
+}
+
+impl SourceProvider for BTreeMap<PathBuf, String> {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        self.get(path).cloned().ok_or_else(|| not_found(path))
+    }
+}
+
+/// Reads files from `overlay` when it has them, and from `base` otherwise, such as an editor's
+/// open buffers over the files saved on disk.
+pub struct Overlay<O, B> {
+    pub overlay: O,
+    pub base: B,
+}
+
+impl<O: SourceProvider, B: SourceProvider> SourceProvider for Overlay<O, B> {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        match self.overlay.read(path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => self.base.read(path),
+            result => result,
+        }
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} has no source", path.display()),
+    )
+}
+
+#[derive(Debug)]
+pub enum SourceError {
+    Read(io::Error),
+    Parse(full_moon::Error),
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SourceError::Read(error) => write!(formatter, "couldn't read source: {error}"),
+            SourceError::Parse(error) => write!(formatter, "couldn't parse: {error}"),
+        }
+    }
+}
+
+impl Error for SourceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{standard_library::StandardLibrary, Checker, CheckerConfig};
+
+    fn sources(files: &[(&str, &str)]) -> HashMap<PathBuf, String> {
+        files
+            .iter()
+            .map(|(path, contents)| (PathBuf::from(path), contents.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_overlay() {
+        let sources = Overlay {
+            overlay: sources(&[("a.lua", "unsaved")]),
+            base: sources(&[("a.lua", "saved"), ("b.lua", "saved")]),
+        };
+
+        assert_eq!(sources.read(Path::new("a.lua")).unwrap(), "unsaved");
+        assert_eq!(sources.read(Path::new("b.lua")).unwrap(), "saved");
+        assert_eq!(
+            sources.read(Path::new("c.lua")).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_checking_sources() {
+        let checker = Checker::<serde_json::Value>::new(
+            CheckerConfig::default(),
+            StandardLibrary::from_name("lua51").unwrap(),
+        )
+        .unwrap();
+
+        let sources = sources(&[("a.lua", "local x = 1"), ("broken.lua", "local = 1")]);
+
+        let diagnostics = checker
+            .test_on_source(&sources, Path::new("a.lua"))
+            .unwrap();
+        assert!(diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.diagnostic.code == "unused_variable"));
+
+        assert!(matches!(
+            checker.test_on_source(&sources, Path::new("broken.lua")),
+            Err(SourceError::Parse(_))
+        ));
+
+        assert!(matches!(
+            checker.test_on_source(&sources, Path::new("missing.lua")),
+            Err(SourceError::Read(_))
+        ));
+    }
+}
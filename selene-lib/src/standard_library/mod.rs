@@ -3,8 +3,12 @@ mod v1_upgrade;
 
 use std::{
     borrow::{Borrow, Cow},
+    cmp::Ordering,
     collections::{BTreeMap, HashMap},
-    fmt, io,
+    fmt,
+    hash::{Hash, Hasher},
+    io,
+    sync::Arc,
 };
 
 use once_cell::sync::OnceCell;
@@ -15,6 +19,8 @@ use serde::{
     Deserialize, Serialize,
 };
 
+static NO_GLOBALS: BTreeMap<String, Field> = BTreeMap::new();
+
 lazy_static::lazy_static! {
     static ref ANY_TABLE: BTreeMap<String, Field> = {
         let mut map = BTreeMap::new();
@@ -36,13 +42,12 @@ struct GlobalTreeNode {
 }
 
 impl GlobalTreeNode {
-    fn field<'a>(&self, names_to_fields: &'a BTreeMap<String, Field>) -> &'a Field {
+    fn field<'a>(&self, get_field: impl FnOnce(&str) -> Option<&'a Field>) -> &'a Field {
         static READ_ONLY_FIELD: Field =
             Field::from_field_kind(FieldKind::Property(PropertyWritability::ReadOnly));
 
         match &self.field {
-            GlobalTreeField::Key(key) => names_to_fields
-                .get(key)
+            GlobalTreeField::Key(key) => get_field(key)
                 .unwrap_or_else(|| panic!("couldn't find {key} inside names_to_fields")),
 
             GlobalTreeField::ReadOnlyField => &READ_ONLY_FIELD,
@@ -59,12 +64,12 @@ struct GlobalTreeCache {
 }
 
 #[profiling::function]
-fn extract_into_tree(
-    names_to_fields: &BTreeMap<String, Field>,
+fn extract_into_tree<'a>(
+    names: impl Iterator<Item = &'a String>,
 ) -> BTreeMap<String, GlobalTreeNode> {
     let mut fields: BTreeMap<String, GlobalTreeNode> = BTreeMap::new();
 
-    for name in names_to_fields.keys() {
+    for name in names {
         let mut current = &mut fields;
 
         let mut split = name.split('.').collect::<Vec<_>>();
@@ -129,10 +134,69 @@ pub struct StandardLibrary {
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub roblox_classes: BTreeMap<String, RobloxClass>,
 
+    #[serde(skip)]
+    sources: GlobalSources,
+
+    /// Globals shared with every copy of this library, which `globals` is layered on top of.
+    /// Removed globals in `globals` hide the ones in here.
+    #[serde(skip)]
+    shared_globals: Option<Arc<BTreeMap<String, Field>>>,
+
     #[serde(skip)]
     global_tree_cache: OnceCell<GlobalTreeCache>,
 }
 
+// Where globals came from isn't part of what a library defines, so it's left out of comparisons
+#[derive(Clone, Debug, Default)]
+struct GlobalSources {
+    /// The library that globals without an entry in `globals` come from, which is set by
+    /// `set_source_name` rather than being read from the file.
+    default: Option<String>,
+
+    /// Where the globals that came from a base library, or that replace its definition, were defined.
+    globals: BTreeMap<String, GlobalSource>,
+}
+
+impl PartialEq for GlobalSources {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for GlobalSources {}
+
+/// Which standard library a global came from, once every library it's based on has been merged
+/// into it with `StandardLibrary::extend`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlobalSource {
+    /// The library whose definition is used, or that removed the global.
+    pub library: Option<String>,
+
+    /// The libraries it's based on that also define it, whose definitions are replaced.
+    pub overrides: Vec<String>,
+
+    /// Whether `library` removed the global, rather than defining it.
+    pub removed: bool,
+}
+
+impl GlobalSource {
+    fn from_library(library: &Option<String>) -> Self {
+        GlobalSource {
+            library: library.clone(),
+            ..GlobalSource::default()
+        }
+    }
+
+    // Adds the libraries in `replaced` to the ones this overrides
+    fn replace(&mut self, replaced: GlobalSource) {
+        for library in replaced.library.into_iter().chain(replaced.overrides) {
+            if self.library.as_ref() != Some(&library) && !self.overrides.contains(&library) {
+                self.overrides.push(library);
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum StandardLibraryError {
     DeserializeTomlError(toml::de::Error),
@@ -189,6 +253,7 @@ impl StandardLibrary {
 
             let mut hasher = DefaultHasher::new();
             self.globals.hash(&mut hasher);
+            self.shared_globals.hash(&mut hasher);
             hasher.finish()
         };
 
@@ -211,7 +276,7 @@ impl StandardLibrary {
             .get_or_init(|| {
                 profiling::scope!("global_tree_cache: create cache");
                 GlobalTreeCache {
-                    cache: extract_into_tree(&self.globals),
+                    cache: extract_into_tree(self.iter_globals().map(|(name, _)| name)),
 
                     #[cfg(debug_assertions)]
                     last_globals_hash: hash,
@@ -220,6 +285,99 @@ impl StandardLibrary {
             .cache
     }
 
+    fn shared_globals(&self) -> &BTreeMap<String, Field> {
+        self.shared_globals.as_deref().unwrap_or(&NO_GLOBALS)
+    }
+
+    /// Returns the global with exactly this name, whether it's in `globals` or shared with the
+    /// copies of this library.
+    pub fn global(&self, name: &str) -> Option<&Field> {
+        match self.globals.get(name) {
+            Some(field) => Some(field).filter(|field| field.field_kind != FieldKind::Removed),
+            None => self.shared_globals().get(name),
+        }
+    }
+
+    /// Every global this library defines, sorted by name, including the ones shared with the
+    /// copies of this library.
+    pub fn iter_globals(&self) -> impl Iterator<Item = (&String, &Field)> {
+        let mut shared = self.shared_globals().iter().peekable();
+        let mut layered = self.globals.iter().peekable();
+
+        std::iter::from_fn(move || loop {
+            let next = match (shared.peek(), layered.peek()) {
+                (Some((shared_name, _)), Some((layered_name, _))) => {
+                    match shared_name.cmp(layered_name) {
+                        Ordering::Less => shared.next(),
+                        Ordering::Equal => {
+                            shared.next();
+                            layered.next()
+                        }
+                        Ordering::Greater => layered.next(),
+                    }
+                }
+
+                (Some(_), None) => shared.next(),
+                (None, _) => layered.next(),
+            };
+
+            match next {
+                Some((_, field)) if field.field_kind == FieldKind::Removed => continue,
+                next => return next,
+            }
+        })
+    }
+
+    fn globals_len(&self) -> usize {
+        self.globals.len() + self.shared_globals().len()
+    }
+
+    /// Moves the globals into a map that's shared by every copy of this library, so that copies,
+    /// like the ones `with_globals` makes for every file that declares globals, don't copy them.
+    /// Anything defined in `globals` afterwards is layered on top, so `global` and `iter_globals`
+    /// should be used to read them.
+    pub fn share_globals(&mut self) {
+        if self.shared_globals.is_some() {
+            return;
+        }
+
+        self.globals
+            .retain(|_, field| field.field_kind != FieldKind::Removed);
+        self.shared_globals = Some(Arc::new(std::mem::take(&mut self.globals)));
+        self.global_tree_cache = OnceCell::new();
+    }
+
+    // Takes every global out as one map, only copying the shared ones if another copy uses them
+    fn take_globals(&mut self) -> BTreeMap<String, Field> {
+        let layered = std::mem::take(&mut self.globals);
+
+        let mut globals = match self.shared_globals.take() {
+            Some(shared) => Arc::try_unwrap(shared).unwrap_or_else(|shared| (*shared).clone()),
+            None => return layered,
+        };
+
+        for (name, field) in layered {
+            if field.field_kind == FieldKind::Removed {
+                globals.remove(&name);
+            } else {
+                globals.insert(name, field);
+            }
+        }
+
+        globals
+    }
+
+    fn field_of<'a>(
+        &'a self,
+        strukt: Option<&'a BTreeMap<String, Field>>,
+        name: &str,
+    ) -> Option<&'a Field> {
+        match strukt {
+            Some(strukt) => strukt.get(name),
+            None => self.global(name),
+        }
+    }
+
     /// Find a global in the standard library through its name path.
     /// Handles all of the following cases:
     /// 1. "x.y" where `x.y` is explicitly defined
@@ -232,7 +390,7 @@ impl StandardLibrary {
     pub fn find_global<S: Borrow<str>>(&self, names: &[S]) -> Option<&Field> {
         assert!(!names.is_empty());
 
-        if let Some(explicit_global) = self.globals.get(&names.join(".")) {
+        if let Some(explicit_global) = self.global(&names.join(".")) {
             profiling::scope!("find_global: explicit global");
             return Some(explicit_global);
         }
@@ -241,13 +399,14 @@ impl StandardLibrary {
         let mut last_extracted_struct;
 
         let mut current = self.global_tree_cache();
-        let mut current_names_to_fields = &self.globals;
+        // The struct whose fields are being looked through, rather than the globals
+        let mut current_struct: Option<&BTreeMap<String, Field>> = None;
 
         profiling::scope!("find_global: look through global tree cache");
 
         for name in names.iter().take(names.len() - 1) {
             let found_segment = current.get(name.borrow()).or_else(|| current.get("*"))?;
-            let field = found_segment.field(current_names_to_fields);
+            let field = found_segment.field(|name| self.field_of(current_struct, name));
 
             match &field.field_kind {
                 FieldKind::Any => {
@@ -260,8 +419,8 @@ impl StandardLibrary {
                         .get(struct_name)
                         .unwrap_or_else(|| panic!("struct `{struct_name}` not found"));
 
-                    last_extracted_struct = extract_into_tree(strukt);
-                    current_names_to_fields = strukt;
+                    last_extracted_struct = extract_into_tree(strukt.keys());
+                    current_struct = Some(strukt);
                     current = &last_extracted_struct;
                 }
 
@@ -274,7 +433,7 @@ impl StandardLibrary {
         current
             .get(names.last().unwrap().borrow())
             .or_else(|| current.get("*"))
-            .map(|node| node.field(current_names_to_fields))
+            .map(|node| node.field(|name| self.field_of(current_struct, name)))
     }
 
     pub fn global_has_fields(&self, name: &str) -> bool {
@@ -282,38 +441,222 @@ impl StandardLibrary {
         self.global_tree_cache().contains_key(name)
     }
 
-    pub fn extend(&mut self, other: StandardLibrary) {
-        self.structs.extend(other.structs);
+    /// Sets the name of the standard library, such as `roblox`, that its globals are said to come
+    /// from in `global_source`. This should be done before it's extended with the ones it's based on.
+    pub fn set_source_name(&mut self, name: &str) {
+        self.sources.default = Some(name.to_owned());
+    }
 
-        // let mut globals = other.globals.to_owned();
-        let mut globals: BTreeMap<String, Field> = other
-            .globals
-            .into_iter()
-            .filter(|(other_field_name, other_field)| {
-                other_field.field_kind != FieldKind::Removed
-                    && !matches!(
-                        self.globals.get(other_field_name),
-                        Some(Field {
-                            field_kind: FieldKind::Removed,
-                            ..
-                        })
-                    )
-            })
-            .collect();
+    fn source_of(&self, name: &str) -> GlobalSource {
+        match self.sources.globals.get(name) {
+            Some(source) => source.clone(),
+            None => GlobalSource::from_library(&self.sources.default),
+        }
+    }
 
-        globals.extend(
-            std::mem::take(&mut self.globals)
-                .into_iter()
-                .filter_map(|(key, value)| {
-                    if value.field_kind == FieldKind::Removed {
-                        None
+    /// Returns where the global, or the closest of its parents that's defined, came from.
+    /// Globals that were removed are included.
+    pub fn global_source<S: Borrow<str>>(&self, names: &[S]) -> Option<GlobalSource> {
+        for bound in (1..=names.len()).rev() {
+            let name = names[0..bound].join(".");
+
+            if self.global(&name).is_some() || self.sources.globals.contains_key(&name) {
+                return Some(self.source_of(&name)).filter(|source| source.library.is_some());
+            }
+        }
+
+        None
+    }
+
+    /// Returns the library that removed the global, when a library it's based on defines it.
+    pub fn removed_by<S: Borrow<str>>(&self, names: &[S]) -> Option<String> {
+        match self.global_source(names) {
+            Some(source) if source.removed && !source.overrides.is_empty() => source.library,
+            _ => None,
+        }
+    }
+
+    /// Notes for diagnostics about the global that say which library it came from, only when
+    /// that's because a library replaced or removed what one it's based on defines.
+    pub fn global_source_notes<S: Borrow<str>>(&self, names: &[S]) -> Vec<String> {
+        let source = match self.global_source(names) {
+            Some(source) if !source.overrides.is_empty() => source,
+            _ => return Vec::new(),
+        };
+
+        let library = source.library.unwrap_or_default();
+        let overrides = source
+            .overrides
+            .iter()
+            .map(|library| format!("`{library}`"))
+            .collect::<Vec<_>>();
+        let overrides = format!(
+            "the {} standard librar{}",
+            crate::text::english_list(&overrides.iter().map(String::as_str).collect::<Vec<_>>()),
+            crate::text::plural(overrides.len(), "y", "ies"),
+        );
+
+        if source.removed {
+            vec![format!(
+                "`{}` is defined by {overrides}, which `{library}` is based on",
+                names.join("."),
+            )]
+        } else {
+            vec![format!(
+                "`{}` is defined by the `{library}` standard library (overriding {overrides})",
+                names.join("."),
+            )]
+        }
+    }
+
+    /// Returns a copy of this library with `globals` defined, replacing any existing definitions.
+    /// Only the globals that aren't shared are copied, see `share_globals`.
+    pub fn with_globals(&self, globals: impl IntoIterator<Item = (String, Field)>) -> Self {
+        let mut library = self.clone();
+        library.globals.extend(globals);
+        library.global_tree_cache = OnceCell::new();
+        library
+    }
+
+    /// Adds everything from `other` that isn't already defined or removed by this library.
+    pub fn extend(&mut self, mut other: StandardLibrary) {
+        self.structs.extend(std::mem::take(&mut other.structs));
+
+        // Standard libraries like Roblox have tens of thousands of globals, so rather than
+        // rebuilding the map, the smaller one is merged into the larger one. Globals that the
+        // larger one shares stay shared, rather than being copied.
+        if other.globals_len() > self.globals_len() {
+            let shared = other.shared_globals.take();
+            let shared_globals = shared.as_deref().unwrap_or(&NO_GLOBALS);
+
+            let mut globals = std::mem::take(&mut other.globals);
+            globals.retain(|name, field| {
+                field.field_kind != FieldKind::Removed || shared_globals.contains_key(name)
+            });
+
+            let is_defined = |globals: &BTreeMap<String, Field>, name: &str| match globals.get(name)
+            {
+                Some(field) => field.field_kind != FieldKind::Removed,
+                None => shared_globals.contains_key(name),
+            };
+
+            // The larger library's sources are kept, so ours have to be written down instead
+            let mut global_sources = other.sources.globals;
+            let default_source = other.sources.default;
+
+            for (name, field) in self.take_globals() {
+                let mut source = self.source_of(&name);
+                let replaced = is_defined(&globals, &name).then(|| {
+                    global_sources
+                        .remove(&name)
+                        .unwrap_or_else(|| GlobalSource::from_library(&default_source))
+                });
+
+                if field.field_kind == FieldKind::Removed {
+                    // Shared globals can't be taken out, so they're hidden instead
+                    if shared_globals.contains_key(&name) {
+                        globals.insert(name.clone(), field);
                     } else {
-                        Some((key, value))
+                        globals.remove(&name);
                     }
-                }),
-        );
 
-        self.globals = globals;
+                    if let Some(replaced) = replaced {
+                        source.removed = true;
+                        source.replace(replaced);
+
+                        if !source.overrides.is_empty() {
+                            global_sources.insert(name, source);
+                        }
+                    }
+
+                    continue;
+                }
+
+                if let Some(replaced) = replaced {
+                    source.replace(replaced);
+                }
+
+                if source.library != default_source || !source.overrides.is_empty() {
+                    global_sources.insert(name.clone(), source);
+                }
+
+                globals.insert(name, field);
+            }
+
+            for (name, source) in std::mem::take(&mut self.sources.globals) {
+                if source.removed && !is_defined(&globals, &name) {
+                    global_sources.entry(name).or_insert(source);
+                }
+            }
+
+            self.globals = globals;
+            self.shared_globals = shared;
+            self.sources = GlobalSources {
+                default: default_source,
+                globals: global_sources,
+            };
+        } else {
+            let shared_globals = self.shared_globals.as_deref().unwrap_or(&NO_GLOBALS);
+
+            for (name, field) in other.take_globals() {
+                if field.field_kind == FieldKind::Removed {
+                    continue;
+                }
+
+                let recorded_source = other.sources.globals.get(&name);
+                let their_source = || match recorded_source {
+                    Some(source) => source.clone(),
+                    None => GlobalSource::from_library(&other.sources.default),
+                };
+
+                match self
+                    .globals
+                    .get(&name)
+                    .or_else(|| shared_globals.get(&name))
+                {
+                    Some(ours) => {
+                        let mut source = self.source_of(&name);
+                        source.removed = ours.field_kind == FieldKind::Removed;
+                        source.replace(their_source());
+
+                        if source.library != self.sources.default || !source.overrides.is_empty() {
+                            self.sources.globals.insert(name, source);
+                        }
+                    }
+
+                    None => {
+                        // Checked without cloning, since most globals come from the same library
+                        let is_different = match recorded_source {
+                            Some(source) => {
+                                source.library != self.sources.default
+                                    || !source.overrides.is_empty()
+                            }
+
+                            None => other.sources.default != self.sources.default,
+                        };
+
+                        if is_different {
+                            self.sources.globals.insert(name.clone(), their_source());
+                        }
+
+                        self.globals.insert(name, field);
+                    }
+                }
+            }
+
+            for (name, source) in other.sources.globals {
+                if source.removed
+                    && !self.globals.contains_key(&name)
+                    && !shared_globals.contains_key(&name)
+                {
+                    self.sources.globals.entry(name).or_insert(source);
+                }
+            }
+
+            self.globals.retain(|name, field| {
+                field.field_kind != FieldKind::Removed || shared_globals.contains_key(name)
+            });
+        }
     }
 
     #[cfg(feature = "roblox")]
@@ -330,6 +673,8 @@ impl StandardLibrary {
             panic!("default standard library '{name}' failed deserialization: {error}")
         });
 
+        std.set_source_name(name);
+
         if let Some(base_name) = &std.base {
             let base = StandardLibrary::from_name(base_name);
 
@@ -380,6 +725,7 @@ names! {
     "lua52" => "../../default_std/lua52.yml",
     "lua53" => "../../default_std/lua53.yml",
     "luau" => "../../default_std/luau.yml",
+    "testing" => "../../default_std/testing.yml",
 }
 
 fn is_default<T>(value: &T) -> bool
@@ -401,6 +747,18 @@ pub struct FunctionBehavior {
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
     pub must_use: bool,
+
+    /// Other arguments the function can be called with. A call is correct if it matches
+    /// `arguments` or any of these.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overloads: Vec<Overload>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Overload {
+    #[serde(rename = "args")]
+    pub arguments: Vec<Argument>,
 }
 
 fn is_false(value: &bool) -> bool {
@@ -408,6 +766,7 @@ fn is_false(value: &bool) -> bool {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "FieldSerde")]
 pub struct Field {
     #[serde(flatten)]
     pub field_kind: FieldKind,
@@ -415,6 +774,19 @@ pub struct Field {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Deprecated>,
+
+    /// Why the field, and everything inside of it, can't be used where the code runs, such as
+    /// `os` in a sandbox. Read by the restricted lint.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restricted: Option<String>,
+
+    /// The type of the value of a property, such as `number` for `math.huge`, which tells what
+    /// can be done with it.
+    #[serde(default)]
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property_type: Option<PropertyType>,
 }
 
 impl Field {
@@ -422,10 +794,58 @@ impl Field {
         Self {
             field_kind,
             deprecated: None,
+            restricted: None,
+            property_type: None,
         }
     }
 }
 
+// Fields that are restricted don't need a kind, so that `os: { restricted: "..." }` can restrict
+// all of `os` without redefining it.
+#[derive(Deserialize)]
+struct FieldSerde {
+    #[serde(flatten)]
+    field_kind: Option<FieldKind>,
+
+    #[serde(default)]
+    deprecated: Option<Deprecated>,
+
+    #[serde(default)]
+    restricted: Option<String>,
+
+    #[serde(default)]
+    #[serde(rename = "type")]
+    property_type: Option<PropertyType>,
+}
+
+impl TryFrom<FieldSerde> for Field {
+    type Error = String;
+
+    fn try_from(field: FieldSerde) -> Result<Self, Self::Error> {
+        let field_kind = match (field.field_kind, &field.restricted) {
+            (Some(field_kind), _) => field_kind,
+            (None, Some(_)) => FieldKind::Property(PropertyWritability::ReadOnly),
+            (None, None) => {
+                return Err(
+                    "expected a field, such as `any: true`, `args`, `property`, `struct`, or `removed: true`"
+                        .to_owned(),
+                )
+            }
+        };
+
+        if field.property_type.is_some() && !matches!(field_kind, FieldKind::Property(_)) {
+            return Err("`type` can only be given to properties".to_owned());
+        }
+
+        Ok(Field {
+            field_kind,
+            deprecated: field.deprecated,
+            restricted: field.restricted,
+            property_type: field.property_type,
+        })
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Deprecated {
@@ -586,8 +1006,45 @@ pub enum PropertyWritability {
     FullWrite,
 }
 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PropertyType {
+    Bool,
+    Function,
+    Nil,
+    Number,
+    String,
+    Table,
+}
+
+impl PropertyType {
+    /// Whether indexing a value of this type errors. Strings can be indexed, since they use the
+    /// string library.
+    pub fn is_indexable(self) -> bool {
+        matches!(self, PropertyType::String | PropertyType::Table)
+    }
+}
+
+impl fmt::Display for PropertyType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropertyType::Bool => write!(formatter, "bool"),
+            PropertyType::Function => write!(formatter, "function"),
+            PropertyType::Nil => write!(formatter, "nil"),
+            PropertyType::Number => write!(formatter, "number"),
+            PropertyType::String => write!(formatter, "string"),
+            PropertyType::Table => write!(formatter, "table"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Argument {
+    /// The name of the parameter, shown in signatures and used by diagnostics about the argument.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
     #[serde(default)]
     #[serde(skip_serializing_if = "is_default")]
     pub required: Required,
@@ -598,6 +1055,26 @@ pub struct Argument {
     #[serde(default)]
     #[serde(skip_serializing_if = "is_default")]
     pub observes: Observes,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<NumberRange>,
+
+    /// The most bytes a constant string argument can be, such as for keys that are sent over the
+    /// network.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+impl Argument {
+    /// How diagnostics refer to the argument at `index`, such as "argument #2 `pattern`".
+    /// Returns None if the argument has no name.
+    pub fn description(&self, index: usize) -> Option<String> {
+        self.name
+            .as_ref()
+            .map(|name| format!("argument #{} `{name}`", index + 1))
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -793,11 +1270,61 @@ impl Default for Observes {
     }
 }
 
+/// The numbers a constant argument is allowed to be, such as 0 to 1 for `Color3.new`.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct NumberRange {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+
+    /// Shown alongside the error, such as to suggest a different function.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+impl NumberRange {
+    pub fn contains(&self, number: f64) -> bool {
+        self.min.is_none_or(|min| number >= min) && self.max.is_none_or(|max| number <= max)
+    }
+}
+
+impl fmt::Display for NumberRange {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => write!(formatter, "from {min} to {max}"),
+            (Some(min), None) => write!(formatter, "of at least {min}"),
+            (None, Some(max)) => write!(formatter, "of at most {max}"),
+            (None, None) => write!(formatter, "of any size"),
+        }
+    }
+}
+
+// Standard libraries are hashed to check they don't change, which needs the bits of the floats
+impl Hash for NumberRange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.min.map(f64::to_bits).hash(state);
+        self.max.map(f64::to_bits).hash(state);
+        self.help.hash(state);
+    }
+}
+
+impl Eq for NumberRange {}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RobloxClass {
     pub superclass: String,
     pub events: Vec<String>,
     pub properties: Vec<String>,
+
+    /// Properties that have to be given when creating an element of the class with Roact.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub required_properties: Vec<String>,
 }
 
 impl RobloxClass {
@@ -828,6 +1355,23 @@ impl RobloxClass {
             false
         }
     }
+
+    pub fn required_properties<'a>(
+        &'a self,
+        roblox_classes: &'a BTreeMap<String, RobloxClass>,
+    ) -> Vec<&'a str> {
+        let mut required_properties = self
+            .required_properties
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        if let Some(superclass) = roblox_classes.get(&self.superclass) {
+            required_properties.extend(superclass.required_properties(roblox_classes));
+        }
+
+        required_properties
+    }
 }
 
 #[cfg(test)]
@@ -844,6 +1388,204 @@ mod tests {
         StandardLibrary::from_name("lua52").expect("lua52.toml wasn't found");
     }
 
+    #[test]
+    fn extend_precedence() {
+        fn library(fields: &[(&str, FieldKind)]) -> StandardLibrary {
+            StandardLibrary {
+                globals: fields
+                    .iter()
+                    .map(|(name, field_kind)| {
+                        (name.to_string(), Field::from_field_kind(field_kind.clone()))
+                    })
+                    .collect(),
+                ..StandardLibrary::default()
+            }
+        }
+
+        let ours = [
+            ("overridden", FieldKind::Any),
+            ("removed", FieldKind::Removed),
+            ("kept", FieldKind::Any),
+        ];
+
+        let theirs = [
+            (
+                "overridden",
+                FieldKind::Property(PropertyWritability::ReadOnly),
+            ),
+            ("removed", FieldKind::Any),
+            ("kept", FieldKind::Removed),
+            ("inherited", FieldKind::Any),
+        ];
+
+        let expected = library(&[
+            ("overridden", FieldKind::Any),
+            ("kept", FieldKind::Any),
+            ("inherited", FieldKind::Any),
+        ]);
+
+        // Merging goes down different paths depending on which library is bigger
+        let mut smaller = library(&ours);
+        smaller.extend(library(&theirs));
+        assert_eq!(smaller, expected);
+
+        let mut bigger = library(&ours);
+        bigger
+            .globals
+            .insert("extra".to_owned(), Field::from_field_kind(FieldKind::Any));
+        bigger.globals.insert(
+            "extra_removed".to_owned(),
+            Field::from_field_kind(FieldKind::Removed),
+        );
+        bigger.extend(library(&theirs));
+
+        let mut expected_bigger = expected.clone();
+        expected_bigger
+            .globals
+            .insert("extra".to_owned(), Field::from_field_kind(FieldKind::Any));
+        assert_eq!(bigger, expected_bigger);
+    }
+
+    #[test]
+    fn shared_globals() {
+        let mut lua51 = StandardLibrary::from_name("lua51").unwrap();
+        let expected = lua51.globals.clone();
+        lua51.share_globals();
+
+        assert!(lua51.globals.is_empty());
+        assert_eq!(lua51.iter_globals().count(), expected.len());
+        assert_eq!(lua51.global("print"), expected.get("print"));
+        assert!(lua51.find_global(&["string", "rep"]).is_some());
+
+        let declared = lua51.with_globals(vec![(
+            "declared".to_owned(),
+            Field::from_field_kind(FieldKind::Any),
+        )]);
+        assert_eq!(declared.globals.len(), 1);
+        assert!(declared.find_global(&["declared"]).is_some());
+        assert!(lua51.find_global(&["declared"]).is_none());
+
+        // Extending a smaller library with a shared one layers it on top, without copying
+        let mut project = StandardLibrary {
+            globals: [
+                ("print", FieldKind::Removed),
+                ("string.rep", FieldKind::Any),
+                ("extra", FieldKind::Any),
+            ]
+            .into_iter()
+            .map(|(name, field_kind)| (name.to_owned(), Field::from_field_kind(field_kind)))
+            .collect(),
+            ..StandardLibrary::default()
+        };
+        project.extend(lua51.clone());
+
+        assert!(Arc::ptr_eq(
+            project.shared_globals.as_ref().unwrap(),
+            lua51.shared_globals.as_ref().unwrap()
+        ));
+        assert_eq!(project.global("print"), None);
+        assert_eq!(project.find_global(&["print"]), None);
+        assert_eq!(
+            project.global("string.rep").unwrap().field_kind,
+            FieldKind::Any
+        );
+        assert!(project.global("extra").is_some());
+        assert!(project.global("assert").is_some());
+        assert!(project.iter_globals().all(|(name, _)| name != "print"));
+        assert_eq!(project.iter_globals().count(), expected.len());
+
+        // Libraries that are already layered can be based on others too
+        let mut copied = project.clone();
+        copied.extend(StandardLibrary {
+            globals: [("print", FieldKind::Any), ("other", FieldKind::Any)]
+                .into_iter()
+                .map(|(name, field_kind)| (name.to_owned(), Field::from_field_kind(field_kind)))
+                .collect(),
+            ..StandardLibrary::default()
+        });
+
+        assert_eq!(copied.global("print"), None);
+        assert!(copied.global("other").is_some());
+    }
+
+    #[test]
+    fn extend_global_sources() {
+        fn library(name: &str, fields: &[(&str, FieldKind)]) -> StandardLibrary {
+            let mut library = StandardLibrary {
+                globals: fields
+                    .iter()
+                    .map(|(name, field_kind)| {
+                        (name.to_string(), Field::from_field_kind(field_kind.clone()))
+                    })
+                    .collect(),
+                ..StandardLibrary::default()
+            };
+
+            library.set_source_name(name);
+            library
+        }
+
+        let base = library(
+            "base",
+            &[
+                ("overridden", FieldKind::Any),
+                ("removed", FieldKind::Any),
+                ("inherited.field", FieldKind::Any),
+            ],
+        );
+
+        let mut middle = library("middle", &[("overridden", FieldKind::Any)]);
+        middle.extend(base);
+
+        // The project's library is smaller than the ones it's based on, then bigger
+        for extra_globals in [0, 5] {
+            let mut fields = vec![
+                ("overridden", FieldKind::Any),
+                ("removed", FieldKind::Removed),
+            ];
+
+            let extra_names = (0..extra_globals)
+                .map(|index| format!("extra{index}"))
+                .collect::<Vec<_>>();
+            fields.extend(
+                extra_names
+                    .iter()
+                    .map(|name| (name.as_str(), FieldKind::Any)),
+            );
+
+            let mut project = library("project", &fields);
+            project.extend(middle.clone());
+
+            let source = |names: &[&str]| project.global_source(names).unwrap();
+
+            assert_eq!(
+                source(&["overridden"]),
+                GlobalSource {
+                    library: Some("project".to_owned()),
+                    overrides: vec!["middle".to_owned(), "base".to_owned()],
+                    removed: false,
+                }
+            );
+
+            assert_eq!(
+                source(&["inherited", "field", "child"]).library.as_deref(),
+                Some("base")
+            );
+            assert!(source(&["removed"]).removed);
+            assert_eq!(project.removed_by(&["removed"]).as_deref(), Some("project"));
+            assert_eq!(project.removed_by(&["overridden"]), None);
+            assert!(project.global_source(&["unknown"]).is_none());
+
+            assert_eq!(
+                project.global_source_notes(&["removed"]),
+                vec!["`removed` is defined by the `base` standard library, which `project` is based on"]
+            );
+            assert!(project
+                .global_source_notes(&["inherited", "field"])
+                .is_empty());
+        }
+    }
+
     #[test]
     fn deprecated_try_instead() {
         let deprecated = Deprecated {
@@ -894,4 +1636,28 @@ mod tests {
             Some("print(a, b, c)".to_owned())
         );
     }
+
+    #[test]
+    fn restricted_without_field_kind() {
+        let field: Field = serde_yaml::from_str("restricted: \"not in plugins\"").unwrap();
+        assert_eq!(
+            field.field_kind,
+            FieldKind::Property(PropertyWritability::ReadOnly)
+        );
+        assert_eq!(field.restricted.as_deref(), Some("not in plugins"));
+
+        let field: Field =
+            serde_yaml::from_str("restricted: \"not in plugins\"\nany: true").unwrap();
+        assert_eq!(field.field_kind, FieldKind::Any);
+
+        assert!(serde_yaml::from_str::<Field>("deprecated:\n  message: \"old\"").is_err());
+    }
+
+    #[test]
+    fn property_type() {
+        let field: Field = serde_yaml::from_str("property: read-only\ntype: number").unwrap();
+        assert_eq!(field.property_type, Some(PropertyType::Number));
+
+        assert!(serde_yaml::from_str::<Field>("any: true\ntype: number").is_err());
+    }
 }
@@ -44,6 +44,13 @@ pub struct StandardLibraryMeta {
 pub struct FunctionBehavior {
     pub arguments: Vec<Argument>,
     pub method: bool,
+    pub overloads: Vec<Overload>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Overload {
+    #[serde(rename = "args")]
+    pub arguments: Vec<Argument>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -120,6 +127,7 @@ impl<'de> Deserialize<'de> for Field {
             Some(FunctionBehavior {
                 arguments: field_raw.args.unwrap_or_default(),
                 method: field_raw.method,
+                overloads: field_raw.overloads,
             })
         } else {
             None
@@ -149,6 +157,9 @@ impl Serialize for Field {
                         map.serialize_entry("method", &true)?;
                     }
                     map.serialize_entry("args", &function.arguments)?;
+                    if !function.overloads.is_empty() {
+                        map.serialize_entry("overloads", &function.overloads)?;
+                    }
                 }
 
                 for (key, value) in table.iter() {
@@ -206,6 +217,8 @@ struct FieldSerde {
     #[serde(default)]
     args: Option<Vec<Argument>>,
     #[serde(default)]
+    overloads: Vec<Overload>,
+    #[serde(default)]
     #[serde(rename = "struct")]
     strukt: Option<String>,
     #[serde(default)]
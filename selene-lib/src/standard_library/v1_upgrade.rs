@@ -38,9 +38,12 @@ impl From<v1::StandardLibrary> for StandardLibrary {
 impl From<v1::Argument> for Argument {
     fn from(v1_argument: v1::Argument) -> Self {
         Argument {
+            name: None,
             required: v1_argument.required.into(),
             argument_type: v1_argument.argument_type.into(),
             observes: Observes::ReadWrite,
+            range: None,
+            max_length: None,
         }
     }
 }
@@ -84,6 +87,17 @@ fn unpack_v1_field(name: String, v1_field: v1::Field) -> BTreeMap<String, Field>
                             arguments: function.arguments.into_iter().map(Into::into).collect(),
                             method: function.method,
                             must_use: false,
+                            overloads: function
+                                .overloads
+                                .into_iter()
+                                .map(|overload| Overload {
+                                    arguments: overload
+                                        .arguments
+                                        .into_iter()
+                                        .map(Into::into)
+                                        .collect(),
+                                })
+                                .collect(),
                         })),
                     );
                 }
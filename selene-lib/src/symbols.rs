@@ -0,0 +1,342 @@
+// Finds where local variables are declared and used, for editors to go to their definitions and
+// rename them, and what every name refers to, for editors to highlight them.
+use std::collections::{BTreeMap, HashSet};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+
+use crate::{
+    ast_util::scopes::ScopeManager,
+    standard_library::{FieldKind, StandardLibrary},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Local {
+    pub name: String,
+    /// Where the variable is declared, such as the `x` in `local x`, the name of a local function,
+    /// or a parameter.
+    pub declaration: (usize, usize),
+    /// Everywhere else the variable is read or assigned to, in order.
+    pub references: Vec<(usize, usize)>,
+}
+
+/// Returns the local variable that is declared or used at the byte, if there is one.
+pub fn local_at(ast: &Ast, byte: usize) -> Option<Local> {
+    let scope_manager = ScopeManager::new(ast);
+
+    let contains = |range: &(usize, usize)| range.0 <= byte && byte <= range.1;
+
+    let variable = scope_manager
+        .variables
+        .iter()
+        .find(|(_, variable)| variable.identifiers.iter().any(contains))
+        .map(|(_, variable)| variable)
+        .or_else(|| {
+            let reference = scope_manager.reference_at_byte(byte)?;
+            Some(&scope_manager.variables[reference.resolved?])
+        })?;
+
+    if variable.is_self || variable.is_global {
+        return None;
+    }
+
+    let declaration = *variable.identifiers.first()?;
+
+    let mut references = variable
+        .references
+        .iter()
+        .map(|reference| scope_manager.references[*reference].identifier)
+        .filter(|range| !variable.identifiers.contains(range))
+        .collect::<Vec<_>>();
+
+    references.sort_unstable();
+    references.dedup();
+
+    Some(Local {
+        name: variable.name.clone(),
+        declaration,
+        references,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Local,
+    Parameter,
+    /// A global that isn't in the standard library, which is often a typo or a missing `local`.
+    Global,
+    /// A global from the standard library, or one of its fields, such as both `math` and `floor`
+    /// in `math.floor`.
+    StandardLibrary,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub range: (usize, usize),
+    pub kind: SymbolKind,
+    pub deprecated: bool,
+}
+
+/// Returns what every name in the code refers to, in order. Globals not defined in the file are
+/// looked up in the standard library.
+pub fn semantic_tokens(ast: &Ast, standard_library: &StandardLibrary) -> Vec<SemanticToken> {
+    let scope_manager = ScopeManager::new(ast);
+
+    let mut visitor = SemanticTokensVisitor {
+        parameters: HashSet::new(),
+        members: Vec::new(),
+    };
+    visitor.visit_ast(ast);
+
+    let mut tokens = BTreeMap::new();
+    let mut standard_library_roots = HashSet::new();
+
+    for (_, variable) in scope_manager.variables.iter() {
+        // Varargs are highlighted as the symbol they're written as
+        if variable.is_global || variable.name == "..." {
+            continue;
+        }
+
+        let kind = if variable.is_self
+            || matches!(variable.identifiers.first(), Some(identifier) if visitor.parameters.contains(identifier))
+        {
+            SymbolKind::Parameter
+        } else {
+            SymbolKind::Local
+        };
+
+        for range in variable.identifiers.iter().chain(
+            variable
+                .references
+                .iter()
+                .map(|reference| &scope_manager.references[*reference].identifier),
+        ) {
+            tokens.insert(*range, (kind, false));
+        }
+    }
+
+    for (_, reference) in scope_manager.references.iter() {
+        if let Some(resolved) = reference.resolved {
+            if !scope_manager.variables[resolved].is_global {
+                continue;
+            }
+        }
+
+        let field = if reference.resolved.is_none() {
+            standard_library
+                .find_global(&[reference.name.as_str()])
+                .filter(|field| field.field_kind != FieldKind::Removed)
+        } else {
+            None
+        };
+
+        match field {
+            Some(field) => {
+                standard_library_roots.insert(reference.identifier);
+                tokens.insert(
+                    reference.identifier,
+                    (SymbolKind::StandardLibrary, field.deprecated.is_some()),
+                );
+            }
+
+            None => {
+                tokens.insert(reference.identifier, (SymbolKind::Global, false));
+            }
+        }
+    }
+
+    for (root, path) in visitor.members {
+        if !standard_library_roots.contains(&root) {
+            continue;
+        }
+
+        for bound in 2..=path.len() {
+            let names = path[..bound]
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>();
+
+            match standard_library.find_global(&names) {
+                Some(field) if field.field_kind != FieldKind::Removed => {
+                    tokens.insert(
+                        path[bound - 1].1,
+                        (SymbolKind::StandardLibrary, field.deprecated.is_some()),
+                    );
+                }
+
+                _ => break,
+            }
+        }
+    }
+
+    tokens
+        .into_iter()
+        .map(|(range, (kind, deprecated))| SemanticToken {
+            range,
+            kind,
+            deprecated,
+        })
+        .collect()
+}
+
+type NamePath = Vec<(String, (usize, usize))>;
+
+struct SemanticTokensVisitor {
+    parameters: HashSet<(usize, usize)>,
+    // Every `a.b.c` and `a:b()` starting with a name, along with where the name is
+    members: Vec<((usize, usize), NamePath)>,
+}
+
+fn token_range(token: &TokenReference) -> Option<(usize, usize)> {
+    let (start, end) = token.range()?;
+    Some((start.bytes(), end.bytes()))
+}
+
+impl SemanticTokensVisitor {
+    fn add_members<'a>(
+        &mut self,
+        prefix: &ast::Prefix,
+        suffixes: impl Iterator<Item = &'a ast::Suffix>,
+    ) {
+        let name = match prefix {
+            ast::Prefix::Name(name) => name,
+            _ => return,
+        };
+
+        let root = match token_range(name) {
+            Some(root) => root,
+            None => return,
+        };
+
+        let mut path = vec![(name.token().to_string(), root)];
+
+        for suffix in suffixes {
+            let member = match suffix {
+                ast::Suffix::Index(ast::Index::Dot { name, .. }) => name,
+                ast::Suffix::Call(ast::Call::MethodCall(method_call)) => method_call.name(),
+                _ => break,
+            };
+
+            match token_range(member) {
+                Some(range) => path.push((member.token().to_string(), range)),
+                None => break,
+            }
+        }
+
+        if path.len() > 1 {
+            self.members.push((root, path));
+        }
+    }
+}
+
+impl Visitor for SemanticTokensVisitor {
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        for parameter in body.parameters() {
+            if let ast::Parameter::Name(name) = parameter {
+                if let Some(range) = token_range(name) {
+                    self.parameters.insert(range);
+                }
+            }
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.add_members(call.prefix(), call.suffixes());
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        self.add_members(var_expression.prefix(), var_expression.suffixes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local_at_code(code: &str, byte: usize) -> Option<Local> {
+        local_at(&full_moon::parse(code).unwrap(), byte)
+    }
+
+    #[test]
+    fn test_local_at() {
+        let code = "local x = 1\nx = x + 1\nprint(x)";
+        let expected = Some(Local {
+            name: "x".to_owned(),
+            declaration: (6, 7),
+            references: vec![(12, 13), (16, 17), (28, 29)],
+        });
+
+        assert_eq!(local_at_code(code, 6), expected);
+        assert_eq!(local_at_code(code, 16), expected);
+        assert_eq!(local_at_code(code, 22), None);
+    }
+
+    #[test]
+    fn test_shadowing() {
+        let code = "local x = 1\ndo\n\tlocal x = x\n\tprint(x)\nend\nprint(x)";
+
+        assert_eq!(local_at_code(code, 22).unwrap().references, vec![(35, 36)]);
+        assert_eq!(
+            local_at_code(code, 6).unwrap().references,
+            vec![(26, 27), (48, 49)]
+        );
+    }
+
+    #[test]
+    fn test_parameters_and_functions() {
+        let code = "local function f(a)\n\treturn a\nend\nf(1)";
+
+        assert_eq!(
+            local_at_code(code, 17),
+            Some(Local {
+                name: "a".to_owned(),
+                declaration: (17, 18),
+                references: vec![(28, 29)],
+            })
+        );
+
+        assert_eq!(local_at_code(code, 35).unwrap().declaration, (15, 16));
+    }
+
+    #[test]
+    fn test_globals() {
+        assert_eq!(local_at_code("print(x)", 6), None);
+        assert_eq!(local_at_code("x = 1\nprint(x)", 0), None);
+        assert_eq!(local_at_code("function f() end", 9), None);
+    }
+
+    #[test]
+    fn test_semantic_tokens() {
+        let code = "local function f(a, ...)\n\treturn a, math.floor(b), table.getn\nend";
+        let ast = full_moon::parse(code).unwrap();
+
+        let tokens = semantic_tokens(&ast, &StandardLibrary::from_name("lua51").unwrap())
+            .into_iter()
+            .map(|token| {
+                (
+                    &code[token.range.0..token.range.1],
+                    token.kind,
+                    token.deprecated,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                ("f", SymbolKind::Local, false),
+                ("a", SymbolKind::Parameter, false),
+                ("a", SymbolKind::Parameter, false),
+                ("math", SymbolKind::StandardLibrary, false),
+                ("floor", SymbolKind::StandardLibrary, false),
+                ("b", SymbolKind::Global, false),
+                ("table", SymbolKind::StandardLibrary, false),
+                ("getn", SymbolKind::StandardLibrary, true),
+            ]
+        );
+    }
+}
@@ -31,3 +31,30 @@ fn test_std_mistakes() {
 fn test_std_mistakes_roblox() {
     test_full_run_config("std_mistakes", "roblox_mistakes", CheckerConfig::default());
 }
+
+#[test]
+fn test_time_budget() {
+    use crate::{standard_library::StandardLibrary, Checker};
+    use std::{
+        path::Path,
+        time::{Duration, Instant},
+    };
+
+    let checker = Checker::<serde_json::Value>::new(
+        CheckerConfig::default(),
+        StandardLibrary::from_name("lua51").unwrap(),
+    )
+    .unwrap();
+
+    let ast = full_moon::parse("local x = 1").unwrap();
+    let path = Path::new("test.lua");
+
+    assert!(checker
+        .test_on_file_before(&ast, path, Instant::now())
+        .is_none());
+
+    let diagnostics = checker
+        .test_on_file_before(&ast, path, Instant::now() + Duration::from_secs(3600))
+        .unwrap();
+    assert_eq!(diagnostics.len(), checker.test_on_file(&ast, path).len());
+}
@@ -65,3 +65,219 @@ fn uses_rule_variation_allow() {
         .test_on(&parse("if true then\n\treturn\nend").unwrap())
         .is_empty());
 }
+
+#[test]
+fn errors_with_unknown_lint() {
+    match Checker::<serde_json::Value>::new(
+        CheckerConfig {
+            rules: map! {
+                "unused_variabel".to_owned() => RuleVariation::Deny,
+            },
+            ..CheckerConfig::default()
+        },
+        StandardLibrary::default(),
+    ) {
+        Err(error) => {
+            assert_eq!(error.name, "rules");
+            match error.problem {
+                CheckerErrorProblem::UnknownLint { lint, suggestion } => {
+                    assert_eq!(lint, "unused_variabel");
+                    assert_eq!(suggestion, Some("unused_variable"));
+                }
+                other => panic!("error was not UnknownLint: {:?}", other),
+            }
+        }
+
+        _ => panic!("new returned Ok"),
+    }
+}
+
+#[test]
+fn errors_with_unknown_lint_config() {
+    match Checker::new(
+        CheckerConfig {
+            config: map! {
+                "not_a_lint".to_owned() => json!({}),
+            },
+            ..CheckerConfig::default()
+        },
+        StandardLibrary::default(),
+    ) {
+        Err(error) => {
+            assert_eq!(error.name, "config");
+            match error.problem {
+                CheckerErrorProblem::UnknownLint { lint, suggestion } => {
+                    assert_eq!(lint, "not_a_lint");
+                    assert_eq!(suggestion, None);
+                }
+                other => panic!("error was not UnknownLint: {:?}", other),
+            }
+        }
+
+        _ => panic!("new returned Ok"),
+    }
+}
+
+#[test]
+fn allows_unknown_lints() {
+    Checker::new(
+        CheckerConfig {
+            config: map! {
+                "not_a_lint".to_owned() => json!({}),
+            },
+            rules: map! {
+                "not_a_lint".to_owned() => RuleVariation::Deny,
+            },
+            unknown_lints: UnknownLints::Allow,
+            ..CheckerConfig::default()
+        },
+        StandardLibrary::default(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn applies_profile() {
+    let mut config: CheckerConfig<serde_json::Value> = serde_json::from_value(json!({
+        "std": "lua51",
+        "rules": {
+            "empty_if": "allow",
+            "shadowing": "warn",
+        },
+        "profile": {
+            "ci": {
+                "std": "lua52",
+                "rules": {
+                    "empty_if": "deny",
+                },
+            },
+        },
+    }))
+    .unwrap();
+
+    assert!(!config.apply_profile("dev"));
+    assert!(config.apply_profile("ci"));
+
+    assert_eq!(config.std(), "lua52");
+    assert_eq!(config.rules["empty_if"], RuleVariation::Deny);
+    assert_eq!(config.rules["shadowing"], RuleVariation::Warn);
+}
+
+#[test]
+fn applies_ci_severity() {
+    let mut config: CheckerConfig<serde_json::Value> = serde_json::from_value(json!({
+        "rules": {
+            "empty_if": "warn",
+            "shadowing": "warn",
+        },
+        "severity": {
+            "ci": {
+                "empty_if": "deny",
+            },
+        },
+        "profile": {
+            "strict": {
+                "severity": {
+                    "ci": {
+                        "shadowing": "deny",
+                    },
+                },
+            },
+        },
+    }))
+    .unwrap();
+
+    assert!(config.apply_profile("strict"));
+    assert_eq!(config.rules["empty_if"], RuleVariation::Warn);
+
+    config.apply_ci_severity();
+    assert_eq!(config.rules["empty_if"], RuleVariation::Deny);
+    assert_eq!(config.rules["shadowing"], RuleVariation::Deny);
+}
+
+#[test]
+fn errors_with_unknown_lint_in_ci_severity() {
+    let config: CheckerConfig<serde_json::Value> = serde_json::from_value(json!({
+        "severity": {
+            "ci": {
+                "empty_iff": "deny",
+            },
+        },
+    }))
+    .unwrap();
+
+    let error = Checker::new(config, StandardLibrary::default())
+        .err()
+        .unwrap()
+        .to_string();
+
+    assert!(
+        error.starts_with("[severity.ci] Unknown lint `empty_iff`"),
+        "{error}"
+    );
+}
+
+#[test]
+fn config_for_tests() {
+    let config: CheckerConfig<serde_json::Value> = serde_json::from_value(json!({
+        "std": "lua52",
+        "rules": {
+            "shadowing": "warn",
+        },
+        "profile": {
+            "test": {
+                "rules": {
+                    "shadowing": "allow",
+                },
+            },
+        },
+    }))
+    .unwrap();
+
+    let test_config = config.for_tests();
+    assert_eq!(test_config.std(), "lua52+testing");
+    assert_eq!(test_config.rules["shadowing"], RuleVariation::Allow);
+
+    assert_eq!(config.test_files(), selene_lib::DEFAULT_TEST_FILES);
+    assert_eq!(
+        CheckerConfig::<serde_json::Value>::default()
+            .for_tests()
+            .rules
+            .get("shadowing"),
+        None
+    );
+}
+
+#[test]
+fn errors_with_unknown_lint_in_profile() {
+    let config: CheckerConfig<serde_json::Value> = serde_json::from_value(json!({
+        "profile": {
+            "ci": {
+                "rules": {
+                    "empty_iff": "deny",
+                },
+            },
+        },
+    }))
+    .unwrap();
+
+    match Checker::new(config, StandardLibrary::default()) {
+        Err(error) => assert_eq!(error.name, "profile"),
+        _ => panic!("new returned Ok"),
+    }
+}
+
+#[test]
+fn lists_unavailable_lints() {
+    let unavailable = unavailable_lints();
+
+    assert_eq!(
+        unavailable.contains(&("prefer_const", "lua54")),
+        !cfg!(feature = "lua54")
+    );
+
+    assert_eq!(
+        unavailable.contains(&("roblox_task_library", "roblox")),
+        !cfg!(feature = "roblox")
+    );
+}
@@ -0,0 +1,25 @@
+-- selene: globals(config, plugin.hooks)
+-- selene: read_globals(vim, love.graphics)
+
+config = { verbose = true }
+print(config.verbose)
+
+plugin = {}
+plugin.hooks.on_save = function() end
+
+vim()
+vim.api.nvim_command("write")
+vim = nil
+
+love.graphics.draw()
+love = nil
+
+print(undeclared)
+
+-- selene: globals(1invalid)
+local function declared_later()
+	return later
+end
+
+-- selene: globals(later)
+declared_later()
@@ -0,0 +1,7 @@
+local value = getValue() :: any
+local instance = (value :: any) :: Instance
+local fine = getValue() :: number
+
+local function f(x: any): any
+    return x
+end
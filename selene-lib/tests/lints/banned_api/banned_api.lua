@@ -0,0 +1,12 @@
+os.execute("rm -rf build")
+local execute = os.execute
+print(os.time())
+
+debug.traceback()
+local info = debug.getinfo(1).source
+
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+print(game.PlaceId)
+
+local os = { execute = print }
+os.execute("this is fine")
@@ -0,0 +1,6 @@
+local player = 1
+local café = 2
+local рlayer = 3
+local сор = 4
+local λx = 5
+print(player, café, рlayer, сор, λx)
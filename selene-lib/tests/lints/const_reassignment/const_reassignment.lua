@@ -0,0 +1,25 @@
+local constant <const> = 1
+constant = 2
+
+local closed <close> = setmetatable({}, {})
+closed = nil
+
+local fine <const> = {}
+fine.x = 1
+
+local function shadow()
+    local constant = 3
+    constant = 4
+    return constant
+end
+
+local later <const> = 1
+local function closure()
+    later = 2
+end
+
+
+
+local redeclared <const> = 1
+function redeclared() end
+print(constant, closed, fine, shadow, closure, redeclared)
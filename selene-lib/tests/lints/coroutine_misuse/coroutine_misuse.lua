@@ -0,0 +1,20 @@
+local co = coroutine.create(f)
+
+coroutine.resume(co)
+local ok, err = coroutine.resume(co)
+
+coroutine.wrap(function()
+    coroutine.yield(1)
+end)
+
+coroutine.wrap(function()
+    coroutine.yield(1)
+end)()
+
+local generator = coroutine.wrap(f)
+generator()
+
+coroutine.yield()
+
+local coroutine = {}
+coroutine.resume(co)
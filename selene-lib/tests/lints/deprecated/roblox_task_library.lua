@@ -0,0 +1,4 @@
+-- Reported by roblox_task_library instead
+wait(1)
+
+print(wait)
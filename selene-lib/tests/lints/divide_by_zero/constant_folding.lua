@@ -0,0 +1,5 @@
+local _ = 1 / (1 - 1)
+local _ = 5 / (0)
+local _ = (2 * 2) / 0
+local _ = (1 - 1) / 0
+local _ = 1 / (2 - 1)
@@ -0,0 +1,30 @@
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+local Packages = ReplicatedStorage.Packages
+
+local Roact = require(Packages.Roact)
+local Config = require("config")
+require "config"
+
+local OtherRoact = require(game.ReplicatedStorage.Packages.Roact)
+local function getRoact()
+    return require(ReplicatedStorage:WaitForChild("Packages")["Roact"])
+end
+
+require("config")
+
+local function lazy()
+    local Promise = require(Packages.Promise)
+    return Promise
+end
+
+-- Promise is only visible inside of lazy
+local Promise = require(Packages.Promise)
+
+local Dynamic = Packages
+Dynamic = script
+local NotTheSame = require(Dynamic.Roact)
+
+return function()
+    local Packages = script.Packages
+    return require(Packages.Roact)
+end
@@ -0,0 +1,17 @@
+error()
+error(2)
+error(2, "something went wrong")
+error("something went wrong", 2)
+error("something went wrong")
+error({ code = 1 })
+
+assert(x, f())
+assert(x, string.format("expected %s", y))
+assert(x, "message " .. y)
+assert(x)
+
+local function assert(...) end
+assert(x, f())
+
+local error = print
+error(2)
@@ -0,0 +1,4 @@
+error()
+error(2)
+error(2, "something went wrong")
+error("something went wrong", 2)
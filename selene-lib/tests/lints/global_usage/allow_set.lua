@@ -0,0 +1,6 @@
+_G.ENV = {}
+_G.ENV.foo = 1
+print(_G.ENV.foo)
+_G["self"] = _G.ENV
+
+_G.bar = 1
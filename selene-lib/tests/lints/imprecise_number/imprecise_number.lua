@@ -0,0 +1,7 @@
+local fine = 9007199254740992
+local big = 9007199254740993
+local id = 123456789012345678
+local pi = 3.14159265358979323846
+local short_pi = 3.141592653589793
+local mask = 0xFFFFFFFFFFFFFFFF
+local small = 0xFF
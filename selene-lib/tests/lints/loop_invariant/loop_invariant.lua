@@ -0,0 +1,58 @@
+local radius = 5
+local list = { 1, 2, 3 }
+local circles = {}
+
+for _, circle in ipairs(circles) do
+    circle.area = math.pi * radius * radius
+end
+
+for i = 1, 10 do
+    print(i / #list, list[1] .. "!")
+end
+
+local i = 1
+while i <= #list do
+    i = i + 1
+end
+
+-- Uses the loop's variables, or ones assigned in the loop
+local total = 0
+for index = 1, 10 do
+    local doubled = index * 2
+    total = total + doubled * radius
+end
+
+-- The table could change
+local queue = { 1, 2 }
+while #queue > 0 do
+    print(table.remove(queue) + radius)
+end
+
+local counts = {}
+for _ = 1, 10 do
+    counts.n = (counts.n or 0) + #counts
+end
+
+-- Calls could return something different every time
+for _ = 1, 10 do
+    print(radius * get())
+end
+
+-- Only computed once
+for index = 1, #list * 2 do
+    print(index)
+end
+
+-- Functions made in the loop only run when called
+for _ = 1, 10 do
+    table.insert(circles, function()
+        return radius * 2
+    end)
+end
+
+-- Nested loops are reported in the innermost loop it doesn't change in
+for x = 1, 10 do
+    for y = 1, 10 do
+        print(x * 2 + y, radius * 2)
+    end
+end
@@ -0,0 +1,31 @@
+local t = {}
+setmetatable(t, t)
+setmetatable(t, {})
+
+local Class = {}
+Class.__index = Class
+Class.__index = "Class"
+
+local mt = {
+    __index = 5,
+    ["__index"] = true,
+    __eq = function(a)
+        return true
+    end,
+    __lt = function(a, b)
+        return false
+    end,
+    __le = function(...)
+        return false
+    end,
+    __concat = function(a, b, c) end,
+}
+
+function Class.__eq(a) end
+function Class:__lt(other) end
+function Class:__le() end
+
+local metatables = {
+    __index = function(self, key) end,
+    __index = nil,
+}
@@ -0,0 +1,37 @@
+local name = "selene"
+print(name.upper())
+print(name:upper())
+print(name.format(name, 1))
+print(("%d items"):format(5))
+print(("%d items").format(5))
+
+local reassigned = "a"
+reassigned = {}
+print(reassigned.upper())
+
+local list = {}
+list:insert(1)
+list.count = 1
+print(list.count)
+
+local withMethod = { insert = table.insert }
+withMethod:insert(1)
+
+local laterMethod = {}
+function laterMethod:insert() end
+laterMethod:insert(1)
+
+local assignedMethod = {}
+assignedMethod.remove = table.remove
+assignedMethod:remove()
+
+local passed = {}
+setmetatable(passed, { __index = table })
+passed:insert(1)
+
+local inserted = {}
+table.insert(inserted, 1)
+inserted:concat()
+
+local unknown = {}
+unknown:frobnicate()
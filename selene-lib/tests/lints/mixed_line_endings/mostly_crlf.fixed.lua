@@ -0,0 +1,5 @@
+local x = 1
+local y = 2
+print(x, y)
+--[[ a comment
+that spans lines ]]
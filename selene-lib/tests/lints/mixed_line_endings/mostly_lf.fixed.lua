@@ -0,0 +1,4 @@
+local x = 1
+local y = 2
+print(x, y)
+print("日本")
@@ -0,0 +1,9 @@
+local Module = {}
+
+function Module.hello()
+	return "hello"
+end
+
+Module.goodbye = function()
+	return "goodbye"
+end
@@ -0,0 +1,7 @@
+local Module = {}
+
+function Module.hello()
+	print("hello")
+end
+
+return "Module"
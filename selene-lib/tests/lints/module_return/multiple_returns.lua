@@ -0,0 +1,4 @@
+local Module = {}
+local Private = {}
+
+return Module, Private
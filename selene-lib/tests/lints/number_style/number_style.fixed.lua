@@ -0,0 +1,6 @@
+local colors = { 0xFF00FF, 0xFF00FF, 0xFF00FF }
+local mask = 0b1010
+local big = 1e10
+
+-- Already written in the style
+local fine = { 1, 1.5, 1e10, 1_000_000, 0b1010 }
@@ -0,0 +1,10 @@
+local population = 8e9
+local ids = 1_234_567
+local speed = 299_792_458
+local small = 1000
+local thousand = 1500
+local grouped = 10_000
+local million = 1e6
+
+-- Already written in the style
+local fine = { 1e9, 100_000, 1000, 1e-3, 0.5 }
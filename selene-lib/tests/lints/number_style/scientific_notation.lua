@@ -0,0 +1,10 @@
+local population = 8000000000
+local ids = 1234567
+local speed = 299_792_458
+local small = 1e3
+local thousand = 1.5E3
+local grouped = 1_0000
+local million = 1_000_000
+
+-- Already written in the style
+local fine = { 1e9, 100_000, 1000, 1e-3, 0.5 }
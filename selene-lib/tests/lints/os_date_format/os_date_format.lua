@@ -0,0 +1,16 @@
+print(os.date("%Y-%m-%d"))
+print(os.date("*t"))
+print(os.date("!*t", 0))
+print(os.date("%Q"))
+print(os.date("*t %Y"))
+print(os.date(format))
+
+print(os.time())
+print(os.time({ year = 2022, month = 1, day = 1, hour = 0 }))
+print(os.time({ year = 2022, month = 1 }))
+print(os.time({ hour = 12 }))
+print(os.time({ year = 2022, [key] = 1 }))
+print(os.time(date))
+
+local os = {}
+print(os.date("%Q"))
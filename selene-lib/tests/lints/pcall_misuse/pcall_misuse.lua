@@ -0,0 +1,15 @@
+local ok, err = pcall(f(), 1)
+local ok2 = pcall(object.method(a, b))
+local ok3 = pcall(object:method())
+
+pcall(f)
+pcall(f, 1, 2)
+
+local ok4 = pcall(f, 1, 2)
+local ok5 = xpcall(f, handler, 1, 2)
+local ok6 = xpcall(function()
+    return f(1, 2)
+end, handler)
+
+local function pcall() end
+pcall(f())
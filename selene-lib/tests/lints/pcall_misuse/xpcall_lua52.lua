@@ -0,0 +1 @@
+local ok = xpcall(f, handler, 1, 2)
@@ -0,0 +1,2 @@
+count = count + 1
+player.Score = player.Score * 2
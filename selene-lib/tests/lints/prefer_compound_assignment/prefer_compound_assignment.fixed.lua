@@ -0,0 +1,18 @@
+count += 1
+count -= 2 * step
+player.Score *= 2
+message ..= " world" .. "!"
+values[index] /= 2
+x ^= 2
+
+-- The left side has to be the variable itself
+count = 1 + count
+count = count - 1 - 2
+count = other + 1
+
+-- Indexing with a call would call it fewer times
+values[nextIndex()] = values[nextIndex()] + 1
+getTable().field = getTable().field + 1
+
+a, b = a + 1, b + 1
+count += 1
@@ -0,0 +1,23 @@
+local constant = 1
+local reassigned = 1
+reassigned = 2
+
+local already <const> = 1
+local closed <close> = setmetatable({}, {})
+
+local uninitialized
+uninitialized = 1
+
+local a, b = 1
+local c, d = f()
+local e, g = ...
+
+local mutated = {}
+mutated.x = 1
+
+local captured = 0
+local function increment()
+    captured = captured + 1
+end
+
+print(constant, reassigned, already, closed, uninitialized, a, b, c, d, e, g, mutated, increment)
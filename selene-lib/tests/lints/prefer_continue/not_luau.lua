@@ -0,0 +1,6 @@
+for _, player in players do
+    if player.spectating then
+    else
+        respawn(player)
+    end
+end
@@ -0,0 +1,59 @@
+for _, player in players do
+    if player.Team == spectators then
+    else
+        local character = player.Character
+        respawn(character)
+    end
+end
+
+while running do
+    local event = poll()
+    if event == nil then
+    else
+        handle(event)
+
+        if event.last then
+            break
+        end
+    end
+end
+
+for index = 1, 10 do
+    if skip[index] then else print(index) end
+end
+
+-- Code after the `if` would be skipped too
+for _, value in values do
+    if value then
+    else
+        print(value)
+    end
+
+    print("done")
+end
+
+-- Not an empty `then` block
+for _, value in values do
+    if value then
+        print("truthy")
+    else
+        print(value)
+    end
+end
+
+for _, value in values do
+    if value then
+    elseif other then
+    else
+        print(value)
+    end
+end
+
+-- Comments would be lost
+for _, value in values do
+    if value then
+        -- Already handled
+    else
+        print(value)
+    end
+end
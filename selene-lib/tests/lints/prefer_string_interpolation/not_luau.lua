@@ -0,0 +1 @@
+print(string.format("Hello %s", name))
@@ -0,0 +1,17 @@
+print(string.format("%s", value))
+print(string.format("Hello %s, you have %s coins", player.Name, getCoins(player)))
+print(string.format('%s!', name))
+
+-- Other specifiers can't be interpolated
+print(string.format("%d", count))
+print(string.format("%.2f%%", percent))
+
+-- Escapes and braces would have to change
+print(string.format("%s\n", value))
+print(string.format("{%s}", value))
+
+print(string.format("no arguments"))
+print(string.format([[%s]], value))
+
+local string = { format = print }
+print(string.format("%s", value))
@@ -0,0 +1,11 @@
+local count = 5 :: number
+local name = "name" :: string
+local enabled = true :: boolean
+local nothing = nil :: nil
+
+local narrowed = "name" :: "name"
+local optional = nil :: string?
+local widened = 5 :: number?
+
+local double = (getValue() :: string) :: number
+local forced = (getValue() :: any) :: number
@@ -0,0 +1,6 @@
+local Llama = require(Packages.Llama)
+local Roact = require(Packages.Roact)
+
+local Constants = require(Shared.Constants)
+
+local Util = require(script.Parent.Util)
@@ -0,0 +1,5 @@
+local Util = require(script.Parent.Util)
+local Roact = require(Packages.Roact)
+
+local Constants = require(Shared.Constants)
+local Llama = require(Packages.Llama)
@@ -0,0 +1,17 @@
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+local Packages = ReplicatedStorage.Packages
+
+local Roact = require(Packages.Roact)
+local Rodux = require(Packages.Rodux)
+
+local DEFAULT_SIZE = 5
+
+local Llama = require(Packages.Llama)
+
+local function lazy()
+    -- Requires inside of functions can be used to delay loading a module
+    return require(Packages.Promise)
+end
+
+local require = function() end
+local NotARequire = require("foo")
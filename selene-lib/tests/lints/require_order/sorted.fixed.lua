@@ -0,0 +1,17 @@
+local Llama = require("llama")
+local roact = require("roact")
+local Rodux = require("rodux")
+
+local Apple = require("apple")
+local Zebra = require("zebra")
+
+-- Comments between requires keep them from being reordered automatically
+local Beta = require("beta")
+-- Alpha is used for things
+local Alpha = require("alpha")
+
+local First = require("first")
+local Second = require("second") -- Trailing comments move with their require
+
+local Already = require("already")
+local Sorted = require("sorted")
@@ -0,0 +1,17 @@
+local Rodux = require("rodux")
+local roact = require("roact")
+local Llama = require("llama")
+
+local Zebra = require("zebra")
+local Apple = require("apple")
+
+-- Comments between requires keep them from being reordered automatically
+local Beta = require("beta")
+-- Alpha is used for things
+local Alpha = require("alpha")
+
+local Second = require("second") -- Trailing comments move with their require
+local First = require("first")
+
+local Already = require("already")
+local Sorted = require("sorted")
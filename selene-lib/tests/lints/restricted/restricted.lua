@@ -0,0 +1,13 @@
+os.execute("rm -rf /")
+local now = os.time()
+local osLibrary = os
+io.stdout:write("hello")
+local info = debug.getinfo(1)
+
+-- Not restricted
+print(debug.traceback())
+print(string.format("%d", 1))
+
+local function shadowed(os)
+    return os.time()
+end
@@ -0,0 +1,5 @@
+Color3.new(255 + 1, 0, 0)
+Color3.new(2 * 128, 0, 0)
+Color3.new(128 / 255, 0, 0)
+Color3.new(1 - 2, 0, 0)
+Color3.new(0.5 * 2, 0, 0)
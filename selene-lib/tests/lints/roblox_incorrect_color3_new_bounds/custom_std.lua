@@ -0,0 +1,7 @@
+Sound.setVolume(5)
+Sound.setVolume(11)
+Sound.fadeOut(100)
+Sound.fadeOut(-1)
+
+local Sound = {}
+Sound.setVolume(11)
@@ -0,0 +1,13 @@
+Roact.createElement("Widget", {
+    Label = "Play",
+})
+
+Roact.createElement("Widget", {
+    Size = 2,
+})
+
+Roact.createElement("IconWidget", {
+    Label = "Settings",
+})
+
+Roact.createElement("IconWidget", {})
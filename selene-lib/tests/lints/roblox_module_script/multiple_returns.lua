@@ -0,0 +1,3 @@
+local Module = {}
+
+return Module, "extra"
@@ -0,0 +1,5 @@
+local Module = {}
+
+function Module.hello()
+	return "hello"
+end
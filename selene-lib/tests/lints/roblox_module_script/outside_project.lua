@@ -0,0 +1,6 @@
+-- Rojo doesn't sync this, since default.project.json doesn't include it
+local Module = {}
+
+function Module.hello()
+	return "hello"
+end
@@ -0,0 +1,23 @@
+local Players = game:GetService("Players")
+local ReplicatedStorage = game:GetService("ReplicatedStorage")
+
+local Remotes = ReplicatedStorage:WaitForChild("Remotes")
+local player = Players.PlayerAdded:Wait()
+wait(1)
+task.wait()
+
+if not Remotes then
+	ReplicatedStorage:WaitForChild("Fallback")
+end
+
+local Module = {}
+
+function Module.getRemote(name)
+	return Remotes:WaitForChild(name)
+end
+
+Module.onLoad = function()
+	task.wait(1)
+end
+
+return Module
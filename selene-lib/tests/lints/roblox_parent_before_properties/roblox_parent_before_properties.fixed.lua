@@ -0,0 +1,43 @@
+local part = Instance.new("Part")
+part.Size = Vector3.new(4, 1, 2)
+part.Anchored = true
+part.Color = Color3.fromRGB(255, 0, 0)
+part.Parent = workspace
+
+local function createLabel(parent)
+    local label = Instance.new("TextLabel")
+    label.Name = "Label"
+    label.Text = "Hello"
+    label.TextScaled = true
+    label.Parent = parent -- Comments move with the statement
+end
+
+-- Not safe to reorder, since the values could depend on the parent
+local model = Instance.new("Model")
+model.Parent = workspace
+model.Name = getUniqueName()
+
+local folder = Instance.new("Folder")
+folder.Parent = workspace
+folder.Name = folder.Parent.Name
+
+-- Already correct
+local sound = Instance.new("Sound")
+sound.SoundId = "rbxassetid://0"
+sound.Parent = workspace
+
+-- Not created here
+existing.Parent = workspace
+existing.Name = "Existing"
+
+do
+    local Instance = {}
+    local fake = Instance.new("Part")
+    fake.Parent = workspace
+    fake.Name = "Fake"
+end
+
+-- The last statement in the file
+local last = Instance.new("Part")
+last.Name = "Last"
+last.Parent = workspace
\ No newline at end of file
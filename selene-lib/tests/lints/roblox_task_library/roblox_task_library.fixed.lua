@@ -0,0 +1,18 @@
+task.wait()
+task.wait(1)
+local elapsed = task.wait(0.5)
+
+task.spawn(function()
+    print("spawned")
+end)
+
+task.delay(2, function()
+    print("delayed")
+end)
+
+while task.wait(1) do
+    print("tick")
+end
+
+local wait = function() end
+wait(1)
@@ -0,0 +1,18 @@
+wait()
+wait(1)
+local elapsed = wait(0.5)
+
+spawn(function()
+    print("spawned")
+end)
+
+delay(2, function()
+    print("delayed")
+end)
+
+while wait(1) do
+    print("tick")
+end
+
+local wait = function() end
+wait(1)
@@ -0,0 +1,38 @@
+local remote = game.ReplicatedStorage.BuyItem
+
+remote.OnServerEvent:Connect(function(player, item, amount)
+    local price = item.Price * amount
+    player.leaderstats.Cash.Value -= price
+end)
+
+remote.OnServerEvent:Connect(function(player, item, amount)
+    if typeof(item) ~= "Instance" or typeof(amount) ~= "number" then
+        return
+    end
+
+    local price = item.Price * amount
+end)
+
+remote.OnServerEvent:Connect(function(player, amount)
+    amount = tonumber(amount)
+    print(-amount)
+end)
+
+remote.OnServerEvent:Connect(function(player, settings)
+    print(settings:GetAttribute("Volume"))
+    print(settings.Volume)
+end)
+
+-- Only passed along, never used
+remote.OnServerEvent:Connect(function(player, message)
+    print(message)
+end)
+
+game.ReplicatedStorage.GetPrice.OnServerInvoke = function(player, count)
+    return count + 1
+end
+
+-- Not a remote
+workspace.Part.Touched:Connect(function(hit, other)
+    print(hit.Parent, other + 1)
+end)
@@ -0,0 +1,6 @@
+local remote = game.ReplicatedStorage.BuyItem
+
+remote.OnServerEvent:Connect(function(player, amount, item)
+    assert(t.number(amount))
+    print(amount * 2, item.Name)
+end)
@@ -0,0 +1,30 @@
+local number = 5
+string.rep(number, "x")
+string.rep("x", number)
+
+local text = "hello"
+math.floor(text)
+
+local function callback() end
+math.floor(callback)
+
+local list = {}
+math.floor(list)
+
+local reassigned = 5
+reassigned = "x"
+string.rep(reassigned, 2)
+
+local later
+later = 5
+string.rep(later, 2)
+
+local function parameter(value)
+    string.rep(value, 2)
+end
+
+local captured = 5
+local function change()
+    captured = "x"
+end
+string.rep(captured, 2)
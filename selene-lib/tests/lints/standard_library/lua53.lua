@@ -0,0 +1,2 @@
+math.log(16, 2, 1)
+math.log(16, 2)
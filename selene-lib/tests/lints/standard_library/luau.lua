@@ -0,0 +1,2 @@
+print(debug.getinfo(1))
+print(debug.traceback())
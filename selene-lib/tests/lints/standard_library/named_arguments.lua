@@ -0,0 +1,8 @@
+text.match("hello")
+text.match()
+text.match("hello", 1)
+text.match("hello", "l", "2")
+text.match("hello", "l", 2, 3)
+
+-- Arguments without names are described by their position
+text.count("hello")
@@ -0,0 +1,12 @@
+-- Each of these matches one of the signatures
+Instance.new("Part")
+Instance.new("Part", {})
+remove({})
+remove({}, 1)
+remove("a", "b", 1)
+
+-- None of these match, so the problems with the closest signature are reported
+Instance.new()
+Instance.new("Part", 1)
+remove("a", "b")
+remove(1, 2, 3)
@@ -0,0 +1,6 @@
+print(math.huge.foo)
+local x = math.pi.y
+print(_VERSION.len, _VERSION:upper(), _VERSION.nope)
+math.huge.foo = 1
+print(math.huge.a.b)
+print(package.path:gsub("a", "b"))
@@ -0,0 +1,17 @@
+DataStore:GetAsync("player_inventory_backup")
+DataStore:GetAsync("inventory_" .. "backup_for_trading")
+DataStore:GetAsync("éééééééééééé")
+Analytics.track("level_completed")
+
+-- Short enough
+DataStore:GetAsync("inventory")
+DataStore:GetAsync("ééééé")
+Analytics.track("level_1")
+
+-- Not constant
+DataStore:GetAsync(key)
+DataStore:GetAsync("player_" .. player.UserId)
+
+local function shadowed(Analytics)
+    Analytics.track("level_completed")
+end
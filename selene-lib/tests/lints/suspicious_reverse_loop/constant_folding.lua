@@ -0,0 +1,8 @@
+for _ = #x, 2 - 1 do
+end
+
+for _ = #x, (1) do
+end
+
+for _ = #x, 1 + 1 do
+end
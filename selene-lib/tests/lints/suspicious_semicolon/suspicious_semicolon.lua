@@ -0,0 +1,16 @@
+local a = 1;
+local b = 2
+
+if a then
+    print(a);
+end;
+
+local c = a;
+(print)(c)
+
+local function f()
+    return b;
+end
+
+do
+end;
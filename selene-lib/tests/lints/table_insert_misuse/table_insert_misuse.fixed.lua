@@ -0,0 +1,30 @@
+local list = {}
+local values = { player = {} }
+
+table.insert(list)
+
+table.insert(list, "value")
+table.insert(values.player, "value")
+table.insert(list, (#list + 1), call())
+
+table.insert(list, 0, "value")
+table.insert(list, -1, "value")
+table.insert(list, 1.5, "value")
+table.insert(list, "first", "value")
+table.insert(list, nil, "value")
+table.insert(list, #list + 2, "value")
+
+-- Valid positions
+table.insert(list, 1, "value")
+table.insert(list, "2", "value")
+table.insert(list, #list, "value")
+table.insert(list, #other + 1, "value")
+table.insert(list, index, "value")
+table.insert(list, "value")
+table.insert(unpack(values))
+table.insert(getList(), #getList() + 1, "value")
+
+local function shadowed()
+    local table = { insert = print }
+    table.insert(list)
+end
@@ -0,0 +1,51 @@
+local settings = { volume = 1, muted = false, ["theme name"] = "dark" }
+settings.language = "en"
+
+print(settings.volume, settings.muted, settings["theme name"], settings.language)
+print(settings.volum)
+print(settings.missing.deeper)
+print(settings["missing"])
+settings.missing.deeper = 1
+
+if math.random() > 0.5 then
+    settings.later = true
+end
+print(settings.later)
+
+local module = {}
+function module.run() end
+function module:stop() end
+module.run()
+print(module.start)
+
+local passed = { a = 1 }
+print(passed.b)
+setmetatable(passed, {})
+
+local returned = { a = 1 }
+print(returned.b)
+
+local dynamic = { a = 1 }
+dynamic[key] = 2
+print(dynamic.b)
+
+local method = { a = 1 }
+method:update()
+print(method.b)
+
+local captured = { a = 1 }
+local function read()
+    return captured.b
+end
+
+local reassigned = { a = 1 }
+reassigned = other
+print(reassigned.b)
+
+local dynamicKeys = { [key] = 1 }
+print(dynamicKeys.b)
+
+local list = { 1, 2, 3 }
+print(list.n)
+
+return returned, read
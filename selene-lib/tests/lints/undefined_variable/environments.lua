@@ -0,0 +1,4 @@
+local env = setmetatable({ Component = {} }, { __index = getfenv() })
+setfenv(1, env)
+
+return Component.new()
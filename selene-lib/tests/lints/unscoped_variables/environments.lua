@@ -0,0 +1,3 @@
+setfenv(1, {})
+
+State = {}
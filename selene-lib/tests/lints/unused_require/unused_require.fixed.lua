@@ -0,0 +1,18 @@
+local Roact = require(Packages.Roact)
+require(Packages.Llama)
+local _Polyfill = require(Packages.Polyfill)
+
+local function render()
+    require(script.Parent.Theme)
+    return Roact.createElement("Frame")
+end
+
+local Overwritten = require(script.Overwritten)
+Overwritten = nil
+
+require("no_parentheses")
+
+local first, second = require("first"), require("second")
+print(first)
+
+return render
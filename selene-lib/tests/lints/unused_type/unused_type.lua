@@ -0,0 +1,22 @@
+type Unused = { name: string }
+type _Ignored = number
+export type Exported = { id: number }
+
+type Point = { x: number, y: number }
+local origin: Point = { x = 0, y = 0 }
+
+type List<T> = { value: T, next: List<T>? }
+type Recursive = { children: { Recursive } }
+
+type UsedLater = Later
+type Later = string
+
+local function second(list: List<number>): number
+    return list.value
+end
+
+local function make(): UsedLater
+    return ""
+end
+
+return origin, second, make
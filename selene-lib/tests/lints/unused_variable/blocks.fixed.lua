@@ -0,0 +1,16 @@
+-- Functions
+
+print(unusedVariableB)
+
+local function overridesIt()
+    local overidden = false
+    print(overidden)
+end
+
+overridesIt()
+
+-- Anonymous functions
+local a = 1
+print(function()
+    _G.foo = a
+end)
@@ -0,0 +1,71 @@
+-- Overwritten before being read
+local a = f()
+a = g()
+print(a)
+
+-- Reading itself still overwrites the old value
+local b = 1
+b = b + 1
+b = 3
+print(b)
+
+-- Read in between
+local c = f()
+print(c)
+c = g()
+print(c)
+
+-- Conditionally overwritten
+local d = f()
+if condition then
+	d = g()
+end
+print(d)
+
+-- Initializing to nil is fine
+local e = nil
+e = f()
+print(e)
+
+-- Captured by a function that could be called in between
+local h = 1
+local function readH()
+	return h
+end
+h = 2
+readH()
+h = 3
+print(h)
+
+-- Not captured, so calls can't read it
+local i = f()
+i = g()
+print(i)
+
+-- Escapes before the overwrite
+for _ = 1, 10 do
+	local j = f()
+	if j then
+		break
+	end
+	j = g()
+	print(j)
+end
+
+-- Fields are not stores
+local k = {}
+k.x = 1
+k = {}
+print(k)
+
+-- Captured, and a metamethod could read it in between
+local l = 1
+local proxy = setmetatable({}, {
+	__index = function()
+		return l
+	end,
+})
+l = 2
+local value = proxy.value
+l = 3
+print(l, value)
@@ -0,0 +1,6 @@
+local foo = 1
+
+-- The variables inside of a repeat...until are accessible from the until statement
+repeat
+    local baz
+until baz
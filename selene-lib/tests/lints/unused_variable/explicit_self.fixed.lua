@@ -0,0 +1,4 @@
+local function foo(_self)
+end
+
+foo()
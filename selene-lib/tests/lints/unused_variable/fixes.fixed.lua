@@ -0,0 +1,22 @@
+-- Removed, since nothing else uses them
+
+do
+    print(1) 
+end
+
+-- Calling a function could do something, so these are only renamed
+local _result = call()
+local _a, _b = 1, 2
+
+-- Assigned later, so removing the declaration would leave a global
+local _assigned = 1
+_assigned = 2
+
+-- Already taken, so not renamed
+local _taken = 1
+local taken = call()
+print(_taken)
+
+for _index, value in pairs({}) do
+    print(value)
+end
@@ -0,0 +1,27 @@
+-- Removed, since nothing else uses them
+local unused = 1
+local function unusedFunction()
+    return 1
+end
+
+do
+    local indented = { 1, 2 };
+    print(1) local sameLine = "x"
+end
+
+-- Calling a function could do something, so these are only renamed
+local result = call()
+local a, b = 1, 2
+
+-- Assigned later, so removing the declaration would leave a global
+local assigned = 1
+assigned = 2
+
+-- Already taken, so not renamed
+local _taken = 1
+local taken = call()
+print(_taken)
+
+for index, value in pairs({}) do
+    print(value)
+end
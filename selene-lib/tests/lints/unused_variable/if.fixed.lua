@@ -0,0 +1,10 @@
+if 1 then
+elseif 2 then
+    print(localA)
+elseif 3 then
+    print(localB)
+else
+    print(localC)
+end
+
+print(localA, localB, localC, localD)
@@ -0,0 +1,29 @@
+-- Unused
+local _localA, _localB
+
+-- Used
+local localC = 1
+print(localC)
+
+-- Mutated, but never read
+local _localD = 1
+_localD = 2
+_localD = 3
+
+-- Read, mutated, read
+local localE = 1
+print(localE)
+localE = 2
+print(localE)
+
+-- Read, mutated, unread
+local localF = 1
+print(localF)
+localF = 2
+
+-- Called function
+local localG = function() end
+localG()
+
+-- Put into a table
+local localH = 1
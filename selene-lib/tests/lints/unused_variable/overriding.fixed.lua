@@ -0,0 +1,9 @@
+local a = 1
+
+-- Variables defined inside the function should still lint
+
+return function()
+    return a
+end, function(arg)
+    a = arg
+end
@@ -0,0 +1,18 @@
+local template = { name = "" }
+type Template = typeof(template)
+
+local defaults = {}
+local function withDefaults(options: typeof(defaults))
+    return options
+end
+
+-- Type parameters are not values
+local function identity<T>(value: T): T
+    return value
+end
+
+-- A type with the same name as a value doesn't read it
+local Shape = {}
+local shape: Shape = nil
+
+return withDefaults, identity, shape
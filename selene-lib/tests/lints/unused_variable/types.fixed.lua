@@ -0,0 +1,2 @@
+local types = require(script.types)
+
@@ -0,0 +1,6 @@
+local function foo(...)
+    call(...)
+end
+
+
+foo()
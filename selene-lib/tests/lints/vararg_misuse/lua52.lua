@@ -0,0 +1,14 @@
+local function vararg(...)
+    print(arg[1], #arg)
+end
+
+local function shadowed(...)
+    local arg = table.pack(...)
+    print(arg.n)
+end
+
+local function notVararg()
+    print(arg)
+end
+
+print(arg[1])
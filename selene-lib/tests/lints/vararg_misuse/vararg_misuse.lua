@@ -0,0 +1,30 @@
+print(...)
+local count = #{...}
+
+local function notVararg(a, b)
+    print(...)
+
+    return function(...)
+        print(...)
+    end
+end
+
+local function vararg(...)
+    local args = {...}
+    print(#args, select("#", ...))
+
+    local function inner()
+        return #{
+            ...
+        }
+    end
+
+    print(#{ ..., nil })
+    print(#table.pack(...))
+    print(table.pack(...).n)
+
+    -- The implicit `arg` table exists in Lua 5.1
+    print(arg.n, arg[1])
+end
+
+print(arg[1])
@@ -0,0 +1,152 @@
+//! Python bindings for selene-lib, built with maturin. See README.md for usage.
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+use selene_lib::{
+    embedding, lines,
+    rules::{Label, Severity},
+    CheckerConfig,
+};
+
+/// A diagnostic from linting. Lines and columns start at 0, and `start`/`end` are byte offsets.
+#[pyclass(get_all, module = "selene")]
+#[derive(Clone)]
+pub struct Diagnostic {
+    code: &'static str,
+    severity: &'static str,
+    message: String,
+    notes: Vec<String>,
+    help: Vec<String>,
+    suggestions: Vec<String>,
+    start: usize,
+    end: usize,
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+#[pymethods]
+impl Diagnostic {
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagnostic(code={:?}, severity={:?}, message={:?}, start_line={}, start_column={})",
+            self.code, self.severity, self.message, self.start_line, self.start_column
+        )
+    }
+}
+
+fn lint_source(
+    source: &str,
+    config: CheckerConfig<serde_json::Value>,
+) -> Result<Vec<Diagnostic>, String> {
+    let diagnostics = embedding::lint(source, config)?;
+
+    let mut files = codespan::Files::new();
+    let file_id = files.add("source", source);
+
+    Ok(diagnostics
+        .into_iter()
+        .map(|checker_diagnostic| {
+            let severity = match checker_diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Allow => unreachable!("allowed diagnostics are left out"),
+            };
+
+            let diagnostic = checker_diagnostic.diagnostic;
+            let Label { range, .. } = diagnostic.primary_label;
+
+            let start_location = lines::location(&files, file_id, range.0);
+            let end_location = lines::location(&files, file_id, range.1);
+
+            Diagnostic {
+                code: diagnostic.code,
+                severity,
+                message: diagnostic.message,
+                notes: diagnostic.notes,
+                help: diagnostic.help,
+                suggestions: diagnostic.suggestions,
+                start: range.0 as usize,
+                end: range.1 as usize,
+                start_line: start_location.line.into(),
+                start_column: start_location.column.into(),
+                end_line: end_location.line.into(),
+                end_column: end_location.column.into(),
+            }
+        })
+        .collect())
+}
+
+/// Lints Lua source code, returning every diagnostic that is not allowed.
+/// `config` has the same shape as selene.toml, and uses the default configuration when left out.
+#[pyfunction]
+#[pyo3(signature = (source, config = None))]
+fn lint(py: Python, source: &str, config: Option<&PyDict>) -> PyResult<Vec<Diagnostic>> {
+    let json: Option<String> = match config {
+        Some(config) => Some(
+            py.import("json")?
+                .call_method1("dumps", (config,))?
+                .extract()?,
+        ),
+        None => None,
+    };
+
+    let config = embedding::config_from_json(json.as_deref()).map_err(PyValueError::new_err)?;
+
+    // Linting doesn't touch any Python objects, so other threads can run in the meantime
+    let source = source.to_owned();
+    py.allow_threads(move || lint_source(&source, config))
+        .map_err(PyValueError::new_err)
+}
+
+#[pymodule]
+fn selene(_py: Python, module: &PyModule) -> PyResult<()> {
+    module.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    module.add_class::<Diagnostic>()?;
+    module.add_function(wrap_pyfunction!(lint, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_with_config(source: &str, config: &str) -> Result<Vec<Diagnostic>, String> {
+        lint_source(source, serde_json::from_str(config).unwrap())
+    }
+
+    #[test]
+    fn test_lint() {
+        let diagnostics = lint_source("local x = 1", CheckerConfig::default()).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "unused_variable");
+        assert_eq!(diagnostics[0].severity, "warning");
+        assert_eq!(diagnostics[0].start_column, 6);
+    }
+
+    #[test]
+    fn test_multibyte_columns() {
+        let diagnostics = lint_source("print(\"日本\", x)", CheckerConfig::default()).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].start, 16);
+        assert_eq!(diagnostics[0].start_column, 12);
+    }
+
+    #[test]
+    fn test_config() {
+        let diagnostics = lint_with_config(
+            "local x = 1",
+            r#"{ "rules": { "unused_variable": "allow" } }"#,
+        )
+        .unwrap();
+
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(lint_source("local = ", CheckerConfig::default()).is_err());
+        assert!(lint_with_config("", r#"{ "std": "nonexistent" }"#).is_err());
+    }
+}
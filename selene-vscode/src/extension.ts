@@ -56,6 +56,77 @@ function labelToRange(
     )
 }
 
+type SignatureHelp = {
+    label: string
+    documentation: string | null
+    parameters: { label: [number, number]; documentation: string }[]
+}
+
+type Completion = {
+    label: string
+    kind: "function" | "method" | "field" | "module"
+    detail: string | null
+    deprecated: boolean
+}
+
+type SemanticToken = {
+    range: [number, number]
+    kind: "local" | "parameter" | "global" | "standard_library"
+    deprecated: boolean
+}
+
+const semanticTokensLegend = new vscode.SemanticTokensLegend(
+    ["variable", "parameter"],
+    ["defaultLibrary", "deprecated", "global"],
+)
+
+type Local = {
+    name: string
+    declaration: [number, number]
+    references: [number, number][]
+}
+
+// Finds the call the end of the text is inside of, and which argument is being typed
+function findCall(
+    text: string,
+): { name: string; argument: number } | undefined {
+    let depth = 0
+    let argument = 0
+
+    for (let index = text.length - 1; index >= 0; index--) {
+        const character = text[index]
+
+        if (character === ")" || character === "}" || character === "]") {
+            depth++
+        } else if (character === "{" || character === "[") {
+            depth--
+        } else if (character === "(") {
+            if (depth > 0) {
+                depth--
+                continue
+            }
+
+            const name = text
+                .slice(0, index)
+                .match(/([A-Za-z_]\w*(\s*[.:]\s*[A-Za-z_]\w*)*)\s*$/)
+
+            if (name === null) {
+                return undefined
+            }
+
+            return { name: name[1].replace(/\s/g, ""), argument }
+        } else if (character === "," && depth === 0) {
+            argument++
+        }
+
+        if (depth < 0) {
+            return undefined
+        }
+    }
+
+    return undefined
+}
+
 export async function activate(
     context: vscode.ExtensionContext,
 ): Promise<void> {
@@ -91,8 +162,50 @@ export async function activate(
 
     let hasWarnedAboutRoblox = false
 
+    // The extensions of the files selene checks and of standard libraries, from `selene
+    // print-config`, so that .luau files or ones added to `extensions` in selene.toml are linted
+    let fileExtensions = {
+        extensions: ["lua", "luau"],
+        std_extensions: ["toml", "yml"],
+    }
+
+    async function loadFileExtensions() {
+        if (!(await trySelene)) {
+            return
+        }
+
+        const output = await selene
+            .seleneCommand(
+                context.globalStorageUri,
+                "print-config",
+                selene.Expectation.Stdout,
+            )
+            .catch(() => null)
+
+        if (output) {
+            const config = JSON.parse(output)
+            fileExtensions = {
+                extensions: config.extensions ?? fileExtensions.extensions,
+                std_extensions:
+                    config.std_extensions ?? fileExtensions.std_extensions,
+            }
+        }
+    }
+
+    function isLinted(document: vscode.TextDocument) {
+        if (document.languageId === "lua" || document.languageId === "luau") {
+            return true
+        }
+
+        const extension = document.uri.path.match(/\.([^./]+)$/)?.[1]
+        return (
+            extension !== undefined &&
+            fileExtensions.extensions.includes(extension)
+        )
+    }
+
     async function lint(document: vscode.TextDocument) {
-        if (document.languageId !== "lua") {
+        if (!isLinted(document)) {
             return
         }
 
@@ -185,6 +298,481 @@ export async function activate(
         diagnosticsCollection.set(document.uri, diagnostics)
     }
 
+    context.subscriptions.push(
+        vscode.languages.registerHoverProvider("lua", {
+            async provideHover(document, position) {
+                if (
+                    !vscode.workspace
+                        .getConfiguration("selene")
+                        .get<boolean>("hover")
+                ) {
+                    return
+                }
+
+                const wordRange = document.getWordRangeAtPosition(position)
+                const pathRange = document.getWordRangeAtPosition(
+                    position,
+                    /[A-Za-z_]\w*(\s*[.:]\s*[A-Za-z_]\w*)*/,
+                )
+
+                if (wordRange === undefined || pathRange === undefined) {
+                    return
+                }
+
+                if (!(await trySelene)) {
+                    return
+                }
+
+                // Only up to the hovered name, so that hovering over `math` in `math.floor` shows `math`
+                const name = document
+                    .getText(new vscode.Range(pathRange.start, wordRange.end))
+                    .replace(/\s/g, "")
+
+                const output = await selene
+                    .seleneCommand(
+                        context.globalStorageUri,
+                        `hover ${name}`,
+                        selene.Expectation.Stdout,
+                        vscode.workspace.getWorkspaceFolder(document.uri),
+                    )
+                    .catch(() => null)
+
+                if (!output) {
+                    return
+                }
+
+                return new vscode.Hover(
+                    new vscode.MarkdownString(output),
+                    wordRange,
+                )
+            },
+        }),
+    )
+
+    context.subscriptions.push(
+        vscode.languages.registerCompletionItemProvider(
+            "lua",
+            {
+                async provideCompletionItems(document, position) {
+                    if (
+                        !vscode.workspace
+                            .getConfiguration("selene")
+                            .get<boolean>("completion")
+                    ) {
+                        return
+                    }
+
+                    const prefix = document
+                        .lineAt(position)
+                        .text.slice(0, position.character)
+                        .match(/[A-Za-z_]\w*([.:]\w*)*$/)
+
+                    if (prefix === null || !(await trySelene)) {
+                        return
+                    }
+
+                    const output = await selene
+                        .seleneCommand(
+                            context.globalStorageUri,
+                            `completions ${prefix[0]}`,
+                            selene.Expectation.Stdout,
+                            vscode.workspace.getWorkspaceFolder(document.uri),
+                        )
+                        .catch(() => null)
+
+                    if (!output) {
+                        return
+                    }
+
+                    const kinds = {
+                        function: vscode.CompletionItemKind.Function,
+                        method: vscode.CompletionItemKind.Method,
+                        field: vscode.CompletionItemKind.Field,
+                        module: vscode.CompletionItemKind.Module,
+                    }
+
+                    const completions: Completion[] = JSON.parse(output)
+                    return completions.map((completion) => {
+                        const item = new vscode.CompletionItem(
+                            completion.label,
+                            kinds[completion.kind],
+                        )
+
+                        item.detail = completion.detail ?? undefined
+
+                        if (completion.deprecated) {
+                            item.tags = [vscode.CompletionItemTag.Deprecated]
+                        }
+
+                        return item
+                    })
+                },
+            },
+            ".",
+            ":",
+        ),
+    )
+
+    context.subscriptions.push(
+        vscode.languages.registerSignatureHelpProvider(
+            "lua",
+            {
+                async provideSignatureHelp(document, position) {
+                    if (
+                        !vscode.workspace
+                            .getConfiguration("selene")
+                            .get<boolean>("signatureHelp")
+                    ) {
+                        return
+                    }
+
+                    const call = findCall(
+                        document.getText(
+                            new vscode.Range(new vscode.Position(0, 0), position),
+                        ),
+                    )
+
+                    if (call === undefined || !(await trySelene)) {
+                        return
+                    }
+
+                    const output = await selene
+                        .seleneCommand(
+                            context.globalStorageUri,
+                            `signature-help ${call.name}`,
+                            selene.Expectation.Stdout,
+                            vscode.workspace.getWorkspaceFolder(document.uri),
+                        )
+                        .catch(() => null)
+
+                    if (!output) {
+                        return
+                    }
+
+                    const data: SignatureHelp = JSON.parse(output)
+                    const signature = new vscode.SignatureInformation(
+                        data.label,
+                        data.documentation ?? undefined,
+                    )
+
+                    signature.parameters = data.parameters.map(
+                        (parameter) =>
+                            new vscode.ParameterInformation(
+                                parameter.label,
+                                parameter.documentation,
+                            ),
+                    )
+
+                    const help = new vscode.SignatureHelp()
+                    help.signatures = [signature]
+                    help.activeSignature = 0
+                    help.activeParameter = Math.min(
+                        call.argument,
+                        Math.max(signature.parameters.length - 1, 0),
+                    )
+
+                    return help
+                },
+            },
+            "(",
+            ",",
+        ),
+    )
+
+    async function findLocal(
+        document: vscode.TextDocument,
+        position: vscode.Position,
+    ): Promise<
+        { declaration: vscode.Range; references: vscode.Range[] } | undefined
+    > {
+        if (
+            !vscode.workspace
+                .getConfiguration("selene")
+                .get<boolean>("references")
+        ) {
+            return
+        }
+
+        if (!(await trySelene)) {
+            return
+        }
+
+        const text = document.getText()
+        const offset = Buffer.byteLength(
+            text.slice(0, document.offsetAt(position)),
+            "utf-8",
+        )
+
+        const output = await selene
+            .seleneCommand(
+                context.globalStorageUri,
+                `references - ${offset}`,
+                selene.Expectation.Stdout,
+                vscode.workspace.getWorkspaceFolder(document.uri),
+                text,
+            )
+            .catch(() => null)
+
+        if (!output) {
+            return
+        }
+
+        const data: Local = JSON.parse(output)
+        const byteOffsets = new Set<number>()
+        for (const [start, end] of [data.declaration, ...data.references]) {
+            byteOffsets.add(start)
+            byteOffsets.add(end)
+        }
+        const byteOffsetMap = byteToCharMap(document, byteOffsets)
+
+        const toRange = ([start, end]: [number, number]) =>
+            new vscode.Range(
+                document.positionAt(byteOffsetMap.get(start) ?? start),
+                document.positionAt(byteOffsetMap.get(end) ?? end),
+            )
+
+        return {
+            declaration: toRange(data.declaration),
+            references: data.references.map(toRange),
+        }
+    }
+
+    context.subscriptions.push(
+        vscode.languages.registerDefinitionProvider("lua", {
+            async provideDefinition(document, position) {
+                const local = await findLocal(document, position)
+                if (local === undefined) {
+                    return
+                }
+
+                return new vscode.Location(document.uri, local.declaration)
+            },
+        }),
+    )
+
+    context.subscriptions.push(
+        vscode.languages.registerReferenceProvider("lua", {
+            async provideReferences(document, position, referenceContext) {
+                const local = await findLocal(document, position)
+                if (local === undefined) {
+                    return
+                }
+
+                const ranges = referenceContext.includeDeclaration
+                    ? [local.declaration, ...local.references]
+                    : local.references
+
+                return ranges.map(
+                    (range) => new vscode.Location(document.uri, range),
+                )
+            },
+        }),
+    )
+
+    context.subscriptions.push(
+        vscode.languages.registerRenameProvider("lua", {
+            async prepareRename(document, position) {
+                const local = await findLocal(document, position)
+                if (local === undefined) {
+                    throw new Error("Only local variables can be renamed.")
+                }
+
+                return [local.declaration, ...local.references].find((range) =>
+                    range.contains(position),
+                )
+            },
+
+            async provideRenameEdits(document, position, newName) {
+                if (!/^[A-Za-z_]\w*$/.test(newName)) {
+                    throw new Error(`\`${newName}\` isn't a valid name.`)
+                }
+
+                const local = await findLocal(document, position)
+                if (local === undefined) {
+                    return
+                }
+
+                const edit = new vscode.WorkspaceEdit()
+                for (const range of [local.declaration, ...local.references]) {
+                    edit.replace(document.uri, range, newName)
+                }
+
+                return edit
+            },
+        }),
+    )
+
+    context.subscriptions.push(
+        vscode.languages.registerDocumentSemanticTokensProvider(
+            "lua",
+            {
+                async provideDocumentSemanticTokens(document) {
+                    if (
+                        !vscode.workspace
+                            .getConfiguration("selene")
+                            .get<boolean>("semanticHighlighting")
+                    ) {
+                        return
+                    }
+
+                    if (!(await trySelene)) {
+                        return
+                    }
+
+                    const output = await selene
+                        .seleneCommand(
+                            context.globalStorageUri,
+                            "semantic-tokens -",
+                            selene.Expectation.Stdout,
+                            vscode.workspace.getWorkspaceFolder(document.uri),
+                            document.getText(),
+                        )
+                        .catch(() => null)
+
+                    if (!output) {
+                        return
+                    }
+
+                    const tokens: SemanticToken[] = JSON.parse(output)
+                    const byteOffsets = new Set<number>()
+                    for (const token of tokens) {
+                        byteOffsets.add(token.range[0])
+                        byteOffsets.add(token.range[1])
+                    }
+
+                    const byteOffsetMap = byteToCharMap(document, byteOffsets)
+                    const builder = new vscode.SemanticTokensBuilder(
+                        semanticTokensLegend,
+                    )
+
+                    for (const token of tokens) {
+                        const [start, end] = token.range
+                        const modifiers: string[] = []
+
+                        if (token.kind === "standard_library") {
+                            modifiers.push("defaultLibrary")
+                        } else if (token.kind === "global") {
+                            modifiers.push("global")
+                        }
+
+                        if (token.deprecated) {
+                            modifiers.push("deprecated")
+                        }
+
+                        builder.push(
+                            new vscode.Range(
+                                document.positionAt(
+                                    byteOffsetMap.get(start) ?? start,
+                                ),
+                                document.positionAt(
+                                    byteOffsetMap.get(end) ?? end,
+                                ),
+                            ),
+                            token.kind === "parameter"
+                                ? "parameter"
+                                : "variable",
+                            modifiers,
+                        )
+                    }
+
+                    return builder.build()
+                },
+            },
+            semanticTokensLegend,
+        ),
+    )
+
+    let refreshCancellation: vscode.CancellationTokenSource | undefined
+    const refreshingUris = new Set<string>()
+
+    // Lints everything again, such as after selene.toml changes. Open documents are linted right
+    // away, and the rest in the background, stopping if another refresh starts.
+    async function refreshDiagnostics() {
+        refreshCancellation?.cancel()
+        const cancellation = new vscode.CancellationTokenSource()
+        refreshCancellation = cancellation
+
+        await loadFileExtensions()
+
+        const openDocuments = vscode.workspace.textDocuments.filter(isLinted)
+        await Promise.all(openDocuments.map(lint))
+
+        const queued = new Set(
+            openDocuments.map((document) => document.uri.toString()),
+        )
+        const queue: vscode.Uri[] = []
+        const enqueue = (uri: vscode.Uri) => {
+            if (!queued.has(uri.toString())) {
+                queued.add(uri.toString())
+                queue.push(uri)
+            }
+        }
+
+        diagnosticsCollection.forEach(enqueue)
+
+        if (
+            vscode.workspace
+                .getConfiguration("selene")
+                .get<boolean>("lintWorkspace")
+        ) {
+            for (const uri of await vscode.workspace.findFiles(
+                `**/*.{${fileExtensions.extensions.join(",")}}`,
+            )) {
+                enqueue(uri)
+            }
+        }
+
+        if (queue.length === 0 || cancellation.token.isCancellationRequested) {
+            return
+        }
+
+        await vscode.window.withProgress(
+            { location: vscode.ProgressLocation.Window, title: "selene" },
+            async (progress) => {
+                for (const [index, uri] of queue.entries()) {
+                    if (cancellation.token.isCancellationRequested) {
+                        return
+                    }
+
+                    progress.report({
+                        message: `Linting ${index + 1}/${queue.length}`,
+                    })
+
+                    refreshingUris.add(uri.toString())
+
+                    try {
+                        await lint(await vscode.workspace.openTextDocument(uri))
+                    } catch {
+                        // The file was deleted since it was linted last
+                        diagnosticsCollection.delete(uri)
+                    } finally {
+                        refreshingUris.delete(uri.toString())
+                    }
+                }
+            },
+        )
+    }
+
+    await loadFileExtensions()
+
+    const configWatcher = vscode.workspace.createFileSystemWatcher(
+        `**/{selene.toml,${fileExtensions.std_extensions
+            .map((extension) => `*.${extension}`)
+            .join(",")}}`,
+    )
+    context.subscriptions.push(configWatcher)
+    configWatcher.onDidChange(refreshDiagnostics)
+    configWatcher.onDidCreate(refreshDiagnostics)
+    configWatcher.onDidDelete(refreshDiagnostics)
+
+    if (
+        vscode.workspace
+            .getConfiguration("selene")
+            .get<boolean>("lintWorkspace")
+    ) {
+        refreshDiagnostics()
+    }
+
     let lastTimeout: NodeJS.Timeout
     function listenToChange() {
         switch (
@@ -236,9 +824,18 @@ export async function activate(
             disposable?.dispose()
             disposable = listenToChange()
         }
+
+        if (event.affectsConfiguration("selene.lintWorkspace")) {
+            refreshDiagnostics()
+        }
     })
 
-    vscode.workspace.onDidOpenTextDocument(lint)
+    vscode.workspace.onDidOpenTextDocument((document) => {
+        // Documents opened to be linted in the background are linted there
+        if (!refreshingUris.has(document.uri.toString())) {
+            lint(document)
+        }
+    })
     vscode.workspace.onWillDeleteFiles((event) => {
         for (const documentUri of event.files) {
             diagnosticsCollection.set(documentUri, [])
@@ -0,0 +1,146 @@
+// `selene bench`, for measuring how long selene takes on a codebase and which lints are the slowest.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use selene_lib::{rules::AstContext, Checker};
+
+use crate::walk::{DirectoryWalker, FileMatcher, WalkOptions};
+
+fn collect_files(files: &[PathBuf], file_matcher: &FileMatcher) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    for file in files {
+        if file.is_dir() {
+            paths.extend(
+                DirectoryWalker::new(file, file_matcher, WalkOptions::default())
+                    .filter_map(Result::ok),
+            );
+        } else {
+            paths.push(file.to_owned());
+        }
+    }
+
+    paths.sort();
+    paths
+}
+
+fn per_iteration(duration: Duration, iterations: u32) -> Duration {
+    duration / iterations.max(1)
+}
+
+pub fn bench(
+    checker: &Checker<toml::value::Value>,
+    file_matcher: &FileMatcher,
+    files: &[PathBuf],
+    iterations: u32,
+) {
+    let sources = collect_files(files, file_matcher)
+        .into_iter()
+        .filter_map(|path| Some((fs::read_to_string(&path).ok()?, path)))
+        .collect::<Vec<_>>();
+
+    let bytes = sources.iter().map(|(code, _)| code.len()).sum::<usize>();
+
+    let mut parse_time = Duration::default();
+    let mut asts = Vec::new();
+
+    for (code, path) in &sources {
+        for iteration in 0..iterations {
+            let start = Instant::now();
+            let ast = full_moon::parse(code);
+            parse_time += start.elapsed();
+
+            match ast {
+                Ok(ast) if iteration == 0 => asts.push((ast, path.as_path())),
+                Ok(_) => {}
+                Err(error) => {
+                    crate::error(&format!("Couldn't parse {}: {error}", path.display()));
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut scope_time = Duration::default();
+    let mut ast_contexts = Vec::new();
+
+    for (ast, path) in &asts {
+        for iteration in 0..iterations {
+            let start = Instant::now();
+            let ast_context = AstContext::from_ast_with_path(ast, Path::new(path));
+            scope_time += start.elapsed();
+
+            if iteration == 0 {
+                ast_contexts.push(ast_context);
+            }
+        }
+    }
+
+    let mut lint_times = selene_lib::all_rules()
+        .iter()
+        .map(|lint| {
+            let start = Instant::now();
+
+            for _ in 0..iterations {
+                for ((ast, _), ast_context) in asts.iter().zip(&ast_contexts) {
+                    checker.test_on_lint(lint, ast, ast_context);
+                }
+            }
+
+            (*lint, start.elapsed())
+        })
+        .collect::<Vec<_>>();
+
+    lint_times.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let total_lint_time = lint_times.iter().map(|(_, time)| *time).sum::<Duration>();
+    let total_time = parse_time + scope_time + total_lint_time;
+
+    println!(
+        "Checked {} files ({} KB) {} times each\n",
+        asts.len(),
+        bytes / 1024,
+        iterations,
+    );
+
+    println!(
+        "{:<40} {:>12}",
+        "parsing",
+        format!("{:.2?}", per_iteration(parse_time, iterations))
+    );
+    println!(
+        "{:<40} {:>12}",
+        "scopes",
+        format!("{:.2?}", per_iteration(scope_time, iterations))
+    );
+
+    for (lint, time) in &lint_times {
+        println!(
+            "{:<40} {:>12}",
+            lint,
+            format!("{:.2?}", per_iteration(*time, iterations))
+        );
+    }
+
+    let total_per_iteration = per_iteration(total_time, iterations);
+
+    println!(
+        "\n{:<40} {:>12}",
+        "total",
+        format!("{:.2?}", total_per_iteration)
+    );
+
+    if !total_per_iteration.is_zero() {
+        println!(
+            "{:<40} {:>12}",
+            "throughput",
+            format!(
+                "{:.2} MB/s",
+                bytes as f64 / 1_000_000.0 / total_per_iteration.as_secs_f64()
+            )
+        );
+    }
+}
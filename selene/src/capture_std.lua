@@ -0,0 +1,65 @@
+-- Run by `selene capture-std` to list every global after running an init script.
+-- It returns one line for each global, which is tab separated: the kind of value, its name, and for functions written in Lua,
+-- how many parameters it has and whether it is variadic.
+local init = ...
+if init ~= nil then
+	dofile(init)
+end
+
+local getinfo = debug and debug.getinfo
+
+local function sortedIdentifiers(tbl)
+	local keys = {}
+
+	for key in pairs(tbl) do
+		if type(key) == "string" and key:match("^[%a_][%w_]*$") then
+			keys[#keys + 1] = key
+		end
+	end
+
+	table.sort(keys)
+	return keys
+end
+
+local lines = {}
+local seen = { [_G] = true }
+
+-- Breadth first, so that a table reachable from several places (such as `string` and
+-- `package.loaded.string`) is listed under its shortest name.
+local queue = { { value = _G, prefix = "" } }
+local index = 1
+
+while queue[index] do
+	local current = queue[index]
+	index = index + 1
+
+	for _, key in ipairs(sortedIdentifiers(current.value)) do
+		local value = rawget(current.value, key)
+		local name = current.prefix .. key
+		local kind = type(value)
+
+		if kind == "function" then
+			local info = getinfo and getinfo(value, "Su")
+
+			-- Only Lua 5.2 and later know the parameters, and only for functions written in Lua
+			if info and info.nparams and info.what ~= "C" then
+				lines[#lines + 1] = "function\t" .. name .. "\t" .. info.nparams .. "\t" .. tostring(info.isvararg)
+			else
+				lines[#lines + 1] = "function\t" .. name
+			end
+		elseif kind == "table" then
+			lines[#lines + 1] = "table\t" .. name
+
+			if not seen[value] then
+				seen[value] = true
+				queue[#queue + 1] = { value = value, prefix = name .. "." }
+			end
+		elseif kind == "userdata" or kind == "thread" then
+			lines[#lines + 1] = "any\t" .. name
+		else
+			lines[#lines + 1] = "value\t" .. name
+		end
+	end
+end
+
+return table.concat(lines, "\n") .. "\n"
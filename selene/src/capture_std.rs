@@ -0,0 +1,231 @@
+// Generates a standard library skeleton by running an init script in a real Lua interpreter
+// and listing every global it leaves behind. The interpreter is Lua 5.4, embedded with mlua,
+// which is only built with the `mlua` feature.
+use std::{collections::BTreeSet, path::Path};
+
+use crate::standard_library::collect_standard_library_argument;
+use selene_lib::{
+    standard_library::{
+        Argument, ArgumentType, Field, FieldKind, FunctionBehavior, Observes, PropertyWritability,
+        Required, StandardLibrary,
+    },
+    CheckerConfig,
+};
+
+#[cfg(feature = "mlua")]
+const CAPTURE_SCRIPT: &str = include_str!("capture_std.lua");
+
+pub fn capture_std<V>(
+    config: &CheckerConfig<V>,
+    init: &Path,
+    base: Option<&str>,
+) -> color_eyre::Result<()> {
+    let dump = run_capture_script(init)?;
+
+    let mut library = library_from_dump(&dump);
+
+    if let Some(base) = base {
+        let base_library = collect_standard_library_argument(config, base)?;
+
+        library.globals.retain(|name, _| {
+            base_library
+                .find_global(&name.split('.').collect::<Vec<_>>())
+                .is_none()
+        });
+
+        library.base = Some(base.to_owned());
+    }
+
+    print!("{}", serde_yaml::to_string(&library)?);
+
+    Ok(())
+}
+
+#[cfg(feature = "mlua")]
+fn run_capture_script(init: &Path) -> color_eyre::Result<String> {
+    use mlua::{Lua, LuaOptions, StdLib};
+
+    // The init script is run like the application would, so it gets every library, including
+    // `debug`, which is what knows how many parameters functions take
+    let lua = unsafe { Lua::unsafe_new_with(StdLib::ALL, LuaOptions::new()) };
+
+    lua.load(CAPTURE_SCRIPT)
+        .set_name("capture_std.lua")
+        .call::<_, String>(init.to_string_lossy())
+        .map_err(|error| color_eyre::eyre::eyre!("{error}"))
+}
+
+#[cfg(not(feature = "mlua"))]
+fn run_capture_script(_init: &Path) -> color_eyre::Result<String> {
+    color_eyre::eyre::bail!(
+        "capturing a standard library needs a build of selene with the `mlua` feature, \
+        which this one doesn't have. Install one using `cargo install selene --features mlua`"
+    )
+}
+
+/// Converts the output of capture_std.lua into a standard library.
+/// Everything is as permissive as possible, since all that's known is what exists,
+/// not how it's meant to be used.
+pub fn library_from_dump(dump: &str) -> StandardLibrary {
+    let entries: Vec<Vec<&str>> = dump
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split('\t').collect())
+        .collect();
+
+    let parents: BTreeSet<&str> = entries
+        .iter()
+        .filter_map(|entry| Some(entry.get(1)?.rsplit_once('.')?.0))
+        .collect();
+
+    let mut library = StandardLibrary::default();
+
+    for entry in &entries {
+        let (kind, name) = match entry.as_slice() {
+            [kind, name, ..] => (*kind, *name),
+            _ => continue,
+        };
+
+        let field_kind = match kind {
+            "function" => FieldKind::Function(FunctionBehavior {
+                arguments: arguments(&entry[2..]),
+                method: false,
+                must_use: false,
+                overloads: Vec::new(),
+            }),
+
+            // Tables with fields are already implied by their fields
+            "table" if parents.contains(name) => continue,
+            "table" => FieldKind::Property(PropertyWritability::NewFields),
+
+            "value" => FieldKind::Property(PropertyWritability::ReadOnly),
+
+            _ => FieldKind::Any,
+        };
+
+        library
+            .globals
+            .insert(name.to_owned(), Field::from_field_kind(field_kind));
+    }
+
+    library
+}
+
+fn arguments(parameters: &[&str]) -> Vec<Argument> {
+    let argument = |argument_type| Argument {
+        name: None,
+        required: Required::NotRequired,
+        argument_type,
+        observes: Observes::ReadWrite,
+        range: None,
+        max_length: None,
+    };
+
+    match parameters {
+        [count, variadic] => {
+            let mut arguments: Vec<Argument> = (0..count.parse().unwrap_or(0))
+                .map(|_| argument(ArgumentType::Any))
+                .collect();
+
+            if *variadic == "true" {
+                arguments.push(argument(ArgumentType::Vararg));
+            }
+
+            arguments
+        }
+
+        // Without knowing the parameters, the function has to accept anything
+        _ => vec![argument(ArgumentType::Vararg)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_from_dump() {
+        let library = library_from_dump(
+            "table\t_G\n\
+             value\t_VERSION\n\
+             function\tprint\n\
+             table\tgame\n\
+             function\tgame.spawn\t2\tfalse\n\
+             function\tgame.log\t1\ttrue\n\
+             table\tgame.entities\n\
+             any\tgame.world\n",
+        );
+
+        assert_eq!(
+            library.globals.keys().collect::<Vec<_>>(),
+            vec![
+                "_G",
+                "_VERSION",
+                "game.entities",
+                "game.log",
+                "game.spawn",
+                "game.world",
+                "print"
+            ]
+        );
+
+        assert_eq!(
+            library.globals["game.entities"].field_kind,
+            FieldKind::Property(PropertyWritability::NewFields)
+        );
+
+        assert_eq!(
+            library.globals["_VERSION"].field_kind,
+            FieldKind::Property(PropertyWritability::ReadOnly)
+        );
+
+        assert_eq!(library.globals["game.world"].field_kind, FieldKind::Any);
+
+        let argument_types = |name: &str| match &library.globals[name].field_kind {
+            FieldKind::Function(function) => function
+                .arguments
+                .iter()
+                .map(|argument| argument.argument_type.to_string())
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(argument_types("print"), vec!["..."]);
+        assert_eq!(argument_types("game.spawn"), vec!["any", "any"]);
+        assert_eq!(argument_types("game.log"), vec!["any", "..."]);
+    }
+
+    #[test]
+    #[cfg(feature = "mlua")]
+    fn test_capture_std() {
+        let directory = std::env::temp_dir().join("selene-test-capture-std");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let init = directory.join("init.lua");
+        std::fs::write(
+            &init,
+            "game = { log = function(level, ...) end, entities = {} }\nVERSION = 3\n",
+        )
+        .unwrap();
+
+        let library = library_from_dump(&run_capture_script(&init).unwrap());
+
+        assert!(library.globals.contains_key("print"));
+        assert!(library.globals.contains_key("string.format"));
+        assert_eq!(
+            library.globals["game.entities"].field_kind,
+            FieldKind::Property(PropertyWritability::NewFields)
+        );
+        assert_eq!(
+            library.globals["VERSION"].field_kind,
+            FieldKind::Property(PropertyWritability::ReadOnly)
+        );
+
+        match &library.globals["game.log"].field_kind {
+            FieldKind::Function(function) => assert_eq!(function.arguments.len(), 2),
+            _ => unreachable!(),
+        }
+
+        std::fs::remove_dir_all(&directory).ok();
+    }
+}
@@ -0,0 +1,140 @@
+// Used by --compare-to, which compares the problems found with a report saved from an earlier run
+// with --display-style=json, so that only the problems that are new have to be looked at.
+// Problems are matched by their file, lint, and message, so ones that only moved are unchanged.
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::Deserialize;
+
+type Key = (PathBuf, String, String);
+
+lazy_static::lazy_static! {
+    static ref FOUND: Mutex<Vec<Key>> = Mutex::new(Vec::new());
+}
+
+// `selene .` names files like `./src/main.lua`, and `selene src` like `src/main.lua`
+fn key(file: &Path, code: &str, message: &str) -> Key {
+    (
+        file.components()
+            .filter(|component| *component != Component::CurDir)
+            .collect(),
+        code.to_owned(),
+        message.to_owned(),
+    )
+}
+
+pub fn record(file: &Path, code: &str, message: &str) {
+    FOUND.lock().unwrap().push(key(file, code, message));
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Comparison {
+    /// Problems that aren't in the report.
+    pub new: usize,
+    /// Problems in the report that weren't found this time.
+    pub fixed: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Deserialize)]
+struct ReportedDiagnostic {
+    #[serde(default)]
+    filename: String,
+    code: Option<String>,
+    message: String,
+}
+
+/// Compares every problem recorded so far with the ones in the report.
+pub fn compare_to_report(report: &Path) -> io::Result<Comparison> {
+    let previous = parse_report(&fs::read_to_string(report)?)?;
+    Ok(compare(previous, &FOUND.lock().unwrap()))
+}
+
+// Everything but the diagnostics, such as the summary, is skipped
+fn parse_report(report: &str) -> io::Result<Vec<Key>> {
+    report
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| {
+            let diagnostic: ReportedDiagnostic = serde_json::from_str(line)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+            Ok(key(
+                Path::new(&diagnostic.filename),
+                diagnostic.code.as_deref().unwrap_or_default(),
+                &diagnostic.message,
+            ))
+        })
+        .collect()
+}
+
+// The same problem can be found more than once in a file, so each match uses one of them up
+fn compare(previous: Vec<Key>, current: &[Key]) -> Comparison {
+    let mut remaining: HashMap<Key, usize> = HashMap::new();
+    for key in previous {
+        *remaining.entry(key).or_default() += 1;
+    }
+
+    let mut comparison = Comparison::default();
+
+    for key in current {
+        match remaining.get_mut(key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                comparison.unchanged += 1;
+            }
+
+            _ => comparison.new += 1,
+        }
+    }
+
+    comparison.fixed = remaining.values().sum();
+    comparison
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"{"filename":"./src/a.lua","severity":"Warning","code":"unused_variable","message":"x is defined, but never used","primary_label":{"span":{"start":6,"start_line":0,"start_column":6,"end":7,"end_line":0,"end_column":7},"message":""},"notes":[],"secondary_labels":[]}
+{"filename":"./src/a.lua","severity":"Warning","code":"unused_variable","message":"x is defined, but never used","primary_label":{"span":{"start":20,"start_line":2,"start_column":6,"end":21,"end_line":2,"end_column":7},"message":""},"notes":[],"secondary_labels":[]}
+{"filename":"./src/b.lua","severity":"Error","code":"undefined_variable","message":"`y` is not defined","primary_label":{"span":{"start":6,"start_line":0,"start_column":6,"end":7,"end_line":0,"end_column":7},"message":""},"notes":[],"secondary_labels":[]}
+Results:
+1 errors
+2 warnings
+0 parse errors
+"#;
+
+    #[test]
+    fn test_compare() {
+        let previous = parse_report(REPORT).unwrap();
+        assert_eq!(previous.len(), 3);
+
+        let current = vec![
+            key(
+                Path::new("src/a.lua"),
+                "unused_variable",
+                "x is defined, but never used",
+            ),
+            key(Path::new("src/b.lua"), "empty_if", "empty if block"),
+        ];
+
+        assert_eq!(
+            compare(previous, &current),
+            Comparison {
+                new: 1,
+                fixed: 2,
+                unchanged: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_report() {
+        assert!(parse_report("{\"message\": 1}").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+// Writes diagnostics that point into more than one file, for the lints that can only be checked
+// once every file has been, such as circular_require and duplicate_code.
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use codespan_reporting::diagnostic::{Label as CodespanLabel, Severity as CodespanSeverity};
+use selene_lib::{
+    lines,
+    rules::{Diagnostic, Severity},
+    CheckerDiagnostic,
+};
+
+use crate::{
+    compare, emit_codespan, grouped_output, json_output, locale, sorted_output, sources, OPTIONS,
+};
+
+/// A label in a file other than the one the diagnostic is in.
+pub struct OtherLabel {
+    pub file: PathBuf,
+    pub range: (u32, u32),
+    pub message: String,
+}
+
+/// Records a diagnostic whose primary label is in `file`, to be written by sorted_output along
+/// with every other diagnostic. Other labels are only shown with the rich display style.
+pub fn record(
+    file: &Path,
+    mut diagnostic: Diagnostic,
+    other_labels: &[OtherLabel],
+    severity: Severity,
+) -> io::Result<()> {
+    let codespan_severity = match severity {
+        Severity::Allow => return Ok(()),
+        Severity::Error => CodespanSeverity::Error,
+        Severity::Warning => CodespanSeverity::Warning,
+    };
+
+    locale::localize(&mut diagnostic);
+    compare::record(file, diagnostic.code, &diagnostic.message);
+
+    let lock = OPTIONS.read().unwrap();
+    let opts = lock.as_ref().unwrap();
+
+    let paths = std::iter::once(file)
+        .chain(other_labels.iter().map(|label| label.file.as_path()))
+        .collect::<Vec<_>>();
+
+    // The same file can come up more than once, but only needs to be read once
+    let sources = sources::for_options(opts);
+    let mut contents = Vec::new();
+    let mut content_indexes = HashMap::new();
+    for path in &paths {
+        if !content_indexes.contains_key(path) {
+            content_indexes.insert(*path, contents.len());
+            contents.push(sources.read(path)?);
+        }
+    }
+
+    let mut files = codespan::Files::new();
+    let mut file_ids = HashMap::new();
+    for (path, index) in &content_indexes {
+        file_ids.insert(
+            *path,
+            files.add(path.as_os_str(), contents[*index].as_str()),
+        );
+    }
+
+    let file_id = file_ids[file];
+    let range = diagnostic.primary_label.range;
+
+    let start = lines::location(&files, file_id, range.0);
+
+    let key = sorted_output::Key::new(
+        file,
+        start.line.to_usize(),
+        start.column.to_usize(),
+        diagnostic.code,
+        severity == Severity::Error,
+        &diagnostic.message,
+    );
+
+    let mut output = sorted_output::buffer();
+
+    if opts.luacheck {
+        write!(
+            output,
+            "{}:{}:{}",
+            file.display(),
+            start.line.number(),
+            start.column.number()
+        )?;
+
+        if opts.ranges {
+            let end = lines::location(&files, file_id, range.1);
+            write!(output, "-{}", end.column.to_usize())?;
+        }
+
+        writeln!(
+            output,
+            ": ({}000) [{}] {}\n{}",
+            if severity == Severity::Error {
+                "E"
+            } else {
+                "W"
+            },
+            diagnostic.code,
+            diagnostic.message,
+            diagnostic.rendered_notes().join("\n")
+        )?;
+    } else if opts.group() {
+        grouped_output::record(
+            file,
+            &CheckerDiagnostic {
+                diagnostic,
+                severity,
+            },
+            start.line.to_usize() + 1,
+            start.column.to_usize() + 1,
+        );
+
+        return Ok(());
+    } else {
+        let notes = json_output::Notes::new(&diagnostic);
+        let mut codespan_diagnostic =
+            diagnostic.into_codespan_diagnostic(file_id, codespan_severity);
+
+        codespan_diagnostic
+            .labels
+            .extend(other_labels.iter().map(|label| {
+                CodespanLabel::secondary(
+                    file_ids[label.file.as_path()],
+                    codespan::Span::new(label.range.0, label.range.1),
+                )
+                .with_message(label.message.clone())
+            }));
+
+        emit_codespan(&mut output, &files, &codespan_diagnostic, Some(&notes));
+    }
+
+    sorted_output::record(key, output);
+
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::standard_library::collect_standard_library_argument;
+use selene_lib::{
+    standard_library::{
+        Argument, ArgumentType, Field, FieldKind, FunctionBehavior, PropertyWritability, Required,
+        StandardLibrary,
+    },
+    CheckerConfig,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Changed { before: String, after: String },
+}
+
+pub fn diff_std<V>(config: &CheckerConfig<V>, old: &str, new: &str) -> color_eyre::Result<()> {
+    let old_library = collect_standard_library_argument(config, old)?;
+    let new_library = collect_standard_library_argument(config, new)?;
+
+    let changes = diff(&old_library, &new_library);
+
+    let mut added = 0;
+    let mut removed = 0;
+    let mut changed = 0;
+
+    for change in &changes {
+        match change {
+            Change::Added(description) => {
+                added += 1;
+                println!("+ {description}");
+            }
+
+            Change::Removed(description) => {
+                removed += 1;
+                println!("- {description}");
+            }
+
+            Change::Changed { before, after } => {
+                changed += 1;
+                println!("~ {before}");
+                println!("  -> {after}");
+            }
+        }
+    }
+
+    if !changes.is_empty() {
+        println!();
+    }
+
+    println!("{added} added, {removed} removed, {changed} changed");
+
+    Ok(())
+}
+
+pub fn diff(old: &StandardLibrary, new: &StandardLibrary) -> Vec<Change> {
+    let mut changes = diff_fields(&old.globals, &new.globals, "");
+
+    let struct_names: BTreeSet<&String> = old.structs.keys().chain(new.structs.keys()).collect();
+    let empty = BTreeMap::new();
+
+    for struct_name in struct_names {
+        changes.extend(diff_fields(
+            old.structs.get(struct_name).unwrap_or(&empty),
+            new.structs.get(struct_name).unwrap_or(&empty),
+            &format!("<{struct_name}>."),
+        ));
+    }
+
+    changes
+}
+
+fn diff_fields(
+    old: &BTreeMap<String, Field>,
+    new: &BTreeMap<String, Field>,
+    prefix: &str,
+) -> Vec<Change> {
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let full_name = format!("{prefix}{name}");
+
+            let before = old.get(name).and_then(|field| describe(&full_name, field));
+            let after = new.get(name).and_then(|field| describe(&full_name, field));
+
+            match (before, after) {
+                (None, Some(after)) => Some(Change::Added(after)),
+                (Some(before), None) => Some(Change::Removed(before)),
+                (Some(before), Some(after)) if before != after => {
+                    Some(Change::Changed { before, after })
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Describes a field the way it would be used in code, such as `string.rep(string, number)`.
+/// Returns None for fields that are removed.
+pub fn describe(name: &str, field: &Field) -> Option<String> {
+    let mut description = match &field.field_kind {
+        FieldKind::Any => format!("{name} (any)"),
+
+        FieldKind::Function(function) => {
+            let mut signature = std::iter::once(signature(name, function))
+                .chain(overload_signatures(name, function))
+                .collect::<Vec<_>>()
+                .join(" or ");
+
+            if function.must_use {
+                signature.push_str(" (must use)");
+            }
+
+            signature
+        }
+
+        FieldKind::Property(writability) => format!(
+            "{name} ({} property)",
+            match writability {
+                PropertyWritability::ReadOnly => "read-only",
+                PropertyWritability::NewFields => "new-fields",
+                PropertyWritability::OverrideFields => "override-fields",
+                PropertyWritability::FullWrite => "full-write",
+            }
+        ),
+
+        FieldKind::Struct(struct_name) => format!("{name} (struct {struct_name})"),
+
+        FieldKind::Removed => return None,
+    };
+
+    if let Some(deprecated) = &field.deprecated {
+        description.push_str(&format!(" [deprecated: {}]", deprecated.message));
+    }
+
+    if let Some(restricted) = &field.restricted {
+        description.push_str(&format!(" [restricted: {restricted}]"));
+    }
+
+    Some(description)
+}
+
+/// Formats a function with its arguments, such as `Instance:FindFirstChild(string, bool?)`.
+pub fn signature(name: &str, function: &FunctionBehavior) -> String {
+    arguments_signature(name, function.method, &function.arguments)
+}
+
+/// Like `signature`, but for each of the other ways the function can be called.
+pub fn overload_signatures(name: &str, function: &FunctionBehavior) -> Vec<String> {
+    function
+        .overloads
+        .iter()
+        .map(|overload| arguments_signature(name, function.method, &overload.arguments))
+        .collect()
+}
+
+fn arguments_signature(name: &str, method: bool, arguments: &[Argument]) -> String {
+    let name = match (method, name.rsplit_once('.')) {
+        (true, Some((base, method))) => format!("{base}:{method}"),
+        _ => name.to_owned(),
+    };
+
+    format!(
+        "{name}({})",
+        arguments
+            .iter()
+            .map(describe_argument)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+pub fn describe_argument(argument: &Argument) -> String {
+    let description = match (&argument.argument_type, &argument.required) {
+        // Varargs are always optional, so there's no need to say so
+        (ArgumentType::Vararg, _) | (_, Required::Required(_)) => {
+            argument.argument_type.to_string()
+        }
+        (argument_type, Required::NotRequired) => format!("{argument_type}?"),
+    };
+
+    match &argument.name {
+        Some(name) => format!("{name}: {description}"),
+        None => description,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use selene_lib::standard_library::{Deprecated, Overload};
+
+    fn function(arguments: Vec<Argument>) -> Field {
+        Field::from_field_kind(FieldKind::Function(FunctionBehavior {
+            arguments,
+            method: false,
+            must_use: false,
+            overloads: Vec::new(),
+        }))
+    }
+
+    fn argument(argument_type: ArgumentType, required: Required) -> Argument {
+        Argument {
+            name: None,
+            required,
+            argument_type,
+            observes: Default::default(),
+            range: None,
+            max_length: None,
+        }
+    }
+
+    fn library(globals: Vec<(&str, Field)>) -> StandardLibrary {
+        let mut library = StandardLibrary::default();
+        library.globals = globals
+            .into_iter()
+            .map(|(name, field)| (name.to_owned(), field))
+            .collect();
+        library
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            describe(
+                "string.rep",
+                &function(vec![
+                    argument(ArgumentType::String, Required::Required(None)),
+                    argument(ArgumentType::Number, Required::Required(None)),
+                    argument(ArgumentType::String, Required::NotRequired),
+                ])
+            ),
+            Some("string.rep(string, number, string?)".to_owned())
+        );
+
+        let mut table_remove = function(vec![argument(
+            ArgumentType::Table,
+            Required::Required(None),
+        )]);
+        if let FieldKind::Function(function) = &mut table_remove.field_kind {
+            function.overloads.push(Overload {
+                arguments: vec![
+                    argument(ArgumentType::Table, Required::Required(None)),
+                    argument(ArgumentType::Number, Required::Required(None)),
+                ],
+            });
+        }
+
+        assert_eq!(
+            describe("table.remove", &table_remove),
+            Some("table.remove(table) or table.remove(table, number)".to_owned())
+        );
+
+        assert_eq!(
+            describe(
+                "_VERSION",
+                &Field::from_field_kind(FieldKind::Property(PropertyWritability::ReadOnly))
+            ),
+            Some("_VERSION (read-only property)".to_owned())
+        );
+
+        assert_eq!(
+            describe("setfenv", &Field::from_field_kind(FieldKind::Removed)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = library(vec![
+            ("setfenv", function(vec![])),
+            ("unpack", function(vec![])),
+            ("print", function(vec![])),
+        ]);
+
+        let new = library(vec![
+            ("bit32.band", function(vec![])),
+            ("setfenv", Field::from_field_kind(FieldKind::Removed)),
+            (
+                "unpack",
+                Field {
+                    deprecated: Some(Deprecated {
+                        message: "use table.unpack instead".to_owned(),
+                        replace: Vec::new(),
+                    }),
+                    ..function(vec![])
+                },
+            ),
+            ("print", function(vec![])),
+        ]);
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Change::Added("bit32.band()".to_owned()),
+                Change::Removed("setfenv()".to_owned()),
+                Change::Changed {
+                    before: "unpack()".to_owned(),
+                    after: "unpack() [deprecated: use table.unpack instead]".to_owned(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,532 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::{
+    diff_std::{describe_argument, overload_signatures, signature},
+    opts::DocFormat,
+    standard_library::collect_standard_library_argument,
+};
+use selene_lib::{
+    standard_library::{Field, FieldKind, PropertyWritability, Required, StandardLibrary},
+    CheckerConfig,
+};
+
+pub fn doc_std<V>(
+    config: &CheckerConfig<V>,
+    library: &str,
+    format: DocFormat,
+) -> color_eyre::Result<()> {
+    let standard_library = collect_standard_library_argument(config, library)?;
+
+    match format {
+        DocFormat::Markdown => print!("{}", markdown(library, &standard_library)),
+    }
+
+    Ok(())
+}
+
+pub fn hover<V>(
+    config: &CheckerConfig<V>,
+    name: &str,
+    library: Option<&str>,
+) -> color_eyre::Result<()> {
+    let standard_library =
+        collect_standard_library_argument(config, library.unwrap_or_else(|| config.std()))?;
+
+    match hover_markdown(&standard_library, name) {
+        Some(markdown) => print!("{markdown}"),
+        None => color_eyre::eyre::bail!("it isn't in the standard library"),
+    }
+
+    Ok(())
+}
+
+/// The documentation for one global, found the same way as when checking code, so that fields of
+/// structs such as `game.Workspace` work. Methods can be written as `object:Method`.
+pub fn hover_markdown(library: &StandardLibrary, name: &str) -> Option<String> {
+    let name = name.replace(':', ".");
+    let names = name.split('.').collect::<Vec<_>>();
+
+    let field = library.find_global(&names)?;
+    if field.field_kind == FieldKind::Removed {
+        return None;
+    }
+
+    let mut output = String::new();
+    write_field(&mut output, "###", &name, field);
+    Some(output.trim_start().to_owned())
+}
+
+pub fn signature_help<V>(
+    config: &CheckerConfig<V>,
+    name: &str,
+    library: Option<&str>,
+) -> color_eyre::Result<()> {
+    let standard_library =
+        collect_standard_library_argument(config, library.unwrap_or_else(|| config.std()))?;
+
+    match signature_help_json(&standard_library, name) {
+        Some(json) => println!("{json}"),
+        None => color_eyre::eyre::bail!("it isn't a function in the standard library"),
+    }
+
+    Ok(())
+}
+
+/// The signature of a function along with each of its parameters, in the shape of the signature
+/// help that editors show while typing a call.
+pub fn signature_help_json(library: &StandardLibrary, name: &str) -> Option<serde_json::Value> {
+    let name = name.replace(':', ".");
+    let names = name.split('.').collect::<Vec<_>>();
+
+    let field = library.find_global(&names)?;
+    let function = match &field.field_kind {
+        FieldKind::Function(function) => function,
+        _ => return None,
+    };
+
+    let label = signature(&name, function);
+
+    // Parameters are given as where they are in the label, counted in UTF-16 like editors do,
+    // since a parameter's type can also show up in the function's name, such as `string`
+    let mut position = label.find('(').unwrap() + 1;
+
+    let parameters = function
+        .arguments
+        .iter()
+        .map(|argument| {
+            let description = describe_argument(argument);
+            let start = label[..position].encode_utf16().count();
+            let end = start + description.encode_utf16().count();
+            position += description.len() + ", ".len();
+
+            let documentation = match &argument.required {
+                Required::NotRequired => "Optional.".to_owned(),
+                Required::Required(None) => "Required.".to_owned(),
+                Required::Required(Some(message)) => format!("Required, {message}"),
+            };
+
+            serde_json::json!({
+                "label": [start, end],
+                "documentation": documentation,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Some(serde_json::json!({
+        "label": label,
+        "documentation": field.deprecated.as_ref().map(|deprecated| format!("Deprecated: {}", deprecated.message)),
+        "parameters": parameters,
+    }))
+}
+
+pub fn completions<V>(
+    config: &CheckerConfig<V>,
+    prefix: &str,
+    library: Option<&str>,
+) -> color_eyre::Result<()> {
+    let standard_library =
+        collect_standard_library_argument(config, library.unwrap_or_else(|| config.std()))?;
+
+    println!("{}", completions_json(&standard_library, prefix));
+    Ok(())
+}
+
+/// Everything that can finish the global path being typed, such as `math.floor` and `math.fmod`
+/// for `math.f`, in the shape of the completion items that editors show. After a `:`, only
+/// methods are given.
+pub fn completions_json(library: &StandardLibrary, prefix: &str) -> serde_json::Value {
+    let (parent, partial, methods_only) = match prefix.rfind(['.', ':']) {
+        Some(index) => (
+            Some(prefix[..index].replace(':', ".")),
+            &prefix[index + 1..],
+            prefix[index..].starts_with(':'),
+        ),
+        None => (None, prefix, false),
+    };
+
+    // Namespaces such as `math` aren't globals themselves, so they have no field
+    let mut children: BTreeMap<&str, Option<&Field>> = BTreeMap::new();
+
+    let strukt = parent.as_ref().and_then(|parent| {
+        let names = parent.split('.').collect::<Vec<_>>();
+        match &library.find_global(&names)?.field_kind {
+            FieldKind::Struct(struct_name) => library.structs.get(struct_name),
+            _ => None,
+        }
+    });
+
+    match strukt {
+        Some(strukt) => {
+            for (name, field) in strukt {
+                children.insert(name, Some(field));
+            }
+        }
+
+        None => {
+            for (name, field) in &library.globals {
+                let rest = match &parent {
+                    Some(parent) => match name
+                        .strip_prefix(parent.as_str())
+                        .and_then(|rest| rest.strip_prefix('.'))
+                    {
+                        Some(rest) => rest,
+                        None => continue,
+                    },
+                    None => name,
+                };
+
+                match rest.split_once('.') {
+                    Some((child, _)) => {
+                        children.entry(child).or_insert(None);
+                    }
+                    None => {
+                        children.insert(rest, Some(field));
+                    }
+                }
+            }
+        }
+    }
+
+    let items = children
+        .into_iter()
+        .filter(|(name, field)| {
+            *name != "*"
+                && name.starts_with(partial)
+                && !matches!(field, Some(field) if field.field_kind == FieldKind::Removed)
+        })
+        .filter_map(|(name, field)| {
+            let full_name = match &parent {
+                Some(parent) => format!("{parent}.{name}"),
+                None => name.to_owned(),
+            };
+
+            let (kind, detail) = match field.map(|field| &field.field_kind) {
+                Some(FieldKind::Function(function)) => (
+                    if function.method {
+                        "method"
+                    } else {
+                        "function"
+                    },
+                    Some(signature(&full_name, function)),
+                ),
+                Some(FieldKind::Struct(struct_name)) => ("field", Some(struct_name.clone())),
+                Some(_) => ("field", None),
+                None => ("module", None),
+            };
+
+            if methods_only && kind != "method" {
+                return None;
+            }
+
+            Some(serde_json::json!({
+                "label": name,
+                "kind": kind,
+                "detail": detail,
+                "deprecated": matches!(field, Some(field) if field.deprecated.is_some()),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::Value::Array(items)
+}
+
+pub fn markdown(title: &str, library: &StandardLibrary) -> String {
+    let mut output = format!("# {title}\n");
+
+    // Globals are grouped by what comes before the first dot, so that `math.floor` and
+    // `math.ceil` end up under the same heading.
+    let mut groups: BTreeMap<&str, Vec<(&String, &Field)>> = BTreeMap::new();
+
+    for (name, field) in &library.globals {
+        if field.field_kind == FieldKind::Removed {
+            continue;
+        }
+
+        let group = name.split('.').next().unwrap();
+        groups.entry(group).or_default().push((name, field));
+    }
+
+    for (group, fields) in groups {
+        write!(output, "\n## {group}\n").unwrap();
+
+        for (name, field) in fields {
+            write_field(&mut output, "###", name, field);
+        }
+    }
+
+    if !library.structs.is_empty() {
+        output.push_str("\n## Structs\n");
+
+        for (struct_name, fields) in &library.structs {
+            write!(output, "\n### {struct_name}\n").unwrap();
+
+            for (name, field) in fields {
+                if field.field_kind != FieldKind::Removed {
+                    write_field(&mut output, "####", &format!("{struct_name}.{name}"), field);
+                }
+            }
+        }
+    }
+
+    output
+}
+
+fn write_field(output: &mut String, heading: &str, name: &str, field: &Field) {
+    let (title, description) = match &field.field_kind {
+        FieldKind::Any => (name.to_owned(), "Any value.".to_owned()),
+
+        FieldKind::Function(function) => (
+            signature(name, function),
+            format!(
+                "{}.{}{}",
+                if function.method {
+                    "Method"
+                } else {
+                    "Function"
+                },
+                if function.must_use {
+                    " Its return value must be used."
+                } else {
+                    ""
+                },
+                overload_signatures(name, function)
+                    .iter()
+                    .map(|overload| format!(" Can also be called as `{overload}`."))
+                    .collect::<String>()
+            ),
+        ),
+
+        FieldKind::Property(writability) => (
+            name.to_owned(),
+            match writability {
+                PropertyWritability::ReadOnly => "Read-only property.",
+                PropertyWritability::NewFields => {
+                    "Property that new fields can be added to, but that can't be overwritten."
+                }
+                PropertyWritability::OverrideFields => {
+                    "Property that can be overwritten, but that new fields can't be added to."
+                }
+                PropertyWritability::FullWrite => {
+                    "Property that can be overwritten and that new fields can be added to."
+                }
+            }
+            .to_owned(),
+        ),
+
+        FieldKind::Struct(struct_name) => (name.to_owned(), format!("Struct `{struct_name}`.")),
+
+        FieldKind::Removed => unreachable!("removed fields are skipped"),
+    };
+
+    write!(output, "\n{heading} `{title}`\n\n{description}\n").unwrap();
+
+    if let FieldKind::Function(function) = &field.field_kind {
+        if !function.arguments.is_empty() {
+            output.push_str("\n| Argument | Type | Required |\n| --- | --- | --- |\n");
+
+            for (index, argument) in function.arguments.iter().enumerate() {
+                let required = match &argument.required {
+                    Required::NotRequired => "No".to_owned(),
+                    Required::Required(None) => "Yes".to_owned(),
+                    Required::Required(Some(message)) => format!("Yes, {message}"),
+                };
+
+                writeln!(
+                    output,
+                    "| {} | `{}` | {} |",
+                    index + 1,
+                    table_cell(&argument.argument_type.to_string()),
+                    table_cell(&required),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    if let Some(deprecated) = &field.deprecated {
+        write!(output, "\n> **Deprecated:** {}\n", deprecated.message).unwrap();
+
+        for replacement in &deprecated.replace {
+            write!(output, ">\n> Replace with `{replacement}`\n").unwrap();
+        }
+    }
+
+    if let Some(restricted) = &field.restricted {
+        write!(output, "\n> **Restricted:** {restricted}\n").unwrap();
+    }
+}
+
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use selene_lib::standard_library::{
+        Argument, ArgumentType, Deprecated, FunctionBehavior, Observes,
+    };
+
+    #[test]
+    fn test_markdown() {
+        let mut library = StandardLibrary::default();
+
+        library.globals.insert(
+            "math.floor".to_owned(),
+            Field::from_field_kind(FieldKind::Function(FunctionBehavior {
+                arguments: vec![Argument {
+                    name: None,
+                    required: Required::Required(None),
+                    argument_type: ArgumentType::Number,
+                    observes: Observes::ReadWrite,
+                    range: None,
+                    max_length: None,
+                }],
+                method: false,
+                must_use: true,
+                overloads: Vec::new(),
+            })),
+        );
+
+        library.globals.insert(
+            "math.pi".to_owned(),
+            Field::from_field_kind(FieldKind::Property(PropertyWritability::ReadOnly)),
+        );
+
+        library.globals.insert(
+            "unpack".to_owned(),
+            Field {
+                field_kind: FieldKind::Function(FunctionBehavior {
+                    arguments: vec![Argument {
+                        name: None,
+                        required: Required::NotRequired,
+                        argument_type: ArgumentType::Table,
+                        observes: Observes::ReadWrite,
+                        range: None,
+                        max_length: None,
+                    }],
+                    method: false,
+                    must_use: false,
+                    overloads: Vec::new(),
+                }),
+                deprecated: Some(Deprecated {
+                    message: "use table.unpack instead".to_owned(),
+                    replace: vec!["table.unpack(%...)".to_owned()],
+                }),
+                restricted: None,
+                property_type: None,
+            },
+        );
+
+        library.globals.insert(
+            "setfenv".to_owned(),
+            Field::from_field_kind(FieldKind::Removed),
+        );
+
+        assert_eq!(
+            markdown("test", &library),
+            "# test
+
+## math
+
+### `math.floor(number)`
+
+Function. Its return value must be used.
+
+| Argument | Type | Required |
+| --- | --- | --- |
+| 1 | `number` | Yes |
+
+### `math.pi`
+
+Read-only property.
+
+## unpack
+
+### `unpack(table?)`
+
+Function.
+
+| Argument | Type | Required |
+| --- | --- | --- |
+| 1 | `table` | No |
+
+> **Deprecated:** use table.unpack instead
+>
+> Replace with `table.unpack(%...)`
+"
+        );
+    }
+
+    #[test]
+    fn test_hover_markdown() {
+        let library = StandardLibrary::from_name("lua51").unwrap();
+
+        let markdown = hover_markdown(&library, "string.rep").unwrap();
+        assert!(markdown.starts_with("### `string.rep(string, number)`\n\nFunction."));
+        assert!(markdown.contains("| 2 | `number` | Yes |"));
+
+        assert_eq!(hover_markdown(&library, "string:rep"), Some(markdown));
+
+        assert_eq!(hover_markdown(&library, "string.nonexistent"), None);
+        assert_eq!(hover_markdown(&library, "nonexistent"), None);
+    }
+
+    #[test]
+    fn test_signature_help_json() {
+        let mut library = StandardLibrary::from_name("lua51").unwrap();
+
+        if let Some(Field {
+            field_kind: FieldKind::Function(function),
+            ..
+        }) = library.globals.get_mut("string.rep")
+        {
+            function.arguments[0].name = Some("s".to_owned());
+        }
+
+        assert_eq!(
+            signature_help_json(&library, "string.rep").unwrap(),
+            serde_json::json!({
+                "label": "string.rep(s: string, number)",
+                "documentation": null,
+                "parameters": [
+                    { "label": [11, 20], "documentation": "Required." },
+                    { "label": [22, 28], "documentation": "Required." },
+                ],
+            })
+        );
+
+        assert_eq!(signature_help_json(&library, "math.pi"), None);
+        assert_eq!(signature_help_json(&library, "nonexistent"), None);
+    }
+
+    #[test]
+    fn test_completions_json() {
+        let library = StandardLibrary::from_name("lua51").unwrap();
+
+        assert_eq!(
+            completions_json(&library, "math.fl"),
+            serde_json::json!([{
+                "label": "floor",
+                "kind": "function",
+                "detail": "math.floor(number)",
+                "deprecated": false,
+            }])
+        );
+
+        let labels = |prefix| {
+            completions_json(&library, prefix)
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["label"].as_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(labels("ma").contains(&"math".to_owned()));
+        assert_eq!(completions_json(&library, "ma")[0]["kind"], "module");
+        assert!(labels("math.").contains(&"pi".to_owned()));
+        assert!(labels("nonexistent.").is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+// Used by dump-lints, so that the documentation and editor plugins can get everything they need
+// to know about the lints from the binary itself.
+use selene_lib::{
+    rules::{RuleType, Severity},
+    LintMetadata,
+};
+use serde_json::json;
+
+use crate::opts::LintsFormat;
+
+pub fn dump_lints(format: LintsFormat) -> serde_json::Result<()> {
+    match format {
+        LintsFormat::Json => {
+            let lints = selene_lib::lint_metadata()
+                .iter()
+                .map(lint_to_json)
+                .collect::<serde_json::Result<Vec<_>>>()?;
+
+            println!("{}", serde_json::to_string_pretty(&lints)?);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn lint_to_json(lint: &LintMetadata) -> serde_json::Result<serde_json::Value> {
+    let default_config = lint
+        .default_config
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()?;
+
+    // Some of the documentation is written with Windows line endings
+    let documentation = lint.documentation.replace("\r\n", "\n");
+
+    Ok(json!({
+        "name": lint.name,
+        "category": match lint.rule_type {
+            RuleType::Complexity => "complexity",
+            RuleType::Correctness => "correctness",
+            RuleType::Performance => "performance",
+            RuleType::Style => "style",
+        },
+        "default_severity": match lint.default_severity {
+            Severity::Allow => "allow",
+            Severity::Error => "deny",
+            Severity::Warning => "warn",
+        },
+        "description": section(&documentation, "What it does"),
+        "luacheck_codes": lint.luacheck_codes,
+        "config_schema": default_config.as_ref().map(schema),
+        "default_config": default_config,
+        "example": section(&documentation, "Example").and_then(first_code_block),
+    }))
+}
+
+// The text under a `## heading` of a lint's documentation
+fn section<'a>(documentation: &'a str, heading: &str) -> Option<&'a str> {
+    let start = documentation.find(&format!("## {heading}\n"))? + heading.len() + 4;
+    let rest = &documentation[start..];
+    let end = rest.find("\n## ").unwrap_or(rest.len());
+
+    Some(rest[..end].trim())
+}
+
+fn first_code_block(text: &str) -> Option<&str> {
+    let start = text.find("```")?;
+    let code = &text[start..];
+    let code = &code[code.find('\n')? + 1..];
+    let end = code.find("```")?;
+
+    Some(code[..end].trim_end())
+}
+
+// Configs can only be serialized, so the schema is made from the default config.
+// This knows the type of every option, but not which values are valid, such as with enums.
+pub fn schema(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Null => json!({}),
+        serde_json::Value::Bool(_) => json!({ "type": "boolean", "default": value }),
+        serde_json::Value::Number(number) if number.is_f64() => {
+            json!({ "type": "number", "default": value })
+        }
+        serde_json::Value::Number(_) => json!({ "type": "integer", "default": value }),
+        serde_json::Value::String(_) => json!({ "type": "string", "default": value }),
+
+        serde_json::Value::Array(items) => match items.first() {
+            Some(item) => json!({ "type": "array", "items": schema(item), "default": value }),
+            None => json!({ "type": "array", "default": value }),
+        },
+
+        serde_json::Value::Object(fields) => json!({
+            "type": "object",
+            "properties": fields
+                .iter()
+                .map(|(name, field)| (name.clone(), schema(field)))
+                .collect::<serde_json::Map<_, _>>(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_to_json() {
+        let lints = selene_lib::lint_metadata();
+        let empty_if = lints.iter().find(|lint| lint.name == "empty_if").unwrap();
+
+        let json = lint_to_json(empty_if).unwrap();
+
+        assert_eq!(json["category"], "style");
+        assert_eq!(json["default_severity"], "warn");
+        assert_eq!(json["description"], "Checks for empty if blocks.");
+        assert_eq!(json["luacheck_codes"], json!(["542"]));
+        assert_eq!(json["default_config"], json!({ "comments_count": false }));
+        assert_eq!(
+            json["config_schema"],
+            json!({
+                "type": "object",
+                "properties": {
+                    "comments_count": { "type": "boolean", "default": false },
+                },
+            })
+        );
+        assert!(json["example"]
+            .as_str()
+            .unwrap()
+            .starts_with("-- Each of these branches count as an empty if."));
+
+        let almost_swapped = lints
+            .iter()
+            .find(|lint| lint.name == "almost_swapped")
+            .unwrap();
+
+        assert_eq!(
+            lint_to_json(almost_swapped).unwrap()["default_config"],
+            json!(null)
+        );
+
+        assert_eq!(
+            lint_to_json(almost_swapped).unwrap()["luacheck_codes"],
+            json!([])
+        );
+    }
+}
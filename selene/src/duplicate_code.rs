@@ -0,0 +1,76 @@
+// Used by duplicate_code, which can only find copies once every file has been checked.
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use selene_lib::{
+    duplicate_code::{self, HashedStatement},
+    require_graph,
+    rules::Severity,
+};
+
+use crate::{
+    cross_file::{self, OtherLabel},
+    quality_gate, LINT_ERRORS, LINT_WARNINGS,
+};
+
+lazy_static::lazy_static! {
+    static ref FILES: Mutex<BTreeMap<PathBuf, Vec<Vec<HashedStatement>>>> = Mutex::new(BTreeMap::new());
+}
+
+// Comparing every file against every other is too slow to do when only some files are being
+// checked, such as from an editor, so this is only done when a whole directory is.
+static WHOLE_PROJECT: AtomicBool = AtomicBool::new(false);
+
+pub fn check_whole_project() {
+    WHOLE_PROJECT.store(true, Ordering::SeqCst);
+}
+
+pub fn is_checking() -> bool {
+    WHOLE_PROJECT.load(Ordering::SeqCst)
+}
+
+pub fn record(filename: &Path, blocks: Vec<Vec<HashedStatement>>) {
+    FILES
+        .lock()
+        .unwrap()
+        .insert(require_graph::normalize_path(filename), blocks);
+}
+
+pub fn emit(severity: Severity, min_tokens: usize) {
+    if severity == Severity::Allow || !is_checking() {
+        return;
+    }
+
+    let duplicates = duplicate_code::find_duplicates(&FILES.lock().unwrap(), min_tokens);
+
+    let counter = match severity {
+        Severity::Error => &LINT_ERRORS,
+        _ => &LINT_WARNINGS,
+    };
+
+    counter.fetch_add(duplicates.len(), Ordering::SeqCst);
+    quality_gate::record("duplicate_code", severity, duplicates.len());
+
+    for duplicate in duplicates {
+        let original = OtherLabel {
+            file: duplicate.original_file.clone(),
+            range: duplicate.original_range,
+            message: "copied from here".to_owned(),
+        };
+
+        if let Err(error) = cross_file::record(
+            &duplicate.file,
+            duplicate_code::duplicate_diagnostic(&duplicate),
+            &[original],
+            severity,
+        ) {
+            crate::error(&format!("Couldn't report duplicate code: {error}"));
+        }
+    }
+}
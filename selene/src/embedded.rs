@@ -0,0 +1,296 @@
+// Finds the Lua embedded in other kinds of files, such as `<script type="text/lua">` in HTML and
+// fenced code blocks in Markdown. Each chunk is given as the whole file with everything else
+// blanked out, so that diagnostics point to the same place they are in the file.
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostFormat {
+    /// HTML, as well as templates written like it such as `.vue`, with Lua in `<script>` tags
+    Html,
+    Markdown,
+}
+
+/// Returns how to find the Lua in the file, if it's a kind of file that Lua is embedded in.
+/// Markdown is only looked at when `include_markdown` is set, since its code blocks are usually
+/// examples rather than code that's run.
+pub fn host_format(path: &Path, include_markdown: bool) -> Option<HostFormat> {
+    match path.extension()?.to_str()? {
+        "html" | "htm" | "xhtml" | "vue" => Some(HostFormat::Html),
+        "md" | "markdown" if include_markdown => Some(HostFormat::Markdown),
+        _ => None,
+    }
+}
+
+/// Returns each chunk of Lua in the file, in order. Every chunk is as long as the file, with
+/// everything outside of the chunk replaced with spaces, keeping the lines the same.
+pub fn chunks(format: HostFormat, source: &str) -> Vec<String> {
+    let ranges = match format {
+        HostFormat::Html => script_ranges(source),
+        HostFormat::Markdown => fenced_ranges(source),
+    };
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let mut bytes = source.as_bytes().to_vec();
+
+            for (index, byte) in bytes.iter_mut().enumerate() {
+                if (index < start || index >= end) && *byte != b'\n' && *byte != b'\r' {
+                    *byte = b' ';
+                }
+            }
+
+            String::from_utf8(bytes).expect("blanking out bytes should keep the source UTF-8")
+        })
+        .collect()
+}
+
+fn is_lua_language(language: &str) -> bool {
+    matches!(
+        language.to_ascii_lowercase().as_str(),
+        "lua" | "luau" | "text/lua" | "text/luau" | "application/lua"
+    )
+}
+
+fn script_ranges(source: &str) -> Vec<(usize, usize)> {
+    // Tags are found case insensitively, and lowercasing ASCII keeps every byte where it was
+    let lowercase = source.to_ascii_lowercase();
+
+    let mut ranges = Vec::new();
+    let mut position = 0;
+
+    while let Some(start) = lowercase[position..].find("<script") {
+        let after_name = position + start + "<script".len();
+
+        // Something like `<scripts>` instead
+        if !lowercase[after_name..].starts_with(|character: char| {
+            character.is_ascii_whitespace() || character == '>' || character == '/'
+        }) {
+            position = after_name;
+            continue;
+        }
+
+        let tag_end = match find_tag_end(&source[after_name..]) {
+            Some(tag_end) => after_name + tag_end,
+            None => break,
+        };
+
+        let attributes = parse_attributes(&source[after_name..tag_end]);
+        let content_start = tag_end + 1;
+        let content_end = lowercase[content_start..]
+            .find("</script")
+            .map_or(source.len(), |end| content_start + end);
+
+        let is_lua = attributes
+            .iter()
+            .any(|(name, value)| (name == "type" || name == "lang") && is_lua_language(value));
+
+        // Scripts loaded from somewhere else have nothing inside of them to check
+        let has_source = attributes.iter().any(|(name, _)| name == "src");
+
+        if is_lua && !has_source && !source[tag_end - 1..].starts_with("/>") {
+            ranges.push((content_start, content_end));
+        }
+
+        position = content_end;
+    }
+
+    ranges
+}
+
+// The position of the `>` that ends the tag, skipping any inside of quoted attributes
+fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (index, character) in tag.char_indices() {
+        match (quote, character) {
+            (None, '"' | '\'') => quote = Some(character),
+            (Some(open), _) if open == character => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+// Attribute names are lowercased, since HTML doesn't care about their case
+fn parse_attributes(mut text: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+
+    loop {
+        text = text.trim_start_matches(|character: char| {
+            character.is_ascii_whitespace() || character == '/'
+        });
+
+        if text.is_empty() {
+            return attributes;
+        }
+
+        let name_end = text
+            .find(|character: char| character.is_ascii_whitespace() || character == '=')
+            .unwrap_or(text.len());
+        let name = text[..name_end].to_ascii_lowercase();
+        text = text[name_end..].trim_start();
+
+        let value = match text.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+
+                match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = after[1..].find(quote).map_or(after.len(), |end| end + 1);
+                        text = after.get(end + 1..).unwrap_or_default();
+                        after[1..end].to_owned()
+                    }
+
+                    _ => {
+                        let end = after
+                            .find(|character: char| character.is_ascii_whitespace())
+                            .unwrap_or(after.len());
+                        text = &after[end..];
+                        after[..end].to_owned()
+                    }
+                }
+            }
+
+            None => String::new(),
+        };
+
+        attributes.push((name, value));
+    }
+}
+
+struct Fence {
+    character: char,
+    length: usize,
+    is_lua: bool,
+    content_start: usize,
+}
+
+// Opening fences are three or more backticks or tildes, indented by at most three spaces
+fn parse_fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+
+    let character = trimmed.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let length = trimmed.len() - trimmed.trim_start_matches(character).len();
+
+    if length < 3 {
+        return None;
+    }
+
+    Some((character, length, trimmed[length..].trim()))
+}
+
+fn fenced_ranges(source: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut open: Option<Fence> = None;
+    let mut line_start = 0;
+
+    for line in source.split_inclusive('\n') {
+        let next_line_start = line_start + line.len();
+        let fence = parse_fence(line.trim_end_matches(['\n', '\r']));
+
+        match (&open, fence) {
+            // Backtick fences can't have backticks in their info string
+            (None, Some((character, length, info))) if character == '~' || !info.contains('`') => {
+                open = Some(Fence {
+                    character,
+                    length,
+                    is_lua: matches!(
+                        info.split_whitespace().next(),
+                        Some(language) if is_lua_language(language)
+                    ),
+                    content_start: next_line_start,
+                });
+            }
+
+            (Some(fence), Some((character, length, "")))
+                if character == fence.character && length >= fence.length =>
+            {
+                if fence.is_lua {
+                    ranges.push((fence.content_start, line_start));
+                }
+
+                open = None;
+            }
+
+            _ => {}
+        }
+
+        line_start = next_line_start;
+    }
+
+    // A code block that's never closed goes until the end of the file
+    if let Some(fence) = open {
+        if fence.is_lua {
+            ranges.push((fence.content_start, source.len()));
+        }
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk_contents(format: HostFormat, source: &str) -> Vec<String> {
+        chunks(format, source)
+            .into_iter()
+            .map(|chunk| {
+                assert_eq!(chunk.len(), source.len());
+                assert_eq!(chunk.lines().count(), source.lines().count());
+                chunk.split_whitespace().collect::<Vec<_>>().join(" ")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_html() {
+        let html = r#"<html>
+<SCRIPT type="text/lua">
+local x = 1
+</SCRIPT>
+<script>console.log("not lua")</script>
+<script lang='luau' data-note="a > b">print(2)</script>
+<script type="text/lua" src="main.lua"></script>
+<scripts type="text/lua">nope</scripts>
+</html>"#;
+
+        assert_eq!(
+            chunk_contents(HostFormat::Html, html),
+            vec!["local x = 1", "print(2)"]
+        );
+
+        let chunk = &chunks(HostFormat::Html, html)[0];
+        assert_eq!(chunk.find("local"), html.find("local"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = "# Example\n\n```lua\nlocal x = 1\n```\n\n```js\n```lua\n```\n\n~~~~ luau title\nprint(x)\n~~~\n~~~~\n\n```lua\nunclosed()\n";
+
+        assert_eq!(
+            chunk_contents(HostFormat::Markdown, markdown),
+            vec!["local x = 1", "print(x) ~~~", "unclosed()"]
+        );
+    }
+
+    #[test]
+    fn test_host_format() {
+        assert_eq!(
+            host_format(Path::new("ui.vue"), false),
+            Some(HostFormat::Html)
+        );
+        assert_eq!(host_format(Path::new("README.md"), false), None);
+        assert_eq!(
+            host_format(Path::new("README.md"), true),
+            Some(HostFormat::Markdown)
+        );
+        assert_eq!(host_format(Path::new("main.lua"), true), None);
+    }
+}
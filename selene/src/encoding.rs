@@ -0,0 +1,159 @@
+// selene only checks UTF-8, but editors on Windows can save files as UTF-16. These are found
+// before parsing so that they can be reported clearly, or converted with `transcode-utf16`.
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8 { bom: bool },
+    Utf16 { little_endian: bool, bom: bool },
+}
+
+/// Guesses the encoding of a file from its byte order mark. Files without one are UTF-16 if
+/// they start with an ASCII character next to a NUL byte, which never happens in UTF-8 Lua.
+pub fn detect(bytes: &[u8]) -> Encoding {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, ..] => Encoding::Utf8 { bom: true },
+        [0xFF, 0xFE, ..] => Encoding::Utf16 {
+            little_endian: true,
+            bom: true,
+        },
+        [0xFE, 0xFF, ..] => Encoding::Utf16 {
+            little_endian: false,
+            bom: true,
+        },
+        [first, 0, ..] if first.is_ascii() && *first != 0 => Encoding::Utf16 {
+            little_endian: true,
+            bom: false,
+        },
+        [0, second, ..] if second.is_ascii() && *second != 0 => Encoding::Utf16 {
+            little_endian: false,
+            bom: false,
+        },
+        _ => Encoding::Utf8 { bom: false },
+    }
+}
+
+/// Decodes the file as the given encoding. The byte order mark is left out, so that it isn't
+/// counted as part of the first line, and is put back by `encode`.
+pub fn decode(bytes: &[u8], encoding: Encoding) -> Cow<'_, str> {
+    match encoding {
+        Encoding::Utf8 { bom } => String::from_utf8_lossy(if bom { &bytes[3..] } else { bytes }),
+        Encoding::Utf16 { little_endian, bom } => {
+            let bytes = if bom { &bytes[2..] } else { bytes };
+
+            let units = bytes.chunks(2).map(|pair| {
+                let pair = [pair[0], pair.get(1).copied().unwrap_or_default()];
+
+                if little_endian {
+                    u16::from_le_bytes(pair)
+                } else {
+                    u16::from_be_bytes(pair)
+                }
+            });
+
+            Cow::Owned(
+                char::decode_utf16(units)
+                    .map(|character| character.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect(),
+            )
+        }
+    }
+}
+
+/// Whether `decoded` encodes back to exactly the bytes it was decoded from. It doesn't when the
+/// file has bytes that aren't valid in its encoding, which `decode` replaces with U+FFFD.
+pub fn is_lossless(bytes: &[u8], decoded: &str, encoding: Encoding) -> bool {
+    encode(decoded, encoding) == bytes
+}
+
+/// Encodes the contents of a file again, such as after fixes were applied to it.
+pub fn encode(contents: &str, encoding: Encoding) -> Vec<u8> {
+    match encoding {
+        Encoding::Utf8 { bom } => {
+            let mut bytes = if bom {
+                vec![0xEF, 0xBB, 0xBF]
+            } else {
+                Vec::new()
+            };
+            bytes.extend_from_slice(contents.as_bytes());
+            bytes
+        }
+
+        Encoding::Utf16 { little_endian, bom } => {
+            let byte_order_mark = if bom { Some(0xFEFF) } else { None };
+
+            byte_order_mark
+                .into_iter()
+                .chain(contents.encode_utf16())
+                .flat_map(|unit: u16| {
+                    if little_endian {
+                        unit.to_le_bytes()
+                    } else {
+                        unit.to_be_bytes()
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect(b"local x = 1"), Encoding::Utf8 { bom: false });
+        assert_eq!(
+            detect(b"\xEF\xBB\xBFlocal x = 1"),
+            Encoding::Utf8 { bom: true }
+        );
+        assert_eq!(detect("日本".as_bytes()), Encoding::Utf8 { bom: false });
+        assert_eq!(detect(b""), Encoding::Utf8 { bom: false });
+
+        for little_endian in [true, false] {
+            for bom in [true, false] {
+                let encoding = Encoding::Utf16 { little_endian, bom };
+                assert_eq!(detect(&encode("local x", encoding)), encoding);
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let encodings = [
+            Encoding::Utf8 { bom: true },
+            Encoding::Utf16 {
+                little_endian: true,
+                bom: true,
+            },
+            Encoding::Utf16 {
+                little_endian: false,
+                bom: false,
+            },
+        ];
+
+        for encoding in encodings {
+            let bytes = encode("print(\"日本\")\r\n", encoding);
+
+            let decoded = decode(&bytes, encoding);
+            assert_eq!(decoded, "print(\"日本\")\r\n");
+            assert_eq!(encode(&decoded, encoding), bytes);
+            assert!(is_lossless(&bytes, &decoded, encoding));
+        }
+    }
+
+    #[test]
+    fn test_is_lossless() {
+        let encoding = Encoding::Utf8 { bom: false };
+        let bytes = b"local x = \"caf\xE9\";";
+        assert!(!is_lossless(bytes, &decode(bytes, encoding), encoding));
+
+        let encoding = Encoding::Utf16 {
+            little_endian: true,
+            bom: false,
+        };
+        let bytes = b"x\0\x00\xD8";
+        assert!(!is_lossless(bytes, &decode(bytes, encoding), encoding));
+    }
+}
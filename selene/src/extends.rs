@@ -0,0 +1,304 @@
+// Used by `extends` in selene.toml, which puts the config beneath one somewhere else, such as a
+// file or URL shared by every project in an organization. Configs from URLs are cached, so that
+// selene can still run when they can't be fetched, and can be pinned with `extends-sha256`.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use color_eyre::eyre::{bail, eyre, Context};
+use sha2::{Digest, Sha256};
+
+/// Where a config was read from, which is what an `extends` inside of it is relative to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Location {
+    File(PathBuf),
+    Url(String),
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Location::File(path) => write!(formatter, "{}", path.display()),
+            Location::Url(url) => write!(formatter, "{url}"),
+        }
+    }
+}
+
+fn is_url(extends: &str) -> bool {
+    extends.starts_with("https://") || extends.starts_with("http://")
+}
+
+impl Location {
+    // So that the same file is recognized when it's reached through different paths
+    fn canonicalize(&self) -> Location {
+        match self {
+            Location::File(path) => {
+                Location::File(fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+            }
+            Location::Url(url) => Location::Url(url.clone()),
+        }
+    }
+
+    fn join(&self, extends: &str) -> Location {
+        if is_url(extends) {
+            return Location::Url(extends.to_owned());
+        }
+
+        match self {
+            Location::File(path) => {
+                Location::File(path.parent().unwrap_or_else(|| Path::new("")).join(extends))
+            }
+
+            // A remote config can extend another one next to it
+            Location::Url(url) => {
+                let directory = match url.rfind('/') {
+                    Some(index) => &url[..index],
+                    None => url,
+                };
+
+                Location::Url(format!("{directory}/{}", extends.trim_start_matches("./")))
+            }
+        }
+    }
+}
+
+/// Puts the config on top of the one it extends, if it extends one, which can extend another
+/// one in turn. Tables are merged, and everything else in the config replaces what it extends.
+pub fn resolve(config: toml::Value, location: &Location) -> color_eyre::Result<toml::Value> {
+    resolve_from(config, location, &mut vec![location.canonicalize()])
+}
+
+fn resolve_from(
+    config: toml::Value,
+    location: &Location,
+    seen: &mut Vec<Location>,
+) -> color_eyre::Result<toml::Value> {
+    let extends = match config.get("extends") {
+        Some(toml::Value::String(extends)) => extends.clone(),
+        Some(_) => bail!("`extends` in {location} has to be a string"),
+        None => return Ok(config),
+    };
+
+    let pinned_sha256 = match config.get("extends-sha256") {
+        Some(toml::Value::String(sha256)) => Some(sha256.to_ascii_lowercase()),
+        Some(_) => bail!("`extends-sha256` in {location} has to be a string"),
+        None => None,
+    };
+
+    let base_location = location.join(&extends);
+
+    if seen.contains(&base_location.canonicalize()) {
+        bail!("{location} extends {base_location}, which it was extended from");
+    }
+
+    let contents = match &base_location {
+        Location::File(path) => fs::read_to_string(path)
+            .with_context(|| format!("Couldn't read {base_location}, which {location} extends"))?,
+
+        Location::Url(url) => fetch_cached(url, pinned_sha256.as_deref())?,
+    };
+
+    if let Some(pinned_sha256) = &pinned_sha256 {
+        let sha256 = sha256(contents.as_bytes());
+
+        if sha256 != *pinned_sha256 {
+            bail!(
+                "{base_location} has the SHA-256 {sha256}, but `extends-sha256` in {location} is {pinned_sha256}"
+            );
+        }
+    }
+
+    let base: toml::Value = toml::from_str(&contents)
+        .with_context(|| format!("{base_location} is not in correct format"))?;
+
+    seen.push(base_location.canonicalize());
+    let base = resolve_from(base, &base_location, seen)?;
+
+    Ok(merge(base, config))
+}
+
+fn merge(base: toml::Value, config: toml::Value) -> toml::Value {
+    match (base, config) {
+        (toml::Value::Table(mut base), toml::Value::Table(config)) => {
+            for (key, value) in config {
+                let value = match base.remove(&key) {
+                    Some(base_value) => merge(base_value, value),
+                    None => value,
+                };
+
+                base.insert(key, value);
+            }
+
+            toml::Value::Table(base)
+        }
+
+        (_, config) => config,
+    }
+}
+
+fn cache_path(url: &str) -> Option<PathBuf> {
+    Some(
+        dirs::cache_dir()?
+            .join("selene")
+            .join("extends")
+            .join(format!("{}.toml", &sha256(url.as_bytes())[..16])),
+    )
+}
+
+// A cached config that matches the pin is always what will be fetched, so it's used without
+// fetching it. Otherwise, the cached config is only used when fetching it fails. Fetched configs
+// that don't match the pin aren't cached, so that they can't be used once the pin is removed.
+// Instead, `resolve_from` reports the mismatch.
+fn fetch_cached(url: &str, pinned_sha256: Option<&str>) -> color_eyre::Result<String> {
+    let cache_path = cache_path(url);
+    let cached = cache_path
+        .as_ref()
+        .and_then(|cache_path| fs::read_to_string(cache_path).ok());
+
+    if let (Some(cached), Some(pinned_sha256)) = (&cached, pinned_sha256) {
+        if sha256(cached.as_bytes()) == pinned_sha256 {
+            log::info!("using the cached config from {url}");
+            return Ok(cached.clone());
+        }
+    }
+
+    log::info!("fetching the config from {url}");
+
+    let fetched = ureq::get(url)
+        .timeout(Duration::from_secs(30))
+        .call()
+        .map_err(|error| eyre!(error))
+        .and_then(|response| Ok(response.into_string()?));
+
+    match (fetched, cached) {
+        (Ok(contents), _) => {
+            let matches_pin = pinned_sha256
+                .is_none_or(|pinned_sha256| sha256(contents.as_bytes()) == pinned_sha256);
+
+            if let Some(cache_path) = cache_path.as_ref().filter(|_| matches_pin) {
+                let written = cache_path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(cache_path, &contents));
+
+                // Failing to cache it only means it has to be fetched next time
+                if let Err(error) = written {
+                    log::warn!("couldn't cache the config from {url}: {error}");
+                }
+            }
+
+            Ok(contents)
+        }
+
+        (Err(error), Some(cached)) => {
+            crate::warn(&format!(
+                "Couldn't fetch the config from {url}: {error}\nUsing the cached one instead."
+            ));
+
+            Ok(cached)
+        }
+
+        (Err(error), None) => Err(error.wrap_err(format!("Couldn't fetch the config from {url}"))),
+    }
+}
+
+/// The SHA-256 of the bytes as lowercase hex, the same as what `sha256sum` prints.
+pub fn sha256(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            sha256(&[b'a'; 1000]),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+    }
+
+    #[test]
+    fn test_extends() {
+        let directory = std::env::temp_dir().join("selene-test-extends");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(directory.join("shared")).unwrap();
+
+        let base = "std = \"lua52\"\nextends = \"root.toml\"\n\n[rules]\nshadowing = \"deny\"\nempty_if = \"deny\"\n";
+        fs::write(directory.join("shared/base.toml"), base).unwrap();
+        fs::write(
+            directory.join("shared/root.toml"),
+            "extensions = [\"lua\"]\n\n[config.shadowing]\nignore_pattern = \"^_\"\n",
+        )
+        .unwrap();
+
+        let config: toml::Value = toml::from_str(&format!(
+            "extends = \"./shared/base.toml\"\nextends-sha256 = \"{}\"\n\n[rules]\nempty_if = \"allow\"\n",
+            sha256(base.as_bytes()).to_ascii_uppercase()
+        ))
+        .unwrap();
+
+        let resolved = resolve(config, &Location::File(directory.join("selene.toml"))).unwrap();
+
+        assert_eq!(resolved["std"].as_str(), Some("lua52"));
+        assert_eq!(resolved["extends"].as_str(), Some("./shared/base.toml"));
+        assert_eq!(resolved["rules"]["shadowing"].as_str(), Some("deny"));
+        assert_eq!(resolved["rules"]["empty_if"].as_str(), Some("allow"));
+        assert_eq!(
+            resolved["config"]["shadowing"]["ignore_pattern"].as_str(),
+            Some("^_")
+        );
+
+        let mismatched: toml::Value =
+            toml::from_str("extends = \"shared/base.toml\"\nextends-sha256 = \"abc\"").unwrap();
+        let error = resolve(mismatched, &Location::File(directory.join("selene.toml")))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("but `extends-sha256`"), "{error}");
+
+        fs::remove_dir_all(&directory).ok();
+    }
+
+    #[test]
+    fn test_extends_loop() {
+        let directory = std::env::temp_dir().join("selene-test-extends-loop");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(&directory).unwrap();
+
+        fs::write(directory.join("a.toml"), "extends = \"b.toml\"").unwrap();
+        fs::write(directory.join("b.toml"), "extends = \"a.toml\"").unwrap();
+
+        let config: toml::Value = toml::from_str("extends = \"b.toml\"").unwrap();
+        let error = resolve(config, &Location::File(directory.join("a.toml")))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("which it was extended from"), "{error}");
+
+        fs::remove_dir_all(&directory).ok();
+    }
+
+    #[test]
+    fn test_url_location() {
+        let location = Location::Url("https://example.com/lint/selene.toml".to_owned());
+
+        assert_eq!(
+            location.join("./base.toml"),
+            Location::Url("https://example.com/lint/base.toml".to_owned())
+        );
+        assert_eq!(
+            location.join("https://example.org/selene.toml"),
+            Location::Url("https://example.org/selene.toml".to_owned())
+        );
+    }
+}
@@ -0,0 +1,124 @@
+// Used by fmt-std, which rewrites a standard library file the same way every time, so that changes
+// to large or generated libraries can be reviewed as diffs.
+use std::{error::Error, fs, path::Path};
+
+use selene_lib::standard_library::{v1, StandardLibrary};
+
+/// Returns the standard library in its canonical form: globals sorted by name, arguments written
+/// the same way, and empty tables that only hold other tables left out.
+/// Files ending in `.toml` are read as the old TOML format, and everything else as YAML.
+/// Comments aren't kept, since the library is written out again from what was read.
+pub fn format_std(path: &Path, contents: &str) -> Result<String, Box<dyn Error>> {
+    if matches!(path.extension(), Some(extension) if extension == "toml") {
+        let library: v1::StandardLibrary = toml::from_str(contents)?;
+
+        // Going through a value puts every table's values before its subtables, which TOML needs
+        let formatted = toml::to_string(&toml::Value::try_from(&library)?)?;
+        Ok(separate_tables(&formatted))
+    } else {
+        let library: StandardLibrary = serde_yaml::from_str(contents)?;
+        Ok(serde_yaml::to_string(&library)?)
+    }
+}
+
+// The TOML serializer only sometimes puts an empty line before a table
+fn separate_tables(formatted: &str) -> String {
+    let mut output = String::with_capacity(formatted.len());
+
+    for line in formatted.lines() {
+        if line.starts_with('[') && !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Formats every file, returning the ones that weren't already formatted.
+/// With `check`, the files are left as they are.
+pub fn fmt_std<P: AsRef<Path>>(files: &[P], check: bool) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut unformatted = Vec::new();
+
+    for file in files {
+        let file = file.as_ref();
+
+        let contents = fs::read_to_string(file)
+            .map_err(|error| format!("couldn't read {}: {error}", file.display()))?;
+        let formatted = format_std(file, &contents)
+            .map_err(|error| format!("couldn't read {}: {error}", file.display()))?;
+
+        if formatted != contents {
+            unformatted.push(file.display().to_string());
+
+            if !check {
+                fs::write(file, formatted)?;
+            }
+        }
+    }
+
+    Ok(unformatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_toml() {
+        let unformatted = r#"[string.format]
+args = [{ type = "string", required = true }, { type = "..." }]
+
+[selene]
+base = "lua51"
+
+[math]
+[math.pi]
+property = true
+
+[Vector3.new]
+method = false
+args = []
+"#;
+
+        let formatted = format_std(Path::new("std.toml"), unformatted).unwrap();
+
+        assert_eq!(
+            formatted,
+            r#"[Vector3.new]
+args = []
+
+[math.pi]
+property = true
+
+[selene]
+base = "lua51"
+
+[[string.format.args]]
+type = "string"
+
+[[string.format.args]]
+type = "..."
+"#
+        );
+
+        assert_eq!(
+            format_std(Path::new("std.toml"), &formatted).unwrap(),
+            formatted
+        );
+    }
+
+    #[test]
+    fn test_format_yaml() {
+        let unformatted =
+            "globals:\n  zzz:\n    any: true\n  aaa:\n    args: [{type: number, required: true}]\n";
+        let formatted = format_std(Path::new("std.yml"), unformatted).unwrap();
+
+        assert_eq!(
+            formatted,
+            "---\nglobals:\n  aaa:\n    args:\n      - type: number\n  zzz:\n    any: true\n"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// Reads the files staged in git, so that `--staged` checks exactly what is about to be committed,
+// even when the working tree has changes that aren't staged.
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn git<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(args: I) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|error| format!("couldn't run git: {error}"))?;
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        // Only the first line, since git can follow it with a whole usage message
+        Err(String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned())
+    }
+}
+
+/// Returns the files that are staged to be committed, relative to the current directory.
+/// Deleted files are not included, as there is nothing left to check.
+/// Files outside of the current directory are not included either.
+pub fn staged_files() -> Result<Vec<PathBuf>, String> {
+    let output = git([
+        "diff",
+        "--cached",
+        "--name-only",
+        "--relative",
+        "--diff-filter=ACMR",
+        "-z",
+    ])?;
+
+    Ok(parse_file_list(&output))
+}
+
+/// Returns the contents of the file as it is staged, rather than as it is in the working tree.
+pub fn staged_contents(path: &Path) -> Result<Vec<u8>, String> {
+    // `:./path` is the staged version of the file, relative to the current directory
+    let mut object = OsStr::new(":./").to_owned();
+    object.push(path);
+
+    git([OsStr::new("show"), &object])
+}
+
+fn parse_file_list(output: &[u8]) -> Vec<PathBuf> {
+    output
+        .split(|&byte| byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_list() {
+        assert_eq!(
+            parse_file_list(b"src/init.lua\0src/other file.lua\0"),
+            vec![
+                PathBuf::from("src/init.lua"),
+                PathBuf::from("src/other file.lua")
+            ],
+        );
+
+        assert!(parse_file_list(b"").is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+// Used by --group, which shows identical diagnostics once no matter how many files they are in.
+// This keeps the output of the first run over a large codebase readable.
+use std::{
+    collections::{BTreeSet, HashMap},
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use selene_lib::{rules::Severity, CheckerDiagnostic};
+use termcolor::{Color, ColorSpec, WriteColor};
+
+lazy_static::lazy_static! {
+    static ref GROUPS: Mutex<HashMap<GroupKey, Group>> = Mutex::new(HashMap::new());
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct GroupKey {
+    is_error: bool,
+    code: &'static str,
+    message: String,
+}
+
+#[derive(Default)]
+struct Group {
+    occurrences: usize,
+    files: BTreeSet<PathBuf>,
+    first: Option<(PathBuf, usize, usize)>,
+}
+
+pub fn record(filename: &Path, diagnostic: &CheckerDiagnostic, line: usize, column: usize) {
+    let is_error = match diagnostic.severity {
+        Severity::Allow => return,
+        Severity::Error => true,
+        Severity::Warning => false,
+    };
+
+    let mut groups = GROUPS.lock().unwrap();
+    let group = groups
+        .entry(GroupKey {
+            is_error,
+            code: diagnostic.diagnostic.code,
+            message: diagnostic.diagnostic.message.clone(),
+        })
+        .or_default();
+
+    group.occurrences += 1;
+    group.files.insert(filename.to_path_buf());
+
+    // Files are checked in parallel, so the earliest location is used to keep the output stable
+    let location = (filename.to_path_buf(), line, column);
+    let is_earliest = match &group.first {
+        Some(first) => location < *first,
+        None => true,
+    };
+
+    if is_earliest {
+        group.first = Some(location);
+    }
+}
+
+pub fn emit(writer: &mut impl WriteColor, max_problems: Option<usize>) -> io::Result<()> {
+    let groups = GROUPS.lock().unwrap();
+
+    let mut groups = groups.iter().collect::<Vec<_>>();
+    groups.sort_by(|(a_key, a), (b_key, b)| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| a_key.code.cmp(b_key.code))
+            .then_with(|| a_key.message.cmp(&b_key.message))
+    });
+
+    let shown = max_problems.unwrap_or(groups.len()).min(groups.len());
+    let hidden: usize = groups[shown..]
+        .iter()
+        .map(|(_, group)| group.occurrences)
+        .sum();
+
+    for (key, group) in &groups[..shown] {
+        if key.is_error {
+            writer.set_color(ColorSpec::new().set_fg(Some(Color::Red)).set_bold(true))?;
+            write!(writer, "error")?;
+        } else {
+            writer.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)).set_bold(true))?;
+            write!(writer, "warning")?;
+        }
+
+        write!(writer, "[{}]", key.code)?;
+        writer.set_color(ColorSpec::new().set_bold(true))?;
+        writeln!(writer, ": {}", key.message)?;
+        writer.reset()?;
+
+        write!(
+            writer,
+            "  {} {} in {} {}",
+            group.occurrences,
+            if group.occurrences == 1 {
+                "occurrence"
+            } else {
+                "occurrences"
+            },
+            group.files.len(),
+            if group.files.len() == 1 {
+                "file"
+            } else {
+                "files"
+            },
+        )?;
+
+        if let Some((path, line, column)) = &group.first {
+            write!(writer, ", first at {}:{}:{}", path.display(), line, column)?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    if hidden > 0 {
+        writeln!(writer, "{}", crate::sorted_output::hidden_message(hidden))?;
+    }
+
+    if groups.iter().any(|(_, group)| group.occurrences > 1) {
+        writeln!(
+            writer,
+            "\nuse --display-style=rich to see every occurrence\n"
+        )?;
+    }
+
+    Ok(())
+}
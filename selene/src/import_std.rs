@@ -0,0 +1,691 @@
+// Converts lua-language-server definition files, or any other stubs written with EmmyLua
+// `---@` annotations, into a standard library.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Token, TokenType},
+};
+use selene_lib::standard_library::{
+    Argument, ArgumentType, Deprecated, Field, FieldKind, FunctionBehavior, Observes,
+    PropertyWritability, Required, StandardLibrary,
+};
+
+pub fn import_std(files: &[PathBuf]) -> color_eyre::Result<()> {
+    let mut importer = Importer::default();
+
+    for file in files {
+        let code = std::fs::read_to_string(file)?;
+
+        let ast = full_moon::parse(&code).map_err(|error| {
+            color_eyre::eyre::eyre!("couldn't parse {}: {error}", file.display())
+        })?;
+
+        importer.import(&ast);
+    }
+
+    print!("{}", serde_yaml::to_string(&importer.finish())?);
+
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct Importer {
+    globals: BTreeMap<String, Field>,
+    tables: BTreeSet<String>,
+}
+
+impl Importer {
+    pub fn import(&mut self, ast: &Ast) {
+        let mut locals = BTreeSet::new();
+
+        for stmt in ast.nodes().stmts() {
+            let annotations = stmt
+                .tokens()
+                .next()
+                .map(|token| Annotations::from_trivia(token.leading_trivia()))
+                .unwrap_or_default();
+
+            match stmt {
+                ast::Stmt::FunctionDeclaration(declaration) => {
+                    let mut names = declaration
+                        .name()
+                        .names()
+                        .iter()
+                        .map(|name| name.token().to_string())
+                        .collect::<Vec<_>>();
+
+                    // Definitions for locals, such as classes, aren't globals
+                    if locals.contains(&names[0]) {
+                        continue;
+                    }
+
+                    let method = match declaration.name().method_name() {
+                        Some(method_name) => {
+                            names.push(method_name.token().to_string());
+                            true
+                        }
+
+                        None => false,
+                    };
+
+                    self.add_function(names.join("."), declaration.body(), &annotations, method);
+                }
+
+                ast::Stmt::Assignment(assignment) => {
+                    for (var, expression) in
+                        assignment.variables().iter().zip(assignment.expressions())
+                    {
+                        let name = match var_name(var) {
+                            Some(name) if !locals.contains(name.split('.').next().unwrap()) => name,
+                            _ => continue,
+                        };
+
+                        self.add_assignment(name, expression, &annotations);
+                    }
+                }
+
+                ast::Stmt::LocalAssignment(local_assignment) => {
+                    locals.extend(
+                        local_assignment
+                            .names()
+                            .iter()
+                            .map(|name| name.token().to_string()),
+                    );
+                }
+
+                ast::Stmt::LocalFunction(local_function) => {
+                    locals.insert(local_function.name().token().to_string());
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    pub fn finish(mut self) -> StandardLibrary {
+        // Tables with fields are already implied by their fields
+        for table in std::mem::take(&mut self.tables) {
+            let prefix = format!("{table}.");
+
+            if !self.globals.keys().any(|name| name.starts_with(&prefix)) {
+                self.globals.entry(table).or_insert_with(|| {
+                    Field::from_field_kind(FieldKind::Property(PropertyWritability::NewFields))
+                });
+            }
+        }
+
+        let mut library = StandardLibrary::default();
+        library.globals = self.globals;
+        library
+    }
+
+    fn add_function(
+        &mut self,
+        name: String,
+        body: &ast::FunctionBody,
+        annotations: &Annotations,
+        method: bool,
+    ) {
+        let arguments = body
+            .parameters()
+            .iter()
+            .filter_map(|parameter| match parameter {
+                ast::Parameter::Ellipse(_) => Some(argument(None, ArgumentType::Vararg, false)),
+
+                ast::Parameter::Name(name) => {
+                    let name = name.token().to_string();
+
+                    Some(match annotations.params.get(&name) {
+                        Some(annotation_type) => {
+                            let (argument_type, optional) = parse_type(annotation_type);
+                            argument(Some(&name), argument_type, optional)
+                        }
+
+                        None => argument(Some(&name), ArgumentType::Any, true),
+                    })
+                }
+
+                _ => None,
+            })
+            .collect();
+
+        self.add_field(
+            name,
+            FieldKind::Function(FunctionBehavior {
+                arguments,
+                method,
+                must_use: annotations.nodiscard,
+                overloads: Vec::new(),
+            }),
+            annotations,
+        );
+    }
+
+    fn add_assignment(
+        &mut self,
+        name: String,
+        expression: &ast::Expression,
+        annotations: &Annotations,
+    ) {
+        let value = match expression {
+            ast::Expression::Value { value, .. } => &**value,
+            _ => {
+                self.add_field(name, FieldKind::Any, annotations);
+                return;
+            }
+        };
+
+        match value {
+            ast::Value::Function((_, body)) => self.add_function(name, body, annotations, false),
+
+            ast::Value::TableConstructor(_) => {
+                for (field_name, field_type) in &annotations.fields {
+                    let field_name = format!("{name}.{field_name}");
+
+                    let field_kind = match parse_fun(field_type) {
+                        Some(arguments) => FieldKind::Function(FunctionBehavior {
+                            arguments,
+                            method: false,
+                            must_use: false,
+                            overloads: Vec::new(),
+                        }),
+
+                        None => FieldKind::Any,
+                    };
+
+                    self.globals
+                        .entry(field_name)
+                        .or_insert_with(|| Field::from_field_kind(field_kind));
+                }
+
+                if annotations.deprecated.is_some() {
+                    self.add_field(name, FieldKind::Any, annotations);
+                } else {
+                    self.tables.insert(name);
+                }
+            }
+
+            _ => self.add_field(name, FieldKind::Any, annotations),
+        }
+    }
+
+    fn add_field(&mut self, name: String, field_kind: FieldKind, annotations: &Annotations) {
+        let deprecated = annotations.deprecated.as_ref().map(|message| Deprecated {
+            message: if message.is_empty() {
+                format!("`{name}` is deprecated")
+            } else {
+                message.to_owned()
+            },
+            replace: Vec::new(),
+        });
+
+        self.globals.insert(
+            name,
+            Field {
+                field_kind,
+                deprecated,
+                restricted: None,
+                property_type: None,
+            },
+        );
+    }
+}
+
+fn argument(name: Option<&str>, argument_type: ArgumentType, optional: bool) -> Argument {
+    Argument {
+        name: name.map(ToOwned::to_owned),
+        required: if optional {
+            Required::NotRequired
+        } else {
+            Required::Required(None)
+        },
+        argument_type,
+        observes: Observes::ReadWrite,
+        range: None,
+        max_length: None,
+    }
+}
+
+// Only names made entirely of dots, such as `love.graphics.draw`, can be globals
+fn var_name(var: &ast::Var) -> Option<String> {
+    match var {
+        ast::Var::Name(name) => Some(name.token().to_string()),
+
+        ast::Var::Expression(var_expression) => {
+            let mut name = match var_expression.prefix() {
+                ast::Prefix::Name(name) => name.token().to_string(),
+                _ => return None,
+            };
+
+            for suffix in var_expression.suffixes() {
+                match suffix {
+                    ast::Suffix::Index(ast::Index::Dot { name: index, .. }) => {
+                        name.push('.');
+                        name.push_str(&index.token().to_string());
+                    }
+
+                    _ => return None,
+                }
+            }
+
+            Some(name)
+        }
+
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Annotations {
+    params: BTreeMap<String, String>,
+    fields: Vec<(String, String)>,
+    nodiscard: bool,
+    deprecated: Option<String>,
+}
+
+impl Annotations {
+    fn from_trivia<'a>(trivia: impl Iterator<Item = &'a Token>) -> Self {
+        let mut annotations = Annotations::default();
+
+        for token in trivia {
+            match token.token_type() {
+                // A blank line means the comments above belong to something else
+                TokenType::Whitespace { characters } if characters.matches('\n').count() > 1 => {
+                    annotations = Annotations::default();
+                }
+
+                TokenType::SingleLineComment { comment } => {
+                    if let Some(annotation) = comment.strip_prefix("-@") {
+                        annotations.add(annotation);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        annotations
+    }
+
+    fn add(&mut self, annotation: &str) {
+        let (tag, rest) = annotation
+            .split_once(char::is_whitespace)
+            .unwrap_or((annotation, ""));
+        let rest = rest.trim();
+
+        match tag {
+            "param" => {
+                if let Some((name, rest)) = rest.split_once(char::is_whitespace) {
+                    let argument_type = split_type(rest.trim_start()).0;
+
+                    // `---@param name? type` is the same as `---@param name type?`
+                    match name.strip_suffix('?') {
+                        Some(name) => self
+                            .params
+                            .insert(name.to_owned(), format!("{argument_type}?")),
+                        None => self
+                            .params
+                            .insert(name.to_owned(), argument_type.to_owned()),
+                    };
+                }
+            }
+
+            "field" => {
+                let rest = ["public ", "protected ", "private ", "package "]
+                    .iter()
+                    .find_map(|scope| rest.strip_prefix(scope))
+                    .unwrap_or(rest);
+
+                if let Some((name, rest)) = rest.split_once(char::is_whitespace) {
+                    let name = name.trim_end_matches('?');
+
+                    if is_identifier(name) {
+                        self.fields
+                            .push((name.to_owned(), split_type(rest.trim_start()).0.to_owned()));
+                    }
+                }
+            }
+
+            "nodiscard" => self.nodiscard = true,
+
+            "deprecated" => self.deprecated = Some(rest.to_owned()),
+
+            _ => {}
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(first) if first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+// Types such as `fun(a: number, b: string): boolean` can contain spaces, so the type only ends at
+// whitespace that isn't inside of brackets.
+// Returns the type and everything after it, such as the description.
+fn split_type(text: &str) -> (&str, &str) {
+    let mut depth = 0;
+    let mut after_colon = false;
+
+    for (index, char) in text.char_indices() {
+        match char {
+            '(' | '<' | '{' | '[' => depth += 1,
+            ')' | '>' | '}' | ']' => depth -= 1,
+
+            // The return type of a function comes after `): `
+            ':' if depth == 0 => after_colon = true,
+
+            char if char.is_whitespace() && depth == 0 => {
+                if after_colon {
+                    after_colon = false;
+                    continue;
+                }
+
+                return (&text[..index], &text[index..]);
+            }
+
+            _ => after_colon = false,
+        }
+    }
+
+    (text, "")
+}
+
+// Splits on separators that aren't inside of brackets
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, char) in text.char_indices() {
+        match char {
+            '(' | '<' | '{' | '[' => depth += 1,
+            ')' | '>' | '}' | ']' => depth -= 1,
+            char if char == separator && depth == 0 => {
+                parts.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Returns the closest argument type to the annotated one, and whether it's optional.
+fn parse_type(annotation_type: &str) -> (ArgumentType, bool) {
+    let (annotation_type, mut optional) = match annotation_type.strip_suffix('?') {
+        Some(annotation_type) => (annotation_type, true),
+        None => (annotation_type, false),
+    };
+
+    let mut types = Vec::new();
+
+    for part in split_top_level(annotation_type, '|') {
+        let part = part.strip_suffix('?').map_or(part, |part| {
+            optional = true;
+            part
+        });
+
+        if part == "nil" {
+            optional = true;
+        } else {
+            types.push(part);
+        }
+    }
+
+    let constants: Option<Vec<String>> = types
+        .iter()
+        .map(|part| {
+            part.strip_prefix('"')
+                .and_then(|part| part.strip_suffix('"'))
+                .or_else(|| {
+                    part.strip_prefix('\'')
+                        .and_then(|part| part.strip_suffix('\''))
+                })
+                .map(ToOwned::to_owned)
+        })
+        .collect();
+
+    let argument_type = match (types.as_slice(), constants) {
+        ([], _) => ArgumentType::Nil,
+        (_, Some(constants)) => ArgumentType::Constant(constants),
+        ([single], None) => simple_type(single),
+
+        _ => {
+            let simple_types: BTreeSet<_> = types
+                .iter()
+                .map(|part| simple_type(part).to_string())
+                .collect();
+
+            if simple_types.len() == 1 {
+                simple_type(types[0])
+            } else {
+                ArgumentType::Any
+            }
+        }
+    };
+
+    (argument_type, optional)
+}
+
+fn simple_type(annotation_type: &str) -> ArgumentType {
+    match annotation_type {
+        "any" | "unknown" => ArgumentType::Any,
+        "boolean" | "true" | "false" => ArgumentType::Bool,
+        "function" => ArgumentType::Function,
+        "integer" | "number" => ArgumentType::Number,
+        "string" => ArgumentType::String,
+        "table" | "lightuserdata" => ArgumentType::Table,
+
+        other if other.starts_with("fun(") => ArgumentType::Function,
+
+        other if other.ends_with("[]") || other.starts_with("table<") || other.starts_with('{') => {
+            ArgumentType::Table
+        }
+
+        // Classes and aliases, which could be anything, even a string
+        _ => ArgumentType::Any,
+    }
+}
+
+// Reads the parameters of a `fun(a: number, b?: string)` type
+fn parse_fun(annotation_type: &str) -> Option<Vec<Argument>> {
+    let rest = annotation_type.strip_prefix("fun(")?;
+
+    let mut depth = 0;
+    let (close, _) = rest.char_indices().find(|&(_, char)| match char {
+        '(' | '<' | '{' | '[' => {
+            depth += 1;
+            false
+        }
+
+        ')' | '>' | '}' | ']' => {
+            depth -= 1;
+            depth < 0
+        }
+
+        _ => false,
+    })?;
+
+    let parameters = rest[..close].trim();
+
+    if parameters.is_empty() {
+        return Some(Vec::new());
+    }
+
+    Some(
+        split_top_level(parameters, ',')
+            .into_iter()
+            .map(|parameter| {
+                let (name, parameter_type) = match parameter.split_once(':') {
+                    Some((name, parameter_type)) => (name.trim(), parameter_type.trim()),
+                    None => (parameter, "any"),
+                };
+
+                if name == "..." {
+                    return argument(None, ArgumentType::Vararg, false);
+                }
+
+                let (argument_type, optional) = parse_type(parameter_type);
+                argument(
+                    Some(name.trim_end_matches('?')),
+                    argument_type,
+                    optional || name.ends_with('?'),
+                )
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(code: &str) -> StandardLibrary {
+        let mut importer = Importer::default();
+        importer.import(&full_moon::parse(code).unwrap());
+        importer.finish()
+    }
+
+    fn arguments(library: &StandardLibrary, name: &str) -> Vec<String> {
+        match &library.globals[name].field_kind {
+            FieldKind::Function(function) => function
+                .arguments
+                .iter()
+                .map(|argument| match argument.required {
+                    Required::NotRequired => format!("{}?", argument.argument_type),
+                    Required::Required(_) => argument.argument_type.to_string(),
+                })
+                .collect(),
+            other => panic!("{name} is {other:?}, not a function"),
+        }
+    }
+
+    #[test]
+    fn test_functions() {
+        let library = import(
+            r#"---@meta
+
+love = {}
+love.graphics = {}
+
+---Draws a drawable object.
+---@param drawable love.Drawable The object to draw.
+---@param x? number
+---@param y number|nil
+---@param mode "fill"|"line"
+---@param callback fun(a: number, b: string): boolean
+function love.graphics.draw(drawable, x, y, mode, callback) end
+
+---@nodiscard
+---@param ... any
+function love.graphics.getDimensions(...) end
+
+---@deprecated
+function love.graphics.old() end
+
+local Image = {}
+
+function Image:getWidth() end
+
+return Image
+"#,
+        );
+
+        assert_eq!(
+            library.globals.keys().collect::<Vec<_>>(),
+            vec![
+                "love.graphics.draw",
+                "love.graphics.getDimensions",
+                "love.graphics.old"
+            ]
+        );
+
+        assert_eq!(
+            arguments(&library, "love.graphics.draw"),
+            vec![
+                "any",
+                "number?",
+                "number?",
+                "\"fill\", \"line\"",
+                "function"
+            ]
+        );
+
+        assert_eq!(
+            arguments(&library, "love.graphics.getDimensions"),
+            vec!["..."]
+        );
+
+        match &library.globals["love.graphics.getDimensions"].field_kind {
+            FieldKind::Function(function) => assert!(function.must_use),
+            _ => unreachable!(),
+        }
+
+        assert_eq!(
+            library.globals["love.graphics.old"]
+                .deprecated
+                .as_ref()
+                .unwrap()
+                .message,
+            "`love.graphics.old` is deprecated"
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        let library = import(
+            r#"---@class ngx
+---@field var table
+---@field say fun(message: string, ...: any)
+ngx = {}
+
+ngx.null = nil
+
+-- Not an annotation
+
+---@param x number
+ngx.log = function(x) end
+
+---@class empty
+empty = {}
+"#,
+        );
+
+        assert_eq!(
+            library.globals.keys().collect::<Vec<_>>(),
+            vec!["empty", "ngx.log", "ngx.null", "ngx.say", "ngx.var"]
+        );
+
+        assert_eq!(
+            library.globals["empty"].field_kind,
+            FieldKind::Property(PropertyWritability::NewFields)
+        );
+
+        assert_eq!(library.globals["ngx.var"].field_kind, FieldKind::Any);
+        assert_eq!(arguments(&library, "ngx.say"), vec!["string", "..."]);
+        assert_eq!(arguments(&library, "ngx.log"), vec!["number"]);
+    }
+
+    #[test]
+    fn test_split_type() {
+        assert_eq!(split_type("number The x"), ("number", " The x"));
+        assert_eq!(
+            split_type("fun(a: number, b: string): boolean desc"),
+            ("fun(a: number, b: string): boolean", " desc")
+        );
+        assert_eq!(
+            split_type("table<string, number>"),
+            ("table<string, number>", "")
+        );
+    }
+}
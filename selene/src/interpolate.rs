@@ -0,0 +1,55 @@
+// Replaces `${NAME}` in config values with the environment variable NAME,
+// so shared configs can point to places that differ between machines.
+use std::env;
+
+pub fn interpolate_env_vars(text: &str) -> Result<String, String> {
+    interpolate(text, |name| env::var(name).ok())
+}
+
+fn interpolate(text: &str, get_var: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+
+        let after_start = &rest[start + 2..];
+        let end = after_start
+            .find('}')
+            .ok_or_else(|| format!("`{text}` has a `${{` without a closing `}}`"))?;
+
+        let name = &after_start[..end];
+        match get_var(name) {
+            Some(value) => output.push_str(&value),
+            None => return Err(format!("environment variable `{name}` is not set")),
+        }
+
+        rest = &after_start[end + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_var(name: &str) -> Option<String> {
+        match name {
+            "STD_DIR" => Some("/opt/company/std".to_owned()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(interpolate("roblox", get_var), Ok("roblox".to_owned()));
+        assert_eq!(
+            interpolate("${STD_DIR}/company+roblox", get_var),
+            Ok("/opt/company/std/company+roblox".to_owned())
+        );
+        assert!(interpolate("${MISSING}/company", get_var).is_err());
+        assert!(interpolate("${STD_DIR", get_var).is_err());
+    }
+}
@@ -1,15 +1,43 @@
 use codespan_reporting::diagnostic::{
     Diagnostic as CodespanDiagnostic, Label as CodespanLabel, LabelStyle, Severity,
 };
+use selene_lib::{lines, rules::Diagnostic};
 use serde::Serialize;
 
+use crate::luacheck_output;
+
+/// What a diagnostic says after its message, kept apart since codespan diagnostics only have it
+/// rendered into their notes, along with which message it has.
+#[derive(Clone, Debug, Default)]
+pub struct Notes {
+    pub message_id: Option<&'static str>,
+    pub notes: Vec<String>,
+    pub help: Vec<String>,
+    pub suggestions: Vec<String>,
+}
+
+impl Notes {
+    pub fn new(diagnostic: &Diagnostic) -> Self {
+        Self {
+            message_id: diagnostic.message_id,
+            notes: diagnostic.notes.clone(),
+            help: diagnostic.help.clone(),
+            suggestions: diagnostic.suggestions.clone(),
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct JsonDiagnostic {
+    filename: String,
     severity: Severity,
     code: Option<String>,
+    luacheck_code: Option<&'static str>,
     message: String,
     primary_label: Label,
     notes: Vec<String>,
+    help: Vec<String>,
+    suggestions: Vec<String>,
     secondary_labels: Vec<Label>,
 }
 
@@ -33,12 +61,8 @@ fn label_to_serializable(
     label: &CodespanLabel<codespan::FileId>,
     files: &codespan::Files<&str>,
 ) -> Label {
-    let start_location = files
-        .location(label.file_id, label.range.start as u32)
-        .expect("unable to determine start location for label");
-    let end_location = files
-        .location(label.file_id, label.range.end as u32)
-        .expect("unable to determine end location for label");
+    let start_location = lines::location(files, label.file_id, label.range.start as u32);
+    let end_location = lines::location(files, label.file_id, label.range.end as u32);
     Label {
         message: label.message.to_owned(),
         span: Span {
@@ -52,19 +76,41 @@ fn label_to_serializable(
     }
 }
 
+/// Diagnostics that don't come from lints, such as parse errors, have no `notes`, and only have
+/// the notes of the codespan diagnostic.
 pub fn diagnostic_to_json(
     diagnostic: &CodespanDiagnostic<codespan::FileId>,
+    notes: Option<&Notes>,
     files: &codespan::Files<&str>,
 ) -> serde_json::Result<String> {
+    let primary_label = diagnostic.labels.first().expect("no labels passed");
+
+    let Notes {
+        message_id,
+        notes,
+        help,
+        suggestions,
+    } = notes.cloned().unwrap_or_else(|| Notes {
+        notes: diagnostic.notes.clone(),
+        ..Notes::default()
+    });
+
     serde_json::to_string(&JsonDiagnostic {
+        filename: files
+            .name(primary_label.file_id)
+            .to_string_lossy()
+            .into_owned(),
         code: diagnostic.code.to_owned(),
+        luacheck_code: diagnostic
+            .code
+            .as_deref()
+            .and_then(|code| luacheck_output::luacheck_code(code, message_id)),
         message: diagnostic.message.to_owned(),
         severity: diagnostic.severity.to_owned(),
-        notes: diagnostic.notes.to_owned(),
-        primary_label: label_to_serializable(
-            diagnostic.labels.first().expect("no labels passed"),
-            files,
-        ),
+        notes,
+        help,
+        suggestions,
+        primary_label: label_to_serializable(primary_label, files),
         secondary_labels: diagnostic
             .labels
             .iter()
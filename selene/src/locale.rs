@@ -0,0 +1,49 @@
+// Used by --locale, which shows messages from a message catalog instead of in English. Messages
+// that the catalog doesn't have are still in English.
+use std::{error::Error, fs, path::Path, sync::RwLock};
+
+use selene_lib::{messages::Catalog, rules::Diagnostic};
+
+lazy_static::lazy_static! {
+    static ref CATALOG: RwLock<Option<Catalog>> = RwLock::new(None);
+}
+
+/// Reads the catalog that messages are shown with, returning its problems, such as messages that
+/// don't exist. The locale is either one that selene comes with, such as `pt-BR`, or the path to a
+/// catalog.
+pub fn load(locale: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let catalog = match Catalog::bundled(locale) {
+        Some(catalog) => catalog,
+
+        None if !Path::new(locale).is_file() => {
+            return Err(format!(
+                "selene only comes with {}, and there is no catalog file named `{locale}`",
+                Catalog::bundled_locales().collect::<Vec<_>>().join(", ")
+            )
+            .into());
+        }
+
+        None => Catalog::from_yaml(&fs::read_to_string(locale)?)?,
+    };
+    let problems = catalog.problems();
+
+    *CATALOG.write().unwrap() = Some(catalog);
+    Ok(problems)
+}
+
+pub fn localize(diagnostic: &mut Diagnostic) {
+    if let Some(catalog) = &*CATALOG.read().unwrap() {
+        catalog.localize(diagnostic);
+    }
+}
+
+/// Returns the message shown outside of diagnostics, such as `results.errors`, in the locale.
+pub fn text(id: &str) -> String {
+    CATALOG
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(|catalog| catalog.format(id, &[]))
+        .or_else(|| Catalog::english().format(id, &[]))
+        .unwrap_or_else(|| panic!("message `{id}` isn't in locales/en.yml"))
+}
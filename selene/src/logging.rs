@@ -0,0 +1,44 @@
+// A minimal logger for the `log` facade, writing to stderr so it never mixes with diagnostics.
+use std::io::Write;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+struct Logger {
+    color: ColorChoice,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let mut stderr = StandardStream::stderr(self.color);
+
+        let color = match record.level() {
+            Level::Error => Color::Red,
+            Level::Warn => Color::Yellow,
+            Level::Info => Color::Green,
+            Level::Debug => Color::Blue,
+            Level::Trace => Color::Magenta,
+        };
+
+        stderr.set_color(ColorSpec::new().set_fg(Some(color))).ok();
+        write!(&mut stderr, "{:>5} ", record.level()).ok();
+        stderr.reset().ok();
+        writeln!(&mut stderr, "[{}] {}", record.target(), record.args()).ok();
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn init(level: LevelFilter, color: ColorChoice) {
+    if log::set_boxed_logger(Box::new(Logger { color })).is_ok() {
+        log::set_max_level(level);
+    }
+}
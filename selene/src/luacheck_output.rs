@@ -0,0 +1,121 @@
+// Formats diagnostics like luacheck's plain formatter with codes, `file:line:col: (W211) message`,
+// so that editor integrations and CI parsers written for luacheck keep working.
+use codespan_reporting::diagnostic::{Diagnostic as CodespanDiagnostic, Severity};
+use selene_lib::lines;
+
+use crate::json_output::Notes;
+
+// What luacheck reports syntax errors as
+const PARSE_ERROR_CODE: &str = "011";
+
+// Lints that luacheck has no warning for all share this one
+const UNKNOWN_CODE: &str = "000";
+
+// Messages that luacheck has a warning of their own for, rather than the one their lint is closest to
+const MESSAGE_CODES: &[(&str, &str)] = &[("unused_variable.overwritten", "311")];
+
+/// Every lint that checks for the same thing as luacheck warnings, along with their codes.
+pub fn luacheck_lints() -> impl Iterator<Item = (&'static str, &'static [&'static str])> {
+    selene_lib::lint_metadata()
+        .into_iter()
+        .map(|lint| (lint.name, lint.luacheck_codes))
+        .filter(|(_, codes)| !codes.is_empty())
+}
+
+/// The luacheck warning closest to a diagnostic, such as `211` for `unused_variable`, or `311`
+/// for its message about a value being overwritten before it's used.
+pub fn luacheck_code(code: &str, message_id: Option<&str>) -> Option<&'static str> {
+    if code == "parse_error" {
+        return Some(PARSE_ERROR_CODE);
+    }
+
+    if let Some((_, luacheck_code)) = MESSAGE_CODES.iter().find(|(id, _)| Some(*id) == message_id) {
+        return Some(luacheck_code);
+    }
+
+    selene_lib::luacheck_codes(code).first().copied()
+}
+
+pub fn diagnostic_to_luacheck(
+    diagnostic: &CodespanDiagnostic<codespan::FileId>,
+    notes: Option<&Notes>,
+    files: &codespan::Files<&str>,
+) -> String {
+    let primary_label = diagnostic.labels.first().expect("no labels passed");
+    let start = lines::location(
+        files,
+        primary_label.file_id,
+        primary_label.range.start as u32,
+    );
+    let code = diagnostic.code.as_deref().unwrap_or_default();
+
+    // Luacheck only has one line per warning, so notes aren't shown
+    format!(
+        "{}:{}:{}: ({}{}) [{code}] {}",
+        files.name(primary_label.file_id).to_string_lossy(),
+        start.line.number(),
+        start.column.number(),
+        match diagnostic.severity {
+            Severity::Bug | Severity::Error => "E",
+            _ => "W",
+        },
+        luacheck_code(code, notes.and_then(|notes| notes.message_id)).unwrap_or(UNKNOWN_CODE),
+        diagnostic.message,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codespan_reporting::diagnostic::Label;
+
+    fn emit(code: &str, message_id: Option<&'static str>, severity: Severity) -> String {
+        let mut files = codespan::Files::new();
+        let file_id = files.add("src/code.lua", "local x = 1\nlocal y = 2\n");
+
+        diagnostic_to_luacheck(
+            &CodespanDiagnostic::new(severity)
+                .with_code(code)
+                .with_message("y is assigned a value, but never used")
+                .with_labels(vec![Label::primary(file_id, 18..19)])
+                .with_notes(vec!["note: this isn't shown".to_owned()]),
+            Some(&Notes {
+                message_id,
+                ..Notes::default()
+            }),
+            &files,
+        )
+    }
+
+    #[test]
+    fn test_diagnostic_to_luacheck() {
+        assert_eq!(
+            emit(
+                "unused_variable",
+                Some("unused_variable.assigned"),
+                Severity::Warning
+            ),
+            "src/code.lua:2:7: (W211) [unused_variable] y is assigned a value, but never used"
+        );
+
+        assert_eq!(
+            emit(
+                "unused_variable",
+                Some("unused_variable.assigned"),
+                Severity::Error
+            ),
+            "src/code.lua:2:7: (E211) [unused_variable] y is assigned a value, but never used"
+        );
+
+        // A value that's overwritten before it's used is a warning of its own in luacheck
+        assert!(emit(
+            "unused_variable",
+            Some("unused_variable.overwritten"),
+            Severity::Warning
+        )
+        .contains("(W311) [unused_variable]"));
+
+        assert!(emit("parse_error", None, Severity::Error).contains("(E011) [parse_error]"));
+        assert!(emit("divide_by_zero", None, Severity::Warning).contains("(W000) [divide_by_zero]"));
+    }
+}
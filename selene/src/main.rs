@@ -1,12 +1,15 @@
 use std::{
+    borrow::Cow,
+    collections::BTreeMap,
     ffi::OsString,
     fmt, fs,
     io::{self, Read, Write},
     path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, RwLock,
     },
+    time::{Duration, Instant},
 };
 
 use codespan_reporting::{
@@ -24,12 +27,46 @@ use upgrade_std::upgrade_std;
 #[cfg(feature = "roblox")]
 use selene_lib::standard_library::StandardLibrary;
 
+#[cfg(feature = "bench")]
+mod bench;
+mod capture_std;
+mod compare;
+mod cross_file;
+mod diff_std;
+mod doc_std;
+mod dump_lints;
+mod duplicate_code;
+mod embedded;
+mod encoding;
+mod extends;
+mod fmt_std;
+mod git;
+mod grouped_output;
+mod import_std;
+mod interpolate;
 mod json_output;
+mod locale;
+mod logging;
+mod luacheck_output;
+mod migrate_luacheckrc;
 mod opts;
+mod print_config;
+mod quality_gate;
+mod references;
+mod report;
+mod require_cycles;
 #[cfg(feature = "roblox")]
 mod roblox;
+mod schema;
+mod semantic_tokens;
+mod sorted_output;
+mod source_map;
+mod sources;
 mod standard_library;
+mod std_cache;
 mod upgrade_std;
+mod walk;
+mod workspace;
 
 macro_rules! error {
     ($fmt:expr) => {
@@ -48,6 +85,12 @@ lazy_static::lazy_static! {
 static LINT_ERRORS: AtomicUsize = AtomicUsize::new(0);
 static LINT_WARNINGS: AtomicUsize = AtomicUsize::new(0);
 static PARSE_ERRORS: AtomicUsize = AtomicUsize::new(0);
+static FIXED: AtomicUsize = AtomicUsize::new(0);
+// Fixes that were left out because they need --fix-suggestions
+static SKIPPED_SUGGESTIONS: AtomicUsize = AtomicUsize::new(0);
+// Set by --fail-fast once any file has an error, so that the files still waiting are skipped
+static STOPPED: AtomicBool = AtomicBool::new(false);
+static SKIPPED: AtomicUsize = AtomicUsize::new(0);
 
 fn get_color() -> ColorChoice {
     let lock = OPTIONS.read().unwrap();
@@ -76,11 +119,21 @@ pub fn error(text: &str) {
     writeln!(&mut stderr, "{}", text).unwrap();
 }
 
+pub fn warn(text: &str) {
+    let mut stderr = StandardStream::stderr(get_color());
+    stderr
+        .set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))
+        .unwrap();
+    write!(&mut stderr, "WARNING: ").unwrap();
+    stderr.reset().unwrap();
+    writeln!(&mut stderr, "{}", text).unwrap();
+}
+
 fn log_total(parse_errors: usize, lint_errors: usize, lint_warnings: usize) -> io::Result<()> {
     let mut stdout = StandardStream::stdout(get_color());
 
     stdout.reset()?;
-    writeln!(&mut stdout, "Results:")?;
+    writeln!(&mut stdout, "{}", locale::text("results"))?;
 
     let mut stat = |number: usize, label: &str| -> io::Result<()> {
         if number > 0 {
@@ -94,17 +147,19 @@ fn log_total(parse_errors: usize, lint_errors: usize, lint_warnings: usize) -> i
         writeln!(&mut stdout, " {}", label)
     };
 
-    stat(lint_errors, "errors")?;
-    stat(lint_warnings, "warnings")?;
-    stat(parse_errors, "parse errors")?;
+    stat(lint_errors, &locale::text("results.errors"))?;
+    stat(lint_warnings, &locale::text("results.warnings"))?;
+    stat(parse_errors, &locale::text("results.parse_errors"))?;
 
     Ok(())
 }
 
+// `notes` is None for diagnostics that don't come from lints, such as parse errors
 fn emit_codespan(
     writer: &mut impl termcolor::WriteColor,
     files: &codespan::Files<&str>,
     diagnostic: &CodespanDiagnostic<codespan::FileId>,
+    notes: Option<&json_output::Notes>,
 ) {
     let lock = OPTIONS.read().unwrap();
     let opts = lock.as_ref().unwrap();
@@ -122,7 +177,14 @@ fn emit_codespan(
         writeln!(
             writer,
             "{}",
-            json_output::diagnostic_to_json(diagnostic, files).unwrap()
+            json_output::diagnostic_to_json(diagnostic, notes, files).unwrap()
+        )
+        .unwrap();
+    } else if let Some(opts::DisplayStyle::Luacheck) = opts.display_style {
+        writeln!(
+            writer,
+            "{}",
+            luacheck_output::diagnostic_to_luacheck(diagnostic, notes, files)
         )
         .unwrap();
     } else {
@@ -131,17 +193,45 @@ fn emit_codespan(
     }
 }
 
-fn emit_codespan_locked(
+// Holds the diagnostic until every file has been checked, so that it's written in order
+fn emit_codespan_sorted(
+    filename: &Path,
     files: &codespan::Files<&str>,
     diagnostic: &CodespanDiagnostic<codespan::FileId>,
+    notes: Option<&json_output::Notes>,
 ) {
-    let stdout = termcolor::StandardStream::stdout(get_color());
-    let mut stdout = stdout.lock();
+    let label = &diagnostic.labels[0];
+    let start = lines::location(files, label.file_id, label.range.start as u32);
+
+    compare::record(
+        filename,
+        diagnostic.code.as_deref().unwrap_or_default(),
+        &diagnostic.message,
+    );
 
-    emit_codespan(&mut stdout, files, diagnostic);
+    let mut output = sorted_output::buffer();
+    emit_codespan(&mut output, files, diagnostic, notes);
+
+    sorted_output::record(
+        sorted_output::Key::new(
+            filename,
+            start.line.to_usize(),
+            start.column.to_usize(),
+            diagnostic.code.as_deref().unwrap_or_default(),
+            diagnostic.severity == CodespanSeverity::Error,
+            &diagnostic.message,
+        ),
+        output,
+    );
 }
 
-fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut reader: R) {
+// Code that isn't `on_disk`, such as from stdin or inside a place file, can't have fixes written
+// back to it, and can't be read again for diagnostics that point into more than one file
+fn read<R: Read>(project: &workspace::Project, filename: &Path, on_disk: bool, mut reader: R) {
+    if skip_after_failure() {
+        return;
+    }
+
     let mut buffer = Vec::new();
     if let Err(error) = reader.read_to_end(&mut buffer) {
         error!(
@@ -150,15 +240,78 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
             error,
         );
 
-        LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
+        project.record(1, 0, 0);
         return;
     }
 
-    let contents = String::from_utf8_lossy(&buffer);
+    let encoding = encoding::detect(&buffer);
+    if matches!(encoding, encoding::Encoding::Utf16 { .. }) && !project.transcode_utf16 {
+        error!(
+            "{}: unsupported encoding, the file is saved as UTF-16 but selene can only check UTF-8. \
+            Save it as UTF-8, or set `transcode-utf16 = true` in selene.toml to have selene convert it",
+            filename.display(),
+        );
+
+        project.record(1, 0, 0);
+        return;
+    }
+
+    let mut contents = encoding::decode(&buffer, encoding);
+    let mut on_disk = on_disk;
+
+    // Bundles are checked one module at a time, named like `bundle.lua[src.util]`, so that
+    // diagnostics point into the module they're in. Their lines and columns are counted from the
+    // start of the module rather than the bundle, so modules can't be fixed either. What's left
+    // is checked as the bundle itself, but isn't fixed, since the modules were blanked out of it
+    if let Some(bundle) = bundles::split_bundle(&contents) {
+        for module in bundle.modules {
+            let mut module_path = filename.as_os_str().to_owned();
+            module_path.push(format!("[{}]", module.name));
+
+            read(
+                project,
+                Path::new(&module_path),
+                false,
+                module.source.as_bytes(),
+            );
+        }
+
+        contents = Cow::Owned(bundle.rest);
+        on_disk = false;
+    }
+
+    let deadline = project
+        .time_budget
+        .map(|time_budget| Instant::now() + time_budget);
 
     let lock = OPTIONS.read().unwrap();
     let opts = lock.as_ref().unwrap();
 
+    // Fixed first, so that whatever is left gets checked and shown as it is in the written file
+    let contents =
+        if opts.fixing() && on_disk && can_write_fixes(filename, &buffer, &contents, encoding) {
+            match fix_file(
+                project,
+                filename,
+                &contents,
+                encoding,
+                opts.fix_suggestions,
+                deadline,
+            ) {
+                Some(fixed_contents) => Cow::Owned(fixed_contents),
+                None => contents,
+            }
+        } else {
+            contents
+        };
+
+    // Loaded before `files`, since the original sources are borrowed from it
+    let source_map = if !on_disk {
+        None
+    } else {
+        source_map::load(filename, project.source_map.as_deref())
+    };
+
     let mut files = codespan::Files::new();
     let source_id = files.add(filename.as_os_str(), &*contents);
 
@@ -168,13 +321,14 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
         match full_moon::parse(&contents) {
             Ok(ast) => ast,
             Err(error) => {
-                PARSE_ERRORS.fetch_add(1, Ordering::SeqCst);
+                project.record(0, 0, 1);
 
                 match error {
                     full_moon::Error::AstError(full_moon::ast::AstError::UnexpectedToken {
                         token,
                         additional,
-                    }) => emit_codespan_locked(
+                    }) => emit_codespan_sorted(
+                        filename,
                         &files,
                         &CodespanDiagnostic {
                             severity: CodespanSeverity::Error,
@@ -188,10 +342,17 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
                                 ),
                             )
                             .with_message(additional.unwrap_or_default())],
-                            notes: Vec::new(),
+                            notes: if token.to_string() == ";" {
+                                vec!["note: empty statements, such as `;;` or `then;`, are not allowed"
+                                    .to_owned()]
+                            } else {
+                                Vec::new()
+                            },
                         },
+                        None,
                     ),
-                    full_moon::Error::TokenizerError(error) => emit_codespan_locked(
+                    full_moon::Error::TokenizerError(error) => emit_codespan_sorted(
+                        filename,
                         &files,
                         &CodespanDiagnostic {
                             severity: CodespanSeverity::Error,
@@ -224,6 +385,7 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
                             )],
                             notes: Vec::new(),
                         },
+                        None,
                     ),
                     _ => error!("Error parsing {}: {}", filename.display(), error),
                 }
@@ -233,11 +395,68 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
         }
     };
 
-    let mut diagnostics = checker.test_on(&ast);
+    if on_disk && project.checker.circular_require_severity() != Severity::Allow {
+        require_cycles::record(filename, require_graph::load_time_requires(&ast, filename));
+    }
+
+    if on_disk
+        && duplicate_code::is_checking()
+        && project.checker.duplicate_code_severity() != Severity::Allow
+    {
+        duplicate_code::record(filename, selene_lib::duplicate_code::statement_blocks(&ast));
+    }
+
+    let lint_start = Instant::now();
+
+    let mut diagnostics = match deadline {
+        _ if filename == Path::new("-") => project.checker.test_on(&ast),
+        Some(deadline) => match project
+            .checker
+            .test_on_file_before(&ast, filename, deadline)
+        {
+            Some(diagnostics) => diagnostics,
+            None => {
+                warn(&format!(
+                    "{}: file skipped, analysis budget of {:?} exceeded",
+                    filename.display(),
+                    project.time_budget.unwrap(),
+                ));
+
+                project.record(0, 0, 0);
+                return;
+            }
+        },
+        None => project.checker.test_on_file(&ast, filename),
+    };
+
+    log::debug!(
+        "checked {} in {:.2?}",
+        filename.display(),
+        lint_start.elapsed()
+    );
     diagnostics.sort_by_key(|diagnostic| diagnostic.diagnostic.start_position());
 
+    if opts.fixing() && !opts.fix_suggestions && on_disk {
+        SKIPPED_SUGGESTIONS.fetch_add(
+            diagnostics
+                .iter()
+                .filter(|diagnostic| {
+                    diagnostic.severity != Severity::Allow
+                        && diagnostic
+                            .diagnostic
+                            .fixes
+                            .iter()
+                            .any(|fix| fix.applicability == rules::Applicability::Suggestion)
+                })
+                .count(),
+            Ordering::SeqCst,
+        );
+    }
+
     let (mut errors, mut warnings) = (0, 0);
     for diagnostic in &diagnostics {
+        quality_gate::record(diagnostic.diagnostic.code, diagnostic.severity, 1);
+
         match diagnostic.severity {
             Severity::Allow => {}
             Severity::Error => errors += 1,
@@ -245,17 +464,49 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
         };
     }
 
-    LINT_ERRORS.fetch_add(errors, Ordering::SeqCst);
-    LINT_WARNINGS.fetch_add(warnings, Ordering::SeqCst);
+    project.record(errors, warnings, 0);
+
+    let original_source_ids = source_map
+        .iter()
+        .flat_map(|source_map| &source_map.sources)
+        .map(|source| {
+            source
+                .contents
+                .as_deref()
+                .map(|contents| files.add(source.path.as_os_str(), contents))
+        })
+        .collect::<Vec<_>>();
+
+    for mut diagnostic in diagnostics {
+        locale::localize(&mut diagnostic.diagnostic);
+
+        // Every kind of output then shows the diagnostic in the original source instead
+        let (filename, source_id) = match source_map.as_ref().and_then(|source_map| {
+            let index = source_map.translate(&contents, &mut diagnostic.diagnostic)?;
+            Some((&source_map.sources[index].path, original_source_ids[index]?))
+        }) {
+            Some((original_path, original_source_id)) => {
+                (original_path.as_path(), original_source_id)
+            }
+            None => (filename, source_id),
+        };
 
-    let stdout = termcolor::StandardStream::stdout(get_color());
-    let mut stdout = stdout.lock();
+        // Everything else is recorded when it's emitted, along with parse errors
+        if (opts.luacheck || opts.group()) && diagnostic.severity != Severity::Allow {
+            compare::record(
+                filename,
+                diagnostic.diagnostic.code,
+                &diagnostic.diagnostic.message,
+            );
+        }
 
-    for diagnostic in diagnostics {
         if opts.luacheck {
             // Existing Luacheck consumers presumably use --formatter plain
             let primary_label = &diagnostic.diagnostic.primary_label;
-            let end = files.location(source_id, primary_label.range.1).unwrap();
+            let start = lines::location(&files, source_id, primary_label.range.0);
+            let end = lines::location(&files, source_id, primary_label.range.1);
+
+            let mut stdout = sorted_output::buffer();
 
             // Closures in Rust cannot call themselves recursively, especially not mutable ones.
             // Luacheck only allows one line ranges, so we just repeat the lint for every line it spans.
@@ -307,24 +558,47 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
                 write!(stdout, "[{}] ", diagnostic.diagnostic.code)?;
                 write!(stdout, "{}", diagnostic.diagnostic.message)?;
 
-                if !diagnostic.diagnostic.notes.is_empty() {
-                    write!(stdout, "\n{}", diagnostic.diagnostic.notes.join("\n"))?;
+                let notes = diagnostic.diagnostic.rendered_notes();
+                if !notes.is_empty() {
+                    write!(stdout, "\n{}", notes.join("\n"))?;
                 }
 
                 writeln!(stdout)?;
                 Ok(())
             };
 
-            write(
-                &mut stack,
-                files.location(source_id, primary_label.range.0).unwrap(),
-            )
-            .unwrap();
+            write(&mut stack, start).unwrap();
 
             while let Some(new_start) = stack.pop() {
                 write(&mut stack, new_start).unwrap();
             }
+
+            sorted_output::record(
+                sorted_output::Key::new(
+                    filename,
+                    start.line.to_usize(),
+                    start.column.to_usize(),
+                    diagnostic.diagnostic.code,
+                    diagnostic.severity == Severity::Error,
+                    &diagnostic.diagnostic.message,
+                ),
+                stdout,
+            );
+        } else if opts.group() {
+            let start = lines::location(
+                &files,
+                source_id,
+                diagnostic.diagnostic.primary_label.range.0,
+            );
+
+            grouped_output::record(
+                filename,
+                &diagnostic,
+                start.line.to_usize() + 1,
+                start.column.to_usize() + 1,
+            );
         } else {
+            let notes = json_output::Notes::new(&diagnostic.diagnostic);
             let diagnostic = diagnostic.diagnostic.into_codespan_diagnostic(
                 source_id,
                 match diagnostic.severity {
@@ -334,34 +608,186 @@ fn read<R: Read>(checker: &Checker<toml::value::Value>, filename: &Path, mut rea
                 },
             );
 
-            emit_codespan(&mut stdout, &files, &diagnostic);
+            emit_codespan_sorted(filename, &files, &diagnostic, Some(&notes));
         }
     }
 }
 
-fn read_file(checker: &Checker<toml::value::Value>, filename: &Path) {
-    read(
-        checker,
-        filename,
-        match fs::File::open(filename) {
-            Ok(file) => file,
+// Fixing one problem can uncover another, and of fixes that overlap only one is applied at a time,
+// so files are checked and fixed again until they settle, which should only take a few passes
+const MAX_FIX_PASSES: usize = 10;
+
+// Writing fixed contents back would replace bytes that aren't valid in the file's encoding with
+// U+FFFD, changing strings that the fixes had nothing to do with, so those files aren't fixed
+fn can_write_fixes(
+    filename: &Path,
+    bytes: &[u8],
+    contents: &str,
+    encoding: encoding::Encoding,
+) -> bool {
+    if encoding::is_lossless(bytes, contents, encoding) {
+        return true;
+    }
+
+    warn(&format!(
+        "{}: not applying fixes, since the file has bytes that aren't valid {}",
+        filename.display(),
+        match encoding {
+            encoding::Encoding::Utf8 { .. } => "UTF-8",
+            encoding::Encoding::Utf16 { .. } => "UTF-16",
+        },
+    ));
+
+    false
+}
+
+// Writes fixes to the file, returning its new contents if anything was fixed
+fn fix_file(
+    project: &workspace::Project,
+    filename: &Path,
+    contents: &str,
+    encoding: encoding::Encoding,
+    fix_suggestions: bool,
+    deadline: Option<Instant>,
+) -> Option<String> {
+    let (fixed_contents, applied_count) =
+        fixes::apply_fixes_until_settled(contents, MAX_FIX_PASSES, |source| {
+            // Code that doesn't parse is reported when the file is checked, and fixes that
+            // would break it are a bug in the lint, which shouldn't break the file too
+            let ast = full_moon::parse(source).ok()?;
+
+            let diagnostics = match deadline {
+                // Running out of time stops fixing, but keeps what was already fixed
+                Some(deadline) => project
+                    .checker
+                    .test_on_file_before(&ast, filename, deadline)
+                    .unwrap_or_default(),
+                None => project.checker.test_on_file(&ast, filename),
+            };
+
+            Some(
+                diagnostics
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.severity != Severity::Allow)
+                    .flat_map(|diagnostic| diagnostic.diagnostic.fixes)
+                    .filter(|fix| {
+                        fix_suggestions || fix.applicability == rules::Applicability::Safe
+                    })
+                    .collect(),
+            )
+        });
+
+    if applied_count == 0 {
+        return None;
+    }
+
+    if let Err(error) = fs::write(filename, encoding::encode(&fixed_contents, encoding)) {
+        error!("Couldn't write fixes to {}: {}", filename.display(), error);
+        return None;
+    }
+
+    FIXED.fetch_add(applied_count, Ordering::SeqCst);
+    log::debug!("applied {applied_count} fixes to {}", filename.display());
+
+    Some(fixed_contents)
+}
+
+// Returns true if --fail-fast stopped checking, counting the file as skipped
+fn skip_after_failure() -> bool {
+    if STOPPED.load(Ordering::SeqCst) {
+        SKIPPED.fetch_add(1, Ordering::SeqCst);
+        true
+    } else {
+        false
+    }
+}
+
+// Reads the file from wherever the options say code comes from, the git index with --staged
+fn read_file(project: &workspace::Project, filename: &Path) {
+    if skip_after_failure() {
+        return;
+    }
+
+    let source = sources::for_options(OPTIONS.read().unwrap().as_ref().unwrap());
+
+    if let Some(format) = embedded_format(filename) {
+        match source.read(filename) {
+            Ok(contents) => read_embedded(project, filename, format, &contents),
             Err(error) => {
-                error!("Couldn't open file {}: {}", filename.display(), error);
-                LINT_ERRORS.fetch_add(1, Ordering::SeqCst);
-                return;
+                error!("Couldn't read file {}: {}", filename.display(), error);
+                project.record(1, 0, 0);
             }
-        },
-    );
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "roblox")]
+    if roblox::place_file::is_place_file(filename) {
+        match source.read(filename) {
+            Ok(contents) => read_place_file(project, filename, &contents),
+            Err(error) => {
+                error!("Couldn't read file {}: {}", filename.display(), error);
+                project.record(1, 0, 0);
+            }
+        }
+
+        return;
+    }
+
+    match source.read_bytes(filename) {
+        Ok(contents) => read(project, filename, true, contents.as_slice()),
+        Err(error) => {
+            error!("Couldn't read file {}: {}", filename.display(), error);
+            project.record(1, 0, 0);
+        }
+    }
 }
 
-fn start(mut matches: opts::Options) {
-    *OPTIONS.write().unwrap() = Some(matches.clone());
+fn embedded_format(filename: &Path) -> Option<embedded::HostFormat> {
+    let include_markdown = OPTIONS.read().unwrap().as_ref().unwrap().include_markdown;
+    embedded::host_format(filename, include_markdown)
+}
 
-    if matches.pattern.is_empty() {
-        matches.pattern.push(String::from("**/*.lua"));
-        #[cfg(feature = "roblox")]
-        matches.pattern.push(String::from("**/*.luau"));
+// Checks each chunk of Lua embedded in the file on its own, where it is in the file. They aren't
+// fixed, since that would write the blanked out rest of the file over it
+fn read_embedded(
+    project: &workspace::Project,
+    filename: &Path,
+    format: embedded::HostFormat,
+    contents: &str,
+) {
+    for chunk in embedded::chunks(format, contents) {
+        read(project, filename, false, chunk.as_bytes());
     }
+}
+
+// Checks every script in a place or model file, named by where they are in it
+#[cfg(feature = "roblox")]
+fn read_place_file(project: &workspace::Project, filename: &Path, contents: &str) {
+    match roblox::place_file::scripts(filename, contents) {
+        Ok(scripts) => {
+            for script in scripts {
+                read(
+                    project,
+                    Path::new(&script.instance_path),
+                    false,
+                    script.source.as_bytes(),
+                );
+            }
+        }
+
+        Err(error) => {
+            error!("Couldn't read place file {}: {}", filename.display(), error);
+            project.record(1, 0, 0);
+        }
+    }
+}
+
+fn start(matches: opts::Options) {
+    *OPTIONS.write().unwrap() = Some(matches.clone());
+
+    logging::init(matches.log_level(), get_color());
 
     match matches.command {
         #[cfg(feature = "roblox")]
@@ -397,106 +823,291 @@ fn start(mut matches: opts::Options) {
             return;
         }
 
-        None => {}
-    }
+        Some(opts::Command::FmtStd { files, check }) => {
+            match fmt_std::fmt_std(&files, check) {
+                Ok(unformatted) if check && !unformatted.is_empty() => {
+                    for file in unformatted {
+                        println!("{file} is not formatted");
+                    }
 
-    let config: CheckerConfig<toml::value::Value> = match matches.config {
-        Some(config_file) => {
-            let config_contents = match fs::read_to_string(config_file) {
-                Ok(contents) => contents,
-                Err(error) => {
-                    error!("Couldn't read config file: {}", error);
                     std::process::exit(1);
                 }
-            };
 
-            match toml::from_str(&config_contents) {
-                Ok(config) => config,
+                Ok(_) => {}
+
                 Err(error) => {
-                    error!("Config file not in correct format: {}", error);
+                    error!("Couldn't format standard library: {error}");
                     std::process::exit(1);
                 }
             }
+
+            return;
         }
 
-        None => match fs::read_to_string("selene.toml") {
-            Ok(config_contents) => match toml::from_str(&config_contents) {
-                Ok(config) => config,
-                Err(error) => {
-                    error!("Config file not in correct format: {}", error);
-                    std::process::exit(1);
-                }
-            },
+        Some(opts::Command::ImportStd { files }) => {
+            if let Err(error) = import_std::import_std(&files) {
+                error!("Couldn't import standard library: {error}");
+                std::process::exit(1);
+            }
 
-            Err(_) => CheckerConfig::default(),
-        },
-    };
+            return;
+        }
 
-    let current_dir = std::env::current_dir().unwrap();
+        Some(opts::Command::MigrateLuacheckrc { file, force }) => {
+            if let Err(error) = migrate_luacheckrc::migrate_luacheckrc(&file, force) {
+                error!("Couldn't migrate {}: {error}", file.display());
+                std::process::exit(1);
+            }
 
-    let standard_library =
-        match standard_library::collect_standard_library(&config, config.std(), &current_dir) {
-            Ok(Some(library)) => library,
+            return;
+        }
 
-            Ok(None) => {
-                error!("Standard library was empty.");
+        Some(opts::Command::DumpLints { format }) => {
+            if let Err(error) = dump_lints::dump_lints(format) {
+                error!("Couldn't dump lints: {error}");
                 std::process::exit(1);
             }
 
-            Err(error) => {
-                let missing_files: Vec<_> = config
-                    .std()
-                    .split('+')
-                    .filter(|name| {
-                        !PathBuf::from(format!("{name}.yml")).exists()
-                            && !PathBuf::from(format!("{name}.toml")).exists()
-                    })
-                    .filter(|name| !cfg!(feature = "roblox") || *name != "roblox")
-                    .collect();
+            return;
+        }
 
-                if !missing_files.is_empty() {
-                    eprintln!(
-                        "`std = \"{}\"`, but some libraries could not be found:",
-                        config.std()
-                    );
+        Some(opts::Command::References { file, offset }) => {
+            if let Err(error) = references::references(&file, offset) {
+                error!("Couldn't find references: {error}");
+                std::process::exit(1);
+            }
 
-                    for library_name in missing_files {
-                        eprintln!("  `{library_name}`");
-                    }
+            return;
+        }
 
-                    error!("Could not find all standard library files");
-                    std::process::exit(1);
+        Some(opts::Command::Schema { config, .. }) => {
+            let schema = if config {
+                schema::config_schema()
+            } else {
+                schema::std_schema()
+            };
+
+            println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+            return;
+        }
+
+        #[cfg(feature = "bench")]
+        Some(opts::Command::Bench { .. }) => {}
+
+        // Resolving library names needs the config, so these are handled further down
+        Some(
+            opts::Command::CaptureStd { .. }
+            | opts::Command::DiffStd { .. }
+            | opts::Command::Completions { .. }
+            | opts::Command::DocStd { .. }
+            | opts::Command::Hover { .. }
+            | opts::Command::SemanticTokens { .. }
+            | opts::Command::SignatureHelp { .. },
+        ) => {}
+
+        // Needs to know which project the file is in
+        Some(opts::Command::PrintConfig { .. }) => {}
+
+        None => {}
+    }
+
+    let config = load_config(&matches, matches.config.as_deref().map(Path::new), false);
+
+    if let Some(opts::Command::CaptureStd { init, base }) = &matches.command {
+        if let Err(error) = capture_std::capture_std(&config, init, base.as_deref()) {
+            error!("Couldn't capture standard library: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(opts::Command::DiffStd { old, new }) = &matches.command {
+        if let Err(error) = diff_std::diff_std(&config, old, new) {
+            error!("Couldn't compare standard libraries: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(opts::Command::DocStd { library, format }) = &matches.command {
+        if let Err(error) = doc_std::doc_std(&config, library, *format) {
+            error!("Couldn't document standard library: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(opts::Command::Hover { name, std }) = &matches.command {
+        if let Err(error) = doc_std::hover(&config, name, std.as_deref()) {
+            error!("Couldn't document `{name}`: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(opts::Command::Completions { prefix, std }) = &matches.command {
+        if let Err(error) = doc_std::completions(&config, prefix, std.as_deref()) {
+            error!("Couldn't complete `{prefix}`: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(opts::Command::SemanticTokens { file, std }) = &matches.command {
+        if let Err(error) = semantic_tokens::semantic_tokens(&config, file, std.as_deref()) {
+            error!("Couldn't highlight {}: {error}", file.display());
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(opts::Command::SignatureHelp { name, std }) = &matches.command {
+        if let Err(error) = doc_std::signature_help(&config, name, std.as_deref()) {
+            error!("Couldn't get the signature of `{name}`: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    let current_dir = std::env::current_dir().unwrap();
+
+    let mut extensions = config
+        .extensions
+        .clone()
+        .unwrap_or_else(walk::FileMatcher::default_extensions);
+
+    if matches.include_markdown {
+        extensions.extend(["md".to_owned(), "markdown".to_owned()]);
+    }
+
+    let file_matcher = walk::FileMatcher::new(extensions);
+
+    // Budgets are for the whole run, so the ones in selene.toml files in subdirectories are ignored
+    let quality_gate = config.quality_gate.clone();
+
+    // selene.toml files in subdirectories are only used when a config isn't given explicitly
+    let mut workspace = workspace::Workspace::new(
+        current_dir.clone(),
+        create_project(config, &current_dir, true),
+        matches.config.is_none(),
+    );
+
+    #[cfg(feature = "bench")]
+    if let Some(opts::Command::Bench { files, iterations }) = &matches.command {
+        bench::bench(&workspace.root().checker, &file_matcher, files, *iterations);
+        return;
+    }
+
+    if let Some(opts::Command::PrintConfig { file }) = &matches.command {
+        if let Err(error) =
+            print_config::print_config(&matches, &mut workspace, &current_dir, file.as_deref())
+        {
+            error!("Couldn't print config: {error}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(locale) = &matches.locale {
+        match locale::load(locale) {
+            Ok(problems) => {
+                for problem in problems {
+                    warn(&format!("{locale}: {problem}"));
                 }
+            }
 
-                error!("Could not collect standard library: {error}");
+            Err(error) => {
+                error!("Couldn't read locale {locale}: {error}");
                 std::process::exit(1);
             }
-        };
+        }
+    }
 
-    let checker = Arc::new(match Checker::new(config, standard_library) {
-        Ok(checker) => checker,
+    let reports = match matches.reports() {
+        Ok(reports) => reports,
         Err(error) => {
-            error!("{error}");
+            error!("Couldn't write report: {error}");
             std::process::exit(1);
         }
-    });
+    };
 
     let pool = ThreadPool::new(matches.num_threads);
 
-    for filename in &matches.files {
+    if matches.staged {
+        let staged_files = match git::staged_files() {
+            Ok(staged_files) => staged_files,
+            Err(error) => {
+                error!("Couldn't get the files staged in git: {error}");
+                std::process::exit(1);
+            }
+        };
+
+        for path in staged_files {
+            if !file_matcher.matches(&path) || !is_inside_any(&path, &matches.files) {
+                continue;
+            }
+
+            log::debug!("checking staged file {}", path.display());
+
+            let project = workspace.project_for(&path);
+            pool.execute(move || read_file(&project, &path));
+        }
+    }
+
+    // With --staged, the files given only narrow down which staged files are checked
+    let files: &[OsString] = if matches.staged { &[] } else { &matches.files };
+
+    if files.iter().any(|filename| Path::new(filename).is_dir()) {
+        duplicate_code::check_whole_project();
+    }
+
+    for filename in files {
         if filename == "-" {
-            let checker = Arc::clone(&checker);
-            pool.execute(move || read(&checker, Path::new("-"), io::stdin().lock()));
+            let project = Arc::clone(workspace.root());
+            pool.execute(move || read(&project, Path::new("-"), false, io::stdin().lock()));
             continue;
         }
 
         match fs::metadata(filename) {
             Ok(metadata) => {
                 if metadata.is_file() {
-                    let checker = Arc::clone(&checker);
+                    let project = workspace.project_for(Path::new(filename));
                     let filename = filename.to_owned();
 
-                    pool.execute(move || read_file(&checker, Path::new(&filename)));
+                    pool.execute(move || read_file(&project, Path::new(&filename)));
+                } else if metadata.is_dir() && matches.pattern.is_empty() {
+                    for entry in walk::DirectoryWalker::new(
+                        Path::new(filename),
+                        &file_matcher,
+                        walk::WalkOptions {
+                            follow_symlinks: matches.follow_symlinks,
+                            max_depth: matches.max_depth,
+                        },
+                    ) {
+                        match entry {
+                            Ok(path) => {
+                                let project = workspace.project_for(&path);
+
+                                pool.execute(move || read_file(&project, &path));
+                            }
+
+                            Err(error) => {
+                                error!(
+                                    "Couldn't open file {}: {}",
+                                    filename.to_string_lossy(),
+                                    error
+                                );
+                            }
+                        }
+                    }
                 } else if metadata.is_dir() {
                     for pattern in &matches.pattern {
                         let glob = match glob::glob(&format!(
@@ -514,9 +1125,9 @@ fn start(mut matches: opts::Options) {
                         for entry in glob {
                             match entry {
                                 Ok(path) => {
-                                    let checker = Arc::clone(&checker);
+                                    let project = workspace.project_for(&path);
 
-                                    pool.execute(move || read_file(&checker, &path));
+                                    pool.execute(move || read_file(&project, &path));
                                 }
 
                                 Err(error) => {
@@ -548,16 +1159,109 @@ fn start(mut matches: opts::Options) {
 
     pool.join();
 
+    require_cycles::emit(workspace.root().checker.circular_require_severity());
+    duplicate_code::emit(
+        workspace.root().checker.duplicate_code_severity(),
+        workspace.root().checker.duplicate_code_min_tokens(),
+    );
+
+    sorted_output::emit(matches.sort, matches.max_problems);
+
+    if OPTIONS.read().unwrap().as_ref().unwrap().group() {
+        let stdout = termcolor::StandardStream::stdout(get_color());
+        grouped_output::emit(&mut stdout.lock(), matches.max_problems).ok();
+    }
+
+    let skipped = SKIPPED.load(Ordering::SeqCst);
+    if skipped > 0 {
+        warn(&format!(
+            "Stopped after the first error because of --fail-fast, {skipped} {} not checked",
+            if skipped == 1 {
+                "file was"
+            } else {
+                "files were"
+            }
+        ));
+    }
+
     let (parse_errors, lint_errors, lint_warnings) = (
         PARSE_ERRORS.load(Ordering::SeqCst),
         LINT_ERRORS.load(Ordering::SeqCst),
         LINT_WARNINGS.load(Ordering::SeqCst),
     );
 
-    if !matches.luacheck && !matches.no_summary {
+    if matches.fixing() && !matches.luacheck {
+        let skipped_suggestions = SKIPPED_SUGGESTIONS.load(Ordering::SeqCst);
+
+        if skipped_suggestions > 0 {
+            println!(
+                "Fixed {} problems, {skipped_suggestions} more can be fixed with --fix-suggestions",
+                FIXED.load(Ordering::SeqCst)
+            );
+        } else {
+            println!("Fixed {} problems", FIXED.load(Ordering::SeqCst));
+        }
+    }
+
+    if matches.summary() {
+        workspace.log_projects().ok();
         log_total(parse_errors, lint_errors, lint_warnings).ok();
     }
 
+    for (format, path) in &reports {
+        if let Err(error) =
+            report::write_report(*format, path, parse_errors + lint_errors, lint_warnings)
+        {
+            error!("Couldn't write report {}: {error}", path.display());
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(report) = &matches.compare_to {
+        let comparison = match compare::compare_to_report(report) {
+            Ok(comparison) => comparison,
+            Err(error) => {
+                error!("Couldn't read report {}: {error}", report.display());
+                std::process::exit(1);
+            }
+        };
+
+        if matches.summary() {
+            println!("Compared to {}:", report.display());
+            println!("{} new", comparison.new);
+            println!("{} fixed", comparison.fixed);
+            println!("{} unchanged", comparison.unchanged);
+        }
+
+        if matches.fail_on_new {
+            if comparison.new > 0 || pool.panic_count() > 0 {
+                std::process::exit(1);
+            }
+
+            return;
+        }
+    }
+
+    if let Some(quality_gate) = quality_gate {
+        let outcome = quality_gate::check(&quality_gate);
+
+        if matches.summary() {
+            for lowerable in &outcome.lowerable {
+                println!("Quality gate: {lowerable}");
+            }
+        }
+
+        for exceeded in &outcome.exceeded {
+            error!("Quality gate failed: {exceeded}");
+        }
+
+        if !outcome.exceeded.is_empty() || parse_errors + pool.panic_count() > 0 {
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
     let error_count = parse_errors + lint_errors + lint_warnings + pool.panic_count();
     if error_count > 0 {
         let lock = OPTIONS.read().unwrap();
@@ -569,6 +1273,319 @@ fn start(mut matches: opts::Options) {
     }
 }
 
+fn config_format_error(config_path: &Path, nested: bool, error: impl fmt::Display) -> ! {
+    if nested {
+        error!(
+            "Config file {} not in correct format: {}",
+            config_path.display(),
+            error
+        );
+    } else {
+        error!("Config file not in correct format: {}", error);
+    }
+
+    std::process::exit(1);
+}
+
+// Reads selene.toml and applies the options that change it, exiting if anything is wrong.
+// Without a config file, selene.toml in the current directory is used if there is one.
+// Nested configs are the ones found in subdirectories, which don't need to have the profile.
+fn load_config(
+    matches: &opts::Options,
+    config_file: Option<&Path>,
+    nested: bool,
+) -> CheckerConfig<toml::value::Value> {
+    let (config_contents, config_path) = match config_file {
+        Some(config_file) => {
+            log::info!("using config file {}", config_file.display());
+
+            match fs::read_to_string(config_file) {
+                Ok(contents) => (Some(contents), config_file.to_owned()),
+                Err(error) => {
+                    error!("Couldn't read config file: {}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        None => match fs::read_to_string("selene.toml") {
+            Ok(config_contents) => {
+                log::info!("using config file selene.toml");
+                (Some(config_contents), PathBuf::from("selene.toml"))
+            }
+
+            Err(error) => {
+                log::info!("couldn't read selene.toml ({error}), using the default config");
+                (None, PathBuf::from("selene.toml"))
+            }
+        },
+    };
+
+    let mut config: CheckerConfig<toml::value::Value> = match config_contents {
+        Some(config_contents) => {
+            let value: toml::Value = match toml::from_str(&config_contents) {
+                Ok(value) => value,
+                Err(error) => config_format_error(&config_path, nested, error),
+            };
+
+            // Reading the config straight from the file keeps the lines in its errors
+            if value.get("extends").is_none() {
+                match toml::from_str(&config_contents) {
+                    Ok(config) => config,
+                    Err(error) => config_format_error(&config_path, nested, error),
+                }
+            } else {
+                let value =
+                    match extends::resolve(value, &extends::Location::File(config_path.clone())) {
+                        Ok(value) => value,
+                        Err(error) => {
+                            error!(
+                                "{}",
+                                error
+                                    .chain()
+                                    .map(ToString::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join(": ")
+                            );
+                            std::process::exit(1);
+                        }
+                    };
+
+                match value.try_into() {
+                    Ok(config) => config,
+                    Err(error) => config_format_error(&config_path, nested, error),
+                }
+            }
+        }
+
+        None => CheckerConfig::default(),
+    };
+
+    for warning in config.rename_lints() {
+        warn(&warning.to_string());
+    }
+
+    for warning in unavailable_lint_warnings(&config) {
+        warn(&warning);
+    }
+
+    if let Some(profile) = &matches.profile {
+        if config.apply_profile(profile) {
+            log::info!("using profile `{profile}`");
+        } else if !nested {
+            error!("Config file does not have a profile named `{profile}`");
+            std::process::exit(1);
+        }
+    }
+
+    if running_in_ci(matches) {
+        log::info!("using the severities in [severity.ci]");
+        config.apply_ci_severity();
+    }
+
+    if let Some(std) = &config.std {
+        match interpolate::interpolate_env_vars(std) {
+            Ok(std) => config.std = Some(std),
+            Err(error) => {
+                error!("Couldn't read `std` in config file: {error}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(time_budget) = config.time_budget {
+        if !(time_budget.is_finite() && time_budget > 0.0) {
+            error!("`time-budget` in config file must be a positive number of seconds");
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(requirement) = &config.selene_version {
+        if let Err(message) = check_selene_version(requirement, env!("CARGO_PKG_VERSION")) {
+            if matches.no_version_check {
+                warn(&message);
+            } else {
+                error!("{message}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    config
+}
+
+// Checks `selene-version` in selene.toml against the version of selene that is running
+fn check_selene_version(requirement: &str, version: &str) -> Result<(), String> {
+    let requirement = semver::VersionReq::parse(requirement)
+        .map_err(|error| format!("`selene-version = \"{requirement}\"` is not valid: {error}"))?;
+
+    let version = semver::Version::parse(version).expect("selene's own version is invalid");
+
+    if requirement.matches(&version) {
+        Ok(())
+    } else {
+        Err(format!(
+            "selene.toml requires selene {requirement}, but this is selene {version}. \
+            Install a matching version, or pass --no-version-check to run anyway."
+        ))
+    }
+}
+
+// `--ci`, or the CI environment variable that CI services such as GitHub Actions set to `true`
+pub fn running_in_ci(options: &opts::Options) -> bool {
+    options.ci
+        || matches!(std::env::var("CI"), Ok(ci) if !ci.is_empty() && ci != "false" && ci != "0")
+}
+
+// Lints from features this build was made without can still be in selene.toml, so that the same
+// config works with every build of selene, but they don't do anything
+fn unavailable_lint_warnings<V>(config: &CheckerConfig<V>) -> Vec<String> {
+    let mut configured = config
+        .rules
+        .keys()
+        .chain(config.config.keys())
+        .chain(config.severity.ci.keys())
+        .chain(config.profile.values().flat_map(|profile| {
+            profile
+                .rules
+                .keys()
+                .chain(profile.config.keys())
+                .chain(profile.severity.ci.keys())
+        }))
+        .collect::<Vec<_>>();
+
+    configured.sort();
+    configured.dedup();
+
+    let mut lints_by_feature: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+
+    for (lint, feature) in selene_lib::unavailable_lints() {
+        if configured.iter().any(|name| *name == lint) {
+            lints_by_feature.entry(feature).or_default().push(lint);
+        }
+    }
+
+    lints_by_feature
+        .into_iter()
+        .map(|(feature, lints)| {
+            format!(
+                "selene.toml configures {}, which won't run since this build of selene doesn't have the `{feature}` feature. \
+                Install a build with it using `cargo install selene --features {feature}`",
+                lints
+                    .iter()
+                    .map(|lint| format!("`{lint}`"))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })
+        .collect()
+}
+
+/// Creates the project for the files in the directory. When `with_tests` is true, the test files
+/// in the project are found so that they can be checked with the test config.
+fn create_project(
+    config: CheckerConfig<toml::value::Value>,
+    directory: &Path,
+    with_tests: bool,
+) -> workspace::Project {
+    log::info!("using standard library `{}`", config.std());
+
+    let tests = if with_tests {
+        let patterns = match config
+            .test_files()
+            .into_iter()
+            .map(glob::Pattern::new)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(patterns) => patterns,
+            Err(error) => {
+                error!("Invalid glob in test-files: {error}");
+                std::process::exit(1);
+            }
+        };
+
+        Some(workspace::Tests {
+            patterns,
+            config: config.for_tests(),
+        })
+        .filter(|tests| !tests.patterns.is_empty())
+    } else {
+        None
+    };
+
+    let standard_library =
+        match standard_library::collect_standard_library(&config, config.std(), directory) {
+            Ok(Some(library)) => library,
+
+            Ok(None) => {
+                error!("Standard library was empty.");
+                std::process::exit(1);
+            }
+
+            Err(error) => {
+                let missing_files: Vec<_> = config
+                    .std()
+                    .split('+')
+                    .filter(|name| {
+                        !directory.join(format!("{name}.yml")).exists()
+                            && !directory.join(format!("{name}.toml")).exists()
+                    })
+                    // Builds without the roblox feature explain why it can't be found instead
+                    .filter(|name| *name != "roblox")
+                    .collect();
+
+                if !missing_files.is_empty() {
+                    eprintln!(
+                        "`std = \"{}\"`, but some libraries could not be found:",
+                        config.std()
+                    );
+
+                    for library_name in missing_files {
+                        eprintln!("  `{library_name}`");
+                    }
+
+                    error!("Could not find all standard library files");
+                    std::process::exit(1);
+                }
+
+                error!("Could not collect standard library: {error}");
+                std::process::exit(1);
+            }
+        };
+
+    let source_map = config.source_map.clone();
+    let time_budget = config.time_budget.map(Duration::from_secs_f64);
+    let transcode_utf16 = config.transcode_utf16;
+
+    match Checker::new(config, standard_library) {
+        Ok(checker) => {
+            let mut project = workspace::Project::new(
+                directory.to_path_buf(),
+                checker,
+                source_map,
+                time_budget,
+                transcode_utf16,
+            );
+
+            project.tests = tests;
+            project
+        }
+        Err(error) => {
+            error!("{error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+// Whether the path is inside of any of the given files or directories, or there are none to check
+fn is_inside_any(path: &Path, files: &[OsString]) -> bool {
+    files.is_empty()
+        || files.iter().any(|file| {
+            let file = Path::new(file);
+            path.starts_with(file.strip_prefix(".").unwrap_or(file))
+        })
+}
+
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
@@ -602,7 +1619,18 @@ fn get_opts(luacheck: bool) -> opts::Options {
         .unwrap_or_else(|err| err.exit())
 }
 
-fn get_opts_safe(mut args: Vec<OsString>, luacheck: bool) -> Result<opts::Options, clap::Error> {
+fn get_opts_safe(args: Vec<OsString>, luacheck: bool) -> Result<opts::Options, clap::Error> {
+    // clap only takes the file after `--report=badge` when the format is its own argument
+    let mut args = args
+        .into_iter()
+        .flat_map(
+            |arg| match arg.to_str().and_then(|arg| arg.strip_prefix("--report=")) {
+                Some(format) => vec![OsString::from("--report"), OsString::from(format)],
+                None => vec![arg],
+            },
+        )
+        .collect::<Vec<_>>();
+
     let mut first_error: Option<clap::Error> = None;
 
     loop {
@@ -679,4 +1707,34 @@ mod tests {
 
         assert!(get_opts_safe(args(vec!["--fail", "files"]), true).is_ok());
     }
+
+    #[test]
+    fn test_report_opts() {
+        let opts = get_opts_safe(
+            args(vec![
+                "--report=badge",
+                "badge.svg",
+                "--report",
+                "shield",
+                "shield.json",
+                "src",
+            ]),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            opts.report,
+            vec!["badge", "badge.svg", "shield", "shield.json"]
+        );
+        assert_eq!(opts.files, vec![OsString::from("src")]);
+    }
+
+    #[test]
+    fn test_check_selene_version() {
+        assert!(check_selene_version(">=0.20, <0.30", "0.21.1").is_ok());
+        assert!(check_selene_version("0.21", "0.21.1").is_ok());
+        assert!(check_selene_version(">=0.22", "0.21.1").is_err());
+        assert!(check_selene_version("not a version", "0.21.1").is_err());
+    }
 }
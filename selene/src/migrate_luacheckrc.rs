@@ -0,0 +1,1004 @@
+// Converts a .luacheckrc into a selene.toml, and a standard library for the globals it declares.
+// The .luacheckrc isn't run, so only options that are set to strings, numbers, booleans, and
+// tables of them are read.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::TokenType,
+};
+use selene_lib::standard_library::{Field, FieldKind, PropertyWritability, StandardLibrary};
+
+use crate::luacheck_output::luacheck_lints;
+
+/// The name of the standard library written for the globals the .luacheckrc declares.
+const STD_NAME: &str = "luacheck";
+
+/// The name of the standard library written for the globals only test files have.
+const TESTS_STD_NAME: &str = "luacheck_tests";
+
+// Settings that turn off groups of warnings when they're `false`, and the warnings they turn off
+const BOOLEAN_OPTIONS: &[(&str, &str)] = &[
+    ("global", "1"),
+    ("redefined", "4"),
+    ("unused", "2"),
+    ("unused_args", "212"),
+];
+
+pub fn migrate_luacheckrc(path: &Path, force: bool) -> color_eyre::Result<()> {
+    let code = std::fs::read_to_string(path)?;
+
+    let ast = full_moon::parse(&code)
+        .map_err(|error| color_eyre::eyre::eyre!("couldn't parse {}: {error}", path.display()))?;
+
+    let migration = Migration::from_ast(&ast);
+
+    for note in &migration.notes {
+        log::warn!("{note}");
+    }
+
+    let directory = match path.parent() {
+        Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let files = migration
+        .files
+        .iter()
+        .map(|(file, contents)| (directory.join(file), contents))
+        .collect::<Vec<_>>();
+
+    if !force {
+        if let Some((file, _)) = files.iter().find(|(file, _)| file.exists()) {
+            color_eyre::eyre::bail!(
+                "{} already exists, pass --force to replace it",
+                file.display()
+            );
+        }
+    }
+
+    for (file, contents) in files {
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&file, contents)?;
+        println!("wrote {}", file.display());
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Table(Table),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Table {
+    array: Vec<Value>,
+    fields: BTreeMap<String, Value>,
+}
+
+impl Value {
+    fn as_table(&self) -> Option<&Table> {
+        match self {
+            Value::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    // Lists of strings can be written as either `"a"` or `{ "a" }`
+    fn strings(&self) -> Vec<String> {
+        match self {
+            Value::String(string) => vec![string.to_owned()],
+            Value::Table(table) => table
+                .array
+                .iter()
+                .filter_map(|value| match value {
+                    Value::String(string) => Some(string.to_owned()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+// Reads the values the top level of the .luacheckrc assigns, without running it
+#[derive(Default)]
+struct Reader {
+    /// The options, which are globals.
+    variables: BTreeMap<String, Value>,
+    locals: BTreeMap<String, Value>,
+    notes: Vec<String>,
+}
+
+fn string_literal(token: &full_moon::tokenizer::TokenReference) -> Option<String> {
+    match token.token_type() {
+        TokenType::StringLiteral { literal, .. } => Some(literal.to_string()),
+        _ => None,
+    }
+}
+
+fn line(node: &dyn Node) -> usize {
+    node.start_position().map_or(0, |position| position.line())
+}
+
+impl Reader {
+    fn read(&mut self, ast: &Ast) {
+        for stmt in ast.nodes().stmts() {
+            match stmt {
+                ast::Stmt::Assignment(assignment) => {
+                    for (var, expression) in
+                        assignment.variables().iter().zip(assignment.expressions())
+                    {
+                        let path = match self.var_path(var) {
+                            Some(path) => path,
+                            None => continue,
+                        };
+
+                        self.assign(&path, expression, false);
+                    }
+                }
+
+                ast::Stmt::LocalAssignment(local_assignment) => {
+                    for (name, expression) in local_assignment
+                        .names()
+                        .iter()
+                        .zip(local_assignment.expressions())
+                    {
+                        self.assign(&[name.token().to_string()], expression, true);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn assign(&mut self, path: &[String], expression: &ast::Expression, local: bool) {
+        let value = match self.evaluate(expression) {
+            Some(value) => value,
+            None => {
+                self.notes.push(format!(
+                    "line {}: `{}` isn't set to a value that can be read without running the .luacheckrc, so it's skipped",
+                    line(expression),
+                    path.join("."),
+                ));
+
+                return;
+            }
+        };
+
+        let (last, parents) = path.split_last().unwrap();
+        let mut fields = if local || self.locals.contains_key(&path[0]) {
+            &mut self.locals
+        } else {
+            &mut self.variables
+        };
+
+        for parent in parents {
+            let parent = fields
+                .entry(parent.to_owned())
+                .or_insert_with(|| Value::Table(Table::default()));
+
+            if !matches!(parent, Value::Table(_)) {
+                *parent = Value::Table(Table::default());
+            }
+
+            fields = match parent {
+                Value::Table(table) => &mut table.fields,
+                _ => unreachable!(),
+            };
+        }
+
+        fields.insert(last.to_owned(), value);
+    }
+
+    // Returns `files, spec/` for `files["spec/"]`
+    fn var_path(&self, var: &ast::Var) -> Option<Vec<String>> {
+        match var {
+            ast::Var::Name(name) => Some(vec![name.token().to_string()]),
+
+            ast::Var::Expression(var_expression) => {
+                let mut path = match var_expression.prefix() {
+                    ast::Prefix::Name(name) => vec![name.token().to_string()],
+                    _ => return None,
+                };
+
+                for suffix in var_expression.suffixes() {
+                    path.push(match suffix {
+                        ast::Suffix::Index(ast::Index::Dot { name, .. }) => {
+                            name.token().to_string()
+                        }
+
+                        ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                            match self.evaluate(expression)? {
+                                Value::String(key) => key,
+                                _ => return None,
+                            }
+                        }
+
+                        _ => return None,
+                    });
+                }
+
+                Some(path)
+            }
+
+            _ => None,
+        }
+    }
+
+    fn evaluate(&self, expression: &ast::Expression) -> Option<Value> {
+        match expression {
+            ast::Expression::Parentheses { expression, .. } => self.evaluate(expression),
+
+            ast::Expression::BinaryOperator {
+                lhs,
+                binop: ast::BinOp::TwoDots(_),
+                rhs,
+            } => match (self.evaluate(lhs)?, self.evaluate(rhs)?) {
+                (Value::String(lhs), Value::String(rhs)) => Some(Value::String(lhs + &rhs)),
+                _ => None,
+            },
+
+            ast::Expression::Value { value, .. } => match &**value {
+                ast::Value::String(token) => string_literal(token).map(Value::String),
+
+                ast::Value::Number(token) => {
+                    token.token().to_string().parse().ok().map(Value::Number)
+                }
+
+                ast::Value::Symbol(symbol) => match symbol.token_type() {
+                    TokenType::Symbol {
+                        symbol: full_moon::tokenizer::Symbol::True,
+                    } => Some(Value::Bool(true)),
+                    TokenType::Symbol {
+                        symbol: full_moon::tokenizer::Symbol::False,
+                    } => Some(Value::Bool(false)),
+                    _ => None,
+                },
+
+                ast::Value::ParenthesesExpression(expression) => self.evaluate(expression),
+
+                ast::Value::TableConstructor(table_constructor) => {
+                    let mut table = Table::default();
+
+                    for field in table_constructor.fields() {
+                        match field {
+                            ast::Field::NoKey(value) => table.array.push(self.evaluate(value)?),
+
+                            ast::Field::NameKey { key, value, .. } => {
+                                table
+                                    .fields
+                                    .insert(key.token().to_string(), self.evaluate(value)?);
+                            }
+
+                            ast::Field::ExpressionKey { key, value, .. } => {
+                                match self.evaluate(key)? {
+                                    Value::String(key) => {
+                                        table.fields.insert(key, self.evaluate(value)?);
+                                    }
+
+                                    _ => return None,
+                                }
+                            }
+
+                            _ => return None,
+                        }
+                    }
+
+                    Some(Value::Table(table))
+                }
+
+                ast::Value::Var(var) => {
+                    let path = self.var_path(var)?;
+                    let mut value = self
+                        .locals
+                        .get(&path[0])
+                        .or_else(|| self.variables.get(&path[0]))?;
+
+                    for key in &path[1..] {
+                        value = value.as_table()?.fields.get(key)?;
+                    }
+
+                    Some(value.clone())
+                }
+
+                _ => None,
+            },
+
+            _ => None,
+        }
+    }
+}
+
+// The options that can be set at the top level of the .luacheckrc, or for some files in `files`
+#[derive(Default)]
+struct Options {
+    std: Option<Value>,
+    globals: BTreeMap<String, Field>,
+    ignore: Vec<String>,
+    enable: Vec<String>,
+    max_cyclomatic_complexity: Option<f64>,
+}
+
+// Tables that can be assigned to, but only have the fields that are listed
+fn define_tables(globals: &mut BTreeMap<String, Field>, name: &str) {
+    let mut table = Some(name);
+
+    while let Some(name) = table {
+        globals.entry(name.to_owned()).or_insert_with(|| {
+            Field::from_field_kind(FieldKind::Property(PropertyWritability::OverrideFields))
+        });
+
+        table = name.rsplit_once('.').map(|(parent, _)| parent);
+    }
+}
+
+// Like `-- selene: globals(name)`, a global could be a table or a function, so it can be used as
+// either. Whether it's read only only matters for the tables it's a field of.
+fn define_global(globals: &mut BTreeMap<String, Field>, name: String, read_only: bool) {
+    if !read_only {
+        if let Some((parent, _)) = name.rsplit_once('.') {
+            define_tables(globals, parent);
+        }
+    }
+
+    globals.insert(name, Field::from_field_kind(FieldKind::Any));
+}
+
+fn define_globals(
+    value: &Value,
+    prefix: &str,
+    read_only: bool,
+    globals: &mut BTreeMap<String, Field>,
+) {
+    for name in value.strings() {
+        define_global(globals, format!("{prefix}{name}"), read_only);
+    }
+
+    let table = match value {
+        Value::Table(table) => table,
+        _ => return,
+    };
+
+    for (name, definition) in &table.fields {
+        let name = format!("{prefix}{name}");
+
+        let definition = match definition.as_table() {
+            Some(definition) => definition,
+            None => {
+                define_global(globals, name, read_only);
+                continue;
+            }
+        };
+
+        let read_only = match definition.fields.get("read_only") {
+            Some(Value::Bool(read_only)) => *read_only,
+            _ => read_only,
+        };
+
+        let other_fields = definition.fields.get("other_fields") == Some(&Value::Bool(true));
+
+        match definition.fields.get("fields") {
+            // Without a definition of its own, the global can only have the fields it lists
+            Some(fields) if !other_fields => {
+                if !read_only {
+                    define_tables(globals, &name);
+                }
+
+                define_globals(fields, &format!("{name}."), read_only, globals);
+            }
+
+            _ => define_global(globals, name, read_only),
+        }
+    }
+}
+
+impl Options {
+    fn from_table(table: &Table, location: &str, notes: &mut Vec<String>) -> Self {
+        let mut options = Options::default();
+
+        for (key, value) in &table.fields {
+            match key.as_str() {
+                "std" => options.std = Some(value.clone()),
+
+                "globals" | "new_globals" => define_globals(value, "", false, &mut options.globals),
+
+                "read_globals" | "new_read_globals" => {
+                    define_globals(value, "", true, &mut options.globals)
+                }
+
+                "ignore" => options.ignore.extend(value.strings()),
+                "enable" => options.enable.extend(value.strings()),
+
+                "max_cyclomatic_complexity" => match value {
+                    Value::Number(maximum) => options.max_cyclomatic_complexity = Some(*maximum),
+                    _ => notes.push(format!(
+                        "{location}: `max_cyclomatic_complexity` isn't a number, so it's skipped"
+                    )),
+                },
+
+                // Read separately, since they're only at the top level
+                "files" | "stds" if location == ".luacheckrc" => {}
+
+                _ => match BOOLEAN_OPTIONS.iter().find(|(option, _)| option == key) {
+                    Some((_, codes)) => {
+                        if *value == Value::Bool(false) {
+                            options.ignore.push((*codes).to_owned());
+                        }
+                    }
+
+                    None => notes.push(format!(
+                        "{location}: `{key}` has no equivalent in selene, so it's skipped"
+                    )),
+                },
+            }
+
+            if key.starts_with("new_") {
+                notes.push(format!(
+                    "{location}: `{key}` replaces the globals from `std` in luacheck, but selene adds them to the standard library instead"
+                ));
+            }
+        }
+
+        options
+    }
+}
+
+// Whether a luacheck pattern for warning codes, such as `21` or `21.`, matches the code. These are
+// Lua patterns matched against the start of the code.
+fn code_matches(pattern: &str, code: &str) -> bool {
+    pattern.len() <= code.len()
+        && pattern
+            .chars()
+            .zip(code.chars())
+            .all(|(pattern, code)| pattern == '.' || pattern == code)
+}
+
+// Returns what `std = "lua51+busted"` is in selene, such as `lua51+testing`
+fn standard_library_names(
+    std: &str,
+    stds: &BTreeMap<String, Options>,
+    location: &str,
+    globals: &mut BTreeMap<String, Field>,
+    notes: &mut Vec<String>,
+) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for name in std.split('+').filter(|name| !name.is_empty()) {
+        if let Some(custom) = stds.get(name) {
+            globals.extend(custom.globals.clone());
+            continue;
+        }
+
+        let selene_name = match name {
+            "lua51" | "lua51c" | "luajit" | "min" => "lua51",
+            "lua52" | "lua52c" => "lua52",
+            "lua53" | "lua53c" => "lua53",
+            "busted" => "testing",
+            "roblox" => "roblox",
+
+            "lua54" | "lua54c" | "max" => {
+                notes.push(format!(
+                    "{location}: selene doesn't have the `{name}` standard library, so `lua53` is used instead"
+                ));
+
+                "lua53"
+            }
+
+            _ => {
+                notes.push(format!(
+                    "{location}: selene doesn't have the `{name}` standard library, so its globals have to be added to `{STD_NAME}.yml`"
+                ));
+
+                continue;
+            }
+        };
+
+        if !names.iter().any(|existing| existing == selene_name) {
+            names.push(selene_name.to_owned());
+        }
+    }
+
+    names
+}
+
+// The directory that a pattern in `files` is for, if it's for every file in a directory
+fn pattern_directory(pattern: &str) -> Option<&str> {
+    let directory = pattern
+        .strip_suffix("/**/*.lua")
+        .or_else(|| pattern.strip_suffix("/**"))
+        .unwrap_or(pattern)
+        .trim_end_matches('/');
+
+    if directory.is_empty()
+        || directory.contains(['*', '?', '['])
+        || directory.ends_with(".lua")
+        || directory.ends_with(".luau")
+    {
+        None
+    } else {
+        Some(directory)
+    }
+}
+
+#[derive(Default)]
+struct Migration {
+    /// The files to write, relative to the directory of the .luacheckrc, and what's in them.
+    files: Vec<(PathBuf, String)>,
+    /// Everything in the .luacheckrc that couldn't be migrated exactly.
+    notes: Vec<String>,
+}
+
+// What one set of options becomes in selene.toml
+struct Translated {
+    std: Vec<String>,
+    globals: BTreeMap<String, Field>,
+    rules: toml::value::Table,
+    config: toml::value::Table,
+}
+
+impl Migration {
+    fn from_ast(ast: &Ast) -> Self {
+        let mut reader = Reader::default();
+        reader.read(ast);
+
+        let mut migration = Migration {
+            notes: std::mem::take(&mut reader.notes),
+            ..Migration::default()
+        };
+
+        let empty = Table::default();
+        let root = Value::Table(Table {
+            fields: reader.variables,
+            ..Table::default()
+        });
+        let root = root.as_table().unwrap();
+
+        let mut stds = BTreeMap::new();
+        if let Some(Value::Table(custom_stds)) = root.fields.get("stds") {
+            for (name, definition) in &custom_stds.fields {
+                let location = format!("stds.{name}");
+                stds.insert(
+                    name.to_owned(),
+                    Options::from_table(
+                        definition.as_table().unwrap_or(&empty),
+                        &location,
+                        &mut migration.notes,
+                    ),
+                );
+            }
+        }
+
+        let options = Options::from_table(root, ".luacheckrc", &mut migration.notes);
+        let translated = migration.translate(&options, &stds, None, ".luacheckrc");
+
+        // The globals are written without a base, so that they can be added to any other library
+        let mut root_std = translated.std.clone();
+        if !translated.globals.is_empty() {
+            migration.add_std_file(Path::new(""), STD_NAME, translated.globals.clone());
+            root_std.push(STD_NAME.to_owned());
+        }
+
+        let mut selene_toml = toml::value::Table::new();
+        if !root_std.is_empty() {
+            selene_toml.insert("std".to_owned(), toml::Value::String(root_std.join("+")));
+        }
+
+        let mut test_files = Vec::new();
+        let mut test_profile = toml::value::Table::new();
+
+        if let Some(Value::Table(files)) = root.fields.get("files") {
+            for (pattern, file_options) in &files.fields {
+                let location = format!("files[\"{pattern}\"]");
+                let file_options = Options::from_table(
+                    file_options.as_table().unwrap_or(&empty),
+                    &location,
+                    &mut migration.notes,
+                );
+
+                let file_translated =
+                    migration.translate(&file_options, &stds, Some(&translated.std), &location);
+
+                // busted globals are what selene gives test files
+                if file_translated.std.iter().any(|name| name == "testing") {
+                    test_files.push(toml::Value::String(match pattern_directory(pattern) {
+                        Some(directory) => format!("{directory}/**"),
+                        None => pattern.to_owned(),
+                    }));
+
+                    let mut std = file_translated.std.clone();
+                    std.retain(|name| name != "testing");
+
+                    // Test files are already given `std` with the testing globals added
+                    if std != translated.std || !file_translated.globals.is_empty() {
+                        if !translated.globals.is_empty() {
+                            std.push(STD_NAME.to_owned());
+                        }
+
+                        std.push("testing".to_owned());
+
+                        if !file_translated.globals.is_empty() {
+                            migration.add_std_file(
+                                Path::new(""),
+                                TESTS_STD_NAME,
+                                file_translated.globals,
+                            );
+
+                            std.push(TESTS_STD_NAME.to_owned());
+                        }
+
+                        test_profile.insert("std".to_owned(), toml::Value::String(std.join("+")));
+                    }
+
+                    insert_tables(
+                        &mut test_profile,
+                        file_translated.rules,
+                        file_translated.config,
+                    );
+                    continue;
+                }
+
+                let directory = match pattern_directory(pattern) {
+                    Some(directory) => directory,
+                    None => {
+                        migration.notes.push(format!(
+                            "{location}: selene.toml can only have different options for whole directories, so these are skipped. Globals can be declared in the files themselves with `-- selene: globals(name)`"
+                        ));
+
+                        continue;
+                    }
+                };
+
+                migration.write_directory(directory, &translated, file_translated);
+            }
+        }
+
+        if !test_files.is_empty() {
+            selene_toml.insert("test-files".to_owned(), toml::Value::Array(test_files));
+        }
+
+        insert_tables(&mut selene_toml, translated.rules, translated.config);
+
+        if !test_profile.is_empty() {
+            let mut profile = toml::value::Table::new();
+            profile.insert("test".to_owned(), toml::Value::Table(test_profile));
+            selene_toml.insert("profile".to_owned(), toml::Value::Table(profile));
+        }
+
+        migration
+            .files
+            .insert(0, (PathBuf::from("selene.toml"), toml_string(selene_toml)));
+
+        migration
+    }
+
+    fn translate(
+        &mut self,
+        options: &Options,
+        stds: &BTreeMap<String, Options>,
+        parent_std: Option<&[String]>,
+        location: &str,
+    ) -> Translated {
+        let mut globals = options.globals.clone();
+
+        let std = match &options.std {
+            Some(Value::String(std)) => {
+                let names =
+                    standard_library_names(std, stds, location, &mut globals, &mut self.notes);
+
+                // `+busted` adds to the standard library being used instead of replacing it
+                match parent_std {
+                    Some(parent_std) if std.starts_with('+') => {
+                        let mut std = parent_std.to_vec();
+                        std.extend(names.into_iter().filter(|name| !parent_std.contains(name)));
+                        std
+                    }
+
+                    _ => names,
+                }
+            }
+
+            Some(Value::Table(table)) => {
+                globals.extend(Options::from_table(table, location, &mut self.notes).globals);
+                parent_std.map(<[String]>::to_vec).unwrap_or_default()
+            }
+
+            _ => parent_std.map(<[String]>::to_vec).unwrap_or_default(),
+        };
+
+        let mut ignored: BTreeSet<&str> = BTreeSet::new();
+        for pattern in &options.ignore {
+            if !pattern
+                .chars()
+                .all(|character| character.is_ascii_digit() || character == '.')
+            {
+                self.notes.push(format!(
+                    "{location}: selene can't ignore warnings for specific names, so `{pattern}` is skipped"
+                ));
+
+                continue;
+            }
+
+            for (_, codes) in luacheck_lints() {
+                ignored.extend(
+                    codes
+                        .iter()
+                        .copied()
+                        .filter(|code| code_matches(pattern, code))
+                        .filter(|code| {
+                            !options
+                                .enable
+                                .iter()
+                                .any(|pattern| code_matches(pattern, code))
+                        }),
+                );
+            }
+        }
+
+        let mut rules = toml::value::Table::new();
+        let mut config = toml::value::Table::new();
+
+        // A lint is only allowed when every warning it checks for is ignored
+        for (lint, codes) in luacheck_lints() {
+            let ignored_codes = codes
+                .iter()
+                .filter(|code| ignored.contains(*code))
+                .collect::<Vec<_>>();
+
+            if ignored_codes.len() == codes.len() {
+                rules.insert(lint.to_owned(), toml::Value::String("allow".to_owned()));
+            } else if !ignored_codes.is_empty() {
+                self.notes.push(format!(
+                    "{location}: `{lint}` checks for more than warning {}, so it's left on",
+                    ignored_codes
+                        .iter()
+                        .map(|code| code.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ));
+            }
+        }
+
+        if let Some(maximum) = options.max_cyclomatic_complexity {
+            if !rules.contains_key("high_cyclomatic_complexity") {
+                rules.insert(
+                    "high_cyclomatic_complexity".to_owned(),
+                    toml::Value::String("warn".to_owned()),
+                );
+
+                let mut lint_config = toml::value::Table::new();
+                lint_config.insert(
+                    "maximum_complexity".to_owned(),
+                    toml::Value::Integer(maximum as i64),
+                );
+                config.insert(
+                    "high_cyclomatic_complexity".to_owned(),
+                    toml::Value::Table(lint_config),
+                );
+            }
+        }
+
+        Translated {
+            std,
+            globals,
+            rules,
+            config,
+        }
+    }
+
+    fn add_std_file(&mut self, directory: &Path, name: &str, globals: BTreeMap<String, Field>) {
+        let mut library = StandardLibrary::default();
+        library.globals = globals;
+
+        let file = directory.join(format!("{name}.yml"));
+        if !self.files.iter().any(|(existing, _)| *existing == file) {
+            self.files
+                .push((file, serde_yaml::to_string(&library).unwrap()));
+        }
+    }
+
+    // Writes a selene.toml in the directory that extends the one at the top
+    fn write_directory(&mut self, directory: &str, root: &Translated, translated: Translated) {
+        let to_root = "../".repeat(directory.split('/').filter(|part| !part.is_empty()).count());
+
+        let mut selene_toml = toml::value::Table::new();
+        selene_toml.insert(
+            "extends".to_owned(),
+            toml::Value::String(format!("{to_root}selene.toml")),
+        );
+
+        // Standard libraries are found next to the selene.toml being used, not the one it extends
+        let mut std = translated.std.clone();
+
+        if !root.globals.is_empty() {
+            std.push(format!("{to_root}{STD_NAME}"));
+        }
+
+        if !translated.globals.is_empty() {
+            self.add_std_file(Path::new(directory), STD_NAME, translated.globals);
+            std.push(STD_NAME.to_owned());
+        }
+
+        if !std.is_empty() {
+            selene_toml.insert("std".to_owned(), toml::Value::String(std.join("+")));
+        }
+
+        insert_tables(&mut selene_toml, translated.rules, translated.config);
+
+        self.files.push((
+            Path::new(directory).join("selene.toml"),
+            toml_string(selene_toml),
+        ));
+    }
+}
+
+// toml doesn't always leave a blank line before tables, such as after arrays
+fn toml_string(table: toml::value::Table) -> String {
+    let mut output = String::new();
+
+    for line in toml::to_string(&toml::Value::Table(table)).unwrap().lines() {
+        if line.starts_with('[') && !output.is_empty() && !output.ends_with("\n\n") {
+            output.push('\n');
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    output
+}
+
+fn insert_tables(
+    selene_toml: &mut toml::value::Table,
+    rules: toml::value::Table,
+    config: toml::value::Table,
+) {
+    if !rules.is_empty() {
+        selene_toml.insert("rules".to_owned(), toml::Value::Table(rules));
+    }
+
+    if !config.is_empty() {
+        selene_toml.insert("config".to_owned(), toml::Value::Table(config));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrate(code: &str) -> Migration {
+        Migration::from_ast(&full_moon::parse(code).unwrap())
+    }
+
+    fn file<'a>(migration: &'a Migration, path: &str) -> &'a str {
+        &migration
+            .files
+            .iter()
+            .find(|(file, _)| file == Path::new(path))
+            .unwrap_or_else(|| panic!("{path} wasn't written"))
+            .1
+    }
+
+    #[test]
+    fn test_globals() {
+        let migration = migrate(
+            r#"
+std = "lua51+busted"
+globals = { "game_state", game = { fields = { "score" } } }
+read_globals = {
+    "love",
+    engine = { fields = { "draw", version = { read_only = true } } },
+    config = { other_fields = true },
+}
+"#,
+        );
+
+        assert_eq!(
+            file(&migration, "selene.toml"),
+            "std = \"lua51+testing+luacheck\"\n"
+        );
+
+        let library: StandardLibrary =
+            serde_yaml::from_str(file(&migration, "luacheck.yml")).unwrap();
+
+        assert_eq!(
+            library.globals.keys().collect::<Vec<_>>(),
+            vec![
+                "config",
+                "engine.draw",
+                "engine.version",
+                "game",
+                "game.score",
+                "game_state",
+                "love"
+            ]
+        );
+        assert_eq!(library.globals["game_state"].field_kind, FieldKind::Any);
+        assert_eq!(library.globals["config"].field_kind, FieldKind::Any);
+        assert_eq!(library.globals["love"].field_kind, FieldKind::Any);
+        assert_eq!(
+            library.globals["game"].field_kind,
+            FieldKind::Property(PropertyWritability::OverrideFields)
+        );
+
+        assert!(migration.notes.is_empty(), "{:?}", migration.notes);
+    }
+
+    #[test]
+    fn test_rules() {
+        let migration = migrate(
+            r#"
+local ignored = { "21", "3" }
+ignore = ignored
+enable = { "213" }
+redefined = false
+max_cyclomatic_complexity = 15
+max_line_length = 120
+"#,
+        );
+
+        assert_eq!(
+            file(&migration, "selene.toml"),
+            "[config.high_cyclomatic_complexity]\nmaximum_complexity = 15\n\n[rules]\nduplicate_keys = \"allow\"\nhigh_cyclomatic_complexity = \"warn\"\nshadowing = \"allow\"\n"
+        );
+
+        assert_eq!(
+            migration.notes,
+            vec![
+                ".luacheckrc: `max_line_length` has no equivalent in selene, so it's skipped",
+                ".luacheckrc: `unused_variable` checks for more than warning 211, 212, 311, so it's left on",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_files() {
+        let migration = migrate(
+            r#"
+std = "lua51"
+read_globals = { "engine" }
+
+files["spec/"] = { std = "+busted", globals = { "fixtures" }, ignore = { "4" } }
+files["tools"] = { std = "lua53", ignore = { "113" } }
+files["src/main.lua"] = { globals = { "main" } }
+"#,
+        );
+
+        assert_eq!(
+            file(&migration, "selene.toml"),
+            r#"std = "lua51+luacheck"
+test-files = ["spec/**"]
+
+[profile.test]
+std = "lua51+luacheck+testing+luacheck_tests"
+
+[profile.test.rules]
+shadowing = "allow"
+"#
+        );
+
+        assert_eq!(
+            file(&migration, "tools/selene.toml"),
+            "extends = \"../selene.toml\"\nstd = \"lua53+../luacheck\"\n\n[rules]\nundefined_variable = \"allow\"\n"
+        );
+
+        let library: StandardLibrary =
+            serde_yaml::from_str(file(&migration, "luacheck_tests.yml")).unwrap();
+        assert_eq!(library.globals.keys().collect::<Vec<_>>(), vec!["fixtures"]);
+
+        assert_eq!(migration.notes.len(), 1);
+        assert!(migration.notes[0].starts_with("files[\"src/main.lua\"]"));
+    }
+}
@@ -17,6 +17,27 @@ pub struct Options {
     #[structopt(long)]
     pub config: Option<String>,
 
+    /// Check files inside of symlinked directories
+    #[structopt(long)]
+    pub follow_symlinks: bool,
+
+    /// How many directories deep to look for files, where 1 is only the files directly inside
+    #[structopt(long)]
+    pub max_depth: Option<usize>,
+
+    /// Check fenced Lua code blocks in Markdown files, and look for Markdown files in directories
+    #[structopt(long)]
+    pub include_markdown: bool,
+
+    /// The profile from selene.toml to use, such as `ci` for `[profile.ci]`
+    #[structopt(long)]
+    pub profile: Option<String>,
+
+    /// Use the severities from `[severity.ci]` in selene.toml. This is on when the CI environment
+    /// variable is set, as it is by most CI services
+    #[structopt(long)]
+    pub ci: bool,
+
     /// Number of threads to run on, default to the numbers of logical cores on your system
     #[structopt(long, default_value = get_num_cpus())]
     pub num_threads: usize,
@@ -27,7 +48,7 @@ pub struct Options {
         long,
         possible_values = &DisplayStyle::variants(),
         case_insensitive = true,
-        conflicts_with = "quiet",
+        conflicts_with_all = &["quiet", "group"],
     )]
     pub display_style: Option<DisplayStyle>,
 
@@ -36,6 +57,29 @@ pub struct Options {
     #[structopt(long, short)]
     pub quiet: bool,
 
+    /// Display identical diagnostics once, with how many times they occurred.
+    /// Equivalent to --display-style="group"
+    #[structopt(long, conflicts_with = "quiet")]
+    pub group: bool,
+
+    /// How to order diagnostics: by file, by lint, or errors before warnings.
+    /// Ties are ordered by file, line, column, and then lint
+    #[structopt(
+        long,
+        possible_values = &SortBy::variants(),
+        case_insensitive = true,
+        default_value = "file",
+    )]
+    pub sort: SortBy,
+
+    /// Stop displaying diagnostics after this many. Every problem is still counted in the summary
+    #[structopt(long)]
+    pub max_problems: Option<usize>,
+
+    /// Stop checking the remaining files as soon as any file has an error
+    #[structopt(long)]
+    pub fail_fast: bool,
+
     #[structopt(
         long,
         possible_values = &Color::variants(),
@@ -44,6 +88,11 @@ pub struct Options {
     )]
     pub color: Color,
 
+    /// The language to show messages in instead of English, such as `pt-BR`, or a message
+    /// catalog file to read them from
+    #[structopt(long)]
+    pub locale: Option<String>,
+
     /// Suppress summary information.
     #[structopt(long, short)]
     pub no_summary: bool,
@@ -52,6 +101,46 @@ pub struct Options {
     #[structopt(long)]
     pub allow_warnings: bool,
 
+    /// A report saved from an earlier run with --display-style=json to compare the problems found
+    /// with, printing how many are new, fixed, and unchanged
+    #[structopt(long, parse(from_os_str))]
+    pub compare_to: Option<PathBuf>,
+
+    /// Write how many errors and warnings were found to a file, as an SVG badge with `badge`, or as
+    /// a shields.io endpoint with `shield`. Can be given more than once
+    #[structopt(long, number_of_values = 2, value_names = &["format", "file"])]
+    pub report: Vec<String>,
+
+    /// Only fail when there are problems that aren't in the report given to --compare-to
+    #[structopt(long, requires = "compare-to")]
+    pub fail_on_new: bool,
+
+    /// Only warn when this version of selene does not match `selene-version` in selene.toml
+    #[structopt(long)]
+    pub no_version_check: bool,
+
+    /// Log what selene is doing, such as which config and standard library are used.
+    /// Can be repeated (-vv, -vvv) to log even more
+    #[structopt(short, long, parse(from_occurrences))]
+    pub verbose: u8,
+
+    /// Sets how much to log, overriding --verbose
+    #[structopt(long, possible_values = &LogLevel::variants(), case_insensitive = true)]
+    pub log_level: Option<LogLevel>,
+
+    /// Apply automatic fixes to the files being checked, for lints that have them
+    #[structopt(long, conflicts_with = "staged")]
+    pub fix: bool,
+
+    /// Like --fix, but also apply fixes that could change what the code does, such as reordering requires
+    #[structopt(long, conflicts_with = "staged")]
+    pub fix_suggestions: bool,
+
+    /// Only check the files staged in git, as they will be committed.
+    /// Uses the quiet display style unless --display-style is given
+    #[structopt(long)]
+    pub staged: bool,
+
     /// Whether to pretend to be luacheck for existing consumers
     #[structopt(long, hidden(true))]
     pub luacheck: bool,
@@ -60,7 +149,7 @@ pub struct Options {
     #[structopt(long, hidden(true))]
     pub ranges: bool,
 
-    #[structopt(parse(from_os_str), min_values(1), index(1), required(true))]
+    #[structopt(parse(from_os_str), min_values(1), index(1), required_unless("staged"))]
     pub files: Vec<OsString>,
 
     #[structopt(subcommand)]
@@ -71,9 +160,58 @@ impl Options {
     pub fn quiet(&self) -> bool {
         match self.display_style {
             Some(display_style) => display_style == DisplayStyle::Quiet,
-            None => self.quiet,
+            None => self.quiet || (self.staged && !self.group),
+        }
+    }
+
+    pub fn log_level(&self) -> log::LevelFilter {
+        match self.log_level {
+            Some(LogLevel::Off) => log::LevelFilter::Off,
+            Some(LogLevel::Error) => log::LevelFilter::Error,
+            Some(LogLevel::Warn) => log::LevelFilter::Warn,
+            Some(LogLevel::Info) => log::LevelFilter::Info,
+            Some(LogLevel::Debug) => log::LevelFilter::Debug,
+            Some(LogLevel::Trace) => log::LevelFilter::Trace,
+
+            None => match self.verbose {
+                0 => log::LevelFilter::Warn,
+                1 => log::LevelFilter::Info,
+                2 => log::LevelFilter::Debug,
+                _ => log::LevelFilter::Trace,
+            },
         }
     }
+
+    /// Whether fixes are being applied, with either --fix or --fix-suggestions
+    pub fn fixing(&self) -> bool {
+        self.fix || self.fix_suggestions
+    }
+
+    pub fn group(&self) -> bool {
+        match self.display_style {
+            Some(display_style) => display_style == DisplayStyle::Group,
+            None => self.group,
+        }
+    }
+
+    // The formats and files given to --report, in pairs
+    pub fn reports(&self) -> Result<Vec<(ReportFormat, PathBuf)>, String> {
+        self.report
+            .chunks(2)
+            .map(|pair| match pair[0].parse::<ReportFormat>() {
+                Ok(format) => Ok((format, PathBuf::from(&pair[1]))),
+                Err(_) => Err(format!(
+                    "unknown report format `{}`, expected `badge` or `shield`",
+                    pair[0]
+                )),
+            })
+            .collect()
+    }
+
+    // Luacheck consumers don't expect anything but the diagnostics
+    pub fn summary(&self) -> bool {
+        !self.luacheck && !self.no_summary && self.display_style != Some(DisplayStyle::Luacheck)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, StructOpt)]
@@ -91,6 +229,153 @@ pub enum Command {
         #[structopt(parse(from_os_str))]
         filename: PathBuf,
     },
+
+    /// Runs an init script in a Lua interpreter and prints a standard library containing every
+    /// global it leaves behind
+    CaptureStd {
+        #[structopt(parse(from_os_str))]
+        init: PathBuf,
+
+        /// A standard library to build on top of, whose globals are left out of the output
+        #[structopt(long)]
+        base: Option<String>,
+    },
+
+    /// Converts lua-language-server definition files, or other stubs with EmmyLua annotations,
+    /// into a standard library
+    ImportStd {
+        #[structopt(parse(from_os_str), min_values(1), required(true))]
+        files: Vec<PathBuf>,
+    },
+
+    /// Writes a selene.toml, and a standard library for the globals it declares, that do the same
+    /// as a .luacheckrc. Prints what couldn't be migrated
+    MigrateLuacheckrc {
+        #[structopt(parse(from_os_str), default_value = ".luacheckrc")]
+        file: PathBuf,
+
+        /// Replace selene.toml and the standard libraries if they already exist
+        #[structopt(long)]
+        force: bool,
+    },
+
+    /// Rewrites standard library files with their globals sorted and written the same way every
+    /// time, so that changes to them are easy to review. Comments are not kept
+    FmtStd {
+        #[structopt(parse(from_os_str), min_values(1), required(true))]
+        files: Vec<PathBuf>,
+
+        /// Don't change the files, and fail if any of them aren't formatted
+        #[structopt(long)]
+        check: bool,
+    },
+
+    /// Prints the globals that were added, removed, or changed between two standard libraries.
+    /// Each library can be a name, such as `lua51`, or a path to a library file
+    DiffStd { old: String, new: String },
+
+    /// Prints documentation for every global in a standard library, which can be a name,
+    /// such as `roblox`, or a path to a library file
+    DocStd {
+        library: String,
+
+        #[structopt(
+            long,
+            possible_values = &DocFormat::variants(),
+            case_insensitive = true,
+            default_value = "markdown",
+        )]
+        format: DocFormat,
+    },
+
+    /// Prints the documentation for a single global, such as `math.floor`, for editors to show
+    /// when hovering over it. Uses the standard library from selene.toml unless one is given
+    Hover {
+        name: String,
+
+        #[structopt(long)]
+        std: Option<String>,
+    },
+
+    /// Prints what every name in the file refers to as JSON, such as a local or a standard library
+    /// global, for editors to highlight them. Reads from stdin if the file is "-"
+    SemanticTokens {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+
+        #[structopt(long)]
+        std: Option<String>,
+    },
+
+    /// Prints the signature and parameters of a standard library function as JSON, for editors to
+    /// show while typing a call to it. Uses the standard library from selene.toml unless one is given
+    SignatureHelp {
+        name: String,
+
+        #[structopt(long)]
+        std: Option<String>,
+    },
+
+    /// Prints the globals and fields that can finish a partially typed global path, such as
+    /// `math.fl`, as JSON, for editors to complete it. Uses the standard library from selene.toml
+    /// unless one is given
+    Completions {
+        prefix: String,
+
+        #[structopt(long)]
+        std: Option<String>,
+    },
+
+    /// Prints where the local variable at a byte offset in the file is declared and used as JSON,
+    /// for editors to go to its definition and rename it. Reads from stdin if the file is "-"
+    References {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+
+        offset: usize,
+    },
+
+    /// Prints every lint with its category, default severity, configuration, and an example,
+    /// for generating documentation
+    DumpLints {
+        #[structopt(
+            long,
+            possible_values = &LintsFormat::variants(),
+            case_insensitive = true,
+            default_value = "json",
+        )]
+        format: LintsFormat,
+    },
+
+    /// Prints a JSON Schema for selene.toml or standard library files,
+    /// for editors to validate and autocomplete them with
+    Schema {
+        /// Print the schema for selene.toml
+        #[structopt(long, required_unless = "std", conflicts_with = "std")]
+        config: bool,
+
+        /// Print the schema for standard libraries written in YAML
+        #[structopt(long)]
+        std: bool,
+    },
+
+    /// Prints the configuration that applies to a file as JSON, such as which selene.toml and
+    /// standard library it uses, and the severity of every lint
+    PrintConfig {
+        #[structopt(parse(from_os_str))]
+        file: Option<PathBuf>,
+    },
+
+    /// Measures how long parsing and each lint take on the given files
+    #[cfg(feature = "bench")]
+    Bench {
+        #[structopt(parse(from_os_str), min_values(1), required(true))]
+        files: Vec<PathBuf>,
+
+        /// How many times to check every file
+        #[structopt(long, default_value = "3")]
+        iterations: u32,
+    },
 }
 
 arg_enum! {
@@ -102,10 +387,55 @@ arg_enum! {
     }
 }
 
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum LogLevel {
+        Off,
+        Error,
+        Warn,
+        Info,
+        Debug,
+        Trace,
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum DocFormat {
+        Markdown,
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum LintsFormat {
+        Json,
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum ReportFormat {
+        Badge,
+        Shield,
+    }
+}
+
+arg_enum! {
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum SortBy {
+        File,
+        Lint,
+        Severity,
+    }
+}
+
 arg_enum! {
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     pub enum DisplayStyle {
+        Group,
         Json,
+        Luacheck,
         Rich,
         Quiet,
     }
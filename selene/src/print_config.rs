@@ -0,0 +1,95 @@
+// Used by print-config, to show which selene.toml and standard library a file ends up using.
+// This is mostly useful with multiple projects, where it's not always obvious.
+use std::path::{Path, PathBuf};
+
+use selene_lib::{rules::Severity, CheckerConfig, UnknownLints};
+use serde_json::json;
+
+use crate::{opts::Options, workspace::Workspace};
+
+pub fn print_config(
+    options: &Options,
+    workspace: &mut Workspace,
+    current_dir: &Path,
+    file: Option<&Path>,
+) -> serde_json::Result<()> {
+    let project = match file {
+        Some(file) => workspace.project_for(file),
+        None => workspace.root().clone(),
+    };
+
+    let nested = project.directory != current_dir;
+
+    // The checker consumes the config of every lint, so the config is read again to show them
+    let config_file = config_file(options, &project.directory, nested);
+    let mut config = match &config_file {
+        Some(config_file) => crate::load_config(options, Some(config_file), nested),
+        None => CheckerConfig::default(),
+    };
+
+    if project.for_tests {
+        config = config.for_tests();
+    }
+
+    let lints = selene_lib::all_rules()
+        .iter()
+        .filter_map(|&name| {
+            let severity = match project.checker.lint_severity(name)? {
+                Severity::Allow => "allow",
+                Severity::Error => "deny",
+                Severity::Warning => "warn",
+            };
+
+            Some((name.to_owned(), json!(severity)))
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    let output = json!({
+        "file": file,
+        "project": project.directory,
+        "config_file": config_file,
+        "profile": options.profile,
+        "ci": crate::running_in_ci(options),
+        "test_file": project.for_tests,
+        "std": config.std(),
+        "extends": config.extends,
+        "extends_sha256": config.extends_sha256,
+        "selene_version": config.selene_version,
+        "extensions": config
+            .extensions
+            .clone()
+            .unwrap_or_else(crate::walk::FileMatcher::default_extensions),
+        "std_extensions": crate::standard_library::FILE_EXTENSIONS,
+        "source_map": config.source_map,
+        "time_budget": config.time_budget,
+        "quality_gate": config.quality_gate.as_ref().map(|quality_gate| json!({
+            "max_errors": quality_gate.max_errors,
+            "max_warnings": quality_gate.max_warnings,
+            "max_per_lint": quality_gate.max_per_lint,
+        })),
+        "transcode_utf16": config.transcode_utf16,
+        "test_files": config.test_files(),
+        "unknown_lints": match config.unknown_lints {
+            UnknownLints::Allow => "allow",
+            UnknownLints::Deny => "deny",
+        },
+        "lints": lints,
+        "config": config.config,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    Ok(())
+}
+
+// The same selene.toml the project was created from, if it has one
+fn config_file(options: &Options, directory: &Path, nested: bool) -> Option<PathBuf> {
+    if nested {
+        return Some(directory.join("selene.toml"));
+    }
+
+    match &options.config {
+        Some(config) => Some(PathBuf::from(config)),
+        None => Some(PathBuf::from("selene.toml")).filter(|path| path.is_file()),
+    }
+}
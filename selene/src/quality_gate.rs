@@ -0,0 +1,163 @@
+// Used by `[quality-gate]` in selene.toml, which lets a run pass as long as it finds no more
+// problems than the config allows, so that lints can be adopted before every problem is fixed.
+use std::{collections::BTreeMap, sync::Mutex};
+
+use selene_lib::{rules::Severity, QualityGate};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LintCounts {
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+lazy_static::lazy_static! {
+    static ref COUNTS: Mutex<BTreeMap<String, LintCounts>> = Mutex::new(BTreeMap::new());
+}
+
+pub fn record(lint: &str, severity: Severity, count: usize) {
+    let mut counts = COUNTS.lock().unwrap();
+    let lint_counts = counts.entry(lint.to_owned()).or_default();
+
+    match severity {
+        Severity::Allow => {}
+        Severity::Error => lint_counts.errors += count,
+        Severity::Warning => lint_counts.warnings += count,
+    }
+}
+
+pub struct Outcome {
+    /// Every budget that was gone over, which fails the run.
+    pub exceeded: Vec<String>,
+    /// Budgets that are higher than they need to be, which can be lowered to what was found.
+    pub lowerable: Vec<String>,
+}
+
+pub fn check(quality_gate: &QualityGate) -> Outcome {
+    evaluate(quality_gate, &COUNTS.lock().unwrap())
+}
+
+fn plural(count: usize, name: &str) -> String {
+    if count == 1 {
+        format!("{count} {name}")
+    } else {
+        format!("{count} {name}s")
+    }
+}
+
+fn evaluate(quality_gate: &QualityGate, counts: &BTreeMap<String, LintCounts>) -> Outcome {
+    let mut exceeded = Vec::new();
+    let mut lowerable = Vec::new();
+
+    let mut compare = |found: usize, budget: usize, what: String, setting: String| {
+        if found > budget {
+            exceeded.push(format!(
+                "found {what}, but `{setting}` only allows {budget}"
+            ));
+        } else if found < budget {
+            lowerable.push(format!(
+                "found {what}, so `{setting}` can be lowered from {budget} to {found}"
+            ));
+        }
+    };
+
+    let (mut errors, mut warnings) = (0, 0);
+
+    for (lint, lint_counts) in counts {
+        if !quality_gate.max_per_lint.contains_key(lint) {
+            errors += lint_counts.errors;
+            warnings += lint_counts.warnings;
+        }
+    }
+
+    compare(
+        errors,
+        quality_gate.max_errors.unwrap_or(0),
+        plural(errors, "error"),
+        "max-errors".to_owned(),
+    );
+
+    if let Some(max_warnings) = quality_gate.max_warnings {
+        compare(
+            warnings,
+            max_warnings,
+            plural(warnings, "warning"),
+            "max-warnings".to_owned(),
+        );
+    }
+
+    let mut budgets = quality_gate.max_per_lint.iter().collect::<Vec<_>>();
+    budgets.sort();
+
+    for (lint, &budget) in budgets {
+        let found = counts
+            .get(lint)
+            .map_or(0, |lint_counts| lint_counts.errors + lint_counts.warnings);
+
+        compare(
+            found,
+            budget,
+            format!("{} from `{lint}`", plural(found, "problem")),
+            format!("max-per-lint.{lint}"),
+        );
+    }
+
+    Outcome {
+        exceeded,
+        lowerable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(lints: &[(&str, usize, usize)]) -> BTreeMap<String, LintCounts> {
+        lints
+            .iter()
+            .map(|&(lint, errors, warnings)| (lint.to_owned(), LintCounts { errors, warnings }))
+            .collect()
+    }
+
+    #[test]
+    fn test_budgets() {
+        let quality_gate = QualityGate {
+            max_errors: None,
+            max_warnings: Some(5),
+            max_per_lint: [("unused_variable".to_owned(), 10)].into_iter().collect(),
+        };
+
+        let outcome = evaluate(
+            &quality_gate,
+            &counts(&[("unused_variable", 0, 12), ("shadowing", 0, 3)]),
+        );
+
+        assert_eq!(
+            outcome.exceeded,
+            vec!["found 12 problems from `unused_variable`, but `max-per-lint.unused_variable` only allows 10"]
+        );
+
+        assert_eq!(
+            outcome.lowerable,
+            vec!["found 3 warnings, so `max-warnings` can be lowered from 5 to 3"]
+        );
+    }
+
+    #[test]
+    fn test_errors_fail_by_default() {
+        let quality_gate = QualityGate {
+            max_per_lint: [("divide_by_zero".to_owned(), 1)].into_iter().collect(),
+            ..QualityGate::default()
+        };
+
+        assert!(
+            evaluate(&quality_gate, &counts(&[("divide_by_zero", 1, 0)]))
+                .exceeded
+                .is_empty()
+        );
+
+        assert_eq!(
+            evaluate(&quality_gate, &counts(&[("empty_if", 1, 5)])).exceeded,
+            vec!["found 1 error, but `max-errors` only allows 0"]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+// Used by editors to go to the definition of a local variable, find its uses, and rename it. Only
+// the one file is looked at, since locals can't be used outside of the file they're declared in.
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use selene_lib::symbols;
+use serde_json::json;
+
+use crate::encoding;
+
+/// Reads the file being edited, or stdin if it's "-" so that unsaved code can be given.
+pub fn read_source(file: &Path) -> io::Result<String> {
+    let bytes = if file == Path::new("-") {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(file)?
+    };
+
+    Ok(encoding::decode(&bytes, encoding::detect(&bytes)).into_owned())
+}
+
+pub fn references(file: &Path, offset: usize) -> Result<(), Box<dyn Error>> {
+    let ast = full_moon::parse(&read_source(file)?)?;
+
+    let local = symbols::local_at(&ast, offset)
+        .ok_or_else(|| format!("there's no local variable at byte {offset}"))?;
+
+    println!(
+        "{}",
+        json!({
+            "name": local.name,
+            "declaration": [local.declaration.0, local.declaration.1],
+            "references": local
+                .references
+                .iter()
+                .map(|(start, end)| [start, end])
+                .collect::<Vec<_>>(),
+        })
+    );
+
+    Ok(())
+}
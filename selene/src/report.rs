@@ -0,0 +1,160 @@
+// Writes how many errors and warnings were found for --report, as an SVG badge to commit next to a
+// README, or as the JSON a shields.io endpoint badge reads.
+use std::{fs, io, path::Path};
+
+use serde_json::json;
+
+use crate::opts::ReportFormat;
+
+const LABEL: &str = "selene";
+
+// Verdana at 11px, which badges are written in, is about this wide on average
+const CHARACTER_WIDTH: usize = 7;
+const PADDING: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Passing,
+    Warnings,
+    Errors,
+}
+
+impl Status {
+    fn new(errors: usize, warnings: usize) -> Self {
+        if errors > 0 {
+            Status::Errors
+        } else if warnings > 0 {
+            Status::Warnings
+        } else {
+            Status::Passing
+        }
+    }
+
+    // The named color that shields.io uses, and the color it is
+    fn colors(self) -> (&'static str, &'static str) {
+        match self {
+            Status::Passing => ("brightgreen", "#4c1"),
+            Status::Warnings => ("yellow", "#dfb317"),
+            Status::Errors => ("red", "#e05d44"),
+        }
+    }
+}
+
+fn count(amount: usize, singular: &str) -> String {
+    if amount == 1 {
+        format!("1 {singular}")
+    } else {
+        format!("{amount} {singular}s")
+    }
+}
+
+fn message(errors: usize, warnings: usize) -> String {
+    match (errors, warnings) {
+        (0, 0) => "passing".to_owned(),
+        (errors, 0) => count(errors, "error"),
+        (0, warnings) => count(warnings, "warning"),
+        (errors, warnings) => format!("{}, {}", count(errors, "error"), count(warnings, "warning")),
+    }
+}
+
+fn badge(errors: usize, warnings: usize) -> String {
+    let message = message(errors, warnings);
+    let (_, color) = Status::new(errors, warnings).colors();
+
+    let label_width = LABEL.len() * CHARACTER_WIDTH + PADDING;
+    let message_width = message.len() * CHARACTER_WIDTH + PADDING;
+    let width = label_width + message_width;
+
+    let label_x = label_width as f64 / 2.0;
+    let message_x = label_width as f64 + message_width as f64 / 2.0;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{LABEL}: {message}">
+  <title>{LABEL}: {message}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r">
+    <rect width="{width}" height="20" rx="3" fill="#fff"/>
+  </clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{LABEL}</text>
+    <text x="{label_x}" y="14">{LABEL}</text>
+    <text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
+// https://shields.io/badges/endpoint-badge
+fn shield(errors: usize, warnings: usize) -> serde_json::Result<String> {
+    let (color, _) = Status::new(errors, warnings).colors();
+
+    serde_json::to_string_pretty(&json!({
+        "schemaVersion": 1,
+        "label": LABEL,
+        "message": message(errors, warnings),
+        "color": color,
+    }))
+}
+
+/// Writes the report to the file. Parse errors are counted as errors, since those files couldn't
+/// be checked.
+pub fn write_report(
+    format: ReportFormat,
+    path: &Path,
+    errors: usize,
+    warnings: usize,
+) -> io::Result<()> {
+    let contents = match format {
+        ReportFormat::Badge => badge(errors, warnings),
+        ReportFormat::Shield => shield(errors, warnings)? + "\n",
+    };
+
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        assert_eq!(message(0, 0), "passing");
+        assert_eq!(message(1, 0), "1 error");
+        assert_eq!(message(0, 3), "3 warnings");
+        assert_eq!(message(2, 1), "2 errors, 1 warning");
+    }
+
+    #[test]
+    fn test_badge() {
+        let badge = badge(0, 2);
+
+        assert!(badge.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="132""#));
+        assert!(badge.contains("<title>selene: 2 warnings</title>"));
+        assert!(badge.contains(r##"fill="#dfb317""##));
+    }
+
+    #[test]
+    fn test_shield() {
+        let shield: serde_json::Value = serde_json::from_str(&shield(1, 4).unwrap()).unwrap();
+
+        assert_eq!(
+            shield,
+            json!({
+                "schemaVersion": 1,
+                "label": "selene",
+                "message": "1 error, 4 warnings",
+                "color": "red",
+            })
+        );
+    }
+}
@@ -0,0 +1,65 @@
+// Used by circular_require, which can only find cycles once every file has been checked.
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{atomic::Ordering, Mutex},
+};
+
+use selene_lib::{
+    require_graph::{self, ModuleRequire},
+    rules::Severity,
+};
+
+use crate::{
+    cross_file::{self, OtherLabel},
+    quality_gate, LINT_ERRORS, LINT_WARNINGS,
+};
+
+lazy_static::lazy_static! {
+    static ref MODULES: Mutex<BTreeMap<PathBuf, Vec<ModuleRequire>>> = Mutex::new(BTreeMap::new());
+}
+
+pub fn record(filename: &Path, requires: Vec<ModuleRequire>) {
+    MODULES
+        .lock()
+        .unwrap()
+        .insert(require_graph::normalize_path(filename), requires);
+}
+
+pub fn emit(severity: Severity) {
+    if severity == Severity::Allow {
+        return;
+    }
+
+    let cycles = require_graph::find_cycles(&MODULES.lock().unwrap());
+
+    let counter = match severity {
+        Severity::Error => &LINT_ERRORS,
+        _ => &LINT_WARNINGS,
+    };
+
+    counter.fetch_add(cycles.len(), Ordering::SeqCst);
+    quality_gate::record("circular_require", severity, cycles.len());
+
+    for cycle in cycles {
+        // The cycle goes through every file, so each one after the first gets a label
+        let other_labels = cycle
+            .iter()
+            .skip(1)
+            .map(|link| OtherLabel {
+                file: link.file.clone(),
+                range: link.range,
+                message: format!("which requires `{}`", link.required.display()),
+            })
+            .collect::<Vec<_>>();
+
+        if let Err(error) = cross_file::record(
+            &cycle[0].file,
+            require_graph::cycle_diagnostic(&cycle),
+            &other_labels,
+            severity,
+        ) {
+            crate::error(&format!("Couldn't report require cycle: {error}"));
+        }
+    }
+}
@@ -67,6 +67,7 @@ pub enum ApiMember {
 #[derive(Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ApiParameter {
+    pub name: String,
     pub default: Option<String>,
     #[serde(rename = "Type")]
     pub parameter_type: ApiValueType,
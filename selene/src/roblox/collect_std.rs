@@ -1,6 +1,5 @@
 use std::{
     fs,
-    io::BufReader,
     path::{Path, PathBuf},
 };
 
@@ -23,10 +22,14 @@ pub fn collect_roblox_standard_library<V>(
             let floating_file = floating_file_directory.join("roblox.yml");
 
             if floating_file.exists() {
-                let mut library: StandardLibrary = serde_yaml::from_reader(BufReader::new(
-                    fs::File::open(&floating_file)
-                        .context("Could not open the floating roblox standard library.")?,
-                ))?;
+                let contents = fs::read_to_string(&floating_file)
+                    .context("Could not open the floating roblox standard library.")?;
+
+                let mut library = crate::std_cache::parse_cached(
+                    crate::std_cache::Format::Roblox,
+                    &contents,
+                    |contents| Ok(serde_yaml::from_str(contents)?),
+                )?;
 
                 if (chrono::Local::now()
                     - chrono::Local.timestamp(library.last_updated.unwrap_or(0), 0))
@@ -34,6 +37,13 @@ pub fn collect_roblox_standard_library<V>(
                     < HOUR_CACHE
                     && library.last_selene_version.as_deref() == Some(env!("CARGO_PKG_VERSION"))
                 {
+                    log::debug!(
+                        "using the floating roblox standard library from {}",
+                        floating_file.display()
+                    );
+
+                    library.set_source_name("roblox");
+
                     if let Some(base) = &library.base {
                         let base_library = StandardLibrary::from_name(base)
                             .expect("Roblox standard library had an invalid base");
@@ -59,6 +69,8 @@ pub fn collect_roblox_standard_library<V>(
         ),
     };
 
+    log::info!("generating a new roblox standard library");
+
     let generated_std = RobloxGenerator::generate();
 
     match (generated_std, cached_library) {
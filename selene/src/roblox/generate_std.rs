@@ -121,14 +121,18 @@ impl RobloxGenerator {
                         FunctionBehavior {
                             arguments: parameters
                                 .iter()
-                                .map(|_| Argument {
+                                .map(|parameter| Argument {
+                                    name: Some(parameter.name.clone()),
                                     argument_type: ArgumentType::Any,
                                     required: Required::NotRequired,
                                     observes: Observes::ReadWrite,
+                                    range: None,
+                                    max_length: None,
                                 })
                                 .collect(),
                             method: true,
                             must_use: false,
+                            overloads: Vec::new(),
                         },
                     ))),
                 ),
@@ -218,6 +222,7 @@ impl RobloxGenerator {
                     arguments: vec![],
                     method: true,
                     must_use: true,
+                    overloads: Vec::new(),
                 })),
             );
 
@@ -247,14 +252,18 @@ impl RobloxGenerator {
             "Instance.new".to_owned(),
             Field::from_field_kind(FieldKind::Function(FunctionBehavior {
                 arguments: vec![Argument {
+                    name: Some("className".to_owned()),
                     argument_type: ArgumentType::Constant(instance_names),
                     required: Required::Required(None),
                     observes: Observes::ReadWrite,
+                    range: None,
+                    max_length: None,
                 }],
                 method: false,
 
                 // Only true because we don't allow the second parameter
                 must_use: true,
+                overloads: Vec::new(),
             })),
         );
     }
@@ -277,12 +286,16 @@ impl RobloxGenerator {
         *data_model.get_mut("GetService").unwrap() =
             Field::from_field_kind(FieldKind::Function(FunctionBehavior {
                 arguments: vec![Argument {
+                    name: Some("className".to_owned()),
                     argument_type: ArgumentType::Constant(service_names),
                     required: Required::Required(None),
                     observes: Observes::ReadWrite,
+                    range: None,
+                    max_length: None,
                 }],
                 method: true,
                 must_use: true,
+                overloads: Vec::new(),
             }));
     }
 
@@ -305,6 +318,7 @@ impl RobloxGenerator {
                     superclass: class.superclass.clone(),
                     events,
                     properties,
+                    required_properties: Vec::new(),
                 },
             );
         }
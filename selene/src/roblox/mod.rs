@@ -1,6 +1,7 @@
 mod api;
 mod collect_std;
 mod generate_std;
+pub mod place_file;
 
 pub use collect_std::{collect_roblox_standard_library, update_roblox_std};
 pub use generate_std::RobloxGenerator;
@@ -0,0 +1,342 @@
+// Reads the scripts out of Roblox XML place (.rbxlx) and model (.rbxmx) files, so that they can be
+// checked without extracting them with Rojo first. Only as much XML is understood as Roblox
+// writes, which is elements, attributes, text, CDATA sections, and comments.
+use std::path::Path;
+
+const SCRIPT_CLASSES: &[&str] = &["Script", "LocalScript", "ModuleScript"];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Script {
+    /// Where the script is in the place or model, such as `game.ServerScriptService.Main`.
+    pub instance_path: String,
+    pub source: String,
+}
+
+/// Returns true if the file should be read as a place or model file, rather than as code.
+pub fn is_place_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("rbxlx" | "rbxmx")
+    )
+}
+
+struct Instance {
+    class: String,
+    name: Option<String>,
+    source: Option<String>,
+    parent: Option<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Property {
+    Name,
+    Source,
+}
+
+/// Returns every script in the file, in the order they appear. Instances in a place file start
+/// with `game`, since its top level instances are the services.
+pub fn scripts(path: &Path, xml: &str) -> Result<Vec<Script>, String> {
+    let is_place = path.extension().and_then(|extension| extension.to_str()) == Some("rbxlx");
+
+    let mut instances: Vec<Instance> = Vec::new();
+    let mut instance_stack: Vec<usize> = Vec::new();
+    let mut elements: Vec<&str> = Vec::new();
+    let mut reading: Option<(Property, usize)> = None;
+    let mut text = String::new();
+
+    let mut rest = xml;
+
+    while !rest.is_empty() {
+        let start = match rest.find('<') {
+            Some(start) => start,
+            None => break,
+        };
+
+        if reading.is_some() {
+            text.push_str(&decode_entities(&rest[..start]));
+        }
+
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let (contents, after) = split_at_end(after, "]]>")?;
+            if reading.is_some() {
+                text.push_str(contents);
+            }
+
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = split_at_end(after, "-->")?.1;
+        } else if let Some(after) = rest.strip_prefix("<?") {
+            rest = split_at_end(after, "?>")?.1;
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = split_at_end(after, ">")?.1;
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let (tag, after) = split_at_end(after, ">")?;
+            let tag = tag.trim();
+
+            match elements.pop() {
+                Some(open) if open == tag => {}
+                Some(open) => return Err(format!("expected </{open}>, found </{tag}>")),
+                None => return Err(format!("unexpected </{tag}>")),
+            }
+
+            if let Some((property, depth)) = reading {
+                if depth == elements.len() {
+                    let instance = &mut instances[*instance_stack.last().unwrap()];
+                    let value = Some(std::mem::take(&mut text));
+
+                    match property {
+                        Property::Name => instance.name = value,
+                        Property::Source => instance.source = value,
+                    }
+
+                    reading = None;
+                }
+            }
+
+            if tag == "Item" {
+                instance_stack.pop();
+            }
+
+            rest = after;
+        } else {
+            let (tag, after) = split_at_end(&rest[1..], ">")?;
+            let (tag, self_closing) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+
+            let (element, attributes) = match tag.find(char::is_whitespace) {
+                Some(index) => (&tag[..index], parse_attributes(&tag[index..])?),
+                None => (tag, Vec::new()),
+            };
+
+            let attribute = |name: &str| {
+                attributes
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.as_str())
+            };
+
+            if element == "Item" && !self_closing {
+                instances.push(Instance {
+                    class: attribute("class").unwrap_or_default().to_owned(),
+                    name: None,
+                    source: None,
+                    parent: instance_stack.last().copied(),
+                });
+
+                instance_stack.push(instances.len() - 1);
+            } else if elements.last() == Some(&"Properties") && !instance_stack.is_empty() {
+                let property = match attribute("name") {
+                    Some("Name") => Some(Property::Name),
+                    Some("Source") => Some(Property::Source),
+                    _ => None,
+                };
+
+                if let Some(property) = property {
+                    if self_closing {
+                        let instance = &mut instances[*instance_stack.last().unwrap()];
+                        match property {
+                            Property::Name => instance.name = Some(String::new()),
+                            Property::Source => instance.source = Some(String::new()),
+                        }
+                    } else {
+                        reading = Some((property, elements.len()));
+                        text.clear();
+                    }
+                }
+            }
+
+            if !self_closing {
+                elements.push(element);
+            }
+
+            rest = after;
+        }
+    }
+
+    if let Some(open) = elements.last() {
+        return Err(format!("<{open}> is never closed"));
+    }
+
+    Ok(instances
+        .iter()
+        .filter(|instance| SCRIPT_CLASSES.contains(&instance.class.as_str()))
+        .filter_map(|instance| {
+            let source = instance.source.clone()?;
+
+            let mut names = Vec::new();
+            let mut current = Some(instance);
+            while let Some(instance) = current {
+                names.push(instance.name.as_deref().unwrap_or(&instance.class));
+                current = instance.parent.map(|parent| &instances[parent]);
+            }
+
+            if is_place {
+                names.push("game");
+            }
+
+            names.reverse();
+
+            Some(Script {
+                instance_path: names.join("."),
+                source,
+            })
+        })
+        .collect())
+}
+
+// Splits the text at the first `end`, leaving it out
+fn split_at_end<'a>(text: &'a str, end: &str) -> Result<(&'a str, &'a str), String> {
+    let index = text
+        .find(end)
+        .ok_or_else(|| format!("expected `{end}` before the end of the file"))?;
+
+    Ok((&text[..index], &text[index + end.len()..]))
+}
+
+fn parse_attributes(mut text: &str) -> Result<Vec<(&str, String)>, String> {
+    let mut attributes = Vec::new();
+
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            return Ok(attributes);
+        }
+
+        let (key, after) = text
+            .split_once('=')
+            .ok_or_else(|| format!("expected `=` after the attribute `{}`", text.trim()))?;
+        let after = after.trim_start();
+
+        let quote = match after.chars().next() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => return Err(format!("expected a quoted value for `{}`", key.trim())),
+        };
+
+        let (value, after) = split_at_end(&after[1..], &quote.to_string())?;
+        attributes.push((key.trim(), decode_entities(value)));
+        text = after;
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let character = rest.find(';').and_then(|end| {
+            let character = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => {
+                    let code = match entity.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+
+                    char::from_u32(code)?
+                }
+            };
+
+            Some((character, end))
+        });
+
+        match character {
+            Some((character, end)) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLACE: &str = r#"<roblox xmlns:xmime="http://www.w3.org/2005/05/xmlmime" version="4">
+	<!-- a comment with an <Item> in it -->
+	<Item class="ServerScriptService" referent="RBX1">
+		<Properties>
+			<string name="Name">ServerScriptService</string>
+		</Properties>
+		<Item class="Script" referent="RBX2">
+			<Properties>
+				<bool name="Disabled">false</bool>
+				<string name="Name">Main</string>
+				<ProtectedString name="Source"><![CDATA[print("hello")
+]]></ProtectedString>
+			</Properties>
+			<Item class="ModuleScript" referent="RBX3">
+				<Properties>
+					<string name="Name">Util &amp; Helpers</string>
+					<ProtectedString name="Source">return 1 &lt; 2</ProtectedString>
+				</Properties>
+			</Item>
+		</Item>
+		<Item class="Folder" referent="RBX4">
+			<Properties>
+				<string name="Name">Empty</string>
+				<ProtectedString name="Source" />
+			</Properties>
+		</Item>
+	</Item>
+</roblox>"#;
+
+    #[test]
+    fn test_place_scripts() {
+        assert_eq!(
+            scripts(Path::new("place.rbxlx"), PLACE).unwrap(),
+            vec![
+                Script {
+                    instance_path: "game.ServerScriptService.Main".to_owned(),
+                    source: "print(\"hello\")\n".to_owned(),
+                },
+                Script {
+                    instance_path: "game.ServerScriptService.Main.Util & Helpers".to_owned(),
+                    source: "return 1 < 2".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_model_scripts() {
+        let scripts = scripts(Path::new("model.rbxmx"), PLACE).unwrap();
+        assert_eq!(scripts[0].instance_path, "ServerScriptService.Main");
+    }
+
+    #[test]
+    fn test_malformed() {
+        let path = Path::new("place.rbxlx");
+
+        assert!(scripts(path, "<roblox><Item class=\"Script\"></roblox>").is_err());
+        assert!(scripts(path, "<roblox><Item class=\"Script\">").is_err());
+        assert!(scripts(path, "<roblox><![CDATA[").is_err());
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &lt;&#65;&#x42;&gt; &unknown; &"),
+            "a <AB> &unknown; &"
+        );
+    }
+}
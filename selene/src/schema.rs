@@ -0,0 +1,354 @@
+// Used by the schema command, which gives editors a JSON Schema to validate and autocomplete
+// selene.toml and standard library files with, such as through taplo or yaml-language-server.
+// These follow the serde structs in selene-lib, and have to be updated along with them.
+use selene_lib::renamed_lints::RENAMED_LINTS;
+use serde_json::json;
+
+use crate::dump_lints;
+
+const DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// The schema for selene.toml.
+pub fn config_schema() -> serde_json::Value {
+    let severity = json!({
+        "type": "string",
+        "enum": ["allow", "warn", "deny"],
+    });
+
+    let lints = selene_lib::lint_metadata();
+
+    let mut rules = lints
+        .iter()
+        .map(|lint| (lint.name.to_owned(), severity.clone()))
+        .collect::<serde_json::Map<_, _>>();
+
+    let mut lint_configs = lints
+        .iter()
+        .filter_map(|lint| {
+            let default_config = serde_json::to_value(lint.default_config.as_ref()?).ok()?;
+            Some((lint.name.to_owned(), dump_lints::schema(&default_config)))
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    // Old names still work, but editors can point out that they should be changed
+    for renamed in RENAMED_LINTS {
+        let deprecate = |schema: &serde_json::Value| {
+            let mut schema = schema.clone();
+            schema["deprecated"] = json!(true);
+            schema["description"] = json!(format!(
+                "Renamed to `{}`. The old name will stop working in selene {}.",
+                renamed.new_name, renamed.removed_in
+            ));
+            schema
+        };
+
+        rules.insert(renamed.old_name.to_owned(), deprecate(&severity));
+
+        if let Some(config) = lint_configs.get(renamed.new_name).map(deprecate) {
+            lint_configs.insert(renamed.old_name.to_owned(), config);
+        }
+    }
+
+    // Everything but `profile` can also go in a profile
+    let mut properties = json!({
+        "std": {
+            "type": "string",
+            "description": "The standard library to use, such as `lua51` or `roblox`. \
+                Multiple libraries can be combined with `+`, such as `lua52+love`.",
+        },
+        "selene-version": {
+            "type": "string",
+            "description": "A semver requirement that the version of selene has to meet, \
+                such as \">=0.20, <0.30\".",
+        },
+        "extensions": {
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "The file extensions to check in directories.",
+        },
+        "source-map": {
+            "type": "string",
+            "description": "Where to find the source map of a file, \
+                where `{file}` is replaced with the path of the file.",
+        },
+        "time-budget": {
+            "type": "number",
+            "exclusiveMinimum": 0,
+            "description": "How many seconds selene can spend checking one file before skipping it.",
+        },
+        "quality-gate": {
+            "type": "object",
+            "description": "How many problems a run can find and still pass.",
+            "properties": {
+                "max-errors": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Errors from lints without their own budget. Defaults to 0.",
+                },
+                "max-warnings": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Warnings from lints without their own budget. Defaults to no limit.",
+                },
+                "max-per-lint": {
+                    "type": "object",
+                    "description": "Errors and warnings from each lint, which don't count towards the others.",
+                    "additionalProperties": { "type": "integer", "minimum": 0 },
+                },
+            },
+        },
+        "transcode-utf16": {
+            "type": "boolean",
+            "description": "Whether files saved as UTF-16 are converted to UTF-8 to be checked, \
+                instead of being reported.",
+        },
+        "test-files": {
+            "type": "array",
+            "items": { "type": "string" },
+            "description": "Globs for the test files in the project, which are checked with the globals \
+                of test frameworks and `[profile.test]`.",
+        },
+        "roblox-std-source": {
+            "type": "string",
+            "enum": ["floating", "pinned"],
+        },
+        "unknown-lints": {
+            "type": "string",
+            "enum": ["allow", "deny"],
+            "description": "Whether lints that selene doesn't know about are allowed in `rules` and `config`.",
+        },
+        "rules": {
+            "type": "object",
+            "description": "The severity of each lint.",
+            "properties": rules.clone(),
+            "additionalProperties": severity.clone(),
+        },
+        "config": {
+            "type": "object",
+            "description": "The configuration of each lint.",
+            "properties": lint_configs,
+        },
+        "severity": {
+            "type": "object",
+            "description": "Severities that replace the ones in `rules` in some environments.",
+            "properties": {
+                "ci": {
+                    "type": "object",
+                    "description": "Used with `--ci`, or when the CI environment variable is set.",
+                    "properties": rules,
+                    "additionalProperties": severity,
+                },
+            },
+        },
+    });
+
+    let profile = json!({
+        "type": "object",
+        "properties": properties.clone(),
+    });
+
+    properties["profile"] = json!({
+        "type": "object",
+        "description": "Named sets of overrides, selected with `--profile`.",
+        "additionalProperties": profile,
+    });
+
+    // A profile can't extend anything, since it's already applied on top of the config
+    properties["extends"] = json!({
+        "type": "string",
+        "description": "A path or URL of another selene.toml to use the settings of, \
+            which this one overrides.",
+    });
+
+    properties["extends-sha256"] = json!({
+        "type": "string",
+        "pattern": "^[0-9a-fA-F]{64}$",
+        "description": "The SHA-256 that what `extends` points to has to have.",
+    });
+
+    json!({
+        "$schema": DRAFT,
+        "title": "selene.toml",
+        "type": "object",
+        "properties": properties,
+    })
+}
+
+/// The schema for standard libraries written in YAML.
+pub fn std_schema() -> serde_json::Value {
+    json!({
+        "$schema": DRAFT,
+        "title": "selene standard library",
+        "type": "object",
+        "properties": {
+            "base": {
+                "type": "string",
+                "description": "The standard library this one builds on, such as `lua51`.",
+            },
+            "name": { "type": "string" },
+            "globals": {
+                "type": "object",
+                "additionalProperties": { "$ref": "#/definitions/field" },
+            },
+            "structs": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/field" },
+                },
+            },
+            "last_updated": { "type": "integer" },
+            "last_selene_version": { "type": "string" },
+            "roblox_classes": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": {
+                        "superclass": { "type": "string" },
+                        "events": { "type": "array", "items": { "type": "string" } },
+                        "properties": { "type": "array", "items": { "type": "string" } },
+                        "required_properties": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Properties that have to be given when creating an element with Roact.",
+                        },
+                    },
+                    "required": ["superclass", "events", "properties"],
+                },
+            },
+        },
+        "definitions": {
+            "field": {
+                "type": "object",
+                "properties": {
+                    "deprecated": {
+                        "type": "object",
+                        "properties": {
+                            "message": { "type": "string" },
+                            "replace": { "type": "array", "items": { "type": "string" } },
+                        },
+                        "required": ["message"],
+                    },
+                },
+                "oneOf": [
+                    {
+                        "properties": { "any": { "const": true } },
+                        "required": ["any"],
+                    },
+                    {
+                        "properties": {
+                            "args": { "type": "array", "items": { "$ref": "#/definitions/argument" } },
+                            "method": { "type": "boolean" },
+                            "must_use": { "type": "boolean" },
+                            "overloads": {
+                                "type": "array",
+                                "items": {
+                                    "type": "object",
+                                    "properties": {
+                                        "args": { "type": "array", "items": { "$ref": "#/definitions/argument" } },
+                                    },
+                                    "required": ["args"],
+                                },
+                            },
+                        },
+                        "required": ["args"],
+                    },
+                    {
+                        "properties": { "removed": { "const": true } },
+                        "required": ["removed"],
+                    },
+                    {
+                        "properties": {
+                            "property": {
+                                "type": "string",
+                                "enum": ["read-only", "new-fields", "override-fields", "full-write"],
+                            },
+                        },
+                        "required": ["property"],
+                    },
+                    {
+                        "properties": { "struct": { "type": "string" } },
+                        "required": ["struct"],
+                    },
+                ],
+            },
+            "argument": {
+                "type": "object",
+                "properties": {
+                    "type": {
+                        "oneOf": [
+                            {
+                                "type": "string",
+                                "enum": ["any", "bool", "function", "nil", "number", "string", "table", "..."],
+                            },
+                            {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Constant strings that are the only values allowed.",
+                            },
+                            {
+                                "type": "object",
+                                "properties": { "display": { "type": "string" } },
+                                "required": ["display"],
+                                "description": "A type that selene doesn't know, displayed with this name.",
+                            },
+                        ],
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "The name of the parameter, shown in signatures.",
+                    },
+                    "required": {
+                        "type": ["boolean", "string"],
+                        "description": "Whether the argument is required, \
+                            or the message to show when it is missing.",
+                    },
+                    "observes": {
+                        "type": "string",
+                        "enum": ["read-write", "read", "write"],
+                    },
+                    "range": {
+                        "type": "object",
+                        "properties": {
+                            "min": { "type": "number" },
+                            "max": { "type": "number" },
+                            "help": { "type": "string" },
+                        },
+                        "description": "The numbers a constant argument is allowed to be.",
+                    },
+                },
+                "required": ["type"],
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_schema() {
+        let schema = config_schema();
+
+        assert_eq!(
+            schema["properties"]["rules"]["properties"]["empty_if"]["enum"],
+            json!(["allow", "warn", "deny"])
+        );
+
+        assert_eq!(
+            schema["properties"]["config"]["properties"]["empty_if"]["properties"]
+                ["comments_count"]["type"],
+            "boolean"
+        );
+
+        let profile = &schema["properties"]["profile"]["additionalProperties"];
+        assert_eq!(profile["properties"]["std"]["type"], "string");
+        assert!(profile["properties"].get("profile").is_none());
+        assert!(profile["properties"].get("extends").is_none());
+        assert_eq!(
+            profile["properties"]["severity"]["properties"]["ci"]["properties"]["empty_if"]["enum"],
+            json!(["allow", "warn", "deny"])
+        );
+    }
+}
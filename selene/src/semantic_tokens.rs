@@ -0,0 +1,41 @@
+// Used by editors to highlight names by what they refer to, so that a global used by accident,
+// such as from a typo or a missing `local`, stands out while typing.
+use std::path::Path;
+
+use selene_lib::{
+    symbols::{self, SymbolKind},
+    CheckerConfig,
+};
+use serde_json::json;
+
+use crate::{references::read_source, standard_library::collect_standard_library_argument};
+
+pub fn semantic_tokens<V>(
+    config: &CheckerConfig<V>,
+    file: &Path,
+    library: Option<&str>,
+) -> color_eyre::Result<()> {
+    let standard_library =
+        collect_standard_library_argument(config, library.unwrap_or_else(|| config.std()))?;
+
+    let ast = full_moon::parse(&read_source(file)?)?;
+
+    let tokens = symbols::semantic_tokens(&ast, &standard_library)
+        .into_iter()
+        .map(|token| {
+            json!({
+                "range": [token.range.0, token.range.1],
+                "kind": match token.kind {
+                    SymbolKind::Local => "local",
+                    SymbolKind::Parameter => "parameter",
+                    SymbolKind::Global => "global",
+                    SymbolKind::StandardLibrary => "standard_library",
+                },
+                "deprecated": token.deprecated,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    println!("{}", serde_json::Value::Array(tokens));
+    Ok(())
+}
@@ -0,0 +1,166 @@
+// Files are checked in parallel, so diagnostics are held until every file is done and then
+// written in an order that doesn't depend on which thread finished first.
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use termcolor::{Buffer, BufferWriter};
+
+use crate::{get_color, opts::SortBy};
+
+lazy_static::lazy_static! {
+    static ref ENTRIES: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Key {
+    pub filename: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub code: String,
+    pub is_error: bool,
+    pub message: String,
+}
+
+impl Key {
+    pub fn new(
+        filename: &Path,
+        line: usize,
+        column: usize,
+        code: &str,
+        is_error: bool,
+        message: &str,
+    ) -> Self {
+        Self {
+            filename: filename.to_path_buf(),
+            line,
+            column,
+            code: code.to_owned(),
+            is_error,
+            message: message.to_owned(),
+        }
+    }
+
+    // The message is last only so that two diagnostics of the same lint in the same place
+    // still come out in the same order every time
+    fn location_order(&self, other: &Self) -> Ordering {
+        self.filename
+            .cmp(&other.filename)
+            .then_with(|| self.line.cmp(&other.line))
+            .then_with(|| self.column.cmp(&other.column))
+            .then_with(|| self.code.cmp(&other.code))
+            .then_with(|| self.message.cmp(&other.message))
+    }
+
+    pub fn order(&self, other: &Self, sort: SortBy) -> Ordering {
+        match sort {
+            SortBy::File => Ordering::Equal,
+            SortBy::Lint => self.code.cmp(&other.code),
+            SortBy::Severity => other.is_error.cmp(&self.is_error),
+        }
+        .then_with(|| self.location_order(other))
+    }
+}
+
+struct Entry {
+    key: Key,
+    output: Buffer,
+}
+
+/// A buffer to write a diagnostic into before passing it to `record`.
+pub fn buffer() -> Buffer {
+    BufferWriter::stdout(get_color()).buffer()
+}
+
+pub fn record(key: Key, output: Buffer) {
+    ENTRIES.lock().unwrap().push(Entry { key, output });
+}
+
+/// Writes every diagnostic recorded, or only the first `max_problems` of them.
+pub fn emit(sort: SortBy, max_problems: Option<usize>) {
+    let mut entries = std::mem::take(&mut *ENTRIES.lock().unwrap());
+    entries.sort_by(|a, b| a.key.order(&b.key, sort));
+
+    let hidden = match max_problems {
+        Some(max_problems) if entries.len() > max_problems => {
+            let hidden = entries.len() - max_problems;
+            entries.truncate(max_problems);
+            hidden
+        }
+        _ => 0,
+    };
+
+    let writer = BufferWriter::stdout(get_color());
+
+    for entry in entries {
+        if let Err(error) = writer.print(&entry.output) {
+            crate::error(&format!("couldn't write diagnostics: {error}"));
+            return;
+        }
+    }
+
+    if hidden > 0 {
+        println!("{}", hidden_message(hidden));
+    }
+}
+
+pub fn hidden_message(hidden: usize) -> String {
+    format!(
+        "{hidden} more {} not shown because of --max-problems",
+        if hidden == 1 { "problem" } else { "problems" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order() {
+        let mut keys = vec![
+            Key::new(Path::new("b.lua"), 0, 0, "unused_variable", false, "x"),
+            Key::new(Path::new("a.lua"), 3, 0, "shadowing", false, "y"),
+            Key::new(Path::new("a.lua"), 1, 4, "undefined_variable", true, "z"),
+            Key::new(Path::new("a.lua"), 1, 4, "empty_if", false, "if"),
+        ];
+
+        fn order(keys: &mut [Key], sort: SortBy) -> Vec<(&str, &str)> {
+            keys.sort_by(|a, b| a.order(b, sort));
+            keys.iter()
+                .map(|key| (key.filename.to_str().unwrap(), key.code.as_str()))
+                .collect()
+        }
+
+        assert_eq!(
+            order(&mut keys, SortBy::File),
+            vec![
+                ("a.lua", "empty_if"),
+                ("a.lua", "undefined_variable"),
+                ("a.lua", "shadowing"),
+                ("b.lua", "unused_variable"),
+            ]
+        );
+
+        assert_eq!(
+            order(&mut keys, SortBy::Severity),
+            vec![
+                ("a.lua", "undefined_variable"),
+                ("a.lua", "empty_if"),
+                ("a.lua", "shadowing"),
+                ("b.lua", "unused_variable"),
+            ]
+        );
+
+        assert_eq!(
+            order(&mut keys, SortBy::Lint),
+            vec![
+                ("a.lua", "empty_if"),
+                ("a.lua", "shadowing"),
+                ("a.lua", "undefined_variable"),
+                ("b.lua", "unused_variable"),
+            ]
+        );
+    }
+}
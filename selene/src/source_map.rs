@@ -0,0 +1,337 @@
+// Translates diagnostics in generated Lua, such as the output of TypeScriptToLua, back to the
+// original source using the standard (version 3) source map format.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use selene_lib::rules::Diagnostic;
+use serde::Deserialize;
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSourceMap {
+    #[serde(default)]
+    source_root: Option<String>,
+    sources: Vec<String>,
+    #[serde(default)]
+    sources_content: Vec<Option<String>>,
+    mappings: String,
+}
+
+pub struct Source {
+    pub path: PathBuf,
+    pub contents: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Mapping {
+    generated_column: usize,
+    source: usize,
+    line: usize,
+    column: usize,
+}
+
+pub struct SourceMap {
+    pub sources: Vec<Source>,
+    // Indexed by the line in the generated file, sorted by column
+    lines: Vec<Vec<Mapping>>,
+}
+
+/// Loads the source map for the file, if it has one.
+/// `pattern` is `source-map` from selene.toml.
+pub fn load(filename: &Path, pattern: Option<&str>) -> Option<SourceMap> {
+    let map_path = match pattern {
+        Some(pattern) => PathBuf::from(pattern.replace("{file}", &filename.to_string_lossy())),
+        None => {
+            let mut map_path = filename.as_os_str().to_owned();
+            map_path.push(".map");
+            PathBuf::from(map_path)
+        }
+    };
+
+    let contents = fs::read_to_string(&map_path).ok()?;
+    let directory = map_path.parent().unwrap_or_else(|| Path::new(""));
+
+    match SourceMap::parse(&contents, directory) {
+        Ok(source_map) => {
+            log::debug!("using source map {}", map_path.display());
+            Some(source_map)
+        }
+
+        Err(error) => {
+            crate::warn(&format!(
+                "couldn't read source map {}: {error}",
+                map_path.display()
+            ));
+            None
+        }
+    }
+}
+
+impl SourceMap {
+    pub fn parse(contents: &str, directory: &Path) -> Result<Self, String> {
+        let raw: RawSourceMap =
+            serde_json::from_str(contents).map_err(|error| error.to_string())?;
+
+        let root = directory.join(raw.source_root.unwrap_or_default());
+
+        let sources = raw
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(index, source)| {
+                let path = root.join(source);
+
+                Source {
+                    contents: raw
+                        .sources_content
+                        .get(index)
+                        .cloned()
+                        .flatten()
+                        .or_else(|| fs::read_to_string(&path).ok()),
+                    path,
+                }
+            })
+            .collect();
+
+        Ok(SourceMap {
+            sources,
+            lines: decode_mappings(&raw.mappings)?,
+        })
+    }
+
+    // Finds the closest mapping at or before the position, and assumes the code after it is the
+    // same in both, which holds for names and most other tokens.
+    // If there isn't one, the first mapping on the line is used instead.
+    fn original_position(&self, line: usize, column: usize) -> Option<Mapping> {
+        let mappings = self.lines.get(line)?;
+
+        match mappings
+            .iter()
+            .rev()
+            .find(|mapping| mapping.generated_column <= column)
+        {
+            Some(mapping) => Some(Mapping {
+                column: mapping.column + (column - mapping.generated_column),
+                ..*mapping
+            }),
+
+            None => mappings.first().copied(),
+        }
+    }
+
+    /// Moves the labels of the diagnostic in the generated code to the original source,
+    /// returning the index of that source.
+    /// Nothing is changed if the primary label can't be translated.
+    pub fn translate(&self, generated: &str, diagnostic: &mut Diagnostic) -> Option<usize> {
+        let (source, primary_range) =
+            self.translate_range(generated, diagnostic.primary_label.range)?;
+
+        diagnostic.primary_label.range = primary_range;
+
+        // Labels can only be shown in one file
+        diagnostic.secondary_labels.retain_mut(|label| {
+            match self.translate_range(generated, label.range) {
+                Some((label_source, range)) if label_source == source => {
+                    label.range = range;
+                    true
+                }
+
+                _ => false,
+            }
+        });
+
+        Some(source)
+    }
+
+    fn translate_range(&self, generated: &str, range: (u32, u32)) -> Option<(usize, (u32, u32))> {
+        let (start_line, start_column) = line_column(generated, range.0 as usize);
+        let start = self.original_position(start_line, start_column)?;
+        let original = self.sources.get(start.source)?.contents.as_deref()?;
+
+        let start_offset = byte_offset(original, start.line, start.column);
+
+        let (end_line, end_column) = line_column(generated, range.1 as usize);
+        let end_offset = match self.original_position(end_line, end_column) {
+            Some(end) if end.source == start.source => byte_offset(original, end.line, end.column),
+            _ => start_offset,
+        };
+
+        Some((
+            start.source,
+            (start_offset as u32, end_offset.max(start_offset) as u32),
+        ))
+    }
+}
+
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+
+    match before.rfind('\n') {
+        Some(line_start) => (
+            before.matches('\n').count(),
+            before[line_start + 1..].chars().count(),
+        ),
+        None => (0, before.chars().count()),
+    }
+}
+
+fn byte_offset(text: &str, line: usize, column: usize) -> usize {
+    let line_start = match line {
+        0 => 0,
+        line => match text.match_indices('\n').nth(line - 1) {
+            Some((index, _)) => index + 1,
+            None => return text.len(),
+        },
+    };
+
+    let line_text = text[line_start..].split('\n').next().unwrap();
+
+    line_start
+        + line_text
+            .char_indices()
+            .nth(column)
+            .map_or(line_text.len(), |(index, _)| index)
+}
+
+fn decode_mappings(mappings: &str) -> Result<Vec<Vec<Mapping>>, String> {
+    let mut lines = Vec::new();
+
+    // Everything but the generated column is relative to the previous segment, even across lines
+    let (mut source, mut line, mut column) = (0i64, 0i64, 0i64);
+
+    for line_mappings in mappings.split(';') {
+        let mut generated_column = 0i64;
+        let mut segments = Vec::new();
+
+        for segment in line_mappings
+            .split(',')
+            .filter(|segment| !segment.is_empty())
+        {
+            let fields = decode_vlq(segment)?;
+
+            generated_column += fields[0];
+
+            // Segments with only a column don't map to anything in the original source
+            if fields.len() < 4 {
+                continue;
+            }
+
+            source += fields[1];
+            line += fields[2];
+            column += fields[3];
+
+            if generated_column < 0 || source < 0 || line < 0 || column < 0 {
+                return Err(format!("mapping `{segment}` is out of bounds"));
+            }
+
+            segments.push(Mapping {
+                generated_column: generated_column as usize,
+                source: source as usize,
+                line: line as usize,
+                column: column as usize,
+            });
+        }
+
+        segments.sort_by_key(|mapping| mapping.generated_column);
+        lines.push(segments);
+    }
+
+    Ok(lines)
+}
+
+fn decode_vlq(segment: &str) -> Result<Vec<i64>, String> {
+    let mut values = Vec::new();
+    let mut value = 0i64;
+    let mut shift = 0;
+
+    for byte in segment.bytes() {
+        let digit = BASE64
+            .iter()
+            .position(|&character| character == byte)
+            .ok_or_else(|| format!("invalid character `{}` in mappings", byte as char))?
+            as i64;
+
+        value += (digit & 0b11111) << shift;
+
+        if digit & 0b100000 == 0 {
+            let magnitude = value >> 1;
+            values.push(if value & 1 == 1 {
+                -magnitude
+            } else {
+                magnitude
+            });
+
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 5;
+
+            if shift > 60 {
+                return Err("mapping is too large".to_owned());
+            }
+        }
+    }
+
+    if shift != 0 || values.is_empty() {
+        return Err(format!("mapping `{segment}` is incomplete"));
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use selene_lib::rules::Label;
+
+    #[test]
+    fn test_decode_vlq() {
+        assert_eq!(decode_vlq("AAAA"), Ok(vec![0, 0, 0, 0]));
+        assert_eq!(decode_vlq("SAAQ"), Ok(vec![9, 0, 0, 8]));
+        assert_eq!(decode_vlq("D"), Ok(vec![-1]));
+        assert_eq!(decode_vlq("gB"), Ok(vec![16]));
+        assert!(decode_vlq("g").is_err());
+    }
+
+    #[test]
+    fn test_translate() {
+        // `local x = 1` on line 2 of the Lua comes from `let x = 1;` on line 1 of the TypeScript,
+        // and `print(x)` on line 3 comes from `print(x);` on line 3.
+        let source_map = SourceMap::parse(
+            r#"{
+                "version": 3,
+                "sources": ["main.ts"],
+                "sourcesContent": ["let x = 1;\n\n  print(x);\n"],
+                "mappings": ";AAAA,MAAI;AAEF"
+            }"#,
+            Path::new("out"),
+        )
+        .unwrap();
+
+        assert_eq!(source_map.sources[0].path, Path::new("out").join("main.ts"));
+
+        let generated = "-- generated\nlocal x = 1\nprint(x)\n";
+
+        let mut diagnostic = Diagnostic::new_complete(
+            "unused_variable",
+            "x is unused".to_owned(),
+            Label::new((19, 20)),
+            Vec::new(),
+            vec![Label::new((25, 33))],
+        );
+
+        assert_eq!(source_map.translate(generated, &mut diagnostic), Some(0));
+        assert_eq!(diagnostic.primary_label.range, (4, 5));
+        assert_eq!(diagnostic.secondary_labels[0].range, (14, 22));
+
+        // The first line of the generated code has no mappings
+        let mut unmapped = Diagnostic::new("unused_variable", "".to_owned(), Label::new((0, 2)));
+        assert_eq!(source_map.translate(generated, &mut unmapped), None);
+        assert_eq!(unmapped.primary_label.range, (0, 2));
+    }
+}
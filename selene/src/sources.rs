@@ -0,0 +1,43 @@
+// Where the CLI gets the code it checks from, both when checking files and for diagnostics that
+// need to read them again after. With --staged, this is the git index rather than the working tree.
+use std::{fs, io, path::Path};
+
+use selene_lib::sources::SourceProvider;
+
+use crate::{encoding, git, opts::Options};
+
+/// Reads files from disk, converting them from UTF-16 the same way as when they were checked.
+pub struct FileSystem;
+
+impl SourceProvider for FileSystem {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let bytes = self.read_bytes(path)?;
+        Ok(encoding::decode(&bytes, encoding::detect(&bytes)).into_owned())
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+}
+
+/// Reads the staged versions of files.
+pub struct Staged;
+
+impl SourceProvider for Staged {
+    fn read(&self, path: &Path) -> io::Result<String> {
+        let bytes = self.read_bytes(path)?;
+        Ok(encoding::decode(&bytes, encoding::detect(&bytes)).into_owned())
+    }
+
+    fn read_bytes(&self, path: &Path) -> io::Result<Vec<u8>> {
+        git::staged_contents(path).map_err(io::Error::other)
+    }
+}
+
+pub fn for_options(opts: &Options) -> &'static dyn SourceProvider {
+    if opts.staged {
+        &Staged
+    } else {
+        &FileSystem
+    }
+}
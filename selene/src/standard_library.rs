@@ -1,11 +1,16 @@
 use std::{fs, path::Path};
 
+use crate::{interpolate, std_cache};
 use color_eyre::eyre::Context;
 use selene_lib::{
     standard_library::{v1, StandardLibrary},
     CheckerConfig,
 };
 
+/// The extensions of the files a standard library can be written in, such as `std = "mylib"`
+/// being read from mylib.toml or mylib.yml.
+pub const FILE_EXTENSIONS: &[&str] = &["toml", "yml"];
+
 pub fn collect_standard_library<V>(
     config: &CheckerConfig<V>,
     standard_library_name: &str,
@@ -19,6 +24,12 @@ pub fn collect_standard_library<V>(
             None => {
                 if cfg!(feature = "roblox") && segment == "roblox" {
                     collect_roblox_standard_library(config, directory)?
+                } else if segment == "roblox" {
+                    color_eyre::eyre::bail!(
+                        "The `roblox` standard library needs a build of selene with the `roblox` feature, \
+                        which this one doesn't have. Install one using `cargo install selene`, \
+                        which has it by default"
+                    )
                 } else {
                     color_eyre::eyre::bail!("Could not find the standard library `{segment}`")
                 }
@@ -39,6 +50,38 @@ pub fn collect_standard_library<V>(
     Ok(standard_library)
 }
 
+/// Collects a standard library given on the command line, which is either a name resolved
+/// the same way as `std` in selene.toml, or a path to a library file.
+pub fn collect_standard_library_argument<V>(
+    config: &CheckerConfig<V>,
+    library: &str,
+) -> color_eyre::Result<StandardLibrary> {
+    let path = Path::new(library);
+
+    let (name, directory) = match (path.extension(), path.file_stem()) {
+        (Some(extension), Some(stem))
+            if FILE_EXTENSIONS
+                .iter()
+                .any(|file_extension| extension == *file_extension) =>
+        {
+            (
+                stem.to_string_lossy().into_owned(),
+                match path.parent() {
+                    Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+                    _ => std::env::current_dir()?,
+                },
+            )
+        }
+
+        _ => (library.to_owned(), std::env::current_dir()?),
+    };
+
+    match collect_standard_library(config, &name, &directory)? {
+        Some(library) => Ok(library),
+        None => color_eyre::eyre::bail!("the standard library `{library}` was empty"),
+    }
+}
+
 #[cfg(feature = "roblox")]
 fn collect_roblox_standard_library<V>(
     config: &CheckerConfig<V>,
@@ -64,25 +107,42 @@ fn from_name<V>(
 
     let toml_file = directory.join(format!("{standard_library_name}.toml"));
     if toml_file.exists() {
+        log::debug!("loading standard library from {}", toml_file.display());
+
         let content = fs::read_to_string(&toml_file)?;
 
-        let v1_library: v1::StandardLibrary = toml::from_str(&content)
-            .with_context(|| format!("failed to read {}", toml_file.display()))?;
+        library = std_cache::parse_cached(std_cache::Format::Toml, &content, |content| {
+            let v1_library: v1::StandardLibrary = toml::from_str(content)
+                .with_context(|| format!("failed to read {}", toml_file.display()))?;
 
-        library = v1_library.into();
+            Ok(v1_library.into())
+        })?;
     } else {
         let yaml_file = directory.join(format!("{standard_library_name}.yml"));
         if yaml_file.exists() {
+            log::debug!("loading standard library from {}", yaml_file.display());
+
             let content = fs::read_to_string(&yaml_file)
                 .with_context(|| format!("failed to read {}", yaml_file.display()))?;
-            library = serde_yaml::from_str(&content)?;
+            library = std_cache::parse_cached(std_cache::Format::Yaml, &content, |content| {
+                Ok(serde_yaml::from_str(content)?)
+            })?;
         } else {
+            log::debug!(
+                "no file for standard library `{standard_library_name}`, looking for a built-in one"
+            );
+
             return Ok(StandardLibrary::from_name(standard_library_name));
         }
     }
 
+    library.set_source_name(standard_library_name);
+
     if let Some(base_name) = &library.base {
-        if let Some(base) = collect_standard_library(config, base_name, directory)
+        let base_name = interpolate::interpolate_env_vars(base_name)
+            .map_err(|error| color_eyre::eyre::eyre!("couldn't read `base`: {error}"))?;
+
+        if let Some(base) = collect_standard_library(config, &base_name, directory)
             .with_context(|| format!("failed to collect base standard library `{base_name}`"))?
         {
             library.extend(base);
@@ -0,0 +1,209 @@
+// Parsing large standard libraries (such as the Roblox one) on every run is slow,
+// so the parsed result is cached in a binary format, keyed by the source it came from.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use selene_lib::standard_library::StandardLibrary;
+use sha2::{Digest, Sha256};
+
+// Caches that haven't been used for this long are deleted the next time a cache is written
+const UNUSED_FOR: Duration = Duration::from_secs(60 * 60 * 24 * 30);
+
+fn cache_directory() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("selene").join("std-cache"))
+}
+
+/// How the standard library is parsed. The same contents parse differently as TOML and YAML, so
+/// each has caches of its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// The v1 TOML format
+    Toml,
+    Yaml,
+    /// The generated Roblox standard library, which is YAML
+    #[cfg(feature = "roblox")]
+    Roblox,
+}
+
+impl Format {
+    fn name(self) -> &'static str {
+        match self {
+            Format::Toml => "toml",
+            Format::Yaml => "yaml",
+            #[cfg(feature = "roblox")]
+            Format::Roblox => "roblox",
+        }
+    }
+}
+
+// The key has to be the same between runs and builds, which std's hashers don't promise.
+// The version is part of the name, since another version of selene might parse differently.
+fn cache_file_name(format: Format, contents: &str) -> String {
+    format!(
+        "{}-{}-{:x}.msgpack",
+        env!("CARGO_PKG_VERSION"),
+        format.name(),
+        Sha256::digest(contents.as_bytes())
+    )
+}
+
+// bincode would be faster, but it can't represent the untagged and skipped fields StandardLibrary uses
+fn cache_path(format: Format, contents: &str) -> Option<PathBuf> {
+    Some(cache_directory()?.join(cache_file_name(format, contents)))
+}
+
+/// Deletes the caches written by other versions of selene, and ones that haven't been used in a
+/// while, such as for a standard library that has since been changed.
+fn prune(directory: &Path, now: SystemTime) {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    let current_version = format!("{}-", env!("CARGO_PKG_VERSION"));
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        let unused = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| {
+                now.duration_since(modified)
+                    .is_ok_and(|unused_for| unused_for > UNUSED_FOR)
+            });
+
+        let other_version = !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(&current_version);
+
+        if other_version || unused {
+            log::debug!("removing stale standard library cache {}", path.display());
+            fs::remove_file(&path).ok();
+        }
+    }
+}
+
+/// Parses the standard library with `parse`, unless it was already parsed from the same contents
+/// in the same format before.
+pub fn parse_cached<F>(
+    format: Format,
+    contents: &str,
+    parse: F,
+) -> color_eyre::Result<StandardLibrary>
+where
+    F: FnOnce(&str) -> color_eyre::Result<StandardLibrary>,
+{
+    profiling::scope!("std_cache::parse_cached");
+
+    let cache_path = match cache_path(format, contents) {
+        Some(cache_path) => cache_path,
+        None => return parse(contents),
+    };
+
+    if let Ok(cached) = fs::read(&cache_path) {
+        // A cache that can't be read is treated as missing, and will be overwritten
+        if let Ok(library) = rmp_serde::from_slice(&cached) {
+            log::debug!(
+                "using cached standard library from {}",
+                cache_path.display()
+            );
+
+            // Marks the cache as used, so it isn't pruned
+            fs::File::options()
+                .append(true)
+                .open(&cache_path)
+                .and_then(|file| file.set_modified(SystemTime::now()))
+                .ok();
+
+            return Ok(library);
+        }
+    }
+
+    log::debug!(
+        "standard library is not cached, it will be cached at {}",
+        cache_path.display()
+    );
+
+    let library = parse(contents)?;
+
+    // Failing to write the cache only makes the next run slower, so errors are ignored
+    if let Ok(encoded) = rmp_serde::to_vec_named(&library) {
+        if let Some(directory) = cache_path.parent() {
+            prune(directory, SystemTime::now());
+            fs::create_dir_all(directory).ok();
+        }
+
+        fs::write(&cache_path, encoded).ok();
+    }
+
+    Ok(library)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(library: &StandardLibrary) -> StandardLibrary {
+        rmp_serde::from_slice(&rmp_serde::to_vec_named(library).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for library in StandardLibrary::all_default_standard_libraries().values() {
+            assert_eq!(&round_trip(library), library);
+        }
+
+        #[cfg(feature = "roblox")]
+        {
+            let library = StandardLibrary::roblox_base();
+            assert_eq!(round_trip(&library), library);
+        }
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(cache_path(Format::Yaml, "a"), cache_path(Format::Yaml, "a"));
+        assert_ne!(cache_path(Format::Yaml, "a"), cache_path(Format::Yaml, "b"));
+        assert_ne!(cache_path(Format::Yaml, "a"), cache_path(Format::Toml, "a"));
+        #[cfg(feature = "roblox")]
+        assert_ne!(
+            cache_path(Format::Yaml, "a"),
+            cache_path(Format::Roblox, "a")
+        );
+
+        // The same contents have to map to the same file in every build
+        assert_eq!(
+            cache_file_name(Format::Toml, "a"),
+            format!(
+                "{}-toml-ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb.msgpack",
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn test_prune() {
+        let directory = std::env::temp_dir().join("selene-test-std-cache-prune");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(&directory).unwrap();
+
+        let current = directory.join(cache_file_name(Format::Yaml, "current"));
+        let old_version = directory.join("0.1.0-yaml-0123.msgpack");
+        fs::write(&current, "").unwrap();
+        fs::write(&old_version, "").unwrap();
+
+        prune(&directory, SystemTime::now());
+        assert!(current.exists());
+        assert!(!old_version.exists());
+
+        prune(&directory, SystemTime::now() + UNUSED_FOR * 2);
+        assert!(!current.exists());
+
+        fs::remove_dir_all(&directory).ok();
+    }
+}
@@ -0,0 +1,367 @@
+// Finds the files selene should check inside of a directory, yielding them as they are found
+// rather than collecting the whole tree up front.
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+pub struct FileMatcher {
+    extensions: Vec<String>,
+}
+
+impl FileMatcher {
+    pub fn new(extensions: Vec<String>) -> Self {
+        Self { extensions }
+    }
+
+    pub fn default_extensions() -> Vec<String> {
+        let mut extensions = vec!["lua".to_owned()];
+
+        if cfg!(feature = "roblox") {
+            extensions.push("luau".to_owned());
+        }
+
+        extensions
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        match path.extension() {
+            Some(extension) => self
+                .extensions
+                .iter()
+                .any(|wanted| extension.to_string_lossy() == wanted.trim_start_matches('.')),
+
+            // Scripts such as command line tools often have no extension, just a shebang. Only
+            // executable files are read, since those are the only ones a shebang does anything for.
+            None => is_executable(path) && has_lua_shebang(path),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+// There's no executable bit to check outside of Unix
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+fn has_lua_shebang(path: &Path) -> bool {
+    let mut first_bytes = [0; 128];
+
+    let length = match fs::File::open(path).and_then(|mut file| file.read(&mut first_bytes)) {
+        Ok(length) => length,
+        Err(_) => return false,
+    };
+
+    let first_line = match first_bytes[..length].split(|&byte| byte == b'\n').next() {
+        Some(first_line) => String::from_utf8_lossy(first_line),
+        None => return false,
+    };
+
+    // Matches `#!/usr/bin/env lua`, `#!/usr/bin/lua5.1`, `#!/usr/bin/env luajit`, etc
+    match first_line.strip_prefix("#!") {
+        Some(interpreter) => interpreter
+            .split_whitespace()
+            .filter_map(|part| part.rsplit('/').next())
+            .any(|program| program.starts_with("lua")),
+        None => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WalkOptions {
+    pub follow_symlinks: bool,
+
+    /// How deep to search, where 1 is only the files directly inside the directory
+    pub max_depth: Option<usize>,
+}
+
+pub struct DirectoryWalker<'a> {
+    matcher: &'a FileMatcher,
+    options: WalkOptions,
+    stack: Vec<fs::ReadDir>,
+    pending_error: Option<io::Error>,
+
+    // Symlinks can point to a parent directory, so directories are tracked by their
+    // canonical path to avoid walking them forever.
+    visited: HashSet<PathBuf>,
+}
+
+impl<'a> DirectoryWalker<'a> {
+    pub fn new(directory: &Path, matcher: &'a FileMatcher, options: WalkOptions) -> Self {
+        let mut walker = Self {
+            matcher,
+            options,
+            stack: Vec::new(),
+            pending_error: None,
+            visited: HashSet::new(),
+        };
+
+        if options.max_depth != Some(0) {
+            if let Err(error) = walker.enter(directory) {
+                walker.pending_error = Some(error);
+            }
+        }
+
+        walker
+    }
+
+    fn enter(&mut self, directory: &Path) -> io::Result<()> {
+        if self.options.follow_symlinks && !self.visited.insert(fs::canonicalize(directory)?) {
+            return Ok(());
+        }
+
+        self.stack.push(fs::read_dir(directory)?);
+        Ok(())
+    }
+}
+
+impl Iterator for DirectoryWalker<'_> {
+    type Item = io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.pending_error.take() {
+            return Some(Err(error));
+        }
+
+        loop {
+            let depth = self.stack.len();
+
+            let entry = match self.stack.last_mut()?.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(error)) => return Some(Err(error)),
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+
+            let path = entry.path();
+
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(error) => return Some(Err(error)),
+            };
+
+            // Symlinked files are checked like any other file, but symlinked directories can lead
+            // anywhere, so they are only walked with --follow-symlinks
+            let metadata = if metadata.is_symlink() {
+                match fs::metadata(&path) {
+                    Ok(target) if target.is_dir() && !self.options.follow_symlinks => {
+                        log::trace!("not following symlink {}", path.display());
+                        continue;
+                    }
+
+                    Ok(target) => target,
+
+                    // Such as a link into a dependency that isn't installed
+                    Err(_) if !self.options.follow_symlinks => {
+                        log::trace!("skipping broken symlink {}", path.display());
+                        continue;
+                    }
+
+                    Err(error) => return Some(Err(error)),
+                }
+            } else {
+                metadata
+            };
+
+            if metadata.is_dir() {
+                // Such as .git, which can be huge and never has code that should be checked
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    log::trace!("skipping hidden directory {}", path.display());
+                    continue;
+                }
+
+                let within_depth = match self.options.max_depth {
+                    Some(max_depth) => depth < max_depth,
+                    None => true,
+                };
+
+                if within_depth {
+                    if let Err(error) = self.enter(&path) {
+                        return Some(Err(error));
+                    }
+                }
+            } else if metadata.is_file() && self.matcher.matches(&path) {
+                return Some(Ok(path));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(directory: &Path, matcher: &FileMatcher, options: WalkOptions) -> Vec<PathBuf> {
+        let mut found = DirectoryWalker::new(directory, matcher, options)
+            .map(|path| path.unwrap().strip_prefix(directory).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+
+        found.sort();
+        found
+    }
+
+    fn set_executable(path: &Path, executable: bool) {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = if executable { 0o755 } else { 0o644 };
+            fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        #[cfg(not(unix))]
+        let _ = (path, executable);
+    }
+
+    #[test]
+    fn test_walker() {
+        let directory = std::env::temp_dir().join("selene-test-walker");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(directory.join("nested")).unwrap();
+        fs::create_dir_all(directory.join(".git")).unwrap();
+
+        fs::write(directory.join("a.lua"), "").unwrap();
+        fs::write(directory.join(".git/hidden.lua"), "").unwrap();
+        fs::write(directory.join("b.txt"), "").unwrap();
+        fs::write(directory.join("nested/c.luau"), "").unwrap();
+        fs::write(
+            directory.join("nested/tool"),
+            "#!/usr/bin/env lua\nprint(1)",
+        )
+        .unwrap();
+        set_executable(&directory.join("nested/tool"), true);
+        fs::write(directory.join("nested/shell"), "#!/bin/sh\necho 1").unwrap();
+        set_executable(&directory.join("nested/shell"), true);
+        fs::write(directory.join("nested/empty"), "").unwrap();
+
+        let matcher = FileMatcher::new(vec!["lua".to_owned(), "luau".to_owned()]);
+
+        assert_eq!(
+            walk(&directory, &matcher, WalkOptions::default()),
+            vec![
+                PathBuf::from("a.lua"),
+                PathBuf::from("nested/c.luau"),
+                PathBuf::from("nested/tool"),
+            ]
+        );
+
+        fs::remove_dir_all(&directory).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shebang_needs_executable() {
+        let directory = std::env::temp_dir().join("selene-test-walker-executable");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(&directory).unwrap();
+
+        fs::write(directory.join("tool"), "#!/usr/bin/env lua\nprint(1)").unwrap();
+        set_executable(&directory.join("tool"), true);
+        fs::write(directory.join("notes"), "#!/usr/bin/env lua\nprint(1)").unwrap();
+        set_executable(&directory.join("notes"), false);
+
+        let matcher = FileMatcher::new(vec!["lua".to_owned()]);
+
+        assert_eq!(
+            walk(&directory, &matcher, WalkOptions::default()),
+            vec![PathBuf::from("tool")]
+        );
+
+        fs::remove_dir_all(&directory).ok();
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let directory = std::env::temp_dir().join("selene-test-walker-depth");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(directory.join("one/two")).unwrap();
+
+        fs::write(directory.join("a.lua"), "").unwrap();
+        fs::write(directory.join("one/b.lua"), "").unwrap();
+        fs::write(directory.join("one/two/c.lua"), "").unwrap();
+
+        let matcher = FileMatcher::new(vec!["lua".to_owned()]);
+        let walk_to = |max_depth| {
+            walk(
+                &directory,
+                &matcher,
+                WalkOptions {
+                    max_depth: Some(max_depth),
+                    ..WalkOptions::default()
+                },
+            )
+        };
+
+        assert!(walk_to(0).is_empty());
+        assert_eq!(walk_to(1), vec![PathBuf::from("a.lua")]);
+        assert_eq!(
+            walk_to(2),
+            vec![PathBuf::from("a.lua"), PathBuf::from("one/b.lua")]
+        );
+
+        fs::remove_dir_all(&directory).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks() {
+        let directory = std::env::temp_dir().join("selene-test-walker-symlinks");
+        fs::remove_dir_all(&directory).ok();
+        fs::create_dir_all(directory.join("src")).unwrap();
+        fs::create_dir_all(directory.join("vendor")).unwrap();
+
+        fs::write(directory.join("vendor/library.lua"), "").unwrap();
+        std::os::unix::fs::symlink(directory.join("vendor"), directory.join("src/vendor")).unwrap();
+        std::os::unix::fs::symlink(&directory, directory.join("src/loop")).unwrap();
+        std::os::unix::fs::symlink(
+            directory.join("vendor/library.lua"),
+            directory.join("src/linked.lua"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            directory.join("missing.lua"),
+            directory.join("src/broken.lua"),
+        )
+        .unwrap();
+
+        let matcher = FileMatcher::new(vec!["lua".to_owned()]);
+        let source = directory.join("src");
+
+        // Symlinked files are still checked, only symlinked directories aren't walked
+        assert_eq!(
+            walk(&source, &matcher, WalkOptions::default()),
+            vec![PathBuf::from("linked.lua")]
+        );
+
+        fs::remove_file(directory.join("src/broken.lua")).unwrap();
+
+        let found = walk(
+            &source,
+            &matcher,
+            WalkOptions {
+                follow_symlinks: true,
+                ..WalkOptions::default()
+            },
+        );
+
+        // vendor is reachable both directly and through the loop, but is only walked once
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&PathBuf::from("linked.lua")));
+        assert!(found
+            .iter()
+            .any(|path| path.ends_with("vendor/library.lua")));
+
+        fs::remove_dir_all(&directory).ok();
+    }
+}
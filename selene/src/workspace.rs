@@ -0,0 +1,253 @@
+// Finds the selene.toml each file belongs to, so that a single run can check several projects,
+// such as every package in a monorepo, each with their own config and standard library.
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use selene_lib::{require_graph::normalize_path, Checker, CheckerConfig};
+use termcolor::{Color, ColorSpec, StandardStream, WriteColor};
+
+use crate::{get_color, LINT_ERRORS, LINT_WARNINGS, OPTIONS, PARSE_ERRORS, STOPPED};
+
+pub struct Project {
+    /// The directory the project's selene.toml is in
+    pub directory: PathBuf,
+    pub checker: Checker<toml::value::Value>,
+    /// `source-map` from the project's selene.toml
+    pub source_map: Option<String>,
+    /// `time-budget` from the project's selene.toml
+    pub time_budget: Option<Duration>,
+    /// `transcode-utf16` from the project's selene.toml
+    pub transcode_utf16: bool,
+    /// Which files in the project are tests, checked by a project of their own
+    pub tests: Option<Tests>,
+    /// True for the project that checks the test files of another
+    pub for_tests: bool,
+
+    files: AtomicUsize,
+    lint_errors: AtomicUsize,
+    lint_warnings: AtomicUsize,
+    parse_errors: AtomicUsize,
+}
+
+impl Project {
+    pub fn new(
+        directory: PathBuf,
+        checker: Checker<toml::value::Value>,
+        source_map: Option<String>,
+        time_budget: Option<Duration>,
+        transcode_utf16: bool,
+    ) -> Self {
+        Self {
+            directory,
+            checker,
+            source_map,
+            time_budget,
+            transcode_utf16,
+            tests: None,
+            for_tests: false,
+            files: AtomicUsize::new(0),
+            lint_errors: AtomicUsize::new(0),
+            lint_warnings: AtomicUsize::new(0),
+            parse_errors: AtomicUsize::new(0),
+        }
+    }
+
+    /// Counts the results of one file towards both this project and the total.
+    pub fn record(&self, lint_errors: usize, lint_warnings: usize, parse_errors: usize) {
+        self.files.fetch_add(1, Ordering::SeqCst);
+
+        self.lint_errors.fetch_add(lint_errors, Ordering::SeqCst);
+        self.lint_warnings
+            .fetch_add(lint_warnings, Ordering::SeqCst);
+        self.parse_errors.fetch_add(parse_errors, Ordering::SeqCst);
+
+        LINT_ERRORS.fetch_add(lint_errors, Ordering::SeqCst);
+        LINT_WARNINGS.fetch_add(lint_warnings, Ordering::SeqCst);
+        PARSE_ERRORS.fetch_add(parse_errors, Ordering::SeqCst);
+
+        if lint_errors + parse_errors > 0 && OPTIONS.read().unwrap().as_ref().unwrap().fail_fast {
+            STOPPED.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+pub struct Tests {
+    pub patterns: Vec<glob::Pattern>,
+    /// The config of the project with `for_tests` applied, which its test project is created from
+    /// when the first test file is found
+    pub config: CheckerConfig<toml::value::Value>,
+}
+
+pub struct Workspace {
+    current_dir: PathBuf,
+    root: Arc<Project>,
+    nested: bool,
+    // Every directory looked at so far, and the project that files inside of it belong to
+    directories: HashMap<PathBuf, Arc<Project>>,
+    // The project for the test files of each project, by the directory it's in
+    test_projects: HashMap<PathBuf, Arc<Project>>,
+}
+
+impl Workspace {
+    /// `root` is the project for the current directory.
+    /// When `nested` is false, every file belongs to it.
+    pub fn new(current_dir: PathBuf, root: Project, nested: bool) -> Self {
+        Self {
+            current_dir,
+            root: Arc::new(root),
+            nested,
+            directories: HashMap::new(),
+            test_projects: HashMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Arc<Project> {
+        &self.root
+    }
+
+    /// Returns the project with the closest selene.toml above the file, loading it if needed.
+    /// Files outside of any nested project belong to the root project, and test files belong to
+    /// the test project of the project they're in.
+    pub fn project_for(&mut self, path: &Path) -> Arc<Project> {
+        let absolute = normalize_path(&self.current_dir.join(path));
+        let project = self.closest_project(&absolute);
+
+        let tests = match &project.tests {
+            Some(tests) => tests,
+            None => return project,
+        };
+
+        let relative = absolute
+            .strip_prefix(&project.directory)
+            .unwrap_or(&absolute);
+
+        if !tests
+            .patterns
+            .iter()
+            .any(|pattern| pattern.matches_path(relative))
+        {
+            return project;
+        }
+
+        let test_project = self
+            .test_projects
+            .entry(project.directory.clone())
+            .or_insert_with(|| {
+                let mut test_project =
+                    crate::create_project(tests.config.clone(), &project.directory, false);
+                test_project.for_tests = true;
+                Arc::new(test_project)
+            });
+
+        Arc::clone(test_project)
+    }
+
+    fn closest_project(&mut self, absolute: &Path) -> Arc<Project> {
+        if !self.nested {
+            return Arc::clone(&self.root);
+        }
+
+        let mut unvisited = Vec::new();
+        let mut project = None;
+
+        for directory in absolute.ancestors().skip(1) {
+            if directory == self.current_dir {
+                break;
+            }
+
+            if let Some(known_project) = self.directories.get(directory) {
+                project = Some(Arc::clone(known_project));
+                break;
+            }
+
+            unvisited.push(directory.to_path_buf());
+
+            let config_file = directory.join("selene.toml");
+            if config_file.is_file() {
+                let config = crate::load_config(
+                    OPTIONS.read().unwrap().as_ref().unwrap(),
+                    Some(&config_file),
+                    true,
+                );
+
+                project = Some(Arc::new(crate::create_project(config, directory, true)));
+                break;
+            }
+        }
+
+        let project = project.unwrap_or_else(|| Arc::clone(&self.root));
+
+        for directory in unvisited {
+            self.directories.insert(directory, Arc::clone(&project));
+        }
+
+        project
+    }
+
+    /// Writes how many problems each project had, when more than one project was checked.
+    pub fn log_projects(&self) -> io::Result<()> {
+        let mut projects: Vec<&Arc<Project>> = Vec::new();
+
+        // Test files are counted towards the project they're in
+        let count = |project: &Project, counter: fn(&Project) -> &AtomicUsize| {
+            counter(project).load(Ordering::SeqCst)
+                + self
+                    .test_projects
+                    .get(&project.directory)
+                    .map_or(0, |tests| counter(tests).load(Ordering::SeqCst))
+        };
+
+        for project in std::iter::once(&self.root).chain(self.directories.values()) {
+            if count(project, |project| &project.files) > 0
+                && !projects.iter().any(|other| Arc::ptr_eq(other, project))
+            {
+                projects.push(project);
+            }
+        }
+
+        if projects.len() < 2 {
+            return Ok(());
+        }
+
+        projects.sort_by(|a, b| a.directory.cmp(&b.directory));
+
+        let mut stdout = StandardStream::stdout(get_color());
+        writeln!(stdout, "Projects:")?;
+
+        for project in projects {
+            let directory = match project.directory.strip_prefix(&self.current_dir) {
+                Ok(directory) if directory == Path::new("") => Path::new("."),
+                Ok(directory) => directory,
+                Err(_) => &project.directory,
+            };
+
+            let lint_errors = count(project, |project| &project.lint_errors);
+            let lint_warnings = count(project, |project| &project.lint_warnings);
+            let parse_errors = count(project, |project| &project.parse_errors);
+
+            if lint_errors + parse_errors > 0 {
+                stdout.set_color(ColorSpec::new().set_fg(Some(Color::Red)))?;
+            } else if lint_warnings > 0 {
+                stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+            }
+
+            write!(stdout, "{}", directory.display())?;
+            stdout.reset()?;
+
+            writeln!(
+                stdout,
+                ": {lint_errors} errors, {lint_warnings} warnings, {parse_errors} parse errors"
+            )?;
+        }
+
+        Ok(())
+    }
+}